@@ -0,0 +1,35 @@
+use tokio::sync::broadcast;
+
+use super::{BusEvent, EventBus};
+
+const CAPACITY: usize = 1000;
+
+/// Single-node bus, events only reach subscribers of this process
+#[derive(Clone)]
+pub struct InMemoryEventBus {
+    sender: broadcast::Sender<BusEvent>,
+}
+
+impl InMemoryEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+        Self { sender }
+    }
+}
+
+impl Default for InMemoryEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus for InMemoryEventBus {
+    fn publish(&self, event: BusEvent) {
+        // No subscribers is not an error
+        let _ = self.sender.send(event);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<BusEvent> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,57 @@
+//! Event bus behind the GraphQL subscriptions
+//!
+//! Mutations publish registration, seating and clock events on the bus and every
+//! subscription listens to it. A single instance keeps events in memory; instances
+//! behind a load balancer share them through Postgres `LISTEN/NOTIFY` so a subscriber
+//! connected to one instance sees changes made on another.
+
+pub mod memory;
+pub mod postgres;
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::gql::types::{PlayerRegistrationEvent, SeatingChangeEvent, TournamentClock};
+
+pub use memory::InMemoryEventBus;
+pub use postgres::PgEventBus;
+
+/// Events delivered to subscriptions
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum BusEvent {
+    Registration(PlayerRegistrationEvent),
+    Seating(SeatingChangeEvent),
+    Clock(TournamentClock),
+}
+
+pub trait EventBus: Send + Sync {
+    /// Deliver an event to every subscriber
+    fn publish(&self, event: BusEvent);
+
+    /// Receive the events published from now on
+    fn subscribe(&self) -> broadcast::Receiver<BusEvent>;
+}
+
+static EVENT_BUS: OnceCell<Arc<dyn EventBus>> = OnceCell::new();
+
+/// Use `bus` for all events. Only the first bus installed is used, install it before
+/// serving requests.
+pub fn install(bus: Arc<dyn EventBus>) -> bool {
+    EVENT_BUS.set(bus).is_ok()
+}
+
+/// The installed bus, the in-memory one when none was installed
+pub fn bus() -> &'static Arc<dyn EventBus> {
+    EVENT_BUS.get_or_init(|| Arc::new(InMemoryEventBus::new()))
+}
+
+pub fn publish(event: BusEvent) {
+    bus().publish(event);
+}
+
+pub fn subscribe() -> broadcast::Receiver<BusEvent> {
+    bus().subscribe()
+}
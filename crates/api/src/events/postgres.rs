@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::{BusEvent, EventBus, InMemoryEventBus};
+
+/// Postgres channel the instances notify each other on
+pub const CHANNEL: &str = "pocketpair_events";
+
+/// Postgres rejects NOTIFY payloads of 8000 bytes or more
+const MAX_PAYLOAD_BYTES: usize = 7999;
+
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    /// Instance that published the event
+    origin: Uuid,
+    event: BusEvent,
+}
+
+/// Bus shared by every instance connected to the same database
+///
+/// Events are delivered to local subscribers right away and sent to the other
+/// instances with `pg_notify`. Each instance listens on [`CHANNEL`] and forwards the
+/// events it did not publish itself to its local subscribers.
+#[derive(Clone)]
+pub struct PgEventBus {
+    instance_id: Uuid,
+    local: InMemoryEventBus,
+    outgoing: mpsc::UnboundedSender<String>,
+}
+
+impl PgEventBus {
+    /// Listen for events from other instances and start notifying them
+    pub async fn start(pool: PgPool) -> sqlx::Result<Self> {
+        let mut listener = PgListener::connect_with(&pool).await?;
+        listener.listen(CHANNEL).await?;
+
+        let instance_id = Uuid::new_v4();
+        let local = InMemoryEventBus::new();
+
+        let forward_to = local.clone();
+        tokio::spawn(async move {
+            loop {
+                // The listener reconnects by itself, notifications sent meanwhile are lost
+                let notification = match listener.recv().await {
+                    Ok(notification) => notification,
+                    Err(e) => {
+                        warn!("Event bus listener failed: {}", e);
+                        tokio::time::sleep(RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+                match serde_json::from_str::<Envelope>(notification.payload()) {
+                    Ok(envelope) if envelope.origin == instance_id => {}
+                    Ok(envelope) => forward_to.publish(envelope.event),
+                    Err(e) => warn!("Ignoring malformed event bus payload: {}", e),
+                }
+            }
+        });
+
+        // One task sends the notifications so other instances receive them in order
+        let (outgoing, mut queue) = mpsc::unbounded_channel::<String>();
+        tokio::spawn(async move {
+            while let Some(payload) = queue.recv().await {
+                if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+                    .bind(CHANNEL)
+                    .bind(&payload)
+                    .execute(&pool)
+                    .await
+                {
+                    warn!("Failed to notify other instances of an event: {}", e);
+                }
+            }
+        });
+
+        debug!(
+            "Event bus instance {} listening on {}",
+            instance_id, CHANNEL
+        );
+
+        Ok(Self {
+            instance_id,
+            local,
+            outgoing,
+        })
+    }
+}
+
+impl EventBus for PgEventBus {
+    fn publish(&self, event: BusEvent) {
+        let envelope = Envelope {
+            origin: self.instance_id,
+            event,
+        };
+        match serde_json::to_string(&envelope) {
+            Ok(payload) if payload.len() > MAX_PAYLOAD_BYTES => {
+                warn!(
+                    "Event of {} bytes is too large to notify other instances",
+                    payload.len()
+                );
+            }
+            Ok(payload) => {
+                let _ = self.outgoing.send(payload);
+            }
+            Err(e) => warn!("Failed to serialize event: {}", e),
+        }
+
+        self.local.publish(envelope.event);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<BusEvent> {
+        self.local.subscribe()
+    }
+}
@@ -0,0 +1,443 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::tournament_clock::publish_clock_change;
+use crate::gql::types::{
+    BlindLevelInput, BlindStructureTemplate, ColorUp, CreateBlindStructureTemplateInput,
+    GenerateBlindStructureInput, GeneratedBlindStructure, TournamentStructure,
+    UpdateBlindStructureTemplateInput,
+};
+use crate::state::AppState;
+use infra::blind_structure::{self, BlindStructureParams};
+use infra::models::{BlindStructureTemplateRow, TournamentRow, TournamentStructureRow};
+use infra::repos::{
+    BlindStructureTemplateRepo, CreateBlindStructureTemplate, TournamentClockRepo,
+    TournamentLiveStatus, TournamentRepo, TournamentStructureLevel, UpdateBlindStructureTemplate,
+};
+
+/// Helper function to check a blind level entered by a manager is playable
+pub(crate) fn blind_level_from_input(
+    level: BlindLevelInput,
+    level_number: i32,
+) -> Result<TournamentStructureLevel> {
+    let ante = level.ante.unwrap_or(0);
+    let is_break = level.is_break.unwrap_or(false);
+
+    if level.duration_minutes <= 0 {
+        return Err(async_graphql::Error::new(format!(
+            "Level {} must last at least one minute",
+            level_number
+        )));
+    }
+    if !is_break && (level.small_blind <= 0 || level.big_blind < level.small_blind || ante < 0) {
+        return Err(async_graphql::Error::new(format!(
+            "Level {} has invalid blinds",
+            level_number
+        )));
+    }
+
+    Ok(TournamentStructureLevel {
+        level_number,
+        small_blind: level.small_blind,
+        big_blind: level.big_blind,
+        ante,
+        duration_minutes: level.duration_minutes,
+        is_break,
+        break_duration_minutes: level.break_duration_minutes,
+    })
+}
+
+/// Helper function to number blind levels in the order given and check they are playable
+pub(crate) fn blind_levels_from_input(
+    levels: Vec<BlindLevelInput>,
+) -> Result<Vec<TournamentStructureLevel>> {
+    levels
+        .into_iter()
+        .enumerate()
+        .map(|(i, level)| blind_level_from_input(level, i as i32 + 1))
+        .collect()
+}
+
+/// Helper function to run the blind structure generator on GraphQL input
+fn generate(
+    input: GenerateBlindStructureInput,
+) -> Result<blind_structure::GeneratedBlindStructure> {
+    let defaults = BlindStructureParams::default();
+    blind_structure::generate_blind_structure(&BlindStructureParams {
+        starting_stack: input.starting_stack,
+        expected_players: input.expected_players,
+        target_duration_minutes: input.target_duration_minutes,
+        level_duration_minutes: input.level_duration_minutes,
+        ante_from_level: input.ante_from_level,
+        break_every_levels: input.break_every_levels,
+        break_duration_minutes: input
+            .break_duration_minutes
+            .unwrap_or(defaults.break_duration_minutes),
+        denominations: input.denominations.unwrap_or(defaults.denominations),
+    })
+    .map_err(async_graphql::Error::new)
+}
+
+fn structure_from_row(row: &TournamentStructureRow) -> TournamentStructure {
+    TournamentStructure {
+        id: row.id.into(),
+        tournament_id: row.tournament_id.into(),
+        level_number: row.level_number,
+        small_blind: row.small_blind,
+        big_blind: row.big_blind,
+        ante: row.ante,
+        duration_minutes: row.duration_minutes,
+        is_break: row.is_break,
+        break_duration_minutes: row.break_duration_minutes,
+    }
+}
+
+/// Helper function to check a level number refers to an existing level
+fn check_level_number(levels: &[TournamentStructureLevel], level_number: i32) -> Result<usize> {
+    if level_number < 1 || level_number as usize > levels.len() {
+        return Err(async_graphql::Error::new(format!(
+            "Level {} does not exist",
+            level_number
+        )));
+    }
+    Ok(level_number as usize - 1)
+}
+
+async fn get_managed_tournament(ctx: &Context<'_>, tournament_id: &ID) -> Result<TournamentRow> {
+    let state = ctx.data::<AppState>()?;
+    let tournament_id = Uuid::parse_str(tournament_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+    let tournament = TournamentRepo::new(state.db.clone())
+        .get(tournament_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+    require_club_manager(ctx, tournament.club_id).await?;
+
+    if matches!(
+        tournament.live_status,
+        TournamentLiveStatus::Finished | TournamentLiveStatus::Cancelled
+    ) {
+        return Err(async_graphql::Error::new(
+            "The structure of a finished or cancelled tournament cannot be edited",
+        ));
+    }
+
+    Ok(tournament)
+}
+
+async fn get_managed_template(
+    ctx: &Context<'_>,
+    template_id: &ID,
+) -> Result<BlindStructureTemplateRow> {
+    let state = ctx.data::<AppState>()?;
+    let template_id = Uuid::parse_str(template_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid template ID: {}", e)))?;
+
+    let template = BlindStructureTemplateRepo::new(state.db.clone())
+        .get(template_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Blind structure template not found"))?;
+    require_club_manager(ctx, template.club_id).await?;
+
+    Ok(template)
+}
+
+/// Turn the club's unique template name constraint into a readable error
+fn template_write_error(e: sqlx::Error) -> async_graphql::Error {
+    match e.as_database_error() {
+        Some(db_err) if db_err.constraint() == Some("unique_blind_structure_template_name") => {
+            async_graphql::Error::new("A template with this name already exists in the club")
+        }
+        _ => e.into(),
+    }
+}
+
+/// Apply an edit to a tournament's structure. The edit receives the current levels and
+/// returns the new ones; levels are renumbered in order, and the edit is rejected if it
+/// changes a level the clock has already reached.
+async fn edit_structure<F>(
+    ctx: &Context<'_>,
+    tournament_id: &ID,
+    edit: F,
+) -> Result<Vec<TournamentStructure>>
+where
+    F: FnOnce(Vec<TournamentStructureLevel>) -> Result<Vec<TournamentStructureLevel>>,
+{
+    let tournament = get_managed_tournament(ctx, tournament_id).await?;
+    let state = ctx.data::<AppState>()?;
+    let clock_repo = TournamentClockRepo::new(state.db.clone());
+
+    let existing: Vec<TournamentStructureLevel> = clock_repo
+        .get_all_structures(tournament.id)
+        .await?
+        .iter()
+        .map(Into::into)
+        .collect();
+
+    let mut levels = edit(existing.clone())?;
+    if levels.is_empty() {
+        return Err(async_graphql::Error::new(
+            "A blind structure needs at least one level",
+        ));
+    }
+    for (i, level) in levels.iter_mut().enumerate() {
+        level.level_number = i as i32 + 1;
+    }
+
+    let last_played_level = clock_repo
+        .get_clock(tournament.id)
+        .await?
+        .map(|clock| clock.last_played_level())
+        .unwrap_or(0);
+    for level_number in 1..=last_played_level.max(0) as usize {
+        if existing.get(level_number - 1) != levels.get(level_number - 1) {
+            return Err(async_graphql::Error::new(format!(
+                "Level {} has already been played and cannot be changed",
+                level_number
+            )));
+        }
+    }
+
+    let rows = clock_repo
+        .replace_structures(tournament.id, &existing, &levels)
+        .await?
+        .ok_or_else(|| {
+            async_graphql::Error::new(
+                "The structure or the clock changed meanwhile, reload it and try again",
+            )
+        })?;
+    publish_clock_change(&state.db, tournament.id).await;
+
+    Ok(rows.iter().map(structure_from_row).collect())
+}
+
+pub struct BlindStructureQuery;
+
+impl BlindStructureQuery {
+    /// Get the blind structure templates of a club
+    pub async fn blind_structure_templates(
+        &self,
+        ctx: &Context<'_>,
+        club_id: ID,
+    ) -> Result<Vec<BlindStructureTemplate>> {
+        let state = ctx.data::<AppState>()?;
+        let club_id = Uuid::parse_str(club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        let rows = BlindStructureTemplateRepo::new(state.db.clone())
+            .list_by_club(club_id)
+            .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Generate a blind structure to preview before saving it
+    pub async fn generate_blind_structure(
+        &self,
+        input: GenerateBlindStructureInput,
+    ) -> Result<GeneratedBlindStructure> {
+        let generated = generate(input)?;
+
+        Ok(GeneratedBlindStructure {
+            total_duration_minutes: generated.total_duration_minutes(),
+            levels: generated.levels.into_iter().map(Into::into).collect(),
+            color_ups: generated
+                .color_ups
+                .into_iter()
+                .map(|color_up| ColorUp {
+                    level_number: color_up.level_number,
+                    denominations: color_up.denominations,
+                })
+                .collect(),
+        })
+    }
+}
+
+pub struct BlindStructureMutation;
+
+impl BlindStructureMutation {
+    /// Replace the whole structure of a tournament
+    pub async fn replace_tournament_structure(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        levels: Vec<BlindLevelInput>,
+    ) -> Result<Vec<TournamentStructure>> {
+        let levels = blind_levels_from_input(levels)?;
+        edit_structure(ctx, &tournament_id, |_| Ok(levels)).await
+    }
+
+    /// Insert a level so that it gets `level_number`, pushing later levels back
+    pub async fn insert_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+        level: BlindLevelInput,
+    ) -> Result<Vec<TournamentStructure>> {
+        let level = blind_level_from_input(level, level_number)?;
+        edit_structure(ctx, &tournament_id, |mut levels| {
+            if level_number < 1 || level_number as usize > levels.len() + 1 {
+                return Err(async_graphql::Error::new(format!(
+                    "Levels can only be inserted between 1 and {}",
+                    levels.len() + 1
+                )));
+            }
+            levels.insert(level_number as usize - 1, level);
+            Ok(levels)
+        })
+        .await
+    }
+
+    /// Move a level to another position
+    pub async fn move_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+        new_level_number: i32,
+    ) -> Result<Vec<TournamentStructure>> {
+        edit_structure(ctx, &tournament_id, |mut levels| {
+            let from = check_level_number(&levels, level_number)?;
+            let to = check_level_number(&levels, new_level_number)?;
+            let level = levels.remove(from);
+            levels.insert(to, level);
+            Ok(levels)
+        })
+        .await
+    }
+
+    /// Delete a level, moving later levels up
+    pub async fn delete_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+    ) -> Result<Vec<TournamentStructure>> {
+        edit_structure(ctx, &tournament_id, |mut levels| {
+            let index = check_level_number(&levels, level_number)?;
+            levels.remove(index);
+            Ok(levels)
+        })
+        .await
+    }
+
+    /// Generate a blind structure and save it as a tournament's structure
+    pub async fn save_generated_blind_structure(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        input: GenerateBlindStructureInput,
+    ) -> Result<Vec<TournamentStructure>> {
+        let generated = generate(input)?;
+        edit_structure(ctx, &tournament_id, |_| Ok(generated.levels)).await
+    }
+
+    /// Replace a tournament's structure with the levels of one of its club's templates
+    pub async fn apply_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        template_id: ID,
+    ) -> Result<Vec<TournamentStructure>> {
+        let template = get_managed_template(ctx, &template_id).await?;
+        let tournament = get_managed_tournament(ctx, &tournament_id).await?;
+        if template.club_id != tournament.club_id {
+            return Err(async_graphql::Error::new(
+                "Blind structure template belongs to another club",
+            ));
+        }
+
+        let levels: Vec<TournamentStructureLevel> = serde_json::from_value(template.levels)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid template levels: {}", e)))?;
+        edit_structure(ctx, &tournament_id, |_| Ok(levels)).await
+    }
+
+    /// Save a named blind structure for a club
+    pub async fn create_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateBlindStructureTemplateInput,
+    ) -> Result<BlindStructureTemplate> {
+        let club_id = Uuid::parse_str(input.club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+        let manager = require_club_manager(ctx, club_id).await?;
+        let state = ctx.data::<AppState>()?;
+
+        let name = input.name.trim().to_string();
+        if name.is_empty() {
+            return Err(async_graphql::Error::new("Template name cannot be empty"));
+        }
+        let levels = blind_levels_from_input(input.levels)?;
+        if levels.is_empty() {
+            return Err(async_graphql::Error::new(
+                "A blind structure needs at least one level",
+            ));
+        }
+
+        let row = BlindStructureTemplateRepo::new(state.db.clone())
+            .create(CreateBlindStructureTemplate {
+                club_id,
+                name,
+                description: input.description,
+                levels,
+                created_by: Some(Uuid::parse_str(manager.id.as_str())?),
+            })
+            .await
+            .map_err(template_write_error)?;
+
+        Ok(row.into())
+    }
+
+    /// Edit a template; tournaments it was already applied to are not affected
+    pub async fn update_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateBlindStructureTemplateInput,
+    ) -> Result<BlindStructureTemplate> {
+        let existing = get_managed_template(ctx, &input.id).await?;
+        let state = ctx.data::<AppState>()?;
+
+        let name = input.name.map(|name| name.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Err(async_graphql::Error::new("Template name cannot be empty"));
+        }
+        let levels = input.levels.map(blind_levels_from_input).transpose()?;
+        if levels.as_ref().is_some_and(|levels| levels.is_empty()) {
+            return Err(async_graphql::Error::new(
+                "A blind structure needs at least one level",
+            ));
+        }
+
+        let row = BlindStructureTemplateRepo::new(state.db.clone())
+            .update(
+                existing.id,
+                UpdateBlindStructureTemplate {
+                    name,
+                    description: input.description,
+                    levels,
+                },
+            )
+            .await
+            .map_err(template_write_error)?
+            .ok_or_else(|| async_graphql::Error::new("Blind structure template not found"))?;
+
+        Ok(row.into())
+    }
+
+    /// Delete a template; tournaments it was already applied to are not affected
+    pub async fn delete_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: ID,
+    ) -> Result<bool> {
+        let existing = get_managed_template(ctx, &template_id).await?;
+        let state = ctx.data::<AppState>()?;
+
+        Ok(BlindStructureTemplateRepo::new(state.db.clone())
+            .delete(existing.id)
+            .await?)
+    }
+}
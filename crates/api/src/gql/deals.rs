@@ -0,0 +1,256 @@
+use async_graphql::{Context, Result, ID};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::types::{DealPreview, DealPreviewPlayer};
+use crate::state::AppState;
+use infra::models::{PlayerDealRow, TournamentPayoutRow};
+use infra::repos::{
+    PayoutTemplateRepo, TableSeatAssignmentRepo, TournamentPayoutRepo, TournamentRepo,
+};
+use infra::{deals, icm};
+
+/// Helper function to parse a payout template's `[{position, percentage}]` structure
+pub(crate) fn parse_payout_structure(structure: &serde_json::Value) -> Result<Vec<(i32, f64)>> {
+    let array = structure
+        .as_array()
+        .ok_or_else(|| async_graphql::Error::new("Invalid payout structure format"))?;
+
+    let mut payouts = Vec::new();
+    for item in array {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| async_graphql::Error::new("Invalid payout item format"))?;
+
+        let position = obj
+            .get("position")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| async_graphql::Error::new("Missing or invalid position"))?
+            as i32;
+
+        let percentage = obj
+            .get("percentage")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| async_graphql::Error::new("Missing or invalid percentage"))?;
+
+        payouts.push((position, percentage));
+    }
+
+    Ok(payouts)
+}
+
+pub(crate) fn get_position_percentage(
+    payout_structure: &[(i32, f64)],
+    position: i32,
+) -> Option<f64> {
+    payout_structure
+        .iter()
+        .find(|(pos, _)| *pos == position)
+        .map(|(_, percentage)| *percentage)
+}
+
+/// Helper function to get the prize in cents for each position from a payout template
+pub(crate) async fn template_ladder(
+    payout_repo: &PayoutTemplateRepo,
+    template_id: &ID,
+    positions: &[i32],
+    total_prize_pool: i32,
+) -> Result<Vec<i32>> {
+    let template_id = Uuid::parse_str(template_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid template ID: {}", e)))?;
+    let template = payout_repo
+        .get_by_id(template_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Payout template not found"))?;
+    let payout_structure = parse_payout_structure(&template.payout_structure)?;
+
+    Ok(positions
+        .iter()
+        .map(|position| {
+            get_position_percentage(&payout_structure, *position)
+                .map(|percentage| (total_prize_pool as f64 * percentage / 100.0) as i32)
+                .unwrap_or(0)
+        })
+        .collect())
+}
+
+/// Helper function to get the prize in cents of every paid place of a tournament
+pub(crate) fn payout_amounts(payout: &TournamentPayoutRow) -> Result<HashMap<i32, i32>> {
+    Ok(payout
+        .payout_positions
+        .as_array()
+        .ok_or_else(|| async_graphql::Error::new("Invalid payout positions format"))?
+        .iter()
+        .filter_map(|pos| {
+            let position = pos.get("position").and_then(|v| v.as_i64())?;
+            let amount_cents = pos.get("amount_cents").and_then(|v| v.as_i64())?;
+            Some((position as i32, amount_cents as i32))
+        })
+        .collect())
+}
+
+/// Helper function to apply a recorded deal to the final standings: the players in the
+/// deal get their custom payout, or an even share of the deal
+pub(crate) fn apply_deal(
+    deal: &PlayerDealRow,
+    standings: &[(Uuid, i32)],
+    prizes: &mut [i32],
+) -> Result<()> {
+    let custom_payouts = deal
+        .custom_payouts
+        .as_ref()
+        .map(|payouts| {
+            payouts
+                .as_object()
+                .ok_or_else(|| async_graphql::Error::new("Invalid custom payouts format"))
+        })
+        .transpose()?;
+
+    let in_deal: Vec<usize> = standings
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, position))| deal.affected_positions.contains(position))
+        .map(|(i, _)| i)
+        .collect();
+    let shares = deals::even_split_deal(in_deal.len(), &[deal.total_amount_cents]);
+
+    for (i, share) in in_deal.into_iter().zip(shares) {
+        let custom = custom_payouts
+            .and_then(|payouts| payouts.get(&standings[i].0.to_string()))
+            .and_then(|amount| amount.as_i64());
+        prizes[i] = custom.map(|amount| amount as i32).unwrap_or(share);
+    }
+
+    Ok(())
+}
+
+/// Helper function to look up the current chip stack of each player in a deal
+pub(crate) async fn current_stacks(
+    seat_repo: &TableSeatAssignmentRepo,
+    tournament_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<Vec<i32>> {
+    let stacks: HashMap<Uuid, Option<i32>> = seat_repo
+        .get_current_for_tournament(tournament_id)
+        .await?
+        .into_iter()
+        .map(|assignment| (assignment.user_id, assignment.stack_size))
+        .collect();
+
+    user_ids
+        .iter()
+        .map(|user_id| {
+            stacks.get(user_id).copied().flatten().ok_or_else(|| {
+                async_graphql::Error::new(format!("No chip stack recorded for player {}", user_id))
+            })
+        })
+        .collect()
+}
+
+/// Helper function to reject ICM deals too large to evaluate
+pub(crate) fn check_icm_player_count(players: usize) -> Result<()> {
+    if players > icm::MAX_PLAYERS {
+        return Err(async_graphql::Error::new(format!(
+            "ICM deals support at most {} players",
+            icm::MAX_PLAYERS
+        )));
+    }
+    Ok(())
+}
+
+pub struct DealQuery;
+
+impl DealQuery {
+    /// Compare ICM, chip chop and even split deals for the players still seated
+    pub async fn preview_deal(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        payout_template_id: Option<ID>,
+    ) -> Result<DealPreview> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+        let seat_repo = TableSeatAssignmentRepo::new(state.db.clone());
+        let tournament_payout_repo = TournamentPayoutRepo::new(state.db.clone());
+
+        let tournament_uuid = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let tournament = tournament_repo
+            .get(tournament_uuid)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+        require_club_manager(ctx, tournament.club_id).await?;
+
+        let payout = tournament_payout_repo
+            .get_by_tournament(tournament_uuid)
+            .await?
+            .ok_or_else(|| {
+                async_graphql::Error::new("Payouts have not been calculated for this tournament")
+            })?;
+
+        let mut players: Vec<(Uuid, i32)> = Vec::new();
+        for assignment in seat_repo
+            .get_current_for_tournament(tournament_uuid)
+            .await?
+        {
+            let stack = assignment.stack_size.ok_or_else(|| {
+                async_graphql::Error::new(format!(
+                    "No chip stack recorded for player {}",
+                    assignment.user_id
+                ))
+            })?;
+            players.push((assignment.user_id, stack));
+        }
+        if players.is_empty() {
+            return Err(async_graphql::Error::new(
+                "No players are seated in this tournament",
+            ));
+        }
+        check_icm_player_count(players.len())?;
+        players.sort_by_key(|(_, stack)| std::cmp::Reverse(*stack));
+
+        // The places still to be decided are 1..=players at the final table
+        let positions: Vec<i32> = (1..=players.len() as i32).collect();
+        let ladder = if let Some(template_id) = payout_template_id.as_ref() {
+            template_ladder(
+                &PayoutTemplateRepo::new(state.db.clone()),
+                template_id,
+                &positions,
+                payout.total_prize_pool,
+            )
+            .await?
+        } else {
+            let amounts = payout_amounts(&payout)?;
+            positions
+                .iter()
+                .map(|position| amounts.get(position).copied().unwrap_or(0))
+                .collect()
+        };
+
+        let stacks: Vec<i32> = players.iter().map(|(_, stack)| *stack).collect();
+        let icm_amounts = icm::icm_deal(&stacks, &ladder);
+        let chip_chop_amounts = deals::chip_chop_deal(&stacks, &ladder);
+        let even_split_amounts = deals::even_split_deal(players.len(), &ladder);
+
+        let players = players
+            .iter()
+            .enumerate()
+            .map(|(i, (user_id, stack))| DealPreviewPlayer {
+                user_id: (*user_id).into(),
+                stack_size: *stack,
+                icm_amount_cents: icm_amounts[i],
+                chip_chop_amount_cents: chip_chop_amounts[i],
+                even_split_amount_cents: even_split_amounts[i],
+            })
+            .collect();
+
+        Ok(DealPreview {
+            tournament_id,
+            total_amount_cents: ladder.iter().sum(),
+            ladder_cents: ladder,
+            players,
+        })
+    }
+}
@@ -0,0 +1,444 @@
+use async_graphql::{Context, Result, ID};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::deals::{apply_deal, payout_amounts};
+use crate::gql::registrations::publish_registration_change;
+use crate::gql::subscriptions::publish_seating_event;
+use crate::gql::tournament_clock::publish_clock_change;
+use crate::gql::types::{
+    EliminatePlayersInput, PlayerEliminationInput, SeatAssignment, SeatingChangeEvent,
+    SeatingEventType, TournamentElimination, User,
+};
+use crate::state::AppState;
+use infra::finishing;
+use infra::models::{TableSeatAssignmentRow, TournamentEliminationRow, TournamentRow};
+use infra::repos::{
+    CreateTournamentEliminations, CreateTournamentResult, EliminatedPlayer, PlayerDealRepo,
+    TableSeatAssignmentRepo, TournamentEliminationRepo, TournamentLiveStatus, TournamentPayoutRepo,
+    TournamentRegistrationRepo, TournamentRepo, TournamentResultRepo, UserRepo,
+};
+
+pub struct EliminationQuery;
+
+impl EliminationQuery {
+    /// Get the elimination log of a tournament with levels, finishing positions and the
+    /// bounties paid, oldest first
+    pub async fn tournament_eliminations(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<Vec<TournamentElimination>> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let rows = TournamentEliminationRepo::new(state.db.clone())
+            .get_by_tournament(tournament_id)
+            .await?;
+
+        Ok(rows.into_iter().map(TournamentElimination::from).collect())
+    }
+}
+
+pub struct EliminationMutation;
+
+impl EliminationMutation {
+    /// Eliminate a player from the tournament (club managers only). When the eliminator
+    /// is given they collect the eliminated player's bounty.
+    pub async fn eliminate_player(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        user_id: ID,
+        eliminated_by: Option<ID>,
+        notes: Option<String>,
+    ) -> Result<bool> {
+        self.eliminate_players(
+            ctx,
+            EliminatePlayersInput {
+                tournament_id,
+                eliminations: vec![PlayerEliminationInput {
+                    user_id,
+                    eliminated_by,
+                }],
+                notes,
+            },
+        )
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Eliminate the players busting in the same hand (club managers only). Players at
+    /// the same table finish in order of their stacks, players at different tables tie
+    /// with the players holding the same rank at their own table.
+    pub async fn eliminate_players(
+        &self,
+        ctx: &Context<'_>,
+        input: EliminatePlayersInput,
+    ) -> Result<Vec<TournamentElimination>> {
+        let state = ctx.data::<AppState>()?;
+        let assignment_repo = TableSeatAssignmentRepo::new(state.db.clone());
+
+        let tournament_id = Uuid::parse_str(input.tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let tournament = TournamentRepo::new(state.db.clone())
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        let manager = require_club_manager(ctx, tournament.club_id).await?;
+        let manager_id = Uuid::parse_str(manager.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))?;
+
+        if matches!(
+            tournament.live_status,
+            TournamentLiveStatus::Finished | TournamentLiveStatus::Cancelled
+        ) {
+            return Err(async_graphql::Error::new(
+                "Players cannot be eliminated from a finished or cancelled tournament",
+            ));
+        }
+        if input.eliminations.is_empty() {
+            return Err(async_graphql::Error::new("No players to eliminate"));
+        }
+
+        let mut players = Vec::with_capacity(input.eliminations.len());
+        for elimination in &input.eliminations {
+            let user_id = Uuid::parse_str(elimination.user_id.as_str())
+                .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+            let eliminated_by = elimination
+                .eliminated_by
+                .as_ref()
+                .map(|id| Uuid::parse_str(id.as_str()))
+                .transpose()
+                .map_err(|e| async_graphql::Error::new(format!("Invalid eliminator ID: {}", e)))?;
+            players.push(EliminatedPlayer {
+                user_id,
+                eliminated_by,
+            });
+        }
+
+        let busted: HashSet<Uuid> = players.iter().map(|player| player.user_id).collect();
+        if busted.len() != players.len() {
+            return Err(async_graphql::Error::new(
+                "A player can only be eliminated once per hand",
+            ));
+        }
+
+        let mut assignments = Vec::with_capacity(players.len());
+        for player in &players {
+            let assignment = assignment_repo
+                .get_current_for_user(tournament_id, player.user_id)
+                .await?
+                .ok_or_else(|| {
+                    async_graphql::Error::new("Player not currently assigned to a seat")
+                })?;
+            assignments.push(assignment);
+
+            // The eliminator has to be at a table, and still in, to win the hand
+            if let Some(eliminator_id) = player.eliminated_by {
+                if eliminator_id == player.user_id {
+                    return Err(async_graphql::Error::new(
+                        "A player cannot eliminate themselves",
+                    ));
+                }
+                if busted.contains(&eliminator_id) {
+                    return Err(async_graphql::Error::new(
+                        "The eliminator cannot bust in the same hand",
+                    ));
+                }
+                if assignment_repo
+                    .get_current_for_user(tournament_id, eliminator_id)
+                    .await?
+                    .is_none()
+                {
+                    return Err(async_graphql::Error::new(
+                        "Eliminator not currently assigned to a seat",
+                    ));
+                }
+            }
+        }
+
+        let rows = TournamentEliminationRepo::new(state.db.clone())
+            .record(CreateTournamentEliminations {
+                tournament_id,
+                players,
+                notes: input.notes.clone(),
+                created_by: Some(manager_id),
+            })
+            .await?;
+
+        let registration_repo = TournamentRegistrationRepo::new(state.db.clone());
+        for (row, assignment) in rows.iter().zip(assignments) {
+            publish_seat_change(
+                state,
+                tournament.club_id,
+                SeatAssignment {
+                    stack_size: Some(0),
+                    is_current: false,
+                    unassigned_at: Some(chrono::Utc::now()),
+                    notes: input
+                        .notes
+                        .clone()
+                        .or_else(|| Some("Player eliminated".to_string())),
+                    ..seat_assignment(&assignment)
+                },
+                SeatingEventType::PlayerEliminated,
+                format!(
+                    "Player eliminated from tournament in position {}",
+                    row.finishing_position.unwrap_or_default()
+                ),
+            )
+            .await?;
+            if let Some(registration) = registration_repo.get_by_id(row.registration_id).await? {
+                publish_registration_change(state, &registration, "player_busted").await?;
+            }
+        }
+
+        // The last player standing wins, the results are in
+        if TournamentEliminationRepo::new(state.db.clone())
+            .players_remaining(tournament_id)
+            .await?
+            == 1
+        {
+            finalise_tournament(state, &tournament, manager_id).await?;
+        }
+
+        Ok(rows.into_iter().map(TournamentElimination::from).collect())
+    }
+
+    /// Undo the last elimination of a tournament (club managers only): the player gets
+    /// their seat, stack and bounty back. Undoing the elimination that finished the
+    /// tournament deletes its results and reopens it.
+    pub async fn undo_last_elimination(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<TournamentElimination> {
+        let state = ctx.data::<AppState>()?;
+        let assignment_repo = TableSeatAssignmentRepo::new(state.db.clone());
+        let elimination_repo = TournamentEliminationRepo::new(state.db.clone());
+
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let tournament = TournamentRepo::new(state.db.clone())
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        require_club_manager(ctx, tournament.club_id).await?;
+
+        // Only a tournament finished by its last elimination can be reopened
+        if tournament.live_status == TournamentLiveStatus::Finished
+            && elimination_repo.players_remaining(tournament_id).await? != 1
+        {
+            return Err(async_graphql::Error::new(
+                "Eliminations of a finished tournament cannot be undone",
+            ));
+        }
+
+        let elimination = elimination_repo
+            .get_last(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("No elimination to undo"))?;
+
+        let registration = TournamentRegistrationRepo::new(state.db.clone())
+            .get_by_id(elimination.registration_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Registration not found"))?;
+        if registration.status != "busted" {
+            return Err(async_graphql::Error::new(
+                "The player is back in the tournament, the elimination cannot be undone",
+            ));
+        }
+
+        let assignment = restorable_seat(&assignment_repo, &elimination).await?;
+
+        if !elimination_repo.undo(&elimination).await? {
+            return Err(async_graphql::Error::new(
+                "The tournament changed meanwhile, check its eliminations and try again",
+            ));
+        }
+
+        if let Some(assignment) = assignment {
+            let assignment = assignment_repo
+                .get_by_id(assignment.id)
+                .await?
+                .unwrap_or(assignment);
+            publish_seat_change(
+                state,
+                tournament.club_id,
+                seat_assignment(&assignment),
+                SeatingEventType::PlayerAssigned,
+                "Elimination undone, player back in their seat".to_string(),
+            )
+            .await?;
+        }
+        if let Some(registration) = TournamentRegistrationRepo::new(state.db.clone())
+            .get_by_id(elimination.registration_id)
+            .await?
+        {
+            publish_registration_change(state, &registration, "elimination_undone").await?;
+        }
+
+        Ok(elimination.into())
+    }
+}
+
+/// Close a tournament down to its last player: results for every finisher in the order
+/// they busted, paid from the payout ladder or the recorded deal, then the tournament is
+/// finished (which awards the points) and the clock stopped, all in one transaction
+async fn finalise_tournament(
+    state: &AppState,
+    tournament: &TournamentRow,
+    manager_id: Uuid,
+) -> Result<()> {
+    let result_repo = TournamentResultRepo::new(state.db.clone());
+    let payout_repo = TournamentPayoutRepo::new(state.db.clone());
+    let elimination_repo = TournamentEliminationRepo::new(state.db.clone());
+
+    // Results already entered by hand are left alone
+    if !result_repo
+        .get_by_tournament(tournament.id)
+        .await?
+        .is_empty()
+    {
+        return Ok(());
+    }
+
+    let standings = elimination_repo.final_standings(tournament.id).await?;
+
+    let payout = match payout_repo.get_by_tournament(tournament.id).await? {
+        Some(payout) => Some(payout),
+        None => payout_repo.recalculate(tournament.id).await?,
+    };
+    let ladder = payout
+        .as_ref()
+        .map(payout_amounts)
+        .transpose()?
+        .unwrap_or_default();
+    let positions: Vec<i32> = standings.iter().map(|(_, position)| *position).collect();
+    let mut prizes = finishing::split_prizes(&positions, &ladder);
+
+    if let Some(deal) = PlayerDealRepo::new(state.db.clone())
+        .get_by_tournament(tournament.id)
+        .await?
+    {
+        apply_deal(&deal, &standings, &mut prizes)?;
+    }
+
+    let results = standings
+        .into_iter()
+        .zip(prizes)
+        .map(
+            |((user_id, final_position), prize_cents)| CreateTournamentResult {
+                tournament_id: tournament.id,
+                user_id,
+                final_position,
+                prize_cents,
+                notes: None,
+            },
+        )
+        .collect();
+
+    // Another elimination may have finished the tournament meanwhile
+    if !elimination_repo
+        .finalise(tournament.id, results, Some(manager_id))
+        .await?
+    {
+        return Ok(());
+    }
+    publish_clock_change(&state.db, tournament.id).await;
+
+    publish_seating_event(SeatingChangeEvent {
+        event_type: SeatingEventType::TournamentCompleted,
+        tournament_id: tournament.id.into(),
+        club_id: tournament.club_id.into(),
+        affected_assignment: None,
+        affected_player: None,
+        message: "Tournament completed, results are in".to_string(),
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok(())
+}
+
+/// Seat the player lost in an elimination, as long as nobody took it since
+async fn restorable_seat(
+    assignment_repo: &TableSeatAssignmentRepo,
+    elimination: &TournamentEliminationRow,
+) -> Result<Option<TableSeatAssignmentRow>> {
+    let Some(seat_assignment_id) = elimination.seat_assignment_id else {
+        return Ok(None);
+    };
+    let Some(assignment) = assignment_repo.get_by_id(seat_assignment_id).await? else {
+        return Ok(None);
+    };
+
+    if assignment_repo
+        .get_current_for_user(elimination.tournament_id, elimination.user_id)
+        .await?
+        .is_some()
+    {
+        return Err(async_graphql::Error::new(
+            "The player already has a seat, the elimination cannot be undone",
+        ));
+    }
+    if !assignment_repo
+        .is_seat_available(assignment.club_table_id, assignment.seat_number)
+        .await?
+    {
+        return Err(async_graphql::Error::new(format!(
+            "Seat {} was given to another player, the elimination cannot be undone",
+            assignment.seat_number
+        )));
+    }
+
+    Ok(Some(assignment))
+}
+
+fn seat_assignment(row: &TableSeatAssignmentRow) -> SeatAssignment {
+    SeatAssignment {
+        id: row.id.into(),
+        tournament_id: row.tournament_id.into(),
+        club_table_id: row.club_table_id.into(),
+        user_id: row.user_id.into(),
+        seat_number: row.seat_number,
+        stack_size: row.stack_size,
+        is_current: row.is_current,
+        assigned_at: row.assigned_at,
+        unassigned_at: None,
+        assigned_by: None, // Field not yet implemented in database
+        notes: None,
+    }
+}
+
+/// Let seating subscribers know a player left or got back their seat
+async fn publish_seat_change(
+    state: &AppState,
+    club_id: Uuid,
+    assignment: SeatAssignment,
+    event_type: SeatingEventType,
+    message: String,
+) -> Result<()> {
+    let user_id = Uuid::parse_str(assignment.user_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+    let player = UserRepo::new(state.db.clone()).get_by_id(user_id).await?;
+
+    publish_seating_event(SeatingChangeEvent {
+        event_type,
+        tournament_id: assignment.tournament_id.clone(),
+        club_id: club_id.into(),
+        affected_assignment: Some(assignment),
+        affected_player: player.map(User::from),
+        message,
+        timestamp: chrono::Utc::now(),
+    });
+
+    Ok(())
+}
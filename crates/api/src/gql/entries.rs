@@ -0,0 +1,200 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::registrations::publish_registration_change;
+use crate::gql::types::{
+    RecordTournamentEntryInput, RegistrationStatus, TournamentEntry, TournamentEntryType,
+};
+use crate::state::AppState;
+use infra::repos::{
+    CreateTournamentEntry, TournamentClockRepo, TournamentEntryRepo, TournamentLiveStatus,
+    TournamentRegistrationRepo, TournamentRepo,
+};
+
+pub struct EntryQuery;
+
+impl EntryQuery {
+    /// Get every buy-in, re-entry, rebuy and add-on of a tournament, oldest first
+    pub async fn tournament_entries(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<Vec<TournamentEntry>> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let rows = TournamentEntryRepo::new(state.db.clone())
+            .get_by_tournament(tournament_id)
+            .await?;
+
+        Ok(rows.into_iter().map(TournamentEntry::from).collect())
+    }
+}
+
+pub struct EntryMutation;
+
+impl EntryMutation {
+    /// Record a re-entry, rebuy or add-on for a player (club managers only). The
+    /// tournament's entry rules decide what is allowed and what it costs.
+    pub async fn record_tournament_entry(
+        &self,
+        ctx: &Context<'_>,
+        input: RecordTournamentEntryInput,
+    ) -> Result<TournamentEntry> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(input.tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+        let user_id = Uuid::parse_str(input.user_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let tournament = TournamentRepo::new(state.db.clone())
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        let manager = require_club_manager(ctx, tournament.club_id).await?;
+        let manager_id = Uuid::parse_str(manager.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        if !matches!(
+            tournament.live_status,
+            TournamentLiveStatus::LateRegistration
+                | TournamentLiveStatus::InProgress
+                | TournamentLiveStatus::Break
+        ) {
+            return Err(async_graphql::Error::new(
+                "Entries can only be recorded while the tournament is running",
+            ));
+        }
+
+        if let Some(last_level) = tournament.rebuy_until_level {
+            let clock = TournamentClockRepo::new(state.db.clone())
+                .get_clock(tournament_id)
+                .await?;
+            if clock.is_some_and(|clock| clock.current_level > last_level) {
+                return Err(async_graphql::Error::new(format!(
+                    "Re-entries, rebuys and add-ons closed after level {}",
+                    last_level
+                )));
+            }
+        }
+
+        let registration = TournamentRegistrationRepo::new(state.db.clone())
+            .get_by_tournament_and_user(tournament_id, user_id)
+            .await?
+            .ok_or_else(|| {
+                async_graphql::Error::new("Player not registered for this tournament")
+            })?;
+        let status = RegistrationStatus::from(registration.status.clone());
+
+        let entry_repo = TournamentEntryRepo::new(state.db.clone());
+        let previous = entry_repo
+            .count_by_registration(registration.id, input.entry_type.into())
+            .await?;
+        let still_in = matches!(
+            status,
+            RegistrationStatus::CheckedIn | RegistrationStatus::Seated
+        );
+
+        let (amount_cents, max_entries) = match input.entry_type {
+            TournamentEntryType::BuyIn => {
+                return Err(async_graphql::Error::new(
+                    "Buy-ins are recorded when the player registers",
+                ));
+            }
+            TournamentEntryType::Reentry => {
+                if status != RegistrationStatus::Busted {
+                    return Err(async_graphql::Error::new(
+                        "Only busted players can re-enter",
+                    ));
+                }
+                if previous >= tournament.max_reentries as i64 {
+                    return Err(async_graphql::Error::new(format!(
+                        "Player already used the {} re-entries allowed",
+                        tournament.max_reentries
+                    )));
+                }
+                (tournament.buy_in_cents, tournament.max_reentries)
+            }
+            TournamentEntryType::Rebuy => {
+                if !still_in {
+                    return Err(async_graphql::Error::new(
+                        "Only players still in the tournament can rebuy",
+                    ));
+                }
+                if previous >= tournament.max_rebuys as i64 {
+                    return Err(async_graphql::Error::new(format!(
+                        "Player already used the {} rebuys allowed",
+                        tournament.max_rebuys
+                    )));
+                }
+                (
+                    tournament.rebuy_cents.unwrap_or(tournament.buy_in_cents),
+                    tournament.max_rebuys,
+                )
+            }
+            TournamentEntryType::AddOn => {
+                let add_on_cents = tournament.add_on_cents.ok_or_else(|| {
+                    async_graphql::Error::new("This tournament doesn't offer an add-on")
+                })?;
+                if !still_in {
+                    return Err(async_graphql::Error::new(
+                        "Only players still in the tournament can take the add-on",
+                    ));
+                }
+                if previous > 0 {
+                    return Err(async_graphql::Error::new("Player already took the add-on"));
+                }
+                (add_on_cents, 1)
+            }
+        };
+
+        // Re-entries pay the full buy-in, fees and bounty included; rebuys and add-ons go
+        // to the prize pool in full
+        let (rake_cents, staff_fee_cents, bounty_cents) =
+            if input.entry_type == TournamentEntryType::Reentry {
+                (
+                    tournament.rake_cents,
+                    tournament.staff_fee_cents,
+                    tournament.bounty_cents,
+                )
+            } else {
+                (0, 0, 0)
+            };
+
+        let row = entry_repo
+            .create(
+                CreateTournamentEntry {
+                    registration_id: registration.id,
+                    entry_type: input.entry_type.into(),
+                    amount_cents,
+                    rake_cents,
+                    staff_fee_cents,
+                    bounty_cents,
+                    created_by: Some(manager_id),
+                },
+                max_entries as i64,
+            )
+            .await?
+            // Another entry for the player was recorded meanwhile
+            .ok_or_else(|| {
+                async_graphql::Error::new(
+                    "The player's entries changed meanwhile, check them and try again",
+                )
+            })?;
+
+        // A re-entry puts the player back in the field
+        if input.entry_type == TournamentEntryType::Reentry {
+            if let Some(registration) = TournamentRegistrationRepo::new(state.db.clone())
+                .get_by_id(registration.id)
+                .await?
+            {
+                publish_registration_change(state, &registration, "player_reentered").await?;
+            }
+        }
+
+        Ok(row.into())
+    }
+}
@@ -1,10 +1,21 @@
+pub mod blind_structures;
+pub mod deals;
+pub mod eliminations;
+pub mod entries;
 pub mod loaders;
 pub mod mutations;
+pub mod profiles;
 pub mod queries;
+pub mod registrations;
+pub mod results;
+pub mod rivalries;
 pub mod scalars;
 pub mod schema;
+pub mod scoring;
+pub mod seasons;
 pub mod subscriptions;
 pub mod tournament_clock;
+pub mod tournament_series;
 pub mod types;
 
 pub use mutations::MutationRoot;
@@ -1,9 +1,13 @@
-use async_graphql::{Context, InputObject, Object, Result, ID};
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ID};
 
+use super::deals::{
+    check_icm_player_count, current_stacks, get_position_percentage, parse_payout_structure,
+    template_ladder,
+};
 use super::subscriptions::{publish_registration_event, publish_seating_event};
 use super::types::{
     AssignPlayerToSeatInput, AssignTableToTournamentInput, AssignmentStrategy, AuthPayload,
-    BalanceTablesInput, CheckInPlayerInput, CheckInResponse, CreateOAuthClientInput,
+    BalanceTablesInput, BountyFormat, CheckInPlayerInput, CheckInResponse, CreateOAuthClientInput,
     CreateOAuthClientResponse, DealType, EnterTournamentResultsInput,
     EnterTournamentResultsResponse, MovePlayerInput, OAuthCallbackInput, OAuthClient,
     OAuthUrlResponse, PlayerDeal, PlayerDealInput, PlayerPositionInput, PlayerRegistrationEvent,
@@ -19,11 +23,13 @@ use crate::auth::{
 use crate::state::AppState;
 use infra::models::TournamentRow;
 use infra::repos::{
-    ClubTableRepo, CreatePlayerDeal, CreateSeatAssignment, CreateTournamentRegistration,
-    CreateTournamentResult, PayoutTemplateRepo, PlayerDealRepo, TableSeatAssignmentRepo,
-    TournamentLiveStatus, TournamentRegistrationRepo, TournamentRepo, TournamentResultRepo,
-    UpdateSeatAssignment, UserRepo,
+    ClubTableRepo, CreatePlayerDeal, CreateSeatAssignment, CreateTournament,
+    CreateTournamentRegistration, CreateTournamentResult, PayoutTemplateRepo, PlayerDealRepo,
+    TableSeatAssignmentRepo, TagRepo, TournamentEliminationRepo, TournamentEntryRepo,
+    TournamentEntryRules, TournamentLiveStatus, TournamentRegistrationRepo, TournamentRepo,
+    TournamentResultRepo, UpdateSeatAssignment, UpdateTournament, UserRepo,
 };
+use infra::{deals, icm};
 use rand::{distributions::Alphanumeric, Rng};
 use serde_json;
 use uuid::Uuid;
@@ -75,6 +81,184 @@ pub struct MutationRoot;
 pub struct CreateTournamentInput {
     pub title: String,
     pub club_id: ID,
+    pub description: Option<String>,
+    pub start_time: chrono::DateTime<chrono::Utc>,
+    pub end_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Total price paid by the player, rake and staff fee included
+    pub buy_in_cents: i32,
+    /// Part of the buy-in kept by the house (default 0)
+    pub rake_cents: Option<i32>,
+    /// Part of the buy-in paid to the dealers and staff (default 0)
+    pub staff_fee_cents: Option<i32>,
+    /// Guaranteed prize pool, e.g. 500000 for "€5,000 GTD"
+    pub guarantee_cents: Option<i32>,
+    /// Knockout format (default NONE)
+    pub bounty_format: Option<BountyFormat>,
+    /// Part of the buy-in put on each player's head (default 0)
+    pub bounty_cents: Option<i32>,
+    pub seat_cap: Option<i32>,
+    /// Minutes before the start after which players can no longer unregister (default 60)
+    pub unregister_cutoff_minutes: Option<i32>,
+    /// Re-entry, rebuy and add-on rules (default: freezeout)
+    pub entry_rules: Option<TournamentEntryRulesInput>,
+    /// Tag slugs, e.g. "freezeout" or "bounty"
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateTournamentInput {
+    pub id: ID,
+    pub title: Option<String>,
+    /// `null` removes the description
+    pub description: MaybeUndefined<String>,
+    pub start_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// `null` removes the end time
+    pub end_time: MaybeUndefined<chrono::DateTime<chrono::Utc>>,
+    pub buy_in_cents: Option<i32>,
+    pub rake_cents: Option<i32>,
+    pub staff_fee_cents: Option<i32>,
+    /// `null` removes the guarantee
+    pub guarantee_cents: MaybeUndefined<i32>,
+    pub bounty_format: Option<BountyFormat>,
+    pub bounty_cents: Option<i32>,
+    /// `null` removes the seat cap
+    pub seat_cap: MaybeUndefined<i32>,
+    pub unregister_cutoff_minutes: Option<i32>,
+    /// Replaces all entry rules when set
+    pub entry_rules: Option<TournamentEntryRulesInput>,
+}
+
+#[derive(InputObject, Clone, Default)]
+pub struct TournamentEntryRulesInput {
+    /// Re-entries allowed per player after busting
+    pub max_reentries: Option<i32>,
+    /// Rebuys allowed per player while still in
+    pub max_rebuys: Option<i32>,
+    /// Rebuy price, the buy-in when not set
+    pub rebuy_cents: Option<i32>,
+    /// Add-on price, no add-on when not set
+    pub add_on_cents: Option<i32>,
+    /// Chips the add-on buys
+    pub add_on_chips: Option<i32>,
+    /// Last level in which re-entries, rebuys and add-ons are allowed
+    pub rebuy_until_level: Option<i32>,
+}
+
+impl From<TournamentEntryRulesInput> for TournamentEntryRules {
+    fn from(input: TournamentEntryRulesInput) -> Self {
+        Self {
+            max_reentries: input.max_reentries.unwrap_or(0),
+            max_rebuys: input.max_rebuys.unwrap_or(0),
+            rebuy_cents: input.rebuy_cents,
+            add_on_cents: input.add_on_cents,
+            add_on_chips: input.add_on_chips,
+            rebuy_until_level: input.rebuy_until_level,
+        }
+    }
+}
+
+const DEFAULT_UNREGISTER_CUTOFF_MINUTES: i32 = 60;
+
+/// Helper function to validate the editable details of a tournament
+fn validate_tournament_details(
+    title: &str,
+    start_time: chrono::DateTime<chrono::Utc>,
+    end_time: Option<chrono::DateTime<chrono::Utc>>,
+    buy_in_cents: i32,
+    seat_cap: Option<i32>,
+    unregister_cutoff_minutes: i32,
+    entry_rules: &TournamentEntryRules,
+) -> Result<()> {
+    if title.trim().is_empty() {
+        return Err(async_graphql::Error::new(
+            "Tournament title cannot be empty",
+        ));
+    }
+    if let Some(end_time) = end_time {
+        if end_time <= start_time {
+            return Err(async_graphql::Error::new(
+                "Tournament end time must be after its start time",
+            ));
+        }
+    }
+    if buy_in_cents < 0 {
+        return Err(async_graphql::Error::new("Buy-in cannot be negative"));
+    }
+    if let Some(seat_cap) = seat_cap {
+        if seat_cap <= 0 {
+            return Err(async_graphql::Error::new("Seat cap must be positive"));
+        }
+    }
+    if unregister_cutoff_minutes < 0 {
+        return Err(async_graphql::Error::new(
+            "Unregister cutoff cannot be negative",
+        ));
+    }
+    if entry_rules.max_reentries < 0 || entry_rules.max_rebuys < 0 {
+        return Err(async_graphql::Error::new(
+            "Re-entry and rebuy limits cannot be negative",
+        ));
+    }
+    if entry_rules.rebuy_cents.is_some_and(|cents| cents < 0)
+        || entry_rules.add_on_cents.is_some_and(|cents| cents < 0)
+    {
+        return Err(async_graphql::Error::new(
+            "Rebuy and add-on prices cannot be negative",
+        ));
+    }
+    if entry_rules.add_on_chips.is_some_and(|chips| chips <= 0) {
+        return Err(async_graphql::Error::new("Add-on chips must be positive"));
+    }
+    if entry_rules.rebuy_until_level.is_some_and(|level| level < 1) {
+        return Err(async_graphql::Error::new(
+            "Rebuy period must end at level 1 or later",
+        ));
+    }
+    Ok(())
+}
+
+/// Helper function to validate how a buy-in splits into prize pool, rake and staff fee,
+/// and the guaranteed prize pool
+fn validate_prize_pool_details(
+    buy_in_cents: i32,
+    rake_cents: i32,
+    staff_fee_cents: i32,
+    guarantee_cents: Option<i32>,
+    bounty_format: BountyFormat,
+    bounty_cents: i32,
+) -> Result<()> {
+    if rake_cents < 0 || staff_fee_cents < 0 {
+        return Err(async_graphql::Error::new(
+            "Rake and staff fee cannot be negative",
+        ));
+    }
+    if rake_cents as i64 + staff_fee_cents as i64 > buy_in_cents as i64 {
+        return Err(async_graphql::Error::new(
+            "Rake and staff fee cannot exceed the buy-in",
+        ));
+    }
+    if guarantee_cents.is_some_and(|cents| cents < 0) {
+        return Err(async_graphql::Error::new("Guarantee cannot be negative"));
+    }
+    if bounty_cents < 0 {
+        return Err(async_graphql::Error::new("Bounty cannot be negative"));
+    }
+    if bounty_format == BountyFormat::None && bounty_cents > 0 {
+        return Err(async_graphql::Error::new(
+            "A bounty needs a REGULAR or PROGRESSIVE bounty format",
+        ));
+    }
+    if bounty_format != BountyFormat::None && bounty_cents == 0 {
+        return Err(async_graphql::Error::new(
+            "Bounty tournaments need a bounty amount",
+        ));
+    }
+    if rake_cents as i64 + staff_fee_cents as i64 + bounty_cents as i64 > buy_in_cents as i64 {
+        return Err(async_graphql::Error::new(
+            "Rake, staff fee and bounty cannot exceed the buy-in",
+        ));
+    }
+    Ok(())
 }
 
 #[Object]
@@ -119,53 +303,460 @@ impl MutationRoot {
         mutation.resume_tournament_clock(ctx, tournament_id).await
     }
 
-    /// Manually advance to next level
+    /// Manually advance to next level. With `expectedLevel`, the clock only advances if it
+    /// is still on that level, so a repeated request doesn't skip a level.
     async fn advance_tournament_level(
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
+        expected_level: Option<i32>,
     ) -> Result<crate::gql::types::TournamentClock> {
         let mutation = crate::gql::tournament_clock::TournamentClockMutation;
-        mutation.advance_tournament_level(ctx, tournament_id).await
+        mutation
+            .advance_tournament_level(ctx, tournament_id, expected_level)
+            .await
     }
 
-    /// Manually revert to previous level
+    /// Manually revert to previous level. With `expectedLevel`, the clock only reverts if it
+    /// is still on that level.
     async fn revert_tournament_level(
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
+        expected_level: Option<i32>,
     ) -> Result<crate::gql::types::TournamentClock> {
         let mutation = crate::gql::tournament_clock::TournamentClockMutation;
-        mutation.revert_tournament_level(ctx, tournament_id).await
+        mutation
+            .revert_tournament_level(ctx, tournament_id, expected_level)
+            .await
     }
 
-    /// Minimal example mutation creating a tournament (stub).
-    /// Replace with an INSERT via sqlx later.
+    /// Create a tournament (club managers only). Its clock is created automatically.
     async fn create_tournament(
         &self,
         ctx: &Context<'_>,
         input: CreateTournamentInput,
     ) -> Result<Tournament> {
-        let _state = ctx.data::<AppState>()?;
-        // Example: persist with sqlx here using _state.db
-
-        Ok(Tournament {
-            id: "new_tournament_id".into(),
-            title: input.title,
-            description: None,
-            club_id: input.club_id,
-            start_time: chrono::Utc::now(),
-            end_time: None,
-            buy_in_cents: 0,
-            seat_cap: None,
-            status: crate::gql::types::TournamentStatus::Upcoming,
-            live_status: crate::gql::types::TournamentLiveStatus::NotStarted,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        })
+        use crate::auth::permissions::require_club_manager;
+
+        let club_id = Uuid::parse_str(input.club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        require_club_manager(ctx, club_id).await?;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+        let tag_repo = TagRepo::new(state.db.clone());
+
+        let name = input.title.trim().to_string();
+        let unregister_cutoff_minutes = input
+            .unregister_cutoff_minutes
+            .unwrap_or(DEFAULT_UNREGISTER_CUTOFF_MINUTES);
+        let entry_rules = TournamentEntryRules::from(input.entry_rules.unwrap_or_default());
+        validate_tournament_details(
+            &name,
+            input.start_time,
+            input.end_time,
+            input.buy_in_cents,
+            input.seat_cap,
+            unregister_cutoff_minutes,
+            &entry_rules,
+        )?;
+        let rake_cents = input.rake_cents.unwrap_or(0);
+        let staff_fee_cents = input.staff_fee_cents.unwrap_or(0);
+        let bounty_format = input.bounty_format.unwrap_or(BountyFormat::None);
+        let bounty_cents = input.bounty_cents.unwrap_or(0);
+        validate_prize_pool_details(
+            input.buy_in_cents,
+            rake_cents,
+            staff_fee_cents,
+            input.guarantee_cents,
+            bounty_format,
+            bounty_cents,
+        )?;
+
+        let mut tag_slugs = input.tags.unwrap_or_default();
+        tag_slugs.sort();
+        tag_slugs.dedup();
+        let tags = tag_repo.get_by_slugs(&tag_slugs).await?;
+        if let Some(unknown) = tag_slugs
+            .iter()
+            .find(|slug| !tags.iter().any(|tag| &tag.slug == *slug))
+        {
+            return Err(async_graphql::Error::new(format!(
+                "Unknown tag: {}",
+                unknown
+            )));
+        }
+
+        let row = tournament_repo
+            .create(CreateTournament {
+                club_id,
+                name,
+                description: input.description,
+                start_time: input.start_time,
+                end_time: input.end_time,
+                buy_in_cents: input.buy_in_cents,
+                rake_cents,
+                staff_fee_cents,
+                guarantee_cents: input.guarantee_cents,
+                bounty_format: bounty_format.into(),
+                bounty_cents,
+                seat_cap: input.seat_cap,
+                unregister_cutoff_minutes,
+                entry_rules,
+                tag_ids: tags.iter().map(|tag| tag.id).collect(),
+            })
+            .await?;
+
+        Ok(Tournament::from(row))
+    }
+
+    /// Edit tournament details (club managers only). Omitted fields are left unchanged.
+    async fn update_tournament(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateTournamentInput,
+    ) -> Result<Tournament> {
+        use crate::auth::permissions::require_club_manager;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+
+        let tournament_id = Uuid::parse_str(input.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let existing = tournament_repo
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        require_club_manager(ctx, existing.club_id).await?;
+
+        if matches!(
+            existing.live_status,
+            TournamentLiveStatus::Finished | TournamentLiveStatus::Cancelled
+        ) {
+            return Err(async_graphql::Error::new(
+                "Finished or cancelled tournaments cannot be edited",
+            ));
+        }
+
+        let name = input.title.map(|title| title.trim().to_string());
+        let entry_rules = input.entry_rules.map(TournamentEntryRules::from);
+        let description: Option<Option<String>> = input.description.into();
+        let end_time: Option<Option<chrono::DateTime<chrono::Utc>>> = input.end_time.into();
+        let seat_cap: Option<Option<i32>> = input.seat_cap.into();
+        let guarantee_cents: Option<Option<i32>> = input.guarantee_cents.into();
+        validate_tournament_details(
+            name.as_deref().unwrap_or(&existing.name),
+            input.start_time.unwrap_or(existing.start_time),
+            end_time.unwrap_or(existing.end_time),
+            input.buy_in_cents.unwrap_or(existing.buy_in_cents),
+            seat_cap.unwrap_or(existing.seat_cap),
+            input
+                .unregister_cutoff_minutes
+                .unwrap_or(existing.unregister_cutoff_minutes),
+            entry_rules
+                .as_ref()
+                .unwrap_or(&TournamentEntryRules::from(&existing)),
+        )?;
+        validate_prize_pool_details(
+            input.buy_in_cents.unwrap_or(existing.buy_in_cents),
+            input.rake_cents.unwrap_or(existing.rake_cents),
+            input.staff_fee_cents.unwrap_or(existing.staff_fee_cents),
+            guarantee_cents.unwrap_or(existing.guarantee_cents),
+            input
+                .bounty_format
+                .unwrap_or_else(|| BountyFormat::from(existing.bounty_format.clone())),
+            input.bounty_cents.unwrap_or(existing.bounty_cents),
+        )?;
+
+        let row = tournament_repo
+            .update(
+                tournament_id,
+                UpdateTournament {
+                    name,
+                    description,
+                    start_time: input.start_time,
+                    end_time,
+                    buy_in_cents: input.buy_in_cents,
+                    rake_cents: input.rake_cents,
+                    staff_fee_cents: input.staff_fee_cents,
+                    guarantee_cents,
+                    bounty_format: input.bounty_format.map(Into::into),
+                    bounty_cents: input.bounty_cents,
+                    seat_cap,
+                    unregister_cutoff_minutes: input.unregister_cutoff_minutes,
+                    entry_rules,
+                },
+            )
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        // A higher or removed seat cap lets waitlisted players in
+        if seat_cap.is_some() {
+            crate::gql::registrations::promote_from_waitlist(state, tournament_id).await?;
+        }
+
+        Ok(Tournament::from(row))
+    }
+
+    /// Cancel a tournament and every registration in it (club managers only)
+    async fn cancel_tournament(&self, ctx: &Context<'_>, tournament_id: ID) -> Result<Tournament> {
+        use crate::auth::permissions::require_club_manager;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+        let user_repo = UserRepo::new(state.db.clone());
+
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let existing = tournament_repo
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        require_club_manager(ctx, existing.club_id).await?;
+
+        // The status is checked again under the tournament lock, so a tournament finished
+        // or cancelled in the meantime is reported the same way
+        let Some((row, cancelled_registrations)) = tournament_repo.cancel(tournament_id).await?
+        else {
+            let latest = tournament_repo
+                .get(tournament_id)
+                .await?
+                .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+            return Err(async_graphql::Error::new(match latest.live_status {
+                TournamentLiveStatus::Finished => "Finished tournaments cannot be cancelled",
+                TournamentLiveStatus::Cancelled => "Tournament is already cancelled",
+                _ => "Tournament status changed in the meantime, please try again",
+            }));
+        };
+        crate::gql::tournament_clock::publish_clock_change(&state.db, tournament_id).await;
+
+        // Let every affected player know their registration is gone
+        for registration in cancelled_registrations {
+            if let Some(user_row) = user_repo.get_by_id(registration.user_id).await? {
+                let player = TournamentPlayer {
+                    registration: TournamentRegistration {
+                        id: registration.id.into(),
+                        tournament_id: registration.tournament_id.into(),
+                        user_id: registration.user_id.into(),
+                        registration_time: registration.registration_time,
+                        status: registration.status.into(),
+                        notes: registration.notes,
+                    },
+                    user: User {
+                        id: user_row.id.into(),
+                        email: user_row.email,
+                        username: user_row.username,
+                        first_name: user_row.first_name,
+                        last_name: user_row.last_name,
+                        phone: user_row.phone,
+                        is_active: user_row.is_active,
+                        role: crate::gql::types::Role::from(user_row.role),
+                    },
+                };
+
+                publish_registration_event(PlayerRegistrationEvent {
+                    tournament_id: tournament_id.into(),
+                    player,
+                    event_type: "tournament_cancelled".to_string(),
+                });
+            }
+        }
+
+        Ok(Tournament::from(row))
+    }
+
+    /// Delete a tournament that has not been played yet (club managers only)
+    async fn delete_tournament(&self, ctx: &Context<'_>, tournament_id: ID) -> Result<bool> {
+        use crate::auth::permissions::require_club_manager;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let existing = tournament_repo
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+        require_club_manager(ctx, existing.club_id).await?;
+
+        let not_started = matches!(
+            existing.live_status,
+            TournamentLiveStatus::NotStarted
+                | TournamentLiveStatus::RegistrationOpen
+                | TournamentLiveStatus::Cancelled
+        );
+        if !not_started || tournament_repo.has_play_history(tournament_id).await? {
+            return Err(async_graphql::Error::new(
+                "Tournaments that have already been played cannot be deleted; cancel them instead",
+            ));
+        }
+
+        Ok(tournament_repo.delete(tournament_id).await?)
+    }
+
+    /// Replace the blind structure of a tournament (club managers only)
+    async fn replace_tournament_structure(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        levels: Vec<crate::gql::types::BlindLevelInput>,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .replace_tournament_structure(ctx, tournament_id, levels)
+            .await
+    }
+
+    /// Insert a blind level at the given level number
+    async fn insert_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+        level: crate::gql::types::BlindLevelInput,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .insert_tournament_structure_level(ctx, tournament_id, level_number, level)
+            .await
+    }
+
+    /// Move a blind level to a new level number
+    async fn move_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+        new_level_number: i32,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .move_tournament_structure_level(ctx, tournament_id, level_number, new_level_number)
+            .await
+    }
+
+    /// Delete a blind level
+    async fn delete_tournament_structure_level(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        level_number: i32,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .delete_tournament_structure_level(ctx, tournament_id, level_number)
+            .await
+    }
+
+    /// Generate a blind structure and save it for a tournament
+    async fn save_generated_blind_structure(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        input: crate::gql::types::GenerateBlindStructureInput,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .save_generated_blind_structure(ctx, tournament_id, input)
+            .await
+    }
+
+    /// Replace a tournament's structure with a blind structure template
+    async fn apply_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+        template_id: ID,
+    ) -> Result<Vec<crate::gql::types::TournamentStructure>> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .apply_blind_structure_template(ctx, tournament_id, template_id)
+            .await
+    }
+
+    /// Save a named blind structure template for a club (club managers only)
+    async fn create_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::CreateBlindStructureTemplateInput,
+    ) -> Result<crate::gql::types::BlindStructureTemplate> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation.create_blind_structure_template(ctx, input).await
+    }
+
+    /// Edit a blind structure template
+    async fn update_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdateBlindStructureTemplateInput,
+    ) -> Result<crate::gql::types::BlindStructureTemplate> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation.update_blind_structure_template(ctx, input).await
+    }
+
+    /// Delete a blind structure template
+    async fn delete_blind_structure_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: ID,
+    ) -> Result<bool> {
+        let mutation = crate::gql::blind_structures::BlindStructureMutation;
+        mutation
+            .delete_blind_structure_template(ctx, template_id)
+            .await
+    }
+
+    /// Create a recurring tournament series (club managers only)
+    async fn create_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::CreateTournamentSeriesInput,
+    ) -> Result<crate::gql::types::TournamentSeries> {
+        let mutation = crate::gql::tournament_series::TournamentSeriesMutation;
+        mutation.create_tournament_series(ctx, input).await
+    }
+
+    /// Edit a recurring tournament series, optionally updating its upcoming instances
+    async fn update_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdateTournamentSeriesInput,
+    ) -> Result<crate::gql::types::TournamentSeries> {
+        let mutation = crate::gql::tournament_series::TournamentSeriesMutation;
+        mutation.update_tournament_series(ctx, input).await
+    }
+
+    /// Stop creating new tournaments for a series
+    async fn pause_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<crate::gql::types::TournamentSeries> {
+        let mutation = crate::gql::tournament_series::TournamentSeriesMutation;
+        mutation.pause_tournament_series(ctx, series_id).await
     }
 
-    /// Register a user for a tournament.
+    /// Resume a paused series
+    async fn resume_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<crate::gql::types::TournamentSeries> {
+        let mutation = crate::gql::tournament_series::TournamentSeriesMutation;
+        mutation.resume_tournament_series(ctx, series_id).await
+    }
+
+    /// Register a user for a tournament. Once the seat cap is reached new registrations
+    /// go on the waitlist.
     async fn register_for_tournament(
         &self,
         ctx: &Context<'_>,
@@ -207,7 +798,22 @@ impl MutationRoot {
             notes: input.notes.clone(),
         };
 
-        let row = registration_repo.create(create_data).await?;
+        // The tournament's status and the player's registration are checked under its lock
+        let Some(row) = registration_repo.create(create_data).await? else {
+            TournamentRepo::new(state.db.clone())
+                .get(tournament_id)
+                .await?
+                .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+            let existing = registration_repo
+                .get_by_tournament_and_user(tournament_id, user_id)
+                .await?;
+            return Err(async_graphql::Error::new(match existing {
+                Some(registration) if registration.status != "cancelled" => {
+                    "Player is already registered for this tournament"
+                }
+                _ => "Finished or cancelled tournaments don't take registrations",
+            }));
+        };
 
         let tournament_registration = TournamentRegistration {
             id: row.id.into(),
@@ -236,10 +842,14 @@ impl MutationRoot {
                 user,
             };
 
+            let event_type = match tournament_registration.status {
+                RegistrationStatus::Waitlisted => "player_waitlisted",
+                _ => "player_registered",
+            };
             let event = PlayerRegistrationEvent {
                 tournament_id: tournament_id.into(),
                 player,
-                event_type: "player_registered".to_string(),
+                event_type: event_type.to_string(),
             };
 
             publish_registration_event(event);
@@ -248,6 +858,38 @@ impl MutationRoot {
         Ok(tournament_registration)
     }
 
+    /// Cancel your own registration, until the tournament's unregister cutoff
+    async fn unregister_from_tournament(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<TournamentRegistration> {
+        let mutation = crate::gql::registrations::RegistrationMutation;
+        mutation
+            .unregister_from_tournament(ctx, tournament_id)
+            .await
+    }
+
+    /// Change the status of a player's registration (club managers only)
+    async fn update_registration_status(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdateRegistrationStatusInput,
+    ) -> Result<TournamentRegistration> {
+        let mutation = crate::gql::registrations::RegistrationMutation;
+        mutation.update_registration_status(ctx, input).await
+    }
+
+    /// Record a re-entry, rebuy or add-on for a player (club managers only)
+    async fn record_tournament_entry(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::RecordTournamentEntryInput,
+    ) -> Result<crate::gql::types::TournamentEntry> {
+        let mutation = crate::gql::entries::EntryMutation;
+        mutation.record_tournament_entry(ctx, input).await
+    }
+
     /// Check in a player for a tournament with optional auto-assignment
     async fn check_in_player(
         &self,
@@ -718,10 +1360,16 @@ impl MutationRoot {
             .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
 
         // Calculate payouts
-        let total_prize_pool =
-            calculate_prize_pool(&tournament, input.player_positions.len() as i32)?;
+        let total_prize_pool = calculate_prize_pool(
+            &TournamentEntryRepo::new(state.db.clone()),
+            &tournament,
+            input.player_positions.len() as i32,
+        )
+        .await?;
         let payouts = calculate_payouts(
             &payout_repo,
+            &TableSeatAssignmentRepo::new(state.db.clone()),
+            tournament_id,
             input.payout_template_id.as_ref(),
             &input.player_positions,
             total_prize_pool,
@@ -755,6 +1403,7 @@ impl MutationRoot {
                     DealType::EvenSplit => "even_split".to_string(),
                     DealType::Icm => "icm".to_string(),
                     DealType::Custom => "custom".to_string(),
+                    DealType::ChipChop => "chip_chop".to_string(),
                 },
                 affected_positions: deal_input.affected_positions.clone(),
                 custom_payouts,
@@ -769,7 +1418,7 @@ impl MutationRoot {
         };
 
         // Create tournament results
-        let mut results = Vec::new();
+        let mut result_ids = Vec::new();
         for (position_input, payout_amount) in input.player_positions.iter().zip(payouts.iter()) {
             let user_id = Uuid::parse_str(position_input.user_id.as_str())
                 .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
@@ -783,18 +1432,21 @@ impl MutationRoot {
             };
 
             let result_row = result_repo.create(result_data).await?;
-            results.push(TournamentResult {
-                id: result_row.id.into(),
-                tournament_id: result_row.tournament_id.into(),
-                user_id: result_row.user_id.into(),
-                final_position: result_row.final_position,
-                prize_cents: result_row.prize_cents,
-                points: result_row.points,
-                notes: result_row.notes,
-                created_at: result_row.created_at,
-            });
+            result_ids.push(result_row.id);
         }
 
+        // Bounties are won on top of the prize ladder
+        TournamentEliminationRepo::new(state.db.clone())
+            .apply_bounties_to_results(tournament_id)
+            .await?;
+        let results = result_repo
+            .get_by_tournament(tournament_id)
+            .await?
+            .into_iter()
+            .filter(|row| result_ids.contains(&row.id))
+            .map(TournamentResult::from)
+            .collect();
+
         // Convert deal to GraphQL type
         let gql_deal = if let Some(deal_row) = deal {
             let custom_payouts = if let Some(payouts_json) = &deal_row.custom_payouts {
@@ -822,6 +1474,7 @@ impl MutationRoot {
                 "even_split" => DealType::EvenSplit,
                 "icm" => DealType::Icm,
                 "custom" => DealType::Custom,
+                "chip_chop" => DealType::ChipChop,
                 _ => DealType::EvenSplit,
             };
 
@@ -1220,6 +1873,11 @@ impl MutationRoot {
             crate::gql::types::TournamentLiveStatus::Break => TournamentLiveStatus::Break,
             crate::gql::types::TournamentLiveStatus::FinalTable => TournamentLiveStatus::FinalTable,
             crate::gql::types::TournamentLiveStatus::Finished => TournamentLiveStatus::Finished,
+            crate::gql::types::TournamentLiveStatus::Cancelled => {
+                return Err(async_graphql::Error::new(
+                    "Use cancelTournament to cancel a tournament",
+                ));
+            }
         };
 
         let tournament_row = tournament_repo
@@ -1239,20 +1897,7 @@ impl MutationRoot {
         };
         publish_seating_event(event);
 
-        Ok(Tournament {
-            id: tournament_row.id.into(),
-            title: tournament_row.name.clone(),
-            description: tournament_row.description.clone(),
-            club_id: tournament_row.club_id.into(),
-            start_time: tournament_row.start_time,
-            end_time: tournament_row.end_time,
-            buy_in_cents: tournament_row.buy_in_cents,
-            seat_cap: tournament_row.seat_cap,
-            status: tournament_row.calculate_status().into(),
-            live_status: tournament_row.live_status.into(),
-            created_at: tournament_row.created_at,
-            updated_at: tournament_row.updated_at,
-        })
+        Ok(Tournament::from(tournament_row))
     }
 
     /// Automatically balance tables (managers only)
@@ -1339,7 +1984,7 @@ impl MutationRoot {
                 let excess_count = players.len() - target_per_table as usize;
                 // Take the most recently assigned players for moving (they're likely less settled)
                 let mut sorted_players = players.clone();
-                sorted_players.sort_by(|a, b| b.assigned_at.cmp(&a.assigned_at));
+                sorted_players.sort_by_key(|p| std::cmp::Reverse(p.assigned_at));
                 excess_players.extend(sorted_players.into_iter().take(excess_count));
             }
         }
@@ -1423,92 +2068,119 @@ impl MutationRoot {
         Ok(moves)
     }
 
-    /// Eliminate a player from the tournament (managers only)
+    /// Eliminate a player from the tournament (club managers only), optionally crediting
+    /// the eliminator with the bounty. The finishing position follows from the players
+    /// remaining.
     async fn eliminate_player(
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
         user_id: ID,
+        eliminated_by: Option<ID>,
         notes: Option<String>,
     ) -> Result<bool> {
-        use crate::auth::permissions::require_role;
+        let mutation = crate::gql::eliminations::EliminationMutation;
+        mutation
+            .eliminate_player(ctx, tournament_id, user_id, eliminated_by, notes)
+            .await
+    }
 
-        // Require manager role
-        let manager = require_role(ctx, Role::Manager).await?;
+    /// Eliminate the players busting in the same hand (club managers only). Players at
+    /// the same table finish in order of their stacks, players at different tables tie
+    /// with the players holding the same rank at their own table.
+    async fn eliminate_players(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::EliminatePlayersInput,
+    ) -> Result<Vec<crate::gql::types::TournamentElimination>> {
+        let mutation = crate::gql::eliminations::EliminationMutation;
+        mutation.eliminate_players(ctx, input).await
+    }
 
-        let state = ctx.data::<AppState>()?;
-        let assignment_repo = TableSeatAssignmentRepo::new(state.db.clone());
+    /// Undo the last elimination of a tournament and give the player their seat back
+    /// (club managers only)
+    async fn undo_last_elimination(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<crate::gql::types::TournamentElimination> {
+        let mutation = crate::gql::eliminations::EliminationMutation;
+        mutation.undo_last_elimination(ctx, tournament_id).await
+    }
 
-        let tournament_uuid = Uuid::parse_str(tournament_id.as_str())
-            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
-        let user_uuid = Uuid::parse_str(user_id.as_str())
-            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
-        let manager_id = Uuid::parse_str(manager.id.as_str())
-            .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))?;
+    /// Correct a tournament result and recalculate the tournament's points (club managers
+    /// only)
+    async fn correct_tournament_result(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::CorrectTournamentResultInput,
+    ) -> Result<TournamentResult> {
+        let mutation = crate::gql::results::ResultMutation;
+        mutation.correct_tournament_result(ctx, input).await
+    }
 
-        // Get current assignment for user
-        let current_assignment = assignment_repo
-            .get_current_for_user(tournament_uuid, user_uuid)
-            .await?;
+    /// Delete a tournament result and recalculate the tournament's points (club managers
+    /// only)
+    async fn delete_tournament_result(
+        &self,
+        ctx: &Context<'_>,
+        result_id: ID,
+        reason: Option<String>,
+    ) -> Result<bool> {
+        let mutation = crate::gql::results::ResultMutation;
+        mutation
+            .delete_tournament_result(ctx, result_id, reason)
+            .await
+    }
 
-        if let Some(assignment) = current_assignment {
-            // Update the assignment with elimination notes and unassign
-            let update_data = UpdateSeatAssignment {
-                stack_size: Some(0), // Set stack to 0 to indicate elimination
-                notes: notes
-                    .clone()
-                    .or_else(|| Some("Player eliminated".to_string())),
-            };
+    /// Choose the leaderboard scoring formula of a club (club managers only)
+    async fn update_club_scoring_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdateClubScoringSettingsInput,
+    ) -> Result<crate::gql::types::ClubScoringSettings> {
+        let mutation = crate::gql::scoring::ScoringMutation;
+        mutation.update_club_scoring_settings(ctx, input).await
+    }
 
-            assignment_repo.update(assignment.id, update_data).await?;
-            assignment_repo
-                .unassign(assignment.id, Some(manager_id))
-                .await?;
+    /// Create a league season (club managers only)
+    async fn create_season(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::CreateSeasonInput,
+    ) -> Result<crate::gql::types::Season> {
+        let mutation = crate::gql::seasons::SeasonMutation;
+        mutation.create_season(ctx, input).await
+    }
 
-            // Get player info for the event
-            let user_repo = UserRepo::new(state.db.clone());
-            let player = user_repo.get_by_id(user_uuid).await?;
+    /// Update an open league season (club managers only)
+    async fn update_season(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdateSeasonInput,
+    ) -> Result<crate::gql::types::Season> {
+        let mutation = crate::gql::seasons::SeasonMutation;
+        mutation.update_season(ctx, input).await
+    }
 
-            // Publish seating change event
-            let club_id = get_club_id_for_tournament(&state.db, tournament_uuid).await?;
-            let event = SeatingChangeEvent {
-                event_type: SeatingEventType::PlayerEliminated,
-                tournament_id: tournament_uuid.into(),
-                club_id: club_id.into(),
-                affected_assignment: Some(SeatAssignment {
-                    id: assignment.id.into(),
-                    tournament_id: assignment.tournament_id.into(),
-                    club_table_id: assignment.club_table_id.into(),
-                    user_id: assignment.user_id.into(),
-                    seat_number: assignment.seat_number,
-                    stack_size: Some(0),
-                    is_current: false,
-                    assigned_at: assignment.assigned_at,
-                    unassigned_at: Some(chrono::Utc::now()),
-                    assigned_by: None, // Field not yet implemented in database
-                    notes: notes.or_else(|| Some("Player eliminated".to_string())),
-                }),
-                affected_player: player.map(|p| User {
-                    id: p.id.into(),
-                    email: p.email,
-                    username: p.username,
-                    first_name: p.first_name,
-                    last_name: p.last_name,
-                    phone: p.phone,
-                    is_active: p.is_active,
-                    role: crate::gql::types::Role::from(p.role),
-                }),
-                message: "Player eliminated from tournament".to_string(),
-                timestamp: chrono::Utc::now(),
-            };
-            publish_seating_event(event);
+    /// Close a league season and freeze its standings (club managers only)
+    async fn close_season(
+        &self,
+        ctx: &Context<'_>,
+        season_id: ID,
+    ) -> Result<crate::gql::types::Season> {
+        let mutation = crate::gql::seasons::SeasonMutation;
+        mutation.close_season(ctx, season_id).await
+    }
 
-            Ok(true)
-        } else {
-            Err(async_graphql::Error::new(
-                "Player not currently assigned to a seat",
-            ))
-        }
+    /// Choose what the authenticated player shows on their public profile
+    async fn update_my_privacy_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: crate::gql::types::UpdatePrivacySettingsInput,
+    ) -> Result<crate::gql::types::PrivacySettings> {
+        let mutation = crate::gql::profiles::ProfileMutation;
+        mutation.update_my_privacy_settings(ctx, input).await
     }
 }
 
@@ -1604,14 +2276,26 @@ async fn get_user_by_id(state: &AppState, user_id: Uuid) -> Result<User> {
 
 // Payout calculation functions
 
-fn calculate_prize_pool(tournament: &TournamentRow, player_count: i32) -> Result<i32> {
-    // Calculate total prize pool based on buy-in and player count
-    let total_pool = tournament.buy_in_cents * player_count;
-    Ok(total_pool)
+async fn calculate_prize_pool(
+    entry_repo: &TournamentEntryRepo,
+    tournament: &TournamentRow,
+    player_count: i32,
+) -> Result<i32> {
+    // Everything paid in: buy-ins, re-entries, rebuys and add-ons
+    let mut collected = entry_repo.prize_pool(tournament.id).await?;
+    if collected == 0 {
+        // Results entered without any recorded entries: one buy-in per player, without fees
+        collected = tournament.prize_contribution_cents * player_count;
+    }
+
+    // The club covers any overlay on a guaranteed prize pool
+    Ok(collected.max(tournament.guarantee_cents.unwrap_or(0)))
 }
 
 async fn calculate_payouts(
     payout_repo: &PayoutTemplateRepo,
+    seat_repo: &TableSeatAssignmentRepo,
+    tournament_id: Uuid,
     template_id: Option<&ID>,
     positions: &[PlayerPositionInput],
     total_prize_pool: i32,
@@ -1672,31 +2356,43 @@ async fn calculate_payouts(
                     }
                 }
             }
-            DealType::Icm => {
-                // ICM calculation would be more complex - for now, fall back to even split
-                let affected_total = if let Some(template_id) = template_id {
-                    calculate_template_total(
-                        payout_repo,
-                        template_id,
-                        &deal_input.affected_positions,
-                        total_prize_pool,
-                    )
-                    .await?
+            DealType::Icm | DealType::ChipChop => {
+                let template_id = template_id.ok_or_else(|| {
+                    async_graphql::Error::new("ICM and chip chop deals require a payout template")
+                })?;
+
+                // Remaining payout ladder, best affected position first
+                let mut deal_positions = deal_input.affected_positions.clone();
+                deal_positions.sort_unstable();
+                deal_positions.dedup();
+                let ladder =
+                    template_ladder(payout_repo, template_id, &deal_positions, total_prize_pool)
+                        .await?;
+
+                let deal_players: Vec<&PlayerPositionInput> = positions
+                    .iter()
+                    .filter(|p| deal_input.affected_positions.contains(&p.final_position))
+                    .collect();
+
+                let mut user_ids = Vec::with_capacity(deal_players.len());
+                for position in &deal_players {
+                    user_ids.push(Uuid::parse_str(position.user_id.as_str()).map_err(|e| {
+                        async_graphql::Error::new(format!("Invalid user ID: {}", e))
+                    })?);
+                }
+                let stacks = current_stacks(seat_repo, tournament_id, &user_ids).await?;
+
+                let amounts = if deal_input.deal_type == DealType::Icm {
+                    check_icm_player_count(stacks.len())?;
+                    icm::icm_deal(&stacks, &ladder)
                 } else {
-                    total_prize_pool
+                    deals::chip_chop_deal(&stacks, &ladder)
                 };
 
-                let per_player = affected_total / deal_input.affected_positions.len() as i32;
-
-                for position in positions {
-                    if deal_input
-                        .affected_positions
-                        .contains(&position.final_position)
-                    {
-                        let index = (position.final_position - 1) as usize;
-                        if index < payouts.len() {
-                            payouts[index] = per_player;
-                        }
+                for (position, amount) in deal_players.iter().zip(amounts) {
+                    let index = (position.final_position - 1) as usize;
+                    if index < payouts.len() {
+                        payouts[index] = amount;
                     }
                 }
             }
@@ -1789,7 +2485,7 @@ fn calculate_deal_total(deal_input: &PlayerDealInput, payouts: &[i32]) -> Result
             }
         }
         _ => {
-            // For even split and ICM, calculate from affected positions
+            // For even split, ICM and chip chop, calculate from affected positions
             let mut total = 0;
             for position in &deal_input.affected_positions {
                 let index = (*position - 1) as usize;
@@ -1801,38 +2497,3 @@ fn calculate_deal_total(deal_input: &PlayerDealInput, payouts: &[i32]) -> Result
         }
     }
 }
-
-fn parse_payout_structure(structure: &serde_json::Value) -> Result<Vec<(i32, f64)>> {
-    let array = structure
-        .as_array()
-        .ok_or_else(|| async_graphql::Error::new("Invalid payout structure format"))?;
-
-    let mut payouts = Vec::new();
-    for item in array {
-        let obj = item
-            .as_object()
-            .ok_or_else(|| async_graphql::Error::new("Invalid payout item format"))?;
-
-        let position = obj
-            .get("position")
-            .and_then(|v| v.as_i64())
-            .ok_or_else(|| async_graphql::Error::new("Missing or invalid position"))?
-            as i32;
-
-        let percentage = obj
-            .get("percentage")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| async_graphql::Error::new("Missing or invalid percentage"))?;
-
-        payouts.push((position, percentage));
-    }
-
-    Ok(payouts)
-}
-
-fn get_position_percentage(payout_structure: &[(i32, f64)], position: i32) -> Option<f64> {
-    payout_structure
-        .iter()
-        .find(|(pos, _)| *pos == position)
-        .map(|(_, percentage)| *percentage)
-}
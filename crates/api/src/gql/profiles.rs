@@ -0,0 +1,140 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::gql::types::{
+    ClubProfileStatistics, FinishPositionCount, PlayerProfile, PrivacySettings, ProfileResult,
+    ProfileStatistics, UpdatePrivacySettingsInput, YearlyPoints,
+};
+use crate::state::AppState;
+use infra::profile;
+use infra::repos::{PlayerProfileRepo, UserPrivacySettingsRepo, UserRepo};
+
+const BIGGEST_CASHES: usize = 5;
+const RECENT_RESULTS: usize = 10;
+
+/// The authenticated player, `None` for anonymous requests
+pub(crate) fn viewer_id(ctx: &Context<'_>) -> Option<Uuid> {
+    ctx.data_opt::<Claims>()
+        .and_then(|claims| Uuid::parse_str(&claims.sub).ok())
+}
+
+fn authenticated_user_id(ctx: &Context<'_>) -> Result<Uuid> {
+    let claims = ctx
+        .data::<Claims>()
+        .map_err(|_| async_graphql::Error::new("Authentication required"))?;
+
+    Uuid::parse_str(&claims.sub)
+        .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))
+}
+
+pub struct ProfileQuery;
+
+impl ProfileQuery {
+    /// Get the public profile of a player. Players who hide their winnings still see
+    /// them on their own profile.
+    pub async fn player_profile(&self, ctx: &Context<'_>, user_id: ID) -> Result<PlayerProfile> {
+        let state = ctx.data::<AppState>()?;
+        let user_id = Uuid::parse_str(user_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        UserRepo::new(state.db.clone())
+            .get_by_id(user_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("User not found"))?;
+
+        let winnings_hidden = UserPrivacySettingsRepo::new(state.db.clone())
+            .get_by_user(user_id)
+            .await?
+            .is_some_and(|settings| settings.hide_winnings);
+        let show_winnings = !winnings_hidden || viewer_id(ctx) == Some(user_id);
+
+        let results = PlayerProfileRepo::new(state.db.clone())
+            .results(user_id)
+            .await?;
+
+        let (profit_over_time, biggest_cashes) = if show_winnings {
+            (
+                profile::cumulative_profit(&results)
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                profile::biggest_cashes(&results, BIGGEST_CASHES)
+                    .into_iter()
+                    .map(|result| ProfileResult::new(result, true))
+                    .collect(),
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        Ok(PlayerProfile {
+            user_id: user_id.into(),
+            winnings_hidden,
+            lifetime: ProfileStatistics::new(&profile::profile_stats(&results), show_winnings),
+            clubs: profile::club_stats(&results)
+                .into_iter()
+                .map(|club| ClubProfileStatistics {
+                    club_id: club.club_id.into(),
+                    club_name: club.club_name,
+                    statistics: ProfileStatistics::new(&club.stats, show_winnings),
+                })
+                .collect(),
+            profit_over_time,
+            biggest_cashes,
+            finish_positions: profile::finish_positions(&results)
+                .into_iter()
+                .map(|count| FinishPositionCount {
+                    position: count.position,
+                    count: count.count as i32,
+                })
+                .collect(),
+            points_by_year: profile::points_by_year(&results)
+                .into_iter()
+                .map(|year| YearlyPoints {
+                    year: year.year,
+                    points: year.points as i32,
+                    tournaments_played: year.tournaments as i32,
+                })
+                .collect(),
+            recent_results: profile::recent_results(&results, RECENT_RESULTS)
+                .into_iter()
+                .map(|result| ProfileResult::new(result, show_winnings))
+                .collect(),
+        })
+    }
+
+    /// Get what the authenticated player shows on their public profile
+    pub async fn my_privacy_settings(&self, ctx: &Context<'_>) -> Result<PrivacySettings> {
+        let state = ctx.data::<AppState>()?;
+        let user_id = authenticated_user_id(ctx)?;
+
+        let row = UserPrivacySettingsRepo::new(state.db.clone())
+            .get_by_user(user_id)
+            .await?;
+
+        Ok(row
+            .map(PrivacySettings::from)
+            .unwrap_or_else(|| PrivacySettings::defaults(user_id)))
+    }
+}
+
+pub struct ProfileMutation;
+
+impl ProfileMutation {
+    /// Choose what the authenticated player shows on their public profile
+    pub async fn update_my_privacy_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdatePrivacySettingsInput,
+    ) -> Result<PrivacySettings> {
+        let state = ctx.data::<AppState>()?;
+        let user_id = authenticated_user_id(ctx)?;
+
+        let row = UserPrivacySettingsRepo::new(state.db.clone())
+            .upsert(user_id, input.hide_winnings)
+            .await?;
+
+        Ok(PrivacySettings::from(row))
+    }
+}
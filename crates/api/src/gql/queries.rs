@@ -5,9 +5,9 @@ use crate::state::AppState;
 use infra::{
     pagination::LimitOffset,
     repos::{
-        ClubRepo, ClubTableRepo, LeaderboardPeriod, SeatAssignmentFilter, TableSeatAssignmentRepo,
-        TournamentFilter, TournamentPayoutRepo, TournamentRegistrationRepo, TournamentRepo,
-        TournamentResultRepo, UserFilter, UserRepo, UserStatistics,
+        ClubRepo, ClubTableRepo, SeatAssignmentFilter, TableSeatAssignmentRepo, TournamentFilter,
+        TournamentPayoutRepo, TournamentRegistrationRepo, TournamentRepo, TournamentResultRepo,
+        UserFilter, UserRepo, UserStatistics,
     },
 };
 
@@ -79,23 +79,7 @@ impl QueryRoot {
         let rows = repo.list(filter, page).await?;
         Ok(rows
             .into_iter()
-            .map(|r| {
-                let status = r.calculate_status().into();
-                crate::gql::types::Tournament {
-                    id: r.id.into(),
-                    title: r.name.clone(),
-                    description: r.description.clone(),
-                    club_id: r.club_id.into(),
-                    start_time: r.start_time,
-                    end_time: r.end_time,
-                    buy_in_cents: r.buy_in_cents,
-                    seat_cap: r.seat_cap,
-                    status,
-                    live_status: r.live_status.into(),
-                    created_at: r.created_at,
-                    updated_at: r.updated_at,
-                }
-            })
+            .map(crate::gql::types::Tournament::from)
             .collect())
     }
 
@@ -208,6 +192,123 @@ impl QueryRoot {
         Ok(players)
     }
 
+    /// Get the waitlist of a tournament with each player's position
+    async fn tournament_waitlist(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::WaitlistEntry>> {
+        let query = crate::gql::registrations::RegistrationQuery;
+        query.tournament_waitlist(ctx, tournament_id).await
+    }
+
+    /// Get every buy-in, re-entry, rebuy and add-on recorded for a tournament
+    async fn tournament_entries(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::TournamentEntry>> {
+        let query = crate::gql::entries::EntryQuery;
+        query.tournament_entries(ctx, tournament_id).await
+    }
+
+    /// Get the elimination log of a tournament with levels, finishing positions and the
+    /// bounties paid, oldest first
+    async fn tournament_eliminations(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::TournamentElimination>> {
+        let query = crate::gql::eliminations::EliminationQuery;
+        query.tournament_eliminations(ctx, tournament_id).await
+    }
+
+    /// Get the audit trail of the corrections made to a tournament's results (club
+    /// managers only)
+    async fn tournament_result_corrections(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::TournamentResultCorrection>> {
+        let query = crate::gql::results::ResultQuery;
+        query
+            .tournament_result_corrections(ctx, tournament_id)
+            .await
+    }
+
+    /// Get the leaderboard scoring formula of a club
+    async fn club_scoring_settings(
+        &self,
+        ctx: &Context<'_>,
+        club_id: async_graphql::ID,
+    ) -> Result<crate::gql::types::ClubScoringSettings> {
+        let query = crate::gql::scoring::ScoringQuery;
+        query.club_scoring_settings(ctx, club_id).await
+    }
+
+    /// Get the seasons of a club, latest first
+    async fn seasons(
+        &self,
+        ctx: &Context<'_>,
+        club_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::Season>> {
+        let query = crate::gql::seasons::SeasonQuery;
+        query.seasons(ctx, club_id).await
+    }
+
+    /// Get the standings of a season, frozen once the season is closed
+    async fn season_standings(
+        &self,
+        ctx: &Context<'_>,
+        season_id: async_graphql::ID,
+    ) -> Result<crate::gql::types::SeasonStandings> {
+        let query = crate::gql::seasons::SeasonQuery;
+        query.season_standings(ctx, season_id).await
+    }
+
+    /// Compare two players: shared tournaments, who finished higher and hours spent at
+    /// the same table
+    async fn head_to_head(
+        &self,
+        ctx: &Context<'_>,
+        user_a: async_graphql::ID,
+        user_b: async_graphql::ID,
+    ) -> Result<crate::gql::types::HeadToHead> {
+        let query = crate::gql::rivalries::RivalryQuery;
+        query.head_to_head(ctx, user_a, user_b).await
+    }
+
+    /// Get the opponents a player met most
+    async fn top_rivals(
+        &self,
+        ctx: &Context<'_>,
+        user_id: async_graphql::ID,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::gql::types::HeadToHead>> {
+        let query = crate::gql::rivalries::RivalryQuery;
+        query.top_rivals(ctx, user_id, limit).await
+    }
+
+    /// Get the public profile of a player: lifetime and per-club statistics, profit over
+    /// time, biggest cashes, finishing positions, points per year and recent results
+    async fn player_profile(
+        &self,
+        ctx: &Context<'_>,
+        user_id: async_graphql::ID,
+    ) -> Result<crate::gql::types::PlayerProfile> {
+        let query = crate::gql::profiles::ProfileQuery;
+        query.player_profile(ctx, user_id).await
+    }
+
+    /// Get what the authenticated player shows on their public profile
+    async fn my_privacy_settings(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<crate::gql::types::PrivacySettings> {
+        let query = crate::gql::profiles::ProfileQuery;
+        query.my_privacy_settings(ctx).await
+    }
+
     /// Get the current authenticated user's information
     async fn me(&self, ctx: &Context<'_>) -> Result<crate::gql::types::User> {
         use crate::auth::Claims;
@@ -299,32 +400,9 @@ impl QueryRoot {
         let mut user_results = Vec::new();
         for result_row in results {
             if let Some(tournament_row) = tournament_repo.get(result_row.tournament_id).await? {
-                let tournament_result = crate::gql::types::TournamentResult {
-                    id: result_row.id.into(),
-                    tournament_id: result_row.tournament_id.into(),
-                    user_id: result_row.user_id.into(),
-                    final_position: result_row.final_position,
-                    prize_cents: result_row.prize_cents,
-                    points: result_row.points,
-                    notes: result_row.notes,
-                    created_at: result_row.created_at,
-                };
+                let tournament_result = crate::gql::types::TournamentResult::from(result_row);
 
-                let status = tournament_row.calculate_status().into();
-                let tournament = crate::gql::types::Tournament {
-                    id: tournament_row.id.into(),
-                    title: tournament_row.name.clone(),
-                    description: tournament_row.description.clone(),
-                    club_id: tournament_row.club_id.into(),
-                    start_time: tournament_row.start_time,
-                    end_time: tournament_row.end_time,
-                    buy_in_cents: tournament_row.buy_in_cents,
-                    seat_cap: tournament_row.seat_cap,
-                    status,
-                    live_status: tournament_row.live_status.into(),
-                    created_at: tournament_row.created_at,
-                    updated_at: tournament_row.updated_at,
-                };
+                let tournament = crate::gql::types::Tournament::from(tournament_row);
 
                 user_results.push(crate::gql::types::UserTournamentResult {
                     result: tournament_result,
@@ -364,6 +442,7 @@ impl QueryRoot {
             total_tournaments: stats.total_tournaments,
             total_winnings: stats.total_winnings,
             total_buy_ins: stats.total_buy_ins,
+            total_bounty_winnings: stats.total_bounty_winnings,
             itm_percentage: stats.itm_percentage,
             roi_percentage: stats.roi_percentage,
         };
@@ -392,20 +471,7 @@ impl QueryRoot {
             .await?
             .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
 
-        let tournament = crate::gql::types::Tournament {
-            id: tournament_row.id.into(),
-            title: tournament_row.name.clone(),
-            description: tournament_row.description.clone(),
-            club_id: tournament_row.club_id.into(),
-            start_time: tournament_row.start_time,
-            end_time: tournament_row.end_time,
-            buy_in_cents: tournament_row.buy_in_cents,
-            seat_cap: tournament_row.seat_cap,
-            status: tournament_row.calculate_status().into(),
-            live_status: tournament_row.live_status.into(),
-            created_at: tournament_row.created_at,
-            updated_at: tournament_row.updated_at,
-        };
+        let tournament = crate::gql::types::Tournament::from(tournament_row);
 
         // Get all active tables for the tournament
         let table_rows = club_table_repo
@@ -614,20 +680,7 @@ impl QueryRoot {
             None => return Ok(None),
         };
 
-        Ok(Some(crate::gql::types::Tournament {
-            id: tournament_row.id.into(),
-            title: tournament_row.name.clone(),
-            description: tournament_row.description.clone(),
-            club_id: tournament_row.club_id.into(),
-            start_time: tournament_row.start_time,
-            end_time: tournament_row.end_time,
-            buy_in_cents: tournament_row.buy_in_cents,
-            seat_cap: tournament_row.seat_cap,
-            status: tournament_row.calculate_status().into(),
-            live_status: tournament_row.live_status.into(),
-            created_at: tournament_row.created_at,
-            updated_at: tournament_row.updated_at,
-        }))
+        Ok(Some(crate::gql::types::Tournament::from(tournament_row)))
     }
 
     async fn tournament_seating_history(
@@ -724,6 +777,8 @@ impl QueryRoot {
                 template_id: payout_row.template_id.map(|id| id.into()),
                 player_count: payout_row.player_count,
                 total_prize_pool: payout_row.total_prize_pool,
+                collected_prize_pool: payout_row.total_prize_pool - payout_row.overlay_cents,
+                overlay_cents: payout_row.overlay_cents,
                 positions,
                 created_at: payout_row.created_at,
                 updated_at: payout_row.updated_at,
@@ -733,59 +788,130 @@ impl QueryRoot {
         }
     }
 
-    /// Get player leaderboard with comprehensive statistics and points
+    /// Preview ICM, chip chop and even split deals for the current final table (managers only)
+    async fn preview_deal(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: async_graphql::ID,
+        payout_template_id: Option<async_graphql::ID>,
+    ) -> Result<crate::gql::types::DealPreview> {
+        let query = crate::gql::deals::DealQuery;
+        query
+            .preview_deal(ctx, tournament_id, payout_template_id)
+            .await
+    }
+
+    /// Generate a blind structure from a starting stack and target length to preview
+    async fn generate_blind_structure(
+        &self,
+        input: crate::gql::types::GenerateBlindStructureInput,
+    ) -> Result<crate::gql::types::GeneratedBlindStructure> {
+        let query = crate::gql::blind_structures::BlindStructureQuery;
+        query.generate_blind_structure(input).await
+    }
+
+    /// Get the blind structure templates of a club
+    async fn blind_structure_templates(
+        &self,
+        ctx: &Context<'_>,
+        club_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::BlindStructureTemplate>> {
+        let query = crate::gql::blind_structures::BlindStructureQuery;
+        query.blind_structure_templates(ctx, club_id).await
+    }
+
+    /// Get the recurring tournament series of a club
+    async fn tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        club_id: async_graphql::ID,
+    ) -> Result<Vec<crate::gql::types::TournamentSeries>> {
+        let query = crate::gql::tournament_series::TournamentSeriesQuery;
+        query.tournament_series(ctx, club_id).await
+    }
+
+    /// Get player leaderboard with comprehensive statistics and points, best first
     async fn leaderboard(
         &self,
         ctx: &Context<'_>,
         period: Option<crate::gql::types::LeaderboardPeriod>,
         limit: Option<i32>,
+        offset: Option<i32>,
         club_id: Option<uuid::Uuid>,
     ) -> Result<crate::gql::types::LeaderboardResponse> {
         let state = ctx.data::<AppState>()?;
         let result_repo = TournamentResultRepo::new(state.db.clone());
 
         let period = period.unwrap_or(crate::gql::types::LeaderboardPeriod::AllTime);
-        let infra_period: LeaderboardPeriod = period.into();
+        let page = LimitOffset {
+            limit: limit.unwrap_or(100).clamp(1, 500) as i64,
+            offset: offset.unwrap_or(0).max(0) as i64,
+        };
 
-        let leaderboard_entries = result_repo
-            .get_leaderboard(infra_period, limit, club_id)
+        let leaderboard = result_repo
+            .get_leaderboard(period.into(), club_id, page)
             .await?;
 
-        // Convert to GraphQL types and add rank
-        let entries: Vec<crate::gql::types::LeaderboardEntry> = leaderboard_entries
-            .into_iter()
-            .enumerate()
-            .map(|(index, entry)| crate::gql::types::LeaderboardEntry {
-                user: crate::gql::types::User {
-                    id: entry.user_id.into(),
-                    email: entry.email,
-                    username: entry.username,
-                    first_name: entry.first_name,
-                    last_name: entry.last_name,
-                    phone: entry.phone,
-                    is_active: entry.is_active,
-                    role: crate::gql::types::Role::from(entry.role),
-                },
-                rank: (index + 1) as i32, // 1-based ranking
-                total_tournaments: entry.total_tournaments,
-                total_buy_ins: entry.total_buy_ins,
-                total_winnings: entry.total_winnings,
-                net_profit: entry.net_profit,
-                total_itm: entry.total_itm,
-                itm_percentage: entry.itm_percentage,
-                roi_percentage: entry.roi_percentage,
-                average_finish: entry.average_finish,
-                first_places: entry.first_places,
-                final_tables: entry.final_tables,
-                points: entry.points,
-            })
-            .collect();
-
-        let total_players = entries.len() as i32;
+        let has_next_page =
+            page.offset + (leaderboard.entries.len() as i64) < leaderboard.total_players;
 
+        let viewer_id = crate::gql::profiles::viewer_id(ctx);
         Ok(crate::gql::types::LeaderboardResponse {
-            entries,
-            total_players,
+            entries: leaderboard
+                .entries
+                .into_iter()
+                .map(|entry| crate::gql::types::LeaderboardEntry::new(entry, viewer_id))
+                .collect(),
+            total_players: leaderboard.total_players as i32,
+            has_next_page,
+            period,
+        })
+    }
+
+    /// Get the current user's leaderboard entry with the entries just above and below it
+    async fn my_leaderboard_position(
+        &self,
+        ctx: &Context<'_>,
+        period: Option<crate::gql::types::LeaderboardPeriod>,
+        club_id: Option<uuid::Uuid>,
+        #[graphql(desc = "Entries to show on each side, defaults to 2")] around: Option<i32>,
+    ) -> Result<crate::gql::types::LeaderboardPosition> {
+        use crate::auth::Claims;
+
+        let claims = ctx
+            .data::<Claims>()
+            .map_err(|_| async_graphql::Error::new("Authentication required"))?;
+        let user_id = uuid::Uuid::parse_str(&claims.sub)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let state = ctx.data::<AppState>()?;
+        let period = period.unwrap_or(crate::gql::types::LeaderboardPeriod::AllTime);
+        let around = around.unwrap_or(2).clamp(0, 50) as i64;
+
+        let leaderboard = TournamentResultRepo::new(state.db.clone())
+            .get_leaderboard_around(user_id, period.into(), club_id, around)
+            .await?;
+
+        let mut above = Vec::new();
+        let mut entry = None;
+        let mut below = Vec::new();
+        for row in leaderboard.entries {
+            let is_viewer = row.user_id == user_id;
+            let row = crate::gql::types::LeaderboardEntry::new(row, Some(user_id));
+            if is_viewer {
+                entry = Some(row);
+            } else if entry.is_none() {
+                above.push(row);
+            } else {
+                below.push(row);
+            }
+        }
+
+        Ok(crate::gql::types::LeaderboardPosition {
+            entry,
+            above,
+            below,
+            total_players: leaderboard.total_players as i32,
             period,
         })
     }
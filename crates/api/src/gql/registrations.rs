@@ -0,0 +1,261 @@
+use async_graphql::{Context, Result, ID};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::auth::permissions::{require_club_manager, require_role};
+use crate::gql::subscriptions::publish_registration_event;
+use crate::gql::types::{
+    PlayerRegistrationEvent, RegistrationStatus, Role, TournamentPlayer, TournamentRegistration,
+    UpdateRegistrationStatusInput, WaitlistEntry,
+};
+use crate::state::AppState;
+use infra::models::{TournamentRegistrationRow, TournamentRow};
+use infra::repos::{TournamentLiveStatus, TournamentRegistrationRepo, TournamentRepo, UserRepo};
+
+/// Helper function to let subscribers know a registration changed
+pub(crate) async fn publish_registration_change(
+    state: &AppState,
+    registration: &TournamentRegistrationRow,
+    event_type: &str,
+) -> Result<()> {
+    if let Some(user_row) = UserRepo::new(state.db.clone())
+        .get_by_id(registration.user_id)
+        .await?
+    {
+        publish_registration_event(PlayerRegistrationEvent {
+            tournament_id: registration.tournament_id.into(),
+            player: TournamentPlayer {
+                registration: registration.clone().into(),
+                user: user_row.into(),
+            },
+            event_type: event_type.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Change the status of a registration and publish it. When a seat is given up, the
+/// first waitlisted player is promoted and a `player_promoted` event is published too.
+///
+/// `check` decides whether the registration may leave its status. It runs on the status
+/// read here and again on the latest one when the status changed in the meantime.
+async fn set_registration_status(
+    state: &AppState,
+    registration: &TournamentRegistrationRow,
+    status: RegistrationStatus,
+    notes: Option<String>,
+    event_type: &str,
+    check: impl Fn(RegistrationStatus) -> Result<()>,
+) -> Result<TournamentRegistration> {
+    check(RegistrationStatus::from(registration.status.clone()))?;
+
+    let repo = TournamentRegistrationRepo::new(state.db.clone());
+    let Some(change) = repo
+        .update_status(
+            registration.id,
+            &registration.status,
+            &String::from(status),
+            notes,
+        )
+        .await?
+    else {
+        let latest = repo
+            .get_by_id(registration.id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Registration not found"))?;
+        check(RegistrationStatus::from(latest.status))?;
+        return Err(async_graphql::Error::new(
+            "Registration status changed in the meantime, please try again",
+        ));
+    };
+
+    publish_registration_change(state, &change.registration, event_type).await?;
+    for promoted in &change.promoted {
+        publish_registration_change(state, promoted, "player_promoted").await?;
+    }
+
+    Ok(change.registration.into())
+}
+
+/// Helper function to refuse a status change `RegistrationStatus::next_statuses` doesn't allow
+fn check_transition(current: RegistrationStatus, next: RegistrationStatus) -> Result<()> {
+    if current.can_change_to(next) {
+        return Ok(());
+    }
+
+    let allowed = current
+        .next_statuses()
+        .iter()
+        .map(|status| format!("{:?}", status))
+        .collect::<Vec<_>>();
+    Err(async_graphql::Error::new(if allowed.is_empty() {
+        format!(
+            "Registration status cannot change from {:?} to {:?}: {:?} is final",
+            current, next, current
+        )
+    } else {
+        format!(
+            "Registration status cannot change from {:?} to {:?} (allowed: {})",
+            current,
+            next,
+            allowed.join(", ")
+        )
+    }))
+}
+
+/// Fill any free seats from the waitlist and publish a `player_promoted` event for each
+pub(crate) async fn promote_from_waitlist(state: &AppState, tournament_id: Uuid) -> Result<()> {
+    let promoted = TournamentRegistrationRepo::new(state.db.clone())
+        .promote_from_waitlist(tournament_id)
+        .await?;
+
+    for registration in &promoted {
+        publish_registration_change(state, registration, "player_promoted").await?;
+    }
+    Ok(())
+}
+
+/// Helper function to get a tournament whose registrations can still change
+async fn get_open_tournament(state: &AppState, tournament_id: &ID) -> Result<TournamentRow> {
+    let tournament_id = Uuid::parse_str(tournament_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+    let tournament = TournamentRepo::new(state.db.clone())
+        .get(tournament_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+    if matches!(
+        tournament.live_status,
+        TournamentLiveStatus::Finished | TournamentLiveStatus::Cancelled
+    ) {
+        return Err(async_graphql::Error::new(
+            "Registrations of finished or cancelled tournaments cannot change",
+        ));
+    }
+
+    Ok(tournament)
+}
+
+async fn get_registration(
+    state: &AppState,
+    tournament_id: Uuid,
+    user_id: Uuid,
+) -> Result<TournamentRegistrationRow> {
+    TournamentRegistrationRepo::new(state.db.clone())
+        .get_by_tournament_and_user(tournament_id, user_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Player not registered for this tournament"))
+}
+
+pub struct RegistrationQuery;
+
+impl RegistrationQuery {
+    /// Get the waitlist of a tournament in the order players will get a seat
+    pub async fn tournament_waitlist(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<Vec<WaitlistEntry>> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+        let user_repo = UserRepo::new(state.db.clone());
+
+        let waitlist = TournamentRegistrationRepo::new(state.db.clone())
+            .get_waitlist(tournament_id)
+            .await?;
+
+        let mut entries = Vec::with_capacity(waitlist.len());
+        for (index, registration) in waitlist.into_iter().enumerate() {
+            if let Some(user_row) = user_repo.get_by_id(registration.user_id).await? {
+                entries.push(WaitlistEntry {
+                    position: index as i32 + 1,
+                    player: TournamentPlayer {
+                        registration: registration.into(),
+                        user: user_row.into(),
+                    },
+                });
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+pub struct RegistrationMutation;
+
+impl RegistrationMutation {
+    /// Cancel your own registration, up to the tournament's unregister cutoff
+    pub async fn unregister_from_tournament(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<TournamentRegistration> {
+        let user = require_role(ctx, Role::Player).await?;
+        let user_id = Uuid::parse_str(user.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament = get_open_tournament(state, &tournament_id).await?;
+
+        let cutoff =
+            tournament.start_time - Duration::minutes(tournament.unregister_cutoff_minutes as i64);
+        if Utc::now() >= cutoff {
+            return Err(async_graphql::Error::new(format!(
+                "Registrations can only be cancelled until {} minutes before the start",
+                tournament.unregister_cutoff_minutes
+            )));
+        }
+
+        let registration = get_registration(state, tournament.id, user_id).await?;
+        set_registration_status(
+            state,
+            &registration,
+            RegistrationStatus::Cancelled,
+            None,
+            "player_unregistered",
+            |status| {
+                if matches!(
+                    status,
+                    RegistrationStatus::Registered | RegistrationStatus::Waitlisted
+                ) {
+                    Ok(())
+                } else {
+                    Err(async_graphql::Error::new(format!(
+                        "Registration cannot be cancelled from status: {:?}",
+                        status
+                    )))
+                }
+            },
+        )
+        .await
+    }
+
+    /// Move a registration to its next status (club managers only). Only the steps in
+    /// `RegistrationStatus::next_statuses` are allowed.
+    pub async fn update_registration_status(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateRegistrationStatusInput,
+    ) -> Result<TournamentRegistration> {
+        let state = ctx.data::<AppState>()?;
+        let tournament = get_open_tournament(state, &input.tournament_id).await?;
+        require_club_manager(ctx, tournament.club_id).await?;
+
+        let user_id = Uuid::parse_str(input.user_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let registration = get_registration(state, tournament.id, user_id).await?;
+
+        let event_type = format!("player_{}", String::from(input.status));
+        set_registration_status(
+            state,
+            &registration,
+            input.status,
+            input.notes,
+            &event_type,
+            |current| check_transition(current, input.status),
+        )
+        .await
+    }
+}
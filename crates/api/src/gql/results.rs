@@ -0,0 +1,131 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::types::{
+    CorrectTournamentResultInput, TournamentResult, TournamentResultCorrection,
+};
+use crate::state::AppState;
+use infra::models::TournamentResultRow;
+use infra::repos::{TournamentRepo, TournamentResultRepo, UpdateTournamentResult};
+
+pub struct ResultQuery;
+
+impl ResultQuery {
+    /// Get every correction made to the results of a tournament, oldest first (club
+    /// managers only)
+    pub async fn tournament_result_corrections(
+        &self,
+        ctx: &Context<'_>,
+        tournament_id: ID,
+    ) -> Result<Vec<TournamentResultCorrection>> {
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let tournament = TournamentRepo::new(state.db.clone())
+            .get(tournament_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+        require_club_manager(ctx, tournament.club_id).await?;
+
+        let rows = TournamentResultRepo::new(state.db.clone())
+            .get_corrections(tournament_id)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(TournamentResultCorrection::from)
+            .collect())
+    }
+}
+
+pub struct ResultMutation;
+
+impl ResultMutation {
+    /// Correct a tournament result (club managers only). The old and new values are kept
+    /// in the audit trail and the points of the tournament are recalculated.
+    pub async fn correct_tournament_result(
+        &self,
+        ctx: &Context<'_>,
+        input: CorrectTournamentResultInput,
+    ) -> Result<TournamentResult> {
+        let state = ctx.data::<AppState>()?;
+        let result_id = Uuid::parse_str(input.result_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid result ID: {}", e)))?;
+
+        if input.final_position.is_some_and(|position| position < 1) {
+            return Err(async_graphql::Error::new(
+                "Final position must be at least 1",
+            ));
+        }
+        if input.prize_cents.is_some_and(|cents| cents < 0)
+            || input.bounty_cents.is_some_and(|cents| cents < 0)
+        {
+            return Err(async_graphql::Error::new(
+                "Prize and bounty cannot be negative",
+            ));
+        }
+
+        let repo = TournamentResultRepo::new(state.db.clone());
+        let result = repo
+            .get_by_id(result_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament result not found"))?;
+        let manager_id = require_result_manager(ctx, &result).await?;
+
+        let row = repo
+            .correct(
+                result_id,
+                UpdateTournamentResult {
+                    final_position: input.final_position,
+                    prize_cents: input.prize_cents,
+                    bounty_cents: input.bounty_cents,
+                    notes: input.notes.into(),
+                },
+                manager_id,
+                input.reason,
+            )
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament result not found"))?;
+
+        Ok(TournamentResult::from(row))
+    }
+
+    /// Delete a tournament result (club managers only). The result is kept in the audit
+    /// trail and the points of the tournament are recalculated.
+    pub async fn delete_tournament_result(
+        &self,
+        ctx: &Context<'_>,
+        result_id: ID,
+        reason: Option<String>,
+    ) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let result_id = Uuid::parse_str(result_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid result ID: {}", e)))?;
+
+        let repo = TournamentResultRepo::new(state.db.clone());
+        let result = repo
+            .get_by_id(result_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament result not found"))?;
+        let manager_id = require_result_manager(ctx, &result).await?;
+
+        Ok(repo
+            .delete_with_audit(result_id, manager_id, reason)
+            .await?)
+    }
+}
+
+/// Require a manager of the club that ran the tournament, returning their user ID
+async fn require_result_manager(ctx: &Context<'_>, result: &TournamentResultRow) -> Result<Uuid> {
+    let state = ctx.data::<AppState>()?;
+    let tournament = TournamentRepo::new(state.db.clone())
+        .get(result.tournament_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Tournament not found"))?;
+
+    let manager = require_club_manager(ctx, tournament.club_id).await?;
+    Uuid::parse_str(manager.id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))
+}
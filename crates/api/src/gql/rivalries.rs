@@ -0,0 +1,61 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::gql::types::HeadToHead;
+use crate::state::AppState;
+use infra::repos::RivalryRepo;
+
+const DEFAULT_RIVALS: i32 = 5;
+const MAX_RIVALS: i32 = 50;
+
+fn parse_user_id(user_id: &ID) -> Result<Uuid> {
+    Uuid::parse_str(user_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))
+}
+
+pub struct RivalryQuery;
+
+impl RivalryQuery {
+    /// Compare two players: shared tournaments, who finished higher and time spent at
+    /// the same table, from the first player's side
+    pub async fn head_to_head(
+        &self,
+        ctx: &Context<'_>,
+        user_a: ID,
+        user_b: ID,
+    ) -> Result<HeadToHead> {
+        let state = ctx.data::<AppState>()?;
+        let user_a = parse_user_id(&user_a)?;
+        let user_b = parse_user_id(&user_b)?;
+        if user_a == user_b {
+            return Err(async_graphql::Error::new(
+                "Pick two different players to compare",
+            ));
+        }
+
+        let stats = RivalryRepo::new(state.db.clone())
+            .head_to_head(user_a, user_b)
+            .await?;
+
+        Ok(stats.into())
+    }
+
+    /// Get the opponents a player met most, by shared tournaments then time at the same
+    /// table
+    pub async fn top_rivals(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        limit: Option<i32>,
+    ) -> Result<Vec<HeadToHead>> {
+        let state = ctx.data::<AppState>()?;
+        let user_id = parse_user_id(&user_id)?;
+        let limit = limit.unwrap_or(DEFAULT_RIVALS).clamp(1, MAX_RIVALS);
+
+        let rivals = RivalryRepo::new(state.db.clone())
+            .top_rivals(user_id, limit as i64)
+            .await?;
+
+        Ok(rivals.into_iter().map(Into::into).collect())
+    }
+}
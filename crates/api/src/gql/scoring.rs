@@ -0,0 +1,74 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::types::{ClubScoringSettings, UpdateClubScoringSettingsInput};
+use crate::state::AppState;
+use infra::repos::{ClubScoringSettingsRepo, UpdateClubScoringSettings};
+use infra::scoring::{DEFAULT_CAP, DEFAULT_MULTIPLIER};
+
+pub struct ScoringQuery;
+
+impl ScoringQuery {
+    /// Get the leaderboard scoring formula of a club, the defaults when it never chose one
+    pub async fn club_scoring_settings(
+        &self,
+        ctx: &Context<'_>,
+        club_id: ID,
+    ) -> Result<ClubScoringSettings> {
+        let state = ctx.data::<AppState>()?;
+        let club_id = Uuid::parse_str(club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        let row = ClubScoringSettingsRepo::new(state.db.clone())
+            .get_by_club(club_id)
+            .await?;
+
+        Ok(row
+            .map(ClubScoringSettings::from)
+            .unwrap_or_else(|| ClubScoringSettings::defaults(club_id)))
+    }
+}
+
+pub struct ScoringMutation;
+
+impl ScoringMutation {
+    /// Choose the leaderboard scoring formula of a club (club managers only). It applies
+    /// to tournaments finished or corrected from now on.
+    pub async fn update_club_scoring_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateClubScoringSettingsInput,
+    ) -> Result<ClubScoringSettings> {
+        let state = ctx.data::<AppState>()?;
+        let club_id = Uuid::parse_str(input.club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        let manager = require_club_manager(ctx, club_id).await?;
+        let manager_id = Uuid::parse_str(manager.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))?;
+
+        let cap = input.cap.unwrap_or(DEFAULT_CAP as i32);
+        if cap < 1 {
+            return Err(async_graphql::Error::new("Cap must be at least 1 point"));
+        }
+        let multiplier = input.multiplier.unwrap_or(DEFAULT_MULTIPLIER);
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(async_graphql::Error::new("Multiplier must be positive"));
+        }
+
+        let row = ClubScoringSettingsRepo::new(state.db.clone())
+            .upsert(
+                club_id,
+                UpdateClubScoringSettings {
+                    formula: input.formula.into(),
+                    cap,
+                    multiplier,
+                },
+                manager_id,
+            )
+            .await?;
+
+        Ok(ClubScoringSettings::from(row))
+    }
+}
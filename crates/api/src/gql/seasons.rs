@@ -0,0 +1,286 @@
+use async_graphql::{Context, Result, ID};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::types::{
+    CreateSeasonInput, ScoringFormula, Season, SeasonStanding, SeasonStandings, UpdateSeasonInput,
+};
+use crate::state::AppState;
+use infra::models::SeasonRow;
+use infra::repos::{CreateSeason, SeasonRepo, TournamentResultRepo, UpdateSeason};
+use infra::scoring::ScoringFormulaKind;
+
+/// Helper function to validate the dates and rules of a season
+fn validate_season(
+    name: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    best_results: Option<i32>,
+    min_events: i32,
+    drop_worst: i32,
+) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(async_graphql::Error::new("Season name cannot be empty"));
+    }
+    if end_date < start_date {
+        return Err(async_graphql::Error::new(
+            "A season cannot end before it starts",
+        ));
+    }
+    if matches!(best_results, Some(best) if best < 1) {
+        return Err(async_graphql::Error::new(
+            "Best results must count at least 1 result",
+        ));
+    }
+    if min_events < 0 || drop_worst < 0 {
+        return Err(async_graphql::Error::new(
+            "Minimum events and dropped results cannot be negative",
+        ));
+    }
+    Ok(())
+}
+
+/// Helper function to validate the scoring overrides of a season
+fn validate_scoring(scoring_cap: Option<i32>, scoring_multiplier: Option<f64>) -> Result<()> {
+    if matches!(scoring_cap, Some(cap) if cap < 1) {
+        return Err(async_graphql::Error::new("Cap must be at least 1 point"));
+    }
+    if matches!(scoring_multiplier, Some(multiplier) if !multiplier.is_finite() || multiplier <= 0.0)
+    {
+        return Err(async_graphql::Error::new("Multiplier must be positive"));
+    }
+    Ok(())
+}
+
+fn formula_name(formula: ScoringFormula) -> String {
+    ScoringFormulaKind::from(formula).as_str().to_string()
+}
+
+async fn check_overlap(
+    repo: &SeasonRepo,
+    club_id: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    except_id: Option<Uuid>,
+) -> Result<()> {
+    if repo
+        .overlaps(club_id, start_date, end_date, except_id)
+        .await?
+    {
+        return Err(async_graphql::Error::new(
+            "The club already has a season during these dates",
+        ));
+    }
+    Ok(())
+}
+
+async fn get_season(ctx: &Context<'_>, season_id: &ID) -> Result<SeasonRow> {
+    let state = ctx.data::<AppState>()?;
+    let season_id = Uuid::parse_str(season_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid season ID: {}", e)))?;
+
+    SeasonRepo::new(state.db.clone())
+        .get(season_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Season not found"))
+}
+
+/// Get a season a manager can change, returning the manager's user ID with it
+async fn get_managed_season(ctx: &Context<'_>, season_id: &ID) -> Result<(SeasonRow, Uuid)> {
+    let season = get_season(ctx, season_id).await?;
+    let manager = require_club_manager(ctx, season.club_id).await?;
+    let manager_id = Uuid::parse_str(manager.id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))?;
+
+    if season.closed_at.is_some() {
+        return Err(async_graphql::Error::new("Season is closed"));
+    }
+
+    Ok((season, manager_id))
+}
+
+pub struct SeasonQuery;
+
+impl SeasonQuery {
+    /// Get the seasons of a club, latest first
+    pub async fn seasons(&self, ctx: &Context<'_>, club_id: ID) -> Result<Vec<Season>> {
+        let state = ctx.data::<AppState>()?;
+        let club_id = Uuid::parse_str(club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        let rows = SeasonRepo::new(state.db.clone())
+            .list_by_club(club_id)
+            .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Get the standings of a season, live while it is open and frozen once closed
+    pub async fn season_standings(
+        &self,
+        ctx: &Context<'_>,
+        season_id: ID,
+    ) -> Result<SeasonStandings> {
+        let state = ctx.data::<AppState>()?;
+        let season = get_season(ctx, &season_id).await?;
+
+        let rows = SeasonRepo::new(state.db.clone())
+            .get_standings(&season)
+            .await?;
+
+        Ok(SeasonStandings {
+            is_final: season.closed_at.is_some(),
+            season: season.into(),
+            entries: rows.into_iter().map(SeasonStanding::from).collect(),
+        })
+    }
+}
+
+pub struct SeasonMutation;
+
+impl SeasonMutation {
+    /// Create a season. Tournaments already finished during the season are scored again
+    /// when it overrides the club's scoring settings.
+    pub async fn create_season(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateSeasonInput,
+    ) -> Result<Season> {
+        let club_id = Uuid::parse_str(input.club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+        let manager = require_club_manager(ctx, club_id).await?;
+        let manager_id = Uuid::parse_str(manager.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid manager ID: {}", e)))?;
+
+        let state = ctx.data::<AppState>()?;
+        let repo = SeasonRepo::new(state.db.clone());
+
+        let name = input.name.trim().to_string();
+        let min_events = input.min_events.unwrap_or(0);
+        let drop_worst = input.drop_worst.unwrap_or(0);
+        validate_season(
+            &name,
+            input.start_date,
+            input.end_date,
+            input.best_results,
+            min_events,
+            drop_worst,
+        )?;
+        validate_scoring(input.scoring_cap, input.scoring_multiplier)?;
+        check_overlap(&repo, club_id, input.start_date, input.end_date, None).await?;
+
+        let overrides_scoring = input.scoring_formula.is_some()
+            || input.scoring_cap.is_some()
+            || input.scoring_multiplier.is_some();
+
+        let season = repo
+            .create(CreateSeason {
+                club_id,
+                name,
+                start_date: input.start_date,
+                end_date: input.end_date,
+                best_results: input.best_results,
+                min_events,
+                drop_worst,
+                scoring_formula: input.scoring_formula.map(formula_name),
+                scoring_cap: input.scoring_cap,
+                scoring_multiplier: input.scoring_multiplier,
+                created_by: Some(manager_id),
+            })
+            .await?;
+
+        if overrides_scoring {
+            TournamentResultRepo::new(state.db.clone())
+                .recalculate_club_points(club_id, season.start_date, season.end_date)
+                .await?;
+        }
+
+        Ok(season.into())
+    }
+
+    /// Update an open season. Tournaments finished during the old and new dates are
+    /// scored again when the dates or the scoring overrides change.
+    pub async fn update_season(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateSeasonInput,
+    ) -> Result<Season> {
+        let (season, _) = get_managed_season(ctx, &input.season_id).await?;
+        let state = ctx.data::<AppState>()?;
+        let repo = SeasonRepo::new(state.db.clone());
+
+        let name = input.name.map(|name| name.trim().to_string());
+        let start_date = input.start_date.unwrap_or(season.start_date);
+        let end_date = input.end_date.unwrap_or(season.end_date);
+        let best_results: Option<Option<i32>> = input.best_results.into();
+        let scoring_formula: Option<Option<ScoringFormula>> = input.scoring_formula.into();
+        let scoring_cap: Option<Option<i32>> = input.scoring_cap.into();
+        let scoring_multiplier: Option<Option<f64>> = input.scoring_multiplier.into();
+        validate_season(
+            name.as_deref().unwrap_or(&season.name),
+            start_date,
+            end_date,
+            best_results.unwrap_or(season.best_results),
+            input.min_events.unwrap_or(season.min_events),
+            input.drop_worst.unwrap_or(season.drop_worst),
+        )?;
+        validate_scoring(scoring_cap.flatten(), scoring_multiplier.flatten())?;
+        if input.start_date.is_some() || input.end_date.is_some() {
+            check_overlap(&repo, season.club_id, start_date, end_date, Some(season.id)).await?;
+        }
+
+        let data = UpdateSeason {
+            name,
+            start_date: input.start_date,
+            end_date: input.end_date,
+            best_results,
+            min_events: input.min_events,
+            drop_worst: input.drop_worst,
+            scoring_formula: scoring_formula.map(|formula| formula.map(formula_name)),
+            scoring_cap,
+            scoring_multiplier,
+        };
+        let changes_scoring = data.changes_scoring();
+
+        let updated = repo
+            .update(season.id, data)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Season not found"))?;
+
+        if changes_scoring {
+            TournamentResultRepo::new(state.db.clone())
+                .recalculate_club_points(
+                    season.club_id,
+                    season.start_date.min(updated.start_date),
+                    season.end_date.max(updated.end_date),
+                )
+                .await?;
+        }
+
+        Ok(updated.into())
+    }
+
+    /// Close a season and freeze its standings. Every tournament of the season must be
+    /// finished or cancelled first; later corrections to results no longer change them.
+    pub async fn close_season(&self, ctx: &Context<'_>, season_id: ID) -> Result<Season> {
+        let (season, manager_id) = get_managed_season(ctx, &season_id).await?;
+        let state = ctx.data::<AppState>()?;
+        let repo = SeasonRepo::new(state.db.clone());
+
+        let unfinished = repo.unfinished_tournaments(&season).await?;
+        if unfinished > 0 {
+            return Err(async_graphql::Error::new(format!(
+                "{} tournament(s) of the season are not finished yet",
+                unfinished
+            )));
+        }
+
+        let closed = repo
+            .close(season.id, manager_id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Season is closed"))?;
+
+        Ok(closed.into())
+    }
+}
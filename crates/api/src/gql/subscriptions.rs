@@ -1,23 +1,20 @@
 use async_graphql::{Context, Result, Subscription};
 use futures_util::Stream;
-use once_cell::sync::Lazy;
-use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::StreamExt;
 
-use crate::gql::types::{PlayerRegistrationEvent, SeatingChangeEvent};
+use crate::events::{self, BusEvent};
+use crate::gql::types::{PlayerRegistrationEvent, SeatingChangeEvent, TournamentClock};
 
-static REGISTRATION_BROADCASTER: Lazy<Arc<Mutex<broadcast::Sender<PlayerRegistrationEvent>>>> =
-    Lazy::new(|| {
-        let (tx, _) = broadcast::channel(1000);
-        Arc::new(Mutex::new(tx))
-    });
-
-static SEATING_BROADCASTER: Lazy<Arc<Mutex<broadcast::Sender<SeatingChangeEvent>>>> =
-    Lazy::new(|| {
-        let (tx, _) = broadcast::channel(1000);
-        Arc::new(Mutex::new(tx))
-    });
+/// Seating events from the bus, errors included
+fn seating_events() -> impl Stream<Item = Result<SeatingChangeEvent, BroadcastStreamRecvError>> {
+    BroadcastStream::new(events::subscribe()).filter_map(|event| match event {
+        Ok(BusEvent::Seating(event)) => Some(Ok(event)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    })
+}
 
 pub struct SubscriptionRoot;
 
@@ -39,8 +36,11 @@ impl SubscriptionRoot {
     async fn tournament_registrations(
         &self,
     ) -> impl Stream<Item = Result<PlayerRegistrationEvent, BroadcastStreamRecvError>> {
-        let receiver = REGISTRATION_BROADCASTER.lock().unwrap().subscribe();
-        BroadcastStream::new(receiver)
+        BroadcastStream::new(events::subscribe()).filter_map(|event| match event {
+            Ok(BusEvent::Registration(event)) => Some(Ok(event)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
     }
 
     /// Subscribe to seating changes for a specific tournament
@@ -48,10 +48,9 @@ impl SubscriptionRoot {
         &self,
         tournament_id: async_graphql::ID,
     ) -> impl Stream<Item = Result<SeatingChangeEvent, BroadcastStreamRecvError>> {
-        let receiver = SEATING_BROADCASTER.lock().unwrap().subscribe();
         let tournament_id_filter = tournament_id.to_string();
 
-        tokio_stream::StreamExt::filter(BroadcastStream::new(receiver), move |event| {
+        seating_events().filter(move |event| {
             match event {
                 Ok(seating_event) => seating_event.tournament_id.as_str() == tournament_id_filter,
                 Err(_) => true, // Let errors through
@@ -74,30 +73,31 @@ impl SubscriptionRoot {
         // TODO: Verify manager belongs to this club (would need club_user relationship)
         // For now, we trust the manager can only manage their own club
 
-        let receiver = SEATING_BROADCASTER.lock().unwrap().subscribe();
         let club_id_filter = club_id.to_string();
 
         // Filter events by club_id
-        Ok(tokio_stream::StreamExt::filter(
-            BroadcastStream::new(receiver),
-            move |event| {
-                match event {
-                    Ok(seating_event) => seating_event.club_id.as_str() == club_id_filter,
-                    Err(_) => true, // Let errors through
-                }
-            },
-        ))
+        Ok(seating_events().filter(move |event| {
+            match event {
+                Ok(seating_event) => seating_event.club_id.as_str() == club_id_filter,
+                Err(_) => true, // Let errors through
+            }
+        }))
     }
 }
 
 pub fn publish_registration_event(event: PlayerRegistrationEvent) {
-    if let Ok(sender) = REGISTRATION_BROADCASTER.lock() {
-        let _ = sender.send(event);
-    }
+    events::publish(BusEvent::Registration(event));
 }
 
 pub fn publish_seating_event(event: SeatingChangeEvent) {
-    if let Ok(sender) = SEATING_BROADCASTER.lock() {
-        let _ = sender.send(event);
-    }
+    events::publish(BusEvent::Seating(event));
+}
+
+/// Publish the state of a tournament clock after it changed
+pub fn publish_clock_event(clock: TournamentClock) {
+    events::publish(BusEvent::Clock(clock));
+}
+
+pub(crate) fn subscribe_clock_events() -> broadcast::Receiver<BusEvent> {
+    events::subscribe()
 }
@@ -3,11 +3,16 @@ use chrono::Utc;
 use futures_util::Stream;
 use std::str::FromStr;
 use std::time::Duration as StdDuration;
+use tokio::sync::broadcast;
 use tokio::time::interval;
+use tracing::warn;
 use uuid::Uuid;
 
+use crate::events::BusEvent;
+use crate::gql::subscriptions::{publish_clock_event, subscribe_clock_events};
 use crate::gql::types::{ClockStatus, Role, TournamentClock, TournamentStructure};
 use crate::{auth::permissions::require_role, AppState};
+use infra::db::Db;
 use infra::repos::{ClockStatus as InfraClockStatus, TournamentClockRepo};
 
 /// Helper function to get next structure for a tournament
@@ -37,6 +42,16 @@ async fn get_next_structure(
         })
 }
 
+/// Helper function for a manual level change refused because the clock left the expected level
+fn level_moved_error(expected_level: Option<i32>) -> async_graphql::Error {
+    match expected_level {
+        Some(level) => {
+            async_graphql::Error::new(format!("Tournament clock is no longer on level {}", level))
+        }
+        None => async_graphql::Error::new("Tournament clock not found"),
+    }
+}
+
 /// Helper function to create TournamentClock with all required fields
 fn create_tournament_clock(
     clock_row: &infra::models::TournamentClockRow,
@@ -77,6 +92,121 @@ fn create_tournament_clock(
     }
 }
 
+/// Load the clock of a tournament as subscribers see it, `None` before it is created
+pub(crate) async fn load_tournament_clock(
+    db: &Db,
+    tournament_id: Uuid,
+) -> sqlx::Result<Option<TournamentClock>> {
+    let repo = TournamentClockRepo::new(db.clone());
+
+    if let Some(clock_row) = repo.get_clock(tournament_id).await? {
+        let structure = repo.get_current_structure(tournament_id).await.ok();
+        let next_structure =
+            get_next_structure(&repo, tournament_id, clock_row.current_level).await;
+
+        // Calculate time remaining
+        let time_remaining = if let Ok(status) = InfraClockStatus::from_str(&clock_row.clock_status)
+        {
+            match status {
+                InfraClockStatus::Running => {
+                    if let Some(end_time) = clock_row.level_end_time {
+                        let remaining = end_time - Utc::now();
+                        Some(remaining.num_seconds().max(0))
+                    } else {
+                        None
+                    }
+                }
+                InfraClockStatus::Paused => {
+                    if let (Some(end_time), Some(pause_start)) =
+                        (clock_row.level_end_time, clock_row.pause_started_at)
+                    {
+                        let remaining = end_time - pause_start;
+                        Some(remaining.num_seconds().max(0))
+                    } else {
+                        None
+                    }
+                }
+                InfraClockStatus::Stopped => {
+                    // Show full duration of current level when stopped
+                    // Use already fetched structure or fetch it
+                    if let Some(s) = &structure {
+                        Some((s.duration_minutes as i64) * 60)
+                    } else if let Ok(current_structure) =
+                        repo.get_current_structure(tournament_id).await
+                    {
+                        Some((current_structure.duration_minutes as i64) * 60)
+                    } else {
+                        None
+                    }
+                }
+            }
+        } else {
+            None
+        };
+
+        // Convert PgInterval to seconds
+        let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
+
+        Ok(Some(TournamentClock {
+            id: clock_row.id.into(),
+            tournament_id: clock_row.tournament_id.into(),
+            status: InfraClockStatus::from_str(&clock_row.clock_status)
+                .ok()
+                .unwrap_or(InfraClockStatus::Stopped)
+                .into(),
+            current_level: clock_row.current_level,
+            time_remaining_seconds: time_remaining,
+            level_started_at: clock_row.level_started_at,
+            level_end_time: clock_row.level_end_time,
+            total_pause_duration_seconds: total_pause_seconds,
+            auto_advance: clock_row.auto_advance,
+            current_structure: structure.as_ref().map(|s| TournamentStructure {
+                id: s.id.into(),
+                tournament_id: s.tournament_id.into(),
+                level_number: s.level_number,
+                small_blind: s.small_blind,
+                big_blind: s.big_blind,
+                ante: s.ante,
+                duration_minutes: s.duration_minutes,
+                is_break: s.is_break,
+                break_duration_minutes: s.break_duration_minutes,
+            }),
+            next_structure,
+            // Additional fields from ClockUpdate
+            small_blind: structure.as_ref().map(|s| s.small_blind),
+            big_blind: structure.as_ref().map(|s| s.big_blind),
+            ante: structure.as_ref().map(|s| s.ante),
+            is_break: structure.as_ref().map(|s| s.is_break),
+            level_duration_minutes: structure.as_ref().map(|s| s.duration_minutes),
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Publish the clock of a tournament after a change made outside its mutations
+pub(crate) async fn publish_clock_change(db: &Db, tournament_id: Uuid) {
+    match load_tournament_clock(db, tournament_id).await {
+        Ok(Some(clock)) => publish_clock_event(clock),
+        Ok(None) => {}
+        Err(e) => warn!(
+            "Failed to load clock of tournament {} to publish: {}",
+            tournament_id, e
+        ),
+    }
+}
+
+/// Time left in the current level now, counted down from the level end time while the
+/// clock runs
+fn time_remaining_now(clock: &TournamentClock) -> Option<i64> {
+    match (clock.status, clock.level_end_time) {
+        (ClockStatus::Running, Some(end_time)) => {
+            Some((end_time - Utc::now()).num_seconds().max(0))
+        }
+        _ => clock.time_remaining_seconds,
+    }
+}
+
 pub struct TournamentClockQuery;
 
 #[Object]
@@ -88,92 +218,9 @@ impl TournamentClockQuery {
         tournament_id: ID,
     ) -> Result<Option<TournamentClock>> {
         let state = ctx.data::<AppState>()?;
-        let repo = TournamentClockRepo::new(state.db.clone());
         let tournament_id: Uuid = tournament_id.parse()?;
 
-        if let Some(clock_row) = repo.get_clock(tournament_id).await? {
-            let structure = repo.get_current_structure(tournament_id).await.ok();
-            let next_structure =
-                get_next_structure(&repo, tournament_id, clock_row.current_level).await;
-
-            // Calculate time remaining
-            let time_remaining =
-                if let Ok(status) = InfraClockStatus::from_str(&clock_row.clock_status) {
-                    match status {
-                        InfraClockStatus::Running => {
-                            if let Some(end_time) = clock_row.level_end_time {
-                                let remaining = end_time - Utc::now();
-                                Some(remaining.num_seconds().max(0))
-                            } else {
-                                None
-                            }
-                        }
-                        InfraClockStatus::Paused => {
-                            if let (Some(end_time), Some(pause_start)) =
-                                (clock_row.level_end_time, clock_row.pause_started_at)
-                            {
-                                let remaining = end_time - pause_start;
-                                Some(remaining.num_seconds().max(0))
-                            } else {
-                                None
-                            }
-                        }
-                        InfraClockStatus::Stopped => {
-                            // Show full duration of current level when stopped
-                            // Use already fetched structure or fetch it
-                            if let Some(s) = &structure {
-                                Some((s.duration_minutes as i64) * 60)
-                            } else if let Ok(current_structure) =
-                                repo.get_current_structure(tournament_id).await
-                            {
-                                Some((current_structure.duration_minutes as i64) * 60)
-                            } else {
-                                None
-                            }
-                        }
-                    }
-                } else {
-                    None
-                };
-
-            // Convert PgInterval to seconds
-            let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
-
-            Ok(Some(TournamentClock {
-                id: clock_row.id.into(),
-                tournament_id: clock_row.tournament_id.into(),
-                status: InfraClockStatus::from_str(&clock_row.clock_status)
-                    .ok()
-                    .unwrap_or(InfraClockStatus::Stopped)
-                    .into(),
-                current_level: clock_row.current_level,
-                time_remaining_seconds: time_remaining,
-                level_started_at: clock_row.level_started_at,
-                level_end_time: clock_row.level_end_time,
-                total_pause_duration_seconds: total_pause_seconds,
-                auto_advance: clock_row.auto_advance,
-                current_structure: structure.as_ref().map(|s| TournamentStructure {
-                    id: s.id.into(),
-                    tournament_id: s.tournament_id.into(),
-                    level_number: s.level_number,
-                    small_blind: s.small_blind,
-                    big_blind: s.big_blind,
-                    ante: s.ante,
-                    duration_minutes: s.duration_minutes,
-                    is_break: s.is_break,
-                    break_duration_minutes: s.break_duration_minutes,
-                }),
-                next_structure,
-                // Additional fields from ClockUpdate
-                small_blind: structure.as_ref().map(|s| s.small_blind),
-                big_blind: structure.as_ref().map(|s| s.big_blind),
-                ante: structure.as_ref().map(|s| s.ante),
-                is_break: structure.as_ref().map(|s| s.is_break),
-                level_duration_minutes: structure.as_ref().map(|s| s.duration_minutes),
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(load_tournament_clock(&state.db, tournament_id).await?)
     }
 
     /// Get tournament structure levels
@@ -228,7 +275,7 @@ impl TournamentClockMutation {
         // Show full duration of first level when clock is created (stopped state)
         let time_remaining_seconds = structure.as_ref().map(|s| (s.duration_minutes as i64) * 60);
 
-        Ok(TournamentClock {
+        let clock = TournamentClock {
             id: clock_row.id.into(),
             tournament_id: clock_row.tournament_id.into(),
             status: InfraClockStatus::from_str(&clock_row.clock_status)
@@ -259,7 +306,10 @@ impl TournamentClockMutation {
             ante: structure.as_ref().map(|s| s.ante),
             is_break: structure.as_ref().map(|s| s.is_break),
             level_duration_minutes: structure.as_ref().map(|s| s.duration_minutes),
-        })
+        };
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 
     /// Start tournament clock
@@ -288,14 +338,17 @@ impl TournamentClockMutation {
             None
         };
 
-        Ok(create_tournament_clock(
+        let clock = create_tournament_clock(
             &clock_row,
             structure.as_ref(),
             next_structure,
             time_remaining,
             0,
             ClockStatus::Running,
-        ))
+        );
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 
     /// Pause tournament clock
@@ -328,14 +381,17 @@ impl TournamentClockMutation {
 
         let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
 
-        Ok(create_tournament_clock(
+        let clock = create_tournament_clock(
             &clock_row,
             structure.as_ref(),
             next_structure,
             time_remaining,
             total_pause_seconds,
             ClockStatus::Paused,
-        ))
+        );
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 
     /// Resume tournament clock
@@ -366,14 +422,17 @@ impl TournamentClockMutation {
 
         let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
 
-        Ok(create_tournament_clock(
+        let clock = create_tournament_clock(
             &clock_row,
             structure.as_ref(),
             next_structure,
             time_remaining,
             total_pause_seconds,
             ClockStatus::Running,
-        ))
+        );
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 
     /// Manually advance to next level
@@ -381,6 +440,7 @@ impl TournamentClockMutation {
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
+        expected_level: Option<i32>,
     ) -> Result<TournamentClock> {
         let state = ctx.data::<AppState>()?;
         let tournament_id: Uuid = tournament_id.parse()?;
@@ -399,8 +459,15 @@ impl TournamentClockMutation {
         let repo = TournamentClockRepo::new(state.db.clone());
 
         let clock_row = repo
-            .advance_level(tournament_id, false, Some(manager.id.parse()?))
-            .await?;
+            .advance_level(
+                tournament_id,
+                expected_level,
+                false,
+                Some(manager.id.parse()?),
+                Utc::now(),
+            )
+            .await?
+            .ok_or_else(|| level_moved_error(expected_level))?;
         let structure = repo.get_current_structure(tournament_id).await.ok();
         let next_structure =
             get_next_structure(&repo, tournament_id, clock_row.current_level).await;
@@ -421,14 +488,17 @@ impl TournamentClockMutation {
         // Convert PgInterval to seconds for total pause duration
         let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
 
-        Ok(create_tournament_clock(
+        let clock = create_tournament_clock(
             &clock_row,
             structure.as_ref(),
             next_structure,
             time_remaining,
             total_pause_seconds,
             status,
-        ))
+        );
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 
     /// Manually revert to previous level
@@ -436,6 +506,7 @@ impl TournamentClockMutation {
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
+        expected_level: Option<i32>,
     ) -> Result<TournamentClock> {
         let manager = require_role(ctx, Role::Manager).await?;
         let state = ctx.data::<AppState>()?;
@@ -443,14 +514,15 @@ impl TournamentClockMutation {
         let tournament_id: Uuid = tournament_id.parse()?;
 
         let clock_row = repo
-            .revert_level(tournament_id, Some(manager.id.parse()?))
+            .revert_level(tournament_id, expected_level, Some(manager.id.parse()?))
             .await
             .map_err(|e| match e {
                 sqlx::Error::RowNotFound => {
                     async_graphql::Error::new("Cannot revert: Tournament is already at level 1")
                 }
                 _ => async_graphql::Error::new(format!("Failed to revert level: {}", e)),
-            })?;
+            })?
+            .ok_or_else(|| level_moved_error(expected_level))?;
         let structure = repo.get_current_structure(tournament_id).await.ok();
         let next_structure =
             get_next_structure(&repo, tournament_id, clock_row.current_level).await;
@@ -471,14 +543,17 @@ impl TournamentClockMutation {
         // Convert PgInterval to seconds for total pause duration
         let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
 
-        Ok(create_tournament_clock(
+        let clock = create_tournament_clock(
             &clock_row,
             structure.as_ref(),
             next_structure,
             time_remaining,
             total_pause_seconds,
             status,
-        ))
+        );
+
+        publish_clock_event(clock.clone());
+        Ok(clock)
     }
 }
 
@@ -486,119 +561,57 @@ pub struct TournamentClockSubscription;
 
 #[Subscription]
 impl TournamentClockSubscription {
-    /// Subscribe to tournament clock updates
+    /// Subscribe to tournament clock updates. The clock is read once, then follows the
+    /// changes published on the clock bus; the countdown ticks every second from the
+    /// level end time without going back to the database.
     pub async fn tournament_clock_updates(
         &self,
         ctx: &Context<'_>,
         tournament_id: ID,
     ) -> Result<impl Stream<Item = TournamentClock>> {
         let state = ctx.data::<AppState>()?;
-        let repo = TournamentClockRepo::new(state.db.clone());
+        let db = state.db.clone();
         let tournament_id: Uuid = tournament_id.parse()?;
+        let tournament_id_filter = tournament_id.to_string();
 
-        // Create a stream that emits every second
+        // Subscribe before reading the clock so no change slips in between
+        let mut receiver = subscribe_clock_events();
         let mut interval = interval(StdDuration::from_secs(1));
 
         Ok(async_stream::stream! {
+            let mut clock = load_tournament_clock(&db, tournament_id).await.ok().flatten();
+            if let Some(clock) = &clock {
+                yield clock.clone();
+            }
+            interval.tick().await;
+
             loop {
-                interval.tick().await;
-
-                // Get current clock state
-                if let Ok(Some(clock_row)) = repo.get_clock(tournament_id).await {
-                    if let Ok(structure) = repo.get_current_structure(tournament_id).await {
-                        let clock_status = InfraClockStatus::from_str(&clock_row.clock_status).ok()
-                            .unwrap_or(InfraClockStatus::Stopped);
-
-                        // Calculate time remaining
-                        let time_remaining = match clock_status {
-                            InfraClockStatus::Running => {
-                                if let Some(end_time) = clock_row.level_end_time {
-                                    let remaining = end_time - Utc::now();
-                                    Some(remaining.num_seconds().max(0))
-                                } else {
-                                    None
-                                }
-                            },
-                            InfraClockStatus::Paused => {
-                                if let (Some(end_time), Some(pause_start)) = (clock_row.level_end_time, clock_row.pause_started_at) {
-                                    let remaining = end_time - pause_start;
-                                    Some(remaining.num_seconds().max(0))
-                                } else {
-                                    None
-                                }
-                            },
-                            InfraClockStatus::Stopped => {
-                                // Show full duration of current level when stopped
-                                // If we can't get current level, try to get level 1 as fallback
-                                if let Ok(all_structures) = repo.get_all_structures(tournament_id).await {
-                                    if let Some(current_struct) = all_structures.iter().find(|s| s.level_number == clock_row.current_level) {
-                                        Some((current_struct.duration_minutes as i64) * 60)
-                                    } else if let Some(first_struct) = all_structures.iter().find(|s| s.level_number == 1) {
-                                        Some((first_struct.duration_minutes as i64) * 60)
-                                    } else {
-                                        Some((structure.duration_minutes as i64) * 60)
-                                    }
-                                } else {
-                                    Some((structure.duration_minutes as i64) * 60)
-                                }
-                            }
-                        };
-
-                        // Get next level preview
-                        let next_level_preview = repo.get_all_structures(tournament_id).await
-                            .ok()
-                            .and_then(|structures| {
-                                structures.into_iter()
-                                    .find(|s| s.level_number == clock_row.current_level + 1)
-                                    .map(|s| TournamentStructure {
-                                        id: s.id.into(),
-                                        tournament_id: s.tournament_id.into(),
-                                        level_number: s.level_number,
-                                        small_blind: s.small_blind,
-                                        big_blind: s.big_blind,
-                                        ante: s.ante,
-                                        duration_minutes: s.duration_minutes,
-                                        is_break: s.is_break,
-                                        break_duration_minutes: s.break_duration_minutes,
-                                    })
-                            });
-
-                        // Convert PgInterval to seconds for total pause duration
-                        let total_pause_seconds = clock_row.total_pause_duration.microseconds / 1_000_000;
-
-                        let update = TournamentClock {
-                            id: clock_row.id.into(),
-                            tournament_id: tournament_id.into(),
-                            status: clock_status.into(),
-                            current_level: clock_row.current_level,
-                            time_remaining_seconds: time_remaining,
-                            level_started_at: clock_row.level_started_at,
-                            level_end_time: clock_row.level_end_time,
-                            total_pause_duration_seconds: total_pause_seconds,
-                            auto_advance: clock_row.auto_advance,
-                            current_structure: Some(TournamentStructure {
-                                id: structure.id.into(),
-                                tournament_id: structure.tournament_id.into(),
-                                level_number: structure.level_number,
-                                small_blind: structure.small_blind,
-                                big_blind: structure.big_blind,
-                                ante: structure.ante,
-                                duration_minutes: structure.duration_minutes,
-                                is_break: structure.is_break,
-                                break_duration_minutes: structure.break_duration_minutes,
-                            }),
-                            next_structure: next_level_preview,
-                            // Additional fields from ClockUpdate
-                            small_blind: Some(structure.small_blind),
-                            big_blind: Some(structure.big_blind),
-                            ante: Some(structure.ante),
-                            is_break: Some(structure.is_break),
-                            level_duration_minutes: Some(structure.duration_minutes),
-                        };
-
-                        yield update;
+                tokio::select! {
+                    event = receiver.recv() => match event {
+                        Ok(BusEvent::Clock(update))
+                            if update.tournament_id.as_str() == tournament_id_filter =>
+                        {
+                            clock = Some(update);
+                        }
+                        Ok(_) => continue,
+                        // Missed changes, read the clock again
+                        Err(broadcast::error::RecvError::Lagged(_)) => {
+                            clock = load_tournament_clock(&db, tournament_id).await.ok().flatten();
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = interval.tick() => {
+                        // Only a running clock counts down between changes
+                        if !clock.as_ref().is_some_and(|clock| clock.status == ClockStatus::Running) {
+                            continue;
+                        }
                     }
                 }
+
+                if let Some(clock) = clock.as_mut() {
+                    clock.time_remaining_seconds = time_remaining_now(clock);
+                    yield clock.clone();
+                }
             }
         })
     }
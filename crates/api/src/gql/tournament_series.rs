@@ -0,0 +1,284 @@
+use async_graphql::{Context, Result, ID};
+use uuid::Uuid;
+
+use crate::auth::permissions::require_club_manager;
+use crate::gql::blind_structures::blind_levels_from_input;
+use crate::gql::types::{
+    CreateTournamentSeriesInput, TournamentSeries, UpdateTournamentSeriesInput, Weekday,
+};
+use crate::state::AppState;
+use infra::models::TournamentSeriesRow;
+use infra::repos::{
+    CreateTournamentSeries, PayoutTemplateRepo, TournamentSeriesRepo, UpdateTournamentSeries,
+};
+
+const DEFAULT_TIMEZONE: &str = "Europe/Brussels";
+const DEFAULT_WEEKS_AHEAD: i32 = 4;
+const MAX_WEEKS_AHEAD: i32 = 26;
+
+/// Helper function to turn weekdays into sorted, distinct ISO day numbers
+fn weekday_numbers(weekdays: &[Weekday]) -> Result<Vec<i32>> {
+    let mut days: Vec<i32> = weekdays.iter().map(|day| day.iso_number()).collect();
+    days.sort();
+    days.dedup();
+    if days.is_empty() {
+        return Err(async_graphql::Error::new(
+            "A series must run on at least one weekday",
+        ));
+    }
+    Ok(days)
+}
+
+/// Helper function to validate the details shared by every instance of a series
+fn validate_series_details(
+    name: &str,
+    buy_in_cents: i32,
+    seat_cap: Option<i32>,
+    duration_minutes: Option<i32>,
+    weeks_ahead: i32,
+) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(async_graphql::Error::new("Series name cannot be empty"));
+    }
+    if buy_in_cents < 0 {
+        return Err(async_graphql::Error::new("Buy-in cannot be negative"));
+    }
+    if matches!(seat_cap, Some(cap) if cap <= 0) {
+        return Err(async_graphql::Error::new("Seat cap must be positive"));
+    }
+    if matches!(duration_minutes, Some(minutes) if minutes <= 0) {
+        return Err(async_graphql::Error::new("Duration must be positive"));
+    }
+    if !(1..=MAX_WEEKS_AHEAD).contains(&weeks_ahead) {
+        return Err(async_graphql::Error::new(format!(
+            "Weeks ahead must be between 1 and {}",
+            MAX_WEEKS_AHEAD
+        )));
+    }
+    Ok(())
+}
+
+async fn check_timezone(repo: &TournamentSeriesRepo, timezone: &str) -> Result<()> {
+    if !repo.is_valid_timezone(timezone).await? {
+        return Err(async_graphql::Error::new(format!(
+            "Unknown time zone: {}",
+            timezone
+        )));
+    }
+    Ok(())
+}
+
+async fn parse_payout_template(state: &AppState, template_id: &ID) -> Result<Uuid> {
+    let template_id = Uuid::parse_str(template_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid template ID: {}", e)))?;
+    PayoutTemplateRepo::new(state.db.clone())
+        .get_by_id(template_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Payout template not found"))?;
+    Ok(template_id)
+}
+
+async fn get_managed_series(ctx: &Context<'_>, series_id: &ID) -> Result<TournamentSeriesRow> {
+    let state = ctx.data::<AppState>()?;
+    let series_id = Uuid::parse_str(series_id.as_str())
+        .map_err(|e| async_graphql::Error::new(format!("Invalid series ID: {}", e)))?;
+
+    let series = TournamentSeriesRepo::new(state.db.clone())
+        .get(series_id)
+        .await?
+        .ok_or_else(|| async_graphql::Error::new("Tournament series not found"))?;
+    require_club_manager(ctx, series.club_id).await?;
+
+    Ok(series)
+}
+
+pub struct TournamentSeriesQuery;
+
+impl TournamentSeriesQuery {
+    /// Get the recurring series of a club
+    pub async fn tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        club_id: ID,
+    ) -> Result<Vec<TournamentSeries>> {
+        let state = ctx.data::<AppState>()?;
+        let club_id = Uuid::parse_str(club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+
+        let rows = TournamentSeriesRepo::new(state.db.clone())
+            .list_by_club(club_id)
+            .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+pub struct TournamentSeriesMutation;
+
+impl TournamentSeriesMutation {
+    /// Create a recurring series and its first instances
+    pub async fn create_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateTournamentSeriesInput,
+    ) -> Result<TournamentSeries> {
+        let club_id = Uuid::parse_str(input.club_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid club ID: {}", e)))?;
+        let manager = require_club_manager(ctx, club_id).await?;
+
+        let state = ctx.data::<AppState>()?;
+        let repo = TournamentSeriesRepo::new(state.db.clone());
+
+        let name = input.name.trim().to_string();
+        let weeks_ahead = input.weeks_ahead.unwrap_or(DEFAULT_WEEKS_AHEAD);
+        validate_series_details(
+            &name,
+            input.buy_in_cents,
+            input.seat_cap,
+            input.duration_minutes,
+            weeks_ahead,
+        )?;
+        let weekdays = weekday_numbers(&input.weekdays)?;
+        let blind_structure = blind_levels_from_input(input.blind_structure)?;
+
+        let timezone = input
+            .timezone
+            .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+        check_timezone(&repo, &timezone).await?;
+
+        let payout_template_id = match input.payout_template_id.as_ref() {
+            Some(template_id) => Some(parse_payout_template(state, template_id).await?),
+            None => None,
+        };
+
+        let row = repo
+            .create(CreateTournamentSeries {
+                club_id,
+                name,
+                description: input.description,
+                buy_in_cents: input.buy_in_cents,
+                seat_cap: input.seat_cap,
+                duration_minutes: input.duration_minutes,
+                blind_structure,
+                payout_template_id,
+                weekdays,
+                local_start_time: input.local_start_time,
+                timezone,
+                weeks_ahead,
+                created_by: Some(Uuid::parse_str(manager.id.as_str())?),
+            })
+            .await?;
+
+        repo.materialize(row.id).await?;
+
+        Ok(row.into())
+    }
+
+    /// Edit a series. Existing instances only change when `applyToFutureInstances` is
+    /// set, and then only those that haven't started; instances with registrations keep
+    /// their date even if the schedule moves.
+    pub async fn update_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateTournamentSeriesInput,
+    ) -> Result<TournamentSeries> {
+        let existing = get_managed_series(ctx, &input.id).await?;
+
+        let state = ctx.data::<AppState>()?;
+        let repo = TournamentSeriesRepo::new(state.db.clone());
+
+        let name = input.name.map(|name| name.trim().to_string());
+        let description: Option<Option<String>> = input.description.into();
+        let seat_cap: Option<Option<i32>> = input.seat_cap.into();
+        let duration_minutes: Option<Option<i32>> = input.duration_minutes.into();
+        let payout_template_id: Option<Option<ID>> = input.payout_template_id.into();
+        validate_series_details(
+            name.as_deref().unwrap_or(&existing.name),
+            input.buy_in_cents.unwrap_or(existing.buy_in_cents),
+            seat_cap.unwrap_or(existing.seat_cap),
+            duration_minutes.unwrap_or(existing.duration_minutes),
+            input.weeks_ahead.unwrap_or(existing.weeks_ahead),
+        )?;
+        let weekdays = input.weekdays.as_deref().map(weekday_numbers).transpose()?;
+        let blind_structure = input
+            .blind_structure
+            .map(blind_levels_from_input)
+            .transpose()?;
+        if let Some(timezone) = input.timezone.as_deref() {
+            check_timezone(&repo, timezone).await?;
+        }
+        let payout_template_id = match payout_template_id {
+            Some(Some(template_id)) => {
+                Some(Some(parse_payout_template(state, &template_id).await?))
+            }
+            Some(None) => Some(None),
+            None => None,
+        };
+
+        let update = UpdateTournamentSeries {
+            name,
+            description,
+            buy_in_cents: input.buy_in_cents,
+            seat_cap,
+            duration_minutes,
+            blind_structure,
+            payout_template_id,
+            weekdays,
+            local_start_time: input.local_start_time,
+            timezone: input.timezone,
+            weeks_ahead: input.weeks_ahead,
+        };
+        let changes_schedule = update.changes_schedule();
+
+        let row = repo
+            .update(existing.id, update)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament series not found"))?;
+
+        if input.apply_to_future_instances.unwrap_or(false) {
+            if changes_schedule {
+                repo.reschedule_future_instances(row.id).await?;
+            }
+            repo.apply_to_future_instances(row.id).await?;
+        }
+        repo.materialize(row.id).await?;
+
+        Ok(row.into())
+    }
+
+    /// Stop creating new instances of a series (existing instances are kept)
+    pub async fn pause_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<TournamentSeries> {
+        let existing = get_managed_series(ctx, &series_id).await?;
+        let state = ctx.data::<AppState>()?;
+
+        let row = TournamentSeriesRepo::new(state.db.clone())
+            .set_paused(existing.id, true)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament series not found"))?;
+
+        Ok(row.into())
+    }
+
+    /// Resume a paused series and create its upcoming instances
+    pub async fn resume_tournament_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<TournamentSeries> {
+        let existing = get_managed_series(ctx, &series_id).await?;
+        let state = ctx.data::<AppState>()?;
+        let repo = TournamentSeriesRepo::new(state.db.clone());
+
+        let row = repo
+            .set_paused(existing.id, false)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("Tournament series not found"))?;
+        repo.materialize(row.id).await?;
+
+        Ok(row.into())
+    }
+}
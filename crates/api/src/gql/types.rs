@@ -1,6 +1,8 @@
 use async_graphql::dataloader::DataLoader;
-use async_graphql::{ComplexObject, Context, Enum, Error, InputObject, Result, SimpleObject, ID};
-use chrono::{DateTime, Utc};
+use async_graphql::{
+    ComplexObject, Context, Enum, Error, InputObject, MaybeUndefined, Result, SimpleObject, ID,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use uuid::Uuid;
 
 use crate::gql::loaders::ClubLoader;
@@ -53,6 +55,8 @@ pub enum TournamentStatus {
     InProgress,
     #[graphql(name = "COMPLETED")]
     Completed,
+    #[graphql(name = "CANCELLED")]
+    Cancelled,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
@@ -71,6 +75,8 @@ pub enum TournamentLiveStatus {
     FinalTable,
     #[graphql(name = "FINISHED")]
     Finished,
+    #[graphql(name = "CANCELLED")]
+    Cancelled,
 }
 
 impl From<TournamentStatus> for infra::repos::tournaments::TournamentStatus {
@@ -79,6 +85,7 @@ impl From<TournamentStatus> for infra::repos::tournaments::TournamentStatus {
             TournamentStatus::Upcoming => infra::repos::tournaments::TournamentStatus::Upcoming,
             TournamentStatus::InProgress => infra::repos::tournaments::TournamentStatus::InProgress,
             TournamentStatus::Completed => infra::repos::tournaments::TournamentStatus::Completed,
+            TournamentStatus::Cancelled => infra::repos::tournaments::TournamentStatus::Cancelled,
         }
     }
 }
@@ -89,6 +96,7 @@ impl From<infra::repos::tournaments::TournamentStatus> for TournamentStatus {
             infra::repos::tournaments::TournamentStatus::Upcoming => TournamentStatus::Upcoming,
             infra::repos::tournaments::TournamentStatus::InProgress => TournamentStatus::InProgress,
             infra::repos::tournaments::TournamentStatus::Completed => TournamentStatus::Completed,
+            infra::repos::tournaments::TournamentStatus::Cancelled => TournamentStatus::Cancelled,
         }
     }
 }
@@ -103,6 +111,7 @@ impl From<String> for TournamentLiveStatus {
             "break" => TournamentLiveStatus::Break,
             "final_table" => TournamentLiveStatus::FinalTable,
             "finished" => TournamentLiveStatus::Finished,
+            "cancelled" => TournamentLiveStatus::Cancelled,
             _ => TournamentLiveStatus::NotStarted, // Default to not_started for invalid statuses
         }
     }
@@ -127,6 +136,7 @@ impl From<TournamentLiveStatus> for String {
             TournamentLiveStatus::Break => "break".to_string(),
             TournamentLiveStatus::FinalTable => "final_table".to_string(),
             TournamentLiveStatus::Finished => "finished".to_string(),
+            TournamentLiveStatus::Cancelled => "cancelled".to_string(),
         }
     }
 }
@@ -153,6 +163,9 @@ impl From<infra::repos::tournaments::TournamentLiveStatus> for TournamentLiveSta
             infra::repos::tournaments::TournamentLiveStatus::Finished => {
                 TournamentLiveStatus::Finished
             }
+            infra::repos::tournaments::TournamentLiveStatus::Cancelled => {
+                TournamentLiveStatus::Cancelled
+            }
         }
     }
 }
@@ -179,6 +192,9 @@ impl From<TournamentLiveStatus> for infra::repos::tournaments::TournamentLiveSta
             TournamentLiveStatus::Finished => {
                 infra::repos::tournaments::TournamentLiveStatus::Finished
             }
+            TournamentLiveStatus::Cancelled => {
+                infra::repos::tournaments::TournamentLiveStatus::Cancelled
+            }
         }
     }
 }
@@ -192,14 +208,123 @@ pub struct Tournament {
     pub club_id: ID,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
+    /// Total price paid by the player, rake and staff fee included
     pub buy_in_cents: i32,
+    /// Part of the buy-in kept by the house
+    pub rake_cents: i32,
+    /// Part of the buy-in paid to the dealers and staff
+    pub staff_fee_cents: i32,
+    /// Part of the buy-in that goes to the prize pool
+    pub prize_contribution_cents: i32,
+    /// Guaranteed prize pool, the club covers any overlay
+    pub guarantee_cents: Option<i32>,
+    pub bounty_format: BountyFormat,
+    /// Part of the buy-in put on each player's head
+    pub bounty_cents: i32,
     pub seat_cap: Option<i32>,
     pub status: TournamentStatus, // Calculated: UPCOMING, LIVE, COMPLETED
     pub live_status: TournamentLiveStatus, // Direct from DB: NOT_STARTED, IN_PROGRESS, FINISHED, etc.
+    /// Players can unregister themselves until this many minutes before the start
+    pub unregister_cutoff_minutes: i32,
+    /// Re-entries allowed per player after busting
+    pub max_reentries: i32,
+    /// Rebuys allowed per player while still in
+    pub max_rebuys: i32,
+    /// Rebuy price, the buy-in when not set
+    pub rebuy_cents: Option<i32>,
+    /// Add-on price, no add-on when not set
+    pub add_on_cents: Option<i32>,
+    /// Chips the add-on buys
+    pub add_on_chips: Option<i32>,
+    /// Last level in which re-entries, rebuys and add-ons are allowed
+    pub rebuy_until_level: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl From<infra::models::TournamentRow> for Tournament {
+    fn from(row: infra::models::TournamentRow) -> Self {
+        Self {
+            status: row.calculate_status().into(),
+            id: row.id.into(),
+            title: row.name,
+            description: row.description,
+            club_id: row.club_id.into(),
+            start_time: row.start_time,
+            end_time: row.end_time,
+            buy_in_cents: row.buy_in_cents,
+            rake_cents: row.rake_cents,
+            staff_fee_cents: row.staff_fee_cents,
+            prize_contribution_cents: row.prize_contribution_cents,
+            guarantee_cents: row.guarantee_cents,
+            bounty_format: row.bounty_format.into(),
+            bounty_cents: row.bounty_cents,
+            seat_cap: row.seat_cap,
+            live_status: row.live_status.into(),
+            unregister_cutoff_minutes: row.unregister_cutoff_minutes,
+            max_reentries: row.max_reentries,
+            max_rebuys: row.max_rebuys,
+            rebuy_cents: row.rebuy_cents,
+            add_on_cents: row.add_on_cents,
+            add_on_chips: row.add_on_chips,
+            rebuy_until_level: row.rebuy_until_level,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BountyFormat {
+    #[graphql(name = "NONE")]
+    None,
+
+    /// The eliminator collects the whole bounty
+    #[graphql(name = "REGULAR")]
+    Regular,
+
+    /// Progressive knockout: half is paid, half goes on the eliminator's head
+    #[graphql(name = "PROGRESSIVE")]
+    Progressive,
+}
+
+impl From<infra::bounty::BountyFormat> for BountyFormat {
+    fn from(format: infra::bounty::BountyFormat) -> Self {
+        match format {
+            infra::bounty::BountyFormat::None => BountyFormat::None,
+            infra::bounty::BountyFormat::Regular => BountyFormat::Regular,
+            infra::bounty::BountyFormat::Progressive => BountyFormat::Progressive,
+        }
+    }
+}
+
+impl From<BountyFormat> for infra::bounty::BountyFormat {
+    fn from(format: BountyFormat) -> Self {
+        match format {
+            BountyFormat::None => infra::bounty::BountyFormat::None,
+            BountyFormat::Regular => infra::bounty::BountyFormat::Regular,
+            BountyFormat::Progressive => infra::bounty::BountyFormat::Progressive,
+        }
+    }
+}
+
+impl From<String> for BountyFormat {
+    fn from(format: String) -> Self {
+        use std::str::FromStr;
+
+        infra::bounty::BountyFormat::from_str(&format)
+            .unwrap_or(infra::bounty::BountyFormat::None)
+            .into()
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct Tag {
+    pub id: ID,
+    pub slug: String,
+    pub label: String,
+}
+
 #[derive(SimpleObject, Clone)]
 pub struct Club {
     pub id: ID,
@@ -207,7 +332,7 @@ pub struct Club {
     pub city: Option<String>,
 }
 
-#[derive(SimpleObject, Clone, serde::Serialize)]
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 #[graphql(complex)]
 pub struct User {
     pub id: ID,
@@ -220,7 +345,42 @@ pub struct User {
     pub role: Role,
 }
 
+impl From<infra::models::UserRow> for User {
+    fn from(row: infra::models::UserRow) -> Self {
+        Self {
+            id: row.id.into(),
+            email: row.email,
+            username: row.username,
+            first_name: row.first_name,
+            last_name: row.last_name,
+            phone: row.phone,
+            is_active: row.is_active,
+            role: Role::from(row.role),
+        }
+    }
+}
+
+/// What anyone may see of a player, without their contact details
 #[derive(SimpleObject, Clone)]
+pub struct PublicUser {
+    pub id: ID,
+    pub username: Option<String>,
+    pub first_name: String,
+    pub last_name: Option<String>,
+}
+
+impl From<infra::models::UserRow> for PublicUser {
+    fn from(row: infra::models::UserRow) -> Self {
+        Self {
+            id: row.id.into(),
+            username: row.username,
+            first_name: row.first_name,
+            last_name: row.last_name,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 #[graphql(complex)]
 pub struct TournamentRegistration {
     pub id: ID,
@@ -231,12 +391,32 @@ pub struct TournamentRegistration {
     pub notes: Option<String>,
 }
 
-#[derive(SimpleObject, Clone)]
+impl From<infra::models::TournamentRegistrationRow> for TournamentRegistration {
+    fn from(row: infra::models::TournamentRegistrationRow) -> Self {
+        Self {
+            id: row.id.into(),
+            tournament_id: row.tournament_id.into(),
+            user_id: row.user_id.into(),
+            registration_time: row.registration_time,
+            status: row.status.into(),
+            notes: row.notes,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TournamentPlayer {
     pub registration: TournamentRegistration,
     pub user: User,
 }
 
+#[derive(SimpleObject, Clone)]
+pub struct WaitlistEntry {
+    /// 1 for the next player to get a seat
+    pub position: i32,
+    pub player: TournamentPlayer,
+}
+
 #[derive(SimpleObject, Clone)]
 pub struct TournamentResult {
     pub id: ID,
@@ -244,11 +424,438 @@ pub struct TournamentResult {
     pub user_id: ID,
     pub final_position: i32,
     pub prize_cents: i32,
+    /// Bounties won, on top of the prize
+    pub bounty_cents: i32,
     pub points: i32,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+impl From<infra::models::TournamentResultRow> for TournamentResult {
+    fn from(row: infra::models::TournamentResultRow) -> Self {
+        Self {
+            id: row.id.into(),
+            tournament_id: row.tournament_id.into(),
+            user_id: row.user_id.into(),
+            final_position: row.final_position,
+            prize_cents: row.prize_cents,
+            bounty_cents: row.bounty_cents,
+            points: row.points,
+            notes: row.notes,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ResultCorrectionAction {
+    #[graphql(name = "CORRECT")]
+    Correct,
+    #[graphql(name = "DELETE")]
+    Delete,
+}
+
+impl From<String> for ResultCorrectionAction {
+    fn from(action: String) -> Self {
+        match action.as_str() {
+            "delete" => ResultCorrectionAction::Delete,
+            _ => ResultCorrectionAction::Correct,
+        }
+    }
+}
+
+/// A correction made to a tournament result, with the values before and after
+#[derive(SimpleObject, Clone)]
+pub struct TournamentResultCorrection {
+    pub id: ID,
+    pub tournament_id: ID,
+    pub result_id: ID,
+    pub user_id: ID,
+    pub action: ResultCorrectionAction,
+    pub old_final_position: i32,
+    /// New values are empty when the result was deleted
+    pub new_final_position: Option<i32>,
+    pub old_prize_cents: i32,
+    pub new_prize_cents: Option<i32>,
+    pub old_bounty_cents: i32,
+    pub new_bounty_cents: Option<i32>,
+    pub old_notes: Option<String>,
+    pub new_notes: Option<String>,
+    pub reason: Option<String>,
+    pub corrected_by: Option<ID>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<infra::models::TournamentResultCorrectionRow> for TournamentResultCorrection {
+    fn from(row: infra::models::TournamentResultCorrectionRow) -> Self {
+        Self {
+            id: row.id.into(),
+            tournament_id: row.tournament_id.into(),
+            result_id: row.result_id.into(),
+            user_id: row.user_id.into(),
+            action: row.action.into(),
+            old_final_position: row.old_final_position,
+            new_final_position: row.new_final_position,
+            old_prize_cents: row.old_prize_cents,
+            new_prize_cents: row.new_prize_cents,
+            old_bounty_cents: row.old_bounty_cents,
+            new_bounty_cents: row.new_bounty_cents,
+            old_notes: row.old_notes,
+            new_notes: row.new_notes,
+            reason: row.reason,
+            corrected_by: row.corrected_by.map(Into::into),
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ScoringFormula {
+    /// Field size, finishing position and buy-in
+    #[graphql(name = "AUTHORITATIVE")]
+    Authoritative,
+    /// One point per player beaten, plus one
+    #[graphql(name = "LINEAR")]
+    Linear,
+    /// Field size and finishing position, whatever the buy-in
+    #[graphql(name = "FIELD_SIZE")]
+    FieldSize,
+}
+
+impl From<infra::scoring::ScoringFormulaKind> for ScoringFormula {
+    fn from(kind: infra::scoring::ScoringFormulaKind) -> Self {
+        match kind {
+            infra::scoring::ScoringFormulaKind::Authoritative => ScoringFormula::Authoritative,
+            infra::scoring::ScoringFormulaKind::Linear => ScoringFormula::Linear,
+            infra::scoring::ScoringFormulaKind::FieldSize => ScoringFormula::FieldSize,
+        }
+    }
+}
+
+impl From<ScoringFormula> for infra::scoring::ScoringFormulaKind {
+    fn from(formula: ScoringFormula) -> Self {
+        match formula {
+            ScoringFormula::Authoritative => infra::scoring::ScoringFormulaKind::Authoritative,
+            ScoringFormula::Linear => infra::scoring::ScoringFormulaKind::Linear,
+            ScoringFormula::FieldSize => infra::scoring::ScoringFormulaKind::FieldSize,
+        }
+    }
+}
+
+/// Formula a club scores its leaderboard with
+#[derive(SimpleObject, Clone)]
+pub struct ClubScoringSettings {
+    pub club_id: ID,
+    pub formula: ScoringFormula,
+    /// Most points a single finish can earn
+    pub cap: i32,
+    pub multiplier: f64,
+    /// Empty while the club uses the defaults
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ClubScoringSettings {
+    /// Settings of a club that never changed them
+    pub fn defaults(club_id: Uuid) -> Self {
+        let settings = infra::scoring::ScoringSettings::default();
+        Self {
+            club_id: club_id.into(),
+            formula: settings.formula.into(),
+            cap: settings.cap as i32,
+            multiplier: settings.multiplier,
+            updated_at: None,
+        }
+    }
+}
+
+impl From<infra::models::ClubScoringSettingsRow> for ClubScoringSettings {
+    fn from(row: infra::models::ClubScoringSettingsRow) -> Self {
+        let settings = infra::scoring::ScoringSettings::from(&row);
+        Self {
+            club_id: row.club_id.into(),
+            formula: settings.formula.into(),
+            cap: row.cap,
+            multiplier: row.multiplier,
+            updated_at: Some(row.updated_at),
+        }
+    }
+}
+
+/// A named league season of a club
+#[derive(SimpleObject, Clone)]
+pub struct Season {
+    pub id: ID,
+    pub club_id: ID,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Only the best N results count
+    pub best_results: Option<i32>,
+    /// Events a player must play to be ranked
+    pub min_events: i32,
+    /// Worst results left out
+    pub drop_worst: i32,
+    /// Overrides of the club's scoring settings for the season's tournaments
+    pub scoring_formula: Option<ScoringFormula>,
+    pub scoring_cap: Option<i32>,
+    pub scoring_multiplier: Option<f64>,
+    pub is_closed: bool,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<infra::models::SeasonRow> for Season {
+    fn from(row: infra::models::SeasonRow) -> Self {
+        Self {
+            id: row.id.into(),
+            club_id: row.club_id.into(),
+            name: row.name,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            best_results: row.best_results,
+            min_events: row.min_events,
+            drop_worst: row.drop_worst,
+            scoring_formula: row
+                .scoring_formula
+                .and_then(|formula| formula.parse::<infra::scoring::ScoringFormulaKind>().ok())
+                .map(Into::into),
+            scoring_cap: row.scoring_cap,
+            scoring_multiplier: row.scoring_multiplier,
+            is_closed: row.closed_at.is_some(),
+            closed_at: row.closed_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct SeasonStanding {
+    pub user_id: ID,
+    /// Shared by players on the same points, empty below the minimum number of events
+    pub rank: Option<i32>,
+    pub points: i32,
+    pub events_played: i32,
+    pub results_counted: i32,
+}
+
+impl From<infra::models::SeasonStandingRow> for SeasonStanding {
+    fn from(row: infra::models::SeasonStandingRow) -> Self {
+        Self {
+            user_id: row.user_id.into(),
+            rank: row.rank,
+            points: row.points,
+            events_played: row.events_played,
+            results_counted: row.results_counted,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct SeasonStandings {
+    pub season: Season,
+    /// Standings are final once the season is closed
+    pub is_final: bool,
+    pub entries: Vec<SeasonStanding>,
+}
+
+/// How a player fared against an opponent
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct HeadToHead {
+    pub user_id: ID,
+    pub opponent_id: ID,
+    /// Tournaments both players have a result in
+    pub shared_tournaments: i32,
+    /// Shared tournaments where the player finished ahead of the opponent
+    pub user_finished_higher: i32,
+    /// Shared tournaments where the opponent finished ahead
+    pub opponent_finished_higher: i32,
+    /// Time both players sat at the same table
+    pub hours_together: f64,
+}
+
+impl From<infra::repos::HeadToHeadStats> for HeadToHead {
+    fn from(stats: infra::repos::HeadToHeadStats) -> Self {
+        Self {
+            user_id: stats.user_id.into(),
+            opponent_id: stats.opponent_id.into(),
+            shared_tournaments: stats.shared_tournaments as i32,
+            user_finished_higher: stats.user_finished_higher as i32,
+            opponent_finished_higher: stats.opponent_finished_higher as i32,
+            hours_together: (stats.seconds_together / 36.0).round() / 100.0,
+        }
+    }
+}
+
+/// Totals of a player's results. Amounts are empty when the player hides their winnings.
+#[derive(SimpleObject, Clone)]
+pub struct ProfileStatistics {
+    pub tournaments_played: i32,
+    /// Results paid a prize
+    pub total_itm: i32,
+    pub wins: i32,
+    pub final_tables: i32,
+    pub itm_percentage: f64,
+    pub average_finish: f64,
+    pub points: i32,
+    pub total_buy_ins: Option<i32>,
+    pub total_winnings: Option<i32>,
+    pub total_bounty_winnings: Option<i32>,
+    pub net_profit: Option<i32>,
+    pub roi_percentage: Option<f64>,
+}
+
+impl ProfileStatistics {
+    pub fn new(stats: &infra::profile::ProfileStats, show_winnings: bool) -> Self {
+        let amount = |cents: i64| show_winnings.then_some(cents as i32);
+        Self {
+            tournaments_played: stats.tournaments as i32,
+            total_itm: stats.itm as i32,
+            wins: stats.wins as i32,
+            final_tables: stats.final_tables as i32,
+            itm_percentage: stats.itm_percentage,
+            average_finish: stats.average_finish,
+            points: stats.points as i32,
+            total_buy_ins: amount(stats.total_buy_ins),
+            total_winnings: amount(stats.total_winnings),
+            total_bounty_winnings: amount(stats.total_bounty_winnings),
+            net_profit: amount(stats.net_profit),
+            roi_percentage: show_winnings.then_some(stats.roi_percentage),
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct ClubProfileStatistics {
+    pub club_id: ID,
+    pub club_name: String,
+    pub statistics: ProfileStatistics,
+}
+
+/// Profit after one result, for graphs
+#[derive(SimpleObject, Clone)]
+pub struct ProfitPoint {
+    pub tournament_id: ID,
+    pub date: DateTime<Utc>,
+    pub profit_cents: i32,
+    pub cumulative_profit_cents: i32,
+}
+
+impl From<infra::profile::ProfitPoint> for ProfitPoint {
+    fn from(point: infra::profile::ProfitPoint) -> Self {
+        Self {
+            tournament_id: point.tournament_id.into(),
+            date: point.date,
+            profit_cents: point.profit_cents as i32,
+            cumulative_profit_cents: point.cumulative_profit_cents as i32,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct FinishPositionCount {
+    pub position: i32,
+    pub count: i32,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct YearlyPoints {
+    pub year: i32,
+    pub points: i32,
+    pub tournaments_played: i32,
+}
+
+/// One result on a player's profile. Amounts are empty when the player hides their
+/// winnings.
+#[derive(SimpleObject, Clone)]
+pub struct ProfileResult {
+    pub tournament_id: ID,
+    pub tournament_name: String,
+    pub club_id: ID,
+    pub club_name: String,
+    pub start_time: DateTime<Utc>,
+    pub final_position: i32,
+    pub field_size: i32,
+    pub points: i32,
+    pub buy_in_cents: Option<i32>,
+    pub prize_cents: Option<i32>,
+    pub bounty_cents: Option<i32>,
+}
+
+impl ProfileResult {
+    pub fn new(result: infra::profile::ProfileResult, show_winnings: bool) -> Self {
+        let amount = |cents: i64| show_winnings.then_some(cents as i32);
+        Self {
+            tournament_id: result.tournament_id.into(),
+            tournament_name: result.tournament_name,
+            club_id: result.club_id.into(),
+            club_name: result.club_name,
+            start_time: result.start_time,
+            final_position: result.final_position,
+            field_size: result.field_size,
+            points: result.points,
+            buy_in_cents: amount(result.buy_in_cents),
+            prize_cents: amount(result.prize_cents),
+            bounty_cents: amount(result.bounty_cents),
+        }
+    }
+}
+
+/// Public profile of a player
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct PlayerProfile {
+    pub user_id: ID,
+    /// The player hides their winnings: amounts are empty, and so are the profit graph
+    /// and the biggest cashes
+    pub winnings_hidden: bool,
+    pub lifetime: ProfileStatistics,
+    /// Clubs played most first
+    pub clubs: Vec<ClubProfileStatistics>,
+    /// Cumulative profit after each result, oldest first
+    pub profit_over_time: Vec<ProfitPoint>,
+    pub biggest_cashes: Vec<ProfileResult>,
+    /// How many times the player finished in each position, best first
+    pub finish_positions: Vec<FinishPositionCount>,
+    pub points_by_year: Vec<YearlyPoints>,
+    /// Most recent first
+    pub recent_results: Vec<ProfileResult>,
+}
+
+/// What a player shows on their public profile
+#[derive(SimpleObject, Clone)]
+pub struct PrivacySettings {
+    pub user_id: ID,
+    pub hide_winnings: bool,
+}
+
+impl PrivacySettings {
+    /// Settings of a player who never changed them
+    pub fn defaults(user_id: Uuid) -> Self {
+        Self {
+            user_id: user_id.into(),
+            hide_winnings: false,
+        }
+    }
+}
+
+impl From<infra::models::UserPrivacySettingsRow> for PrivacySettings {
+    fn from(row: infra::models::UserPrivacySettingsRow) -> Self {
+        Self {
+            user_id: row.user_id.into(),
+            hide_winnings: row.hide_winnings,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdatePrivacySettingsInput {
+    pub hide_winnings: bool,
+}
+
 #[derive(SimpleObject, Clone)]
 pub struct UserTournamentResult {
     pub result: TournamentResult,
@@ -263,6 +870,8 @@ pub enum DealType {
     Icm,
     #[graphql(name = "CUSTOM")]
     Custom,
+    #[graphql(name = "CHIP_CHOP")]
+    ChipChop,
 }
 
 #[derive(SimpleObject, Clone)]
@@ -324,6 +933,7 @@ pub struct PlayerStatistics {
     pub total_tournaments: i32,
     pub total_winnings: i32,
     pub total_buy_ins: i32,
+    pub total_bounty_winnings: i32,
     pub itm_percentage: f64,
     pub roi_percentage: f64,
 }
@@ -357,7 +967,7 @@ pub struct ClubTable {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(SimpleObject, Clone)]
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SeatAssignment {
     pub id: ID,
     pub tournament_id: ID,
@@ -451,7 +1061,26 @@ impl From<RegistrationStatus> for String {
     }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+impl RegistrationStatus {
+    /// Statuses a manager can move a registration to from this one
+    pub fn next_statuses(&self) -> &'static [RegistrationStatus] {
+        use RegistrationStatus::*;
+
+        match self {
+            Registered => &[CheckedIn, NoShow, Cancelled],
+            CheckedIn => &[Seated, Busted, Cancelled],
+            Seated => &[Busted],
+            Waitlisted => &[Cancelled],
+            Busted | Cancelled | NoShow => &[],
+        }
+    }
+
+    pub fn can_change_to(&self, next: RegistrationStatus) -> bool {
+        self.next_statuses().contains(&next)
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ClockStatus {
     #[graphql(name = "STOPPED")]
     Stopped,
@@ -481,7 +1110,7 @@ impl From<ClockStatus> for infra::repos::tournament_clock::ClockStatus {
     }
 }
 
-#[derive(SimpleObject, Clone)]
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TournamentStructure {
     pub id: ID,
     pub tournament_id: ID,
@@ -494,7 +1123,118 @@ pub struct TournamentStructure {
     pub break_duration_minutes: Option<i32>,
 }
 
+/// A blind level that doesn't belong to a tournament yet (series or template structures)
+#[derive(SimpleObject, Clone)]
+pub struct BlindLevel {
+    pub level_number: i32,
+    pub small_blind: i32,
+    pub big_blind: i32,
+    pub ante: i32,
+    pub duration_minutes: i32,
+    pub is_break: bool,
+    pub break_duration_minutes: Option<i32>,
+}
+
+impl From<infra::repos::TournamentStructureLevel> for BlindLevel {
+    fn from(level: infra::repos::TournamentStructureLevel) -> Self {
+        Self {
+            level_number: level.level_number,
+            small_blind: level.small_blind,
+            big_blind: level.big_blind,
+            ante: level.ante,
+            duration_minutes: level.duration_minutes,
+            is_break: level.is_break,
+            break_duration_minutes: level.break_duration_minutes,
+        }
+    }
+}
+
+/// A blind level as entered by a manager; levels are numbered in the order given
+#[derive(InputObject, Clone)]
+pub struct BlindLevelInput {
+    pub small_blind: i32,
+    pub big_blind: i32,
+    pub ante: Option<i32>,
+    pub duration_minutes: i32,
+    pub is_break: Option<bool>,
+    pub break_duration_minutes: Option<i32>,
+}
+
+#[derive(InputObject)]
+pub struct GenerateBlindStructureInput {
+    pub starting_stack: i32,
+    pub expected_players: i32,
+    /// Total length including breaks
+    pub target_duration_minutes: i32,
+    pub level_duration_minutes: i32,
+    /// First playing level with a big blind ante; no antes when omitted
+    pub ante_from_level: Option<i32>,
+    /// Insert a break after this many playing levels; no breaks when omitted
+    pub break_every_levels: Option<i32>,
+    pub break_duration_minutes: Option<i32>,
+    /// Chip values in the set, defaults to 25/100/500/1000/5000/25000/100000
+    pub denominations: Option<Vec<i32>>,
+}
+
 #[derive(SimpleObject, Clone)]
+pub struct ColorUp {
+    /// Level number of the break during which the chips are raced off
+    pub level_number: i32,
+    pub denominations: Vec<i32>,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct GeneratedBlindStructure {
+    pub levels: Vec<BlindLevel>,
+    pub color_ups: Vec<ColorUp>,
+    pub total_duration_minutes: i32,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct BlindStructureTemplate {
+    pub id: ID,
+    pub club_id: ID,
+    pub name: String,
+    pub description: Option<String>,
+    pub levels: Vec<BlindLevel>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<infra::models::BlindStructureTemplateRow> for BlindStructureTemplate {
+    fn from(row: infra::models::BlindStructureTemplateRow) -> Self {
+        let levels: Vec<infra::repos::TournamentStructureLevel> =
+            serde_json::from_value(row.levels).unwrap_or_default();
+
+        Self {
+            id: row.id.into(),
+            club_id: row.club_id.into(),
+            name: row.name,
+            description: row.description,
+            levels: levels.into_iter().map(Into::into).collect(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateBlindStructureTemplateInput {
+    pub club_id: ID,
+    pub name: String,
+    pub description: Option<String>,
+    pub levels: Vec<BlindLevelInput>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateBlindStructureTemplateInput {
+    pub id: ID,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub levels: Option<Vec<BlindLevelInput>>,
+}
+
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TournamentClock {
     pub id: ID,
     pub tournament_id: ID,
@@ -522,19 +1262,181 @@ pub struct PayoutPosition {
     pub amount_cents: i32,
 }
 
+#[derive(SimpleObject, Clone)]
+pub struct DealPreviewPlayer {
+    pub user_id: ID,
+    pub stack_size: i32,
+    pub icm_amount_cents: i32,
+    pub chip_chop_amount_cents: i32,
+    pub even_split_amount_cents: i32,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct DealPreview {
+    pub tournament_id: ID,
+    pub total_amount_cents: i32,
+    /// Prize for each remaining place, first place first
+    pub ladder_cents: Vec<i32>,
+    /// Players still seated, biggest stack first
+    pub players: Vec<DealPreviewPlayer>,
+}
+
 #[derive(SimpleObject, Clone)]
 pub struct TournamentPayout {
     pub id: ID,
     pub tournament_id: ID,
     pub template_id: Option<ID>,
     pub player_count: i32,
+    /// Prize pool paid out: the collected pool or the guarantee, whichever is higher
     pub total_prize_pool: i32,
+    /// Prize pool collected from the entries
+    pub collected_prize_pool: i32,
+    /// Part of the prize pool the club pays out of pocket to cover the guarantee
+    pub overlay_cents: i32,
     pub positions: Vec<PayoutPosition>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Weekday {
+    #[graphql(name = "MONDAY")]
+    Monday,
+    #[graphql(name = "TUESDAY")]
+    Tuesday,
+    #[graphql(name = "WEDNESDAY")]
+    Wednesday,
+    #[graphql(name = "THURSDAY")]
+    Thursday,
+    #[graphql(name = "FRIDAY")]
+    Friday,
+    #[graphql(name = "SATURDAY")]
+    Saturday,
+    #[graphql(name = "SUNDAY")]
+    Sunday,
+}
+
+impl Weekday {
+    /// ISO 8601 day number, 1 = Monday
+    pub fn iso_number(self) -> i32 {
+        match self {
+            Weekday::Monday => 1,
+            Weekday::Tuesday => 2,
+            Weekday::Wednesday => 3,
+            Weekday::Thursday => 4,
+            Weekday::Friday => 5,
+            Weekday::Saturday => 6,
+            Weekday::Sunday => 7,
+        }
+    }
+
+    pub fn from_iso_number(day: i32) -> Option<Self> {
+        match day {
+            1 => Some(Weekday::Monday),
+            2 => Some(Weekday::Tuesday),
+            3 => Some(Weekday::Wednesday),
+            4 => Some(Weekday::Thursday),
+            5 => Some(Weekday::Friday),
+            6 => Some(Weekday::Saturday),
+            7 => Some(Weekday::Sunday),
+            _ => None,
+        }
+    }
+}
+
 #[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct TournamentSeries {
+    pub id: ID,
+    pub club_id: ID,
+    pub name: String,
+    pub description: Option<String>,
+    pub buy_in_cents: i32,
+    pub seat_cap: Option<i32>,
+    pub duration_minutes: Option<i32>,
+    pub blind_structure: Vec<BlindLevel>,
+    pub payout_template_id: Option<ID>,
+    pub weekdays: Vec<Weekday>,
+    /// Start time in the series' time zone
+    pub local_start_time: NaiveTime,
+    pub timezone: String,
+    /// How many weeks ahead instances are created
+    pub weeks_ahead: i32,
+    pub is_paused: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<infra::models::TournamentSeriesRow> for TournamentSeries {
+    fn from(row: infra::models::TournamentSeriesRow) -> Self {
+        let blind_structure: Vec<infra::repos::TournamentStructureLevel> =
+            serde_json::from_value(row.blind_structure).unwrap_or_default();
+
+        Self {
+            id: row.id.into(),
+            club_id: row.club_id.into(),
+            name: row.name,
+            description: row.description,
+            buy_in_cents: row.buy_in_cents,
+            seat_cap: row.seat_cap,
+            duration_minutes: row.duration_minutes,
+            blind_structure: blind_structure.into_iter().map(Into::into).collect(),
+            payout_template_id: row.payout_template_id.map(Into::into),
+            weekdays: row
+                .weekdays
+                .into_iter()
+                .filter_map(Weekday::from_iso_number)
+                .collect(),
+            local_start_time: row.local_start_time,
+            timezone: row.timezone,
+            weeks_ahead: row.weeks_ahead,
+            is_paused: row.is_paused,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateTournamentSeriesInput {
+    pub club_id: ID,
+    pub name: String,
+    pub description: Option<String>,
+    pub buy_in_cents: i32,
+    pub seat_cap: Option<i32>,
+    pub duration_minutes: Option<i32>,
+    pub blind_structure: Vec<BlindLevelInput>,
+    pub payout_template_id: Option<ID>,
+    pub weekdays: Vec<Weekday>,
+    pub local_start_time: NaiveTime,
+    /// IANA time zone name, defaults to Europe/Brussels
+    pub timezone: Option<String>,
+    pub weeks_ahead: Option<i32>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateTournamentSeriesInput {
+    pub id: ID,
+    pub name: Option<String>,
+    /// `null` removes the description
+    pub description: MaybeUndefined<String>,
+    pub buy_in_cents: Option<i32>,
+    /// `null` removes the seat cap
+    pub seat_cap: MaybeUndefined<i32>,
+    /// `null` leaves instances without an end time
+    pub duration_minutes: MaybeUndefined<i32>,
+    pub blind_structure: Option<Vec<BlindLevelInput>>,
+    /// `null` removes the payout template
+    pub payout_template_id: MaybeUndefined<ID>,
+    pub weekdays: Option<Vec<Weekday>>,
+    pub local_start_time: Option<NaiveTime>,
+    pub timezone: Option<String>,
+    pub weeks_ahead: Option<i32>,
+    /// Also update upcoming instances nobody has started playing yet
+    pub apply_to_future_instances: Option<bool>,
+}
+
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PlayerRegistrationEvent {
     pub tournament_id: ID,
     pub player: TournamentPlayer,
@@ -691,6 +1593,233 @@ pub struct UpdateRegistrationStatusInput {
     pub notes: Option<String>,
 }
 
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TournamentEntryType {
+    /// The player's first entry, recorded when they take a seat
+    #[graphql(name = "BUY_IN")]
+    BuyIn,
+
+    /// A new entry after busting
+    #[graphql(name = "REENTRY")]
+    Reentry,
+
+    /// More chips bought while still in the tournament
+    #[graphql(name = "REBUY")]
+    Rebuy,
+
+    /// One-time chip purchase, usually at the end of the rebuy period
+    #[graphql(name = "ADD_ON")]
+    AddOn,
+}
+
+impl From<infra::repos::TournamentEntryType> for TournamentEntryType {
+    fn from(entry_type: infra::repos::TournamentEntryType) -> Self {
+        match entry_type {
+            infra::repos::TournamentEntryType::BuyIn => TournamentEntryType::BuyIn,
+            infra::repos::TournamentEntryType::Reentry => TournamentEntryType::Reentry,
+            infra::repos::TournamentEntryType::Rebuy => TournamentEntryType::Rebuy,
+            infra::repos::TournamentEntryType::AddOn => TournamentEntryType::AddOn,
+        }
+    }
+}
+
+impl From<TournamentEntryType> for infra::repos::TournamentEntryType {
+    fn from(entry_type: TournamentEntryType) -> Self {
+        match entry_type {
+            TournamentEntryType::BuyIn => infra::repos::TournamentEntryType::BuyIn,
+            TournamentEntryType::Reentry => infra::repos::TournamentEntryType::Reentry,
+            TournamentEntryType::Rebuy => infra::repos::TournamentEntryType::Rebuy,
+            TournamentEntryType::AddOn => infra::repos::TournamentEntryType::AddOn,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct TournamentEntry {
+    pub id: ID,
+    pub tournament_id: ID,
+    pub registration_id: ID,
+    pub user_id: ID,
+    pub entry_type: TournamentEntryType,
+    /// Total paid for the entry
+    pub amount_cents: i32,
+    pub rake_cents: i32,
+    pub staff_fee_cents: i32,
+    /// Part of the amount put on the player's head
+    pub bounty_cents: i32,
+    /// Part of the amount that went to the prize pool
+    pub prize_cents: i32,
+    pub created_by: Option<ID>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<infra::models::TournamentEntryRow> for TournamentEntry {
+    fn from(row: infra::models::TournamentEntryRow) -> Self {
+        use std::str::FromStr;
+
+        Self {
+            id: row.id.into(),
+            tournament_id: row.tournament_id.into(),
+            registration_id: row.registration_id.into(),
+            user_id: row.user_id.into(),
+            entry_type: infra::repos::TournamentEntryType::from_str(&row.entry_type)
+                .unwrap_or(infra::repos::TournamentEntryType::BuyIn)
+                .into(),
+            amount_cents: row.amount_cents,
+            rake_cents: row.rake_cents,
+            staff_fee_cents: row.staff_fee_cents,
+            bounty_cents: row.bounty_cents,
+            prize_cents: row.prize_cents,
+            created_by: row.created_by.map(|id| id.into()),
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Where the money paid into a tournament went
+#[derive(SimpleObject, Clone)]
+pub struct TournamentAccounting {
+    /// Everything paid in: buy-ins, re-entries, rebuys and add-ons
+    pub paid_cents: i32,
+    pub prize_pool_cents: i32,
+    pub rake_cents: i32,
+    pub staff_fee_cents: i32,
+    /// Put on the players' heads, paid out on knockouts
+    pub bounty_cents: i32,
+}
+
+impl From<infra::repos::TournamentEntryTotals> for TournamentAccounting {
+    fn from(totals: infra::repos::TournamentEntryTotals) -> Self {
+        Self {
+            paid_cents: totals.paid_cents as i32,
+            prize_pool_cents: totals.prize_cents as i32,
+            rake_cents: totals.rake_cents as i32,
+            staff_fee_cents: totals.staff_fee_cents as i32,
+            bounty_cents: totals.bounty_cents as i32,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct TournamentElimination {
+    pub id: ID,
+    pub tournament_id: ID,
+    pub registration_id: ID,
+    /// Eliminated player
+    pub user_id: ID,
+    /// Player who won the hand, when known
+    pub eliminated_by: Option<ID>,
+    /// Bounty paid to the eliminator
+    pub bounty_cents: i32,
+    /// Bounty added to the eliminator's own bounty (progressive knockouts)
+    pub bounty_added_cents: i32,
+    /// Clock level at the bust
+    pub level: Option<i32>,
+    /// Derived from the players remaining; players busting together can tie
+    pub finishing_position: Option<i32>,
+    pub created_by: Option<ID>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<infra::models::TournamentEliminationRow> for TournamentElimination {
+    fn from(row: infra::models::TournamentEliminationRow) -> Self {
+        Self {
+            id: row.id.into(),
+            tournament_id: row.tournament_id.into(),
+            registration_id: row.registration_id.into(),
+            user_id: row.user_id.into(),
+            eliminated_by: row.eliminated_by.map(|id| id.into()),
+            bounty_cents: row.bounty_cents,
+            bounty_added_cents: row.bounty_added_cents,
+            level: row.level,
+            finishing_position: row.finishing_position,
+            created_by: row.created_by.map(|id| id.into()),
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct EliminatePlayersInput {
+    pub tournament_id: ID,
+    /// Players busting in the same hand
+    pub eliminations: Vec<PlayerEliminationInput>,
+    pub notes: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct PlayerEliminationInput {
+    pub user_id: ID,
+    /// Player who won the hand, collects the bounty
+    pub eliminated_by: Option<ID>,
+}
+
+/// Correction of a tournament result, fields left out keep their value
+#[derive(InputObject)]
+pub struct CorrectTournamentResultInput {
+    pub result_id: ID,
+    pub final_position: Option<i32>,
+    pub prize_cents: Option<i32>,
+    pub bounty_cents: Option<i32>,
+    /// `null` removes the notes
+    pub notes: MaybeUndefined<String>,
+    /// Why the result was corrected, kept in the audit trail
+    pub reason: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateClubScoringSettingsInput {
+    pub club_id: ID,
+    pub formula: ScoringFormula,
+    /// Defaults to 60 points
+    pub cap: Option<i32>,
+    /// Defaults to 1
+    pub multiplier: Option<f64>,
+}
+
+#[derive(InputObject)]
+pub struct CreateSeasonInput {
+    pub club_id: ID,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Only the best N results count
+    pub best_results: Option<i32>,
+    /// Events a player must play to be ranked, defaults to 0
+    pub min_events: Option<i32>,
+    /// Worst results left out, defaults to 0
+    pub drop_worst: Option<i32>,
+    /// Overrides of the club's scoring settings, fields left empty follow the club
+    pub scoring_formula: Option<ScoringFormula>,
+    pub scoring_cap: Option<i32>,
+    pub scoring_multiplier: Option<f64>,
+}
+
+/// Changes to an open season, fields left out keep their value
+#[derive(InputObject)]
+pub struct UpdateSeasonInput {
+    pub season_id: ID,
+    pub name: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// `null` counts every result again
+    pub best_results: MaybeUndefined<i32>,
+    pub min_events: Option<i32>,
+    pub drop_worst: Option<i32>,
+    /// `null` follows the club's scoring settings again
+    pub scoring_formula: MaybeUndefined<ScoringFormula>,
+    pub scoring_cap: MaybeUndefined<i32>,
+    pub scoring_multiplier: MaybeUndefined<f64>,
+}
+
+#[derive(InputObject)]
+pub struct RecordTournamentEntryInput {
+    pub tournament_id: ID,
+    pub user_id: ID,
+    /// REENTRY, REBUY or ADD_ON; buy-ins are recorded when the player takes a seat
+    pub entry_type: TournamentEntryType,
+}
+
 #[derive(SimpleObject)]
 pub struct CheckInResponse {
     pub registration: TournamentRegistration,
@@ -698,7 +1827,7 @@ pub struct CheckInResponse {
     pub message: String,
 }
 
-#[derive(SimpleObject, Clone)]
+#[derive(SimpleObject, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SeatingChangeEvent {
     pub event_type: SeatingEventType,
     pub tournament_id: ID,
@@ -709,7 +1838,7 @@ pub struct SeatingChangeEvent {
     pub timestamp: DateTime<Utc>,
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum SeatingEventType {
     #[graphql(name = "PLAYER_ASSIGNED")]
     PlayerAssigned,
@@ -727,6 +1856,8 @@ pub enum SeatingEventType {
     TournamentStatusChanged,
     #[graphql(name = "TABLES_BALANCED")]
     TablesBalanced,
+    #[graphql(name = "TOURNAMENT_COMPLETED")]
+    TournamentCompleted,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
@@ -755,26 +1886,79 @@ impl From<LeaderboardPeriod> for infra::repos::LeaderboardPeriod {
     }
 }
 
+/// A player's place on the leaderboard. Amounts are empty when the player hides their
+/// winnings from other players.
 #[derive(SimpleObject, Clone)]
 pub struct LeaderboardEntry {
-    pub user: User, // Full user object with complete info
-    pub rank: i32,  // Position in leaderboard (1-based)
+    pub user: User,      // Full user object with complete info
+    pub rank: i32,       // 1-based, shared by players on the same points (1, 2, 2, 4)
+    pub dense_rank: i32, // 1-based, shared without gaps (1, 2, 2, 3)
     pub total_tournaments: i32,
-    pub total_buy_ins: i32,  // Total amount spent (cents)
-    pub total_winnings: i32, // Total amount won (cents)
-    pub net_profit: i32,     // winnings - buy_ins (cents)
-    pub total_itm: i32,      // Number of tournaments where player finished in the money
-    pub itm_percentage: f64, // (total_itm / total_tournaments) * 100
-    pub roi_percentage: f64, // ((total_winnings - total_buy_ins) / total_buy_ins) * 100
-    pub average_finish: f64, // Average finishing position
-    pub first_places: i32,   // Number of first place finishes
-    pub final_tables: i32,   // Number of final table finishes (top 9)
-    pub points: f64,         // Calculated leaderboard points
+    pub total_buy_ins: Option<i32>,  // Total amount spent (cents)
+    pub total_winnings: Option<i32>, // Total amount won (cents)
+    pub total_bounty_winnings: Option<i32>, // Total bounties won (cents)
+    pub net_profit: Option<i32>,     // winnings + bounties - buy_ins (cents)
+    pub total_itm: i32,              // Number of tournaments where player finished in the money
+    pub itm_percentage: f64,         // (total_itm / total_tournaments) * 100
+    pub roi_percentage: Option<f64>, // ((total_winnings + total_bounty_winnings - total_buy_ins) / total_buy_ins) * 100
+    pub average_finish: f64,         // Average finishing position
+    pub first_places: i32,           // Number of first place finishes
+    pub final_tables: i32,           // Number of final table finishes (top 9)
+    pub points: f64,                 // Calculated leaderboard points
+}
+
+impl LeaderboardEntry {
+    /// Players who hide their winnings still see them on their own entry
+    pub fn new(entry: infra::repos::LeaderboardEntry, viewer_id: Option<uuid::Uuid>) -> Self {
+        let show_winnings = !entry.winnings_hidden || viewer_id == Some(entry.user_id);
+        let amount = |cents: i32| show_winnings.then_some(cents);
+        Self {
+            user: User {
+                id: entry.user_id.into(),
+                email: entry.email,
+                username: entry.username,
+                first_name: entry.first_name,
+                last_name: entry.last_name,
+                phone: entry.phone,
+                is_active: entry.is_active,
+                role: Role::from(entry.role),
+            },
+            rank: entry.rank,
+            dense_rank: entry.dense_rank,
+            total_tournaments: entry.total_tournaments,
+            total_buy_ins: amount(entry.total_buy_ins),
+            total_winnings: amount(entry.total_winnings),
+            total_bounty_winnings: amount(entry.total_bounty_winnings),
+            net_profit: amount(entry.net_profit),
+            total_itm: entry.total_itm,
+            itm_percentage: entry.itm_percentage,
+            roi_percentage: show_winnings.then_some(entry.roi_percentage),
+            average_finish: entry.average_finish,
+            first_places: entry.first_places,
+            final_tables: entry.final_tables,
+            points: entry.points,
+        }
+    }
 }
 
 #[derive(SimpleObject)]
 pub struct LeaderboardResponse {
     pub entries: Vec<LeaderboardEntry>,
+    /// Players on the whole leaderboard, not just this page
+    pub total_players: i32,
+    pub has_next_page: bool,
+    pub period: LeaderboardPeriod,
+}
+
+/// Where the current user stands on the leaderboard
+#[derive(SimpleObject)]
+pub struct LeaderboardPosition {
+    /// Empty when the user isn't on the leaderboard yet
+    pub entry: Option<LeaderboardEntry>,
+    /// Entries just above the user, best first
+    pub above: Vec<LeaderboardEntry>,
+    /// Entries just below the user, best first
+    pub below: Vec<LeaderboardEntry>,
     pub total_players: i32,
     pub period: LeaderboardPeriod,
 }
@@ -800,6 +1984,49 @@ impl Tournament {
         }
     }
 
+    /// Number of entries, re-entries included
+    async fn entry_count(&self, ctx: &Context<'_>) -> Result<i32> {
+        use crate::state::AppState;
+        use infra::repos::TournamentEntryRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(self.id.as_str())
+            .map_err(|e| Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        Ok(TournamentEntryRepo::new(state.db.clone())
+            .entry_count(tournament_id)
+            .await?)
+    }
+
+    /// Prize pool so far: everything paid in, without rake and staff fees
+    async fn prize_pool_cents(&self, ctx: &Context<'_>) -> Result<i32> {
+        use crate::state::AppState;
+        use infra::repos::TournamentEntryRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(self.id.as_str())
+            .map_err(|e| Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        Ok(TournamentEntryRepo::new(state.db.clone())
+            .prize_pool(tournament_id)
+            .await?)
+    }
+
+    /// Split of everything paid in between prize pool, rake and staff fees
+    async fn accounting(&self, ctx: &Context<'_>) -> Result<TournamentAccounting> {
+        use crate::state::AppState;
+        use infra::repos::TournamentEntryRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = Uuid::parse_str(self.id.as_str())
+            .map_err(|e| Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        Ok(TournamentEntryRepo::new(state.db.clone())
+            .totals(tournament_id)
+            .await?
+            .into())
+    }
+
     async fn structure(
         &self,
         ctx: &Context<'_>,
@@ -866,6 +2093,28 @@ impl Tournament {
         }))
     }
 
+    async fn tags(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Tag>> {
+        use crate::state::AppState;
+        use infra::repos::TagRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let tag_repo = TagRepo::new(state.db.clone());
+
+        let tournament_id = uuid::Uuid::parse_str(self.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let tags = tag_repo.get_for_tournament(tournament_id).await?;
+
+        Ok(tags
+            .into_iter()
+            .map(|tag| Tag {
+                id: tag.id.into(),
+                slug: tag.slug,
+                label: tag.label,
+            })
+            .collect())
+    }
+
     async fn registrations(
         &self,
         ctx: &Context<'_>,
@@ -895,6 +2144,66 @@ impl Tournament {
     }
 }
 
+#[ComplexObject]
+impl SeasonStanding {
+    async fn user(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
+        use crate::state::AppState;
+        use infra::repos::UserRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let user_id = uuid::Uuid::parse_str(self.user_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let user_row = UserRepo::new(state.db.clone()).get_by_id(user_id).await?;
+
+        Ok(user_row.map(User::from))
+    }
+}
+
+#[ComplexObject]
+impl HeadToHead {
+    async fn opponent(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<PublicUser>> {
+        use crate::state::AppState;
+        use infra::repos::UserRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let opponent_id = uuid::Uuid::parse_str(self.opponent_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let user_row = UserRepo::new(state.db.clone())
+            .get_by_id(opponent_id)
+            .await?;
+
+        Ok(user_row.map(PublicUser::from))
+    }
+}
+
+#[ComplexObject]
+impl PlayerProfile {
+    async fn user(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<PublicUser>> {
+        use crate::state::AppState;
+        use infra::repos::UserRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let user_id = uuid::Uuid::parse_str(self.user_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let user_row = UserRepo::new(state.db.clone()).get_by_id(user_id).await?;
+
+        Ok(user_row.map(PublicUser::from))
+    }
+
+    /// The opponents the player met most
+    async fn top_rivals(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<Vec<HeadToHead>> {
+        let query = crate::gql::rivalries::RivalryQuery;
+        query.top_rivals(ctx, self.user_id.clone(), limit).await
+    }
+}
+
 #[ComplexObject]
 impl TournamentRegistration {
     async fn user(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
@@ -920,6 +2229,45 @@ impl TournamentRegistration {
             role: Role::from(user.role),
         }))
     }
+
+    /// Place in the waitlist, only set while the registration is waitlisted
+    async fn waitlist_position(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<i32>> {
+        use crate::state::AppState;
+        use infra::repos::TournamentRegistrationRepo;
+
+        if self.status != RegistrationStatus::Waitlisted {
+            return Ok(None);
+        }
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_id = uuid::Uuid::parse_str(self.tournament_id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid tournament ID: {}", e)))?;
+
+        let waitlist = TournamentRegistrationRepo::new(state.db.clone())
+            .get_waitlist(tournament_id)
+            .await?;
+
+        Ok(waitlist
+            .iter()
+            .position(|registration| registration.id.to_string() == self.id.as_str())
+            .map(|index| index as i32 + 1))
+    }
+
+    /// Bounty currently on the player's head, 0 outside of bounty tournaments
+    async fn current_bounty_cents(&self, ctx: &Context<'_>) -> async_graphql::Result<i32> {
+        use crate::state::AppState;
+        use infra::repos::TournamentEliminationRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let registration_id = uuid::Uuid::parse_str(self.id.as_str())
+            .map_err(|e| async_graphql::Error::new(format!("Invalid registration ID: {}", e)))?;
+
+        let bounty_cents = TournamentEliminationRepo::new(state.db.clone())
+            .current_bounty(registration_id)
+            .await?;
+
+        Ok(bounty_cents.unwrap_or(0))
+    }
 }
 
 #[ComplexObject]
@@ -950,3 +2298,22 @@ impl User {
         }
     }
 }
+
+#[ComplexObject]
+impl TournamentSeries {
+    /// Instances of the series that haven't started yet, soonest first
+    async fn upcoming_tournaments(&self, ctx: &Context<'_>) -> Result<Vec<Tournament>> {
+        use crate::state::AppState;
+        use infra::repos::TournamentRepo;
+
+        let state = ctx.data::<AppState>()?;
+        let tournament_repo = TournamentRepo::new(state.db.clone());
+
+        let series_id = Uuid::parse_str(self.id.as_str())
+            .map_err(|e| Error::new(format!("Invalid series ID: {}", e)))?;
+
+        let rows = tournament_repo.list_upcoming_for_series(series_id).await?;
+
+        Ok(rows.into_iter().map(Tournament::from).collect())
+    }
+}
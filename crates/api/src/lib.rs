@@ -1,6 +1,7 @@
 pub mod app;
 pub mod auth;
 pub mod error;
+pub mod events;
 pub mod gql;
 pub mod middleware;
 pub mod routes;
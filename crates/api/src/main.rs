@@ -1,10 +1,12 @@
 use sqlx::PgPool;
+use std::sync::Arc;
 use tokio::net::TcpListener;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use api::app::build_router;
+use api::events::{self, PgEventBus};
 use api::gql::build_schema;
-use api::services::spawn_clock_service;
+use api::services::{spawn_clock_service, spawn_series_scheduler};
 use api::state::AppState;
 
 #[tokio::main]
@@ -34,6 +36,17 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("Database migrations completed successfully");
     }
 
+    // Share subscription events between instances with EVENT_BUS=postgres
+    let event_bus = std::env::var("EVENT_BUS").unwrap_or_else(|_| "memory".into());
+    match event_bus.to_lowercase().as_str() {
+        "postgres" => {
+            events::install(Arc::new(PgEventBus::start(pool.clone()).await?));
+            tracing::info!("Publishing subscription events through Postgres");
+        }
+        "memory" => {}
+        other => anyhow::bail!("Unknown EVENT_BUS '{}', expected memory or postgres", other),
+    }
+
     let state = AppState::new(pool)?;
 
     // Build GraphQL schema from the gql module
@@ -43,6 +56,10 @@ async fn main() -> anyhow::Result<()> {
     let _clock_handle = spawn_clock_service(state.clone());
     tracing::info!("Tournament clock service started");
 
+    // Start the background scheduler that creates tournaments from recurring series
+    let _series_handle = spawn_series_scheduler(state.clone());
+    tracing::info!("Tournament series scheduler started");
+
     let app = build_router(state, schema);
 
     let port: u16 = std::env::var("PORT")
@@ -1,47 +1,95 @@
-use std::time::Duration;
-use tokio::time::{interval, Interval};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 
+use crate::events::{self, BusEvent};
+use crate::gql::tournament_clock::publish_clock_change;
+use crate::services::{LeaderLock, SystemTime, TimeSource};
 use crate::AppState;
 use infra::repos::TournamentClockRepo;
 
+/// Advisory lock key of the clock service, only its holder auto-advances levels
+pub const CLOCK_SERVICE_LOCK_KEY: i64 = 0x706f_636b_6574_0001;
+
+/// Longest wait between two checks, to renew the leader lock and catch missed changes
+const IDLE_SECONDS: i64 = 5;
+
 pub struct ClockService {
     state: AppState,
-    interval: Interval,
+    time: Arc<dyn TimeSource>,
+    leader: LeaderLock,
 }
 
 impl ClockService {
     pub fn new(state: AppState) -> Self {
-        // Tick every 5 seconds to check for level advances
-        let interval = interval(Duration::from_secs(5));
+        Self::with_time_source(state, Arc::new(SystemTime))
+    }
 
-        Self { state, interval }
+    pub fn with_time_source(state: AppState, time: Arc<dyn TimeSource>) -> Self {
+        let leader = LeaderLock::new(&state.db, CLOCK_SERVICE_LOCK_KEY);
+
+        Self {
+            state,
+            time,
+            leader,
+        }
     }
 
     /// Start the background clock service
+    ///
+    /// The service sleeps until the earliest level end of the running clocks and plans
+    /// again whenever a clock changes.
     pub async fn run(&mut self) {
         info!("Starting tournament clock service");
 
+        // Subscribe before planning so no clock change slips in between
+        let mut changes = events::subscribe();
+
         loop {
-            self.interval.tick().await;
+            // Only one instance advances levels, the others stand by to take over
+            let next_level_end = if self.leader.acquire().await {
+                match self.process_tournaments().await {
+                    Ok(next_level_end) => next_level_end,
+                    Err(e) => {
+                        error!("Error processing tournament clocks: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            let idle_until = self.time.now() + Duration::seconds(IDLE_SECONDS);
+            let wake_at = next_level_end.map_or(idle_until, |end| end.min(idle_until));
 
-            if let Err(e) = self.process_tournaments().await {
-                error!("Error processing tournament clocks: {}", e);
+            tokio::select! {
+                _ = self.time.sleep_until(wake_at) => {}
+                _ = next_clock_change(&mut changes) => {}
             }
         }
     }
 
-    /// Process all tournaments and advance levels if needed
-    async fn process_tournaments(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// Advance the levels that ended and tell when the next one ends
+    async fn process_tournaments(
+        &self,
+    ) -> Result<Option<DateTime<Utc>>, Box<dyn std::error::Error + Send + Sync>> {
         let repo = TournamentClockRepo::new(self.state.db.clone());
 
         // Get tournaments that need level advancement
-        let tournament_ids = repo.get_tournaments_to_advance().await?;
+        let now = self.time.now();
+        let tournament_ids = repo.get_tournaments_to_advance(now).await?;
 
-        for tournament_id in tournament_ids {
-            match repo.advance_level(tournament_id, true, None).await {
-                Ok(_) => {
+        for (tournament_id, level) in tournament_ids {
+            // Advancing from the level seen avoids skipping one if it moved meanwhile
+            match repo
+                .advance_level(tournament_id, Some(level), true, None, now)
+                .await
+            {
+                Ok(None) => {}
+                Ok(Some(_)) => {
                     info!("Auto-advanced level for tournament {}", tournament_id);
+                    publish_clock_change(&self.state.db, tournament_id).await;
                 }
                 Err(e) => {
                     warn!(
@@ -52,7 +100,20 @@ impl ClockService {
             }
         }
 
-        Ok(())
+        // Levels that could not advance are retried on the next idle check
+        Ok(repo.next_level_end_time(now).await?)
+    }
+}
+
+/// Resolve on the next clock change, or when changes may have been missed
+async fn next_clock_change(changes: &mut broadcast::Receiver<BusEvent>) {
+    loop {
+        match changes.recv().await {
+            Ok(BusEvent::Clock(_)) | Err(broadcast::error::RecvError::Lagged(_)) => return,
+            Ok(_) => continue,
+            // The bus lives as long as the process
+            Err(broadcast::error::RecvError::Closed) => std::future::pending().await,
+        }
     }
 }
 
@@ -0,0 +1,87 @@
+use sqlx::postgres::{PgConnectOptions, PgConnection};
+use sqlx::{Connection, PgPool};
+use tracing::{info, warn};
+
+/// Postgres advisory lock electing one instance to run a background job
+///
+/// The lock is held by a dedicated session, outside the pool. When the leader stops or
+/// loses its connection, Postgres releases the lock and the next instance asking for it
+/// takes over.
+pub struct LeaderLock {
+    options: PgConnectOptions,
+    key: i64,
+    connection: Option<PgConnection>,
+    leader: bool,
+}
+
+impl LeaderLock {
+    pub fn new(pool: &PgPool, key: i64) -> Self {
+        Self {
+            options: (*pool.connect_options()).clone(),
+            key,
+            connection: None,
+            leader: false,
+        }
+    }
+
+    /// Whether this instance leads, taking the lock when it is free
+    pub async fn acquire(&mut self) -> bool {
+        match self.try_acquire().await {
+            Ok(leader) => {
+                if leader && !self.leader {
+                    info!("Took leader lock {}", self.key);
+                }
+                self.leader = leader;
+            }
+            Err(e) => {
+                if self.leader {
+                    warn!("Lost leader lock {}: {}", self.key, e);
+                }
+                // Dropping the session releases the lock if it is still held
+                self.connection = None;
+                self.leader = false;
+            }
+        }
+
+        self.leader
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader
+    }
+
+    /// Give up the lock so another instance can take over
+    pub async fn release(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            // Unlock before closing, the server ends the session in the background
+            if self.leader {
+                let _ = sqlx::query("SELECT pg_advisory_unlock($1)")
+                    .bind(self.key)
+                    .execute(&mut connection)
+                    .await;
+            }
+            let _ = connection.close().await;
+        }
+        self.leader = false;
+    }
+
+    async fn try_acquire(&mut self) -> sqlx::Result<bool> {
+        let connection = match self.connection.as_mut() {
+            Some(connection) => connection,
+            None => self
+                .connection
+                .insert(PgConnection::connect_with(&self.options).await?),
+        };
+
+        // The lock lives as long as the session holding it
+        if self.leader {
+            connection.ping().await?;
+            return Ok(true);
+        }
+
+        sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.key)
+            .fetch_one(connection)
+            .await
+    }
+}
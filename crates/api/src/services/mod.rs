@@ -1,3 +1,9 @@
 pub mod clock_service;
+pub mod leader_lock;
+pub mod series_scheduler;
+pub mod time_source;
 
 pub use clock_service::{spawn_clock_service, ClockService};
+pub use leader_lock::LeaderLock;
+pub use series_scheduler::{spawn_series_scheduler, SeriesScheduler};
+pub use time_source::{ManualTime, SystemTime, TimeSource};
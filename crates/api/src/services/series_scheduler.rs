@@ -0,0 +1,56 @@
+use std::time::Duration;
+use tokio::time::{interval, Interval};
+use tracing::{error, info};
+
+use crate::AppState;
+use infra::repos::TournamentSeriesRepo;
+
+pub struct SeriesScheduler {
+    state: AppState,
+    interval: Interval,
+}
+
+impl SeriesScheduler {
+    pub fn new(state: AppState) -> Self {
+        // Instances are created weeks ahead, so checking hourly is plenty
+        let interval = interval(Duration::from_secs(60 * 60));
+
+        Self { state, interval }
+    }
+
+    /// Start the background series scheduler
+    pub async fn run(&mut self) {
+        info!("Starting tournament series scheduler");
+
+        loop {
+            self.interval.tick().await;
+
+            if let Err(e) = self.process_series().await {
+                error!("Error materializing tournament series: {}", e);
+            }
+        }
+    }
+
+    /// Create upcoming tournaments for every active series
+    async fn process_series(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let repo = TournamentSeriesRepo::new(self.state.db.clone());
+
+        let tournament_ids = repo.materialize_all().await?;
+        if !tournament_ids.is_empty() {
+            info!(
+                "Created {} tournaments from recurring series",
+                tournament_ids.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Spawn the series scheduler as a background task
+pub fn spawn_series_scheduler(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut scheduler = SeriesScheduler::new(state);
+        scheduler.run().await;
+    })
+}
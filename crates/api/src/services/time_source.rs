@@ -0,0 +1,62 @@
+use chrono::{DateTime, Duration, Utc};
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Where background services read the time and wait for it
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Resolve once `now` reaches `deadline`
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> BoxFuture<'_, ()>;
+}
+
+/// The system clock
+pub struct SystemTime;
+
+impl TimeSource for SystemTime {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> BoxFuture<'_, ()> {
+        let delay = (deadline - Utc::now()).to_std().unwrap_or_default();
+        Box::pin(tokio::time::sleep(delay))
+    }
+}
+
+/// Time that only moves when told to, for tests
+#[derive(Clone)]
+pub struct ManualTime {
+    now: Arc<watch::Sender<DateTime<Utc>>>,
+}
+
+impl ManualTime {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        let (now, _) = watch::channel(start);
+        Self { now: Arc::new(now) }
+    }
+
+    /// Move to `time`, waking whoever sleeps until then
+    pub fn set(&self, time: DateTime<Utc>) {
+        self.now.send_replace(time);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.send_modify(|now| *now += by);
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.borrow()
+    }
+
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> BoxFuture<'_, ()> {
+        let mut now = self.now.subscribe();
+        Box::pin(async move {
+            // The sender lives as long as self, so this only returns once the time is reached
+            let _ = now.wait_for(|now| *now >= deadline).await;
+        })
+    }
+}
@@ -0,0 +1,466 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use serde_json::json;
+
+const REPLACE_STRUCTURE: &str = r#"
+    mutation ReplaceTournamentStructure($tournamentId: ID!, $levels: [BlindLevelInput!]!) {
+        replaceTournamentStructure(tournamentId: $tournamentId, levels: $levels) {
+            levelNumber
+            smallBlind
+            bigBlind
+        }
+    }
+"#;
+
+fn levels(blinds: &[(i32, i32)]) -> serde_json::Value {
+    json!(blinds
+        .iter()
+        .map(|(small_blind, big_blind)| json!({
+            "smallBlind": small_blind,
+            "bigBlind": big_blind,
+            "durationMinutes": 20
+        }))
+        .collect::<Vec<_>>())
+}
+
+fn small_blinds(structure: &serde_json::Value) -> Vec<i64> {
+    structure
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .map(|(i, level)| {
+            assert_eq!(level["levelNumber"], i as i64 + 1);
+            level["smallBlind"].as_i64().unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_edit_tournament_structure() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "structuremanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Structure Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Structure Tournament").await;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levels": levels(&[(100, 200), (200, 400), (300, 600)])
+    }));
+    let response = execute_graphql(
+        &schema,
+        REPLACE_STRUCTURE,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(
+        response.errors.is_empty(),
+        "Replacing the structure should succeed: {:?}",
+        response.errors
+    );
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["replaceTournamentStructure"]),
+        vec![100, 200, 300]
+    );
+
+    let insert = r#"
+        mutation Insert($tournamentId: ID!, $level: BlindLevelInput!) {
+            insertTournamentStructureLevel(tournamentId: $tournamentId, levelNumber: 2, level: $level) {
+                levelNumber
+                smallBlind
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "level": { "smallBlind": 150, "bigBlind": 300, "durationMinutes": 20 }
+    }));
+    let response = execute_graphql(
+        &schema,
+        insert,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["insertTournamentStructureLevel"]),
+        vec![100, 150, 200, 300]
+    );
+
+    let move_level = r#"
+        mutation Move($tournamentId: ID!) {
+            moveTournamentStructureLevel(tournamentId: $tournamentId, levelNumber: 4, newLevelNumber: 1) {
+                levelNumber
+                smallBlind
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        move_level,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["moveTournamentStructureLevel"]),
+        vec![300, 100, 150, 200]
+    );
+
+    let delete = r#"
+        mutation Delete($tournamentId: ID!, $levelNumber: Int!) {
+            deleteTournamentStructureLevel(tournamentId: $tournamentId, levelNumber: $levelNumber) {
+                levelNumber
+                smallBlind
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levelNumber": 1
+    }));
+    let response = execute_graphql(
+        &schema,
+        delete,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["deleteTournamentStructureLevel"]),
+        vec![100, 150, 200]
+    );
+
+    // Levels that don't exist can't be deleted
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levelNumber": 9
+    }));
+    let response = execute_graphql(&schema, delete, Some(variables), Some(manager_claims)).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_played_levels_cannot_be_edited() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "structurerunning@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Running Structure Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Running Structure Tournament").await;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levels": levels(&[(100, 200), (200, 400), (300, 600)])
+    }));
+    let response = execute_graphql(
+        &schema,
+        REPLACE_STRUCTURE,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    // The clock is running in level 2
+    sqlx::query(
+        "UPDATE tournament_clocks SET clock_status = 'running', current_level = 2, level_started_at = NOW() WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to start clock");
+
+    // Changing level 2 is rejected
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levels": levels(&[(100, 200), (250, 500), (300, 600)])
+    }));
+    let response = execute_graphql(
+        &schema,
+        REPLACE_STRUCTURE,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(
+        !response.errors.is_empty(),
+        "Played levels should not be editable"
+    );
+    assert!(response.errors[0].message.contains("already been played"));
+
+    // Later levels can still change
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levels": levels(&[(100, 200), (200, 400), (400, 800), (500, 1000)])
+    }));
+    let response = execute_graphql(
+        &schema,
+        REPLACE_STRUCTURE,
+        Some(variables),
+        Some(manager_claims),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["replaceTournamentStructure"]),
+        vec![100, 200, 400, 500]
+    );
+}
+
+#[tokio::test]
+async fn test_blind_structure_templates() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "structuretemplates@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Template Club").await;
+    let other_club_id = create_test_club(&app_state, "Other Template Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    create_club_manager(&app_state, manager_id, other_club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Template Tournament").await;
+    let other_tournament_id =
+        create_test_tournament(&app_state, other_club_id, "Other Template Tournament").await;
+
+    let create = r#"
+        mutation CreateTemplate($input: CreateBlindStructureTemplateInput!) {
+            createBlindStructureTemplate(input: $input) {
+                id
+                name
+                levels {
+                    levelNumber
+                    smallBlind
+                }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "input": {
+            "clubId": club_id.to_string(),
+            "name": "Turbo 15min",
+            "levels": levels(&[(25, 50), (50, 100)])
+        }
+    }));
+    let response = execute_graphql(
+        &schema,
+        create,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let template = &data["createBlindStructureTemplate"];
+    assert_eq!(template["name"], "Turbo 15min");
+    assert_eq!(small_blinds(&template["levels"]), vec![25, 50]);
+    let template_id = template["id"].clone();
+
+    // Template names are unique within a club
+    let variables = Variables::from_json(json!({
+        "input": {
+            "clubId": club_id.to_string(),
+            "name": "Turbo 15min",
+            "levels": levels(&[(25, 50)])
+        }
+    }));
+    let response = execute_graphql(
+        &schema,
+        create,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert_eq!(
+        response.errors[0].message,
+        "A template with this name already exists in the club"
+    );
+
+    let list = r#"
+        query Templates($clubId: ID!) {
+            blindStructureTemplates(clubId: $clubId) {
+                id
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, list, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["blindStructureTemplates"],
+        json!([{ "id": template_id }])
+    );
+
+    let apply = r#"
+        mutation Apply($tournamentId: ID!, $templateId: ID!) {
+            applyBlindStructureTemplate(tournamentId: $tournamentId, templateId: $templateId) {
+                levelNumber
+                smallBlind
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "templateId": template_id
+    }));
+    let response = execute_graphql(
+        &schema,
+        apply,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        small_blinds(&data["applyBlindStructureTemplate"]),
+        vec![25, 50]
+    );
+
+    // Templates can't be applied to another club's tournaments
+    let variables = Variables::from_json(json!({
+        "tournamentId": other_tournament_id.to_string(),
+        "templateId": template_id
+    }));
+    let response = execute_graphql(&schema, apply, Some(variables), Some(manager_claims)).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_edit_structure_requires_club_manager() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (_, player_claims) =
+        create_test_user(&app_state, "structureplayer@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Structure Permission Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Structure Permission Tournament").await;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "levels": levels(&[(100, 200)])
+    }));
+    let response = execute_graphql(
+        &schema,
+        REPLACE_STRUCTURE,
+        Some(variables),
+        Some(player_claims),
+    )
+    .await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "Players should not be able to edit structures"
+    );
+}
+
+#[tokio::test]
+async fn test_generate_and_save_blind_structure() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "structuregenerator@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Generator Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Generator Tournament").await;
+
+    let input = json!({
+        "startingStack": 20000,
+        "expectedPlayers": 50,
+        "targetDurationMinutes": 300,
+        "levelDurationMinutes": 20,
+        "anteFromLevel": 5,
+        "breakEveryLevels": 4,
+        "breakDurationMinutes": 10
+    });
+
+    let query = r#"
+        query Generate($input: GenerateBlindStructureInput!) {
+            generateBlindStructure(input: $input) {
+                totalDurationMinutes
+                levels {
+                    levelNumber
+                    smallBlind
+                    bigBlind
+                    ante
+                    isBreak
+                }
+                colorUps {
+                    levelNumber
+                    denominations
+                }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "input": input }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let generated = &data["generateBlindStructure"];
+
+    assert_eq!(generated["totalDurationMinutes"], 290);
+    let levels = generated["levels"].as_array().unwrap();
+    assert_eq!(levels.len(), 16);
+    assert_eq!(levels[0]["smallBlind"], 100);
+    assert_eq!(levels[4]["isBreak"], true);
+    assert_eq!(levels[5]["ante"], levels[5]["bigBlind"]);
+    assert_eq!(
+        generated["colorUps"][0],
+        json!({ "levelNumber": 5, "denominations": [25] })
+    );
+
+    let save = r#"
+        mutation Save($tournamentId: ID!, $input: GenerateBlindStructureInput!) {
+            saveGeneratedBlindStructure(tournamentId: $tournamentId, input: $input) {
+                levelNumber
+                smallBlind
+                isBreak
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "input": input
+    }));
+    let response = execute_graphql(&schema, save, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let saved = data["saveGeneratedBlindStructure"].as_array().unwrap();
+    assert_eq!(saved.len(), 16);
+    assert_eq!(saved[4]["isBreak"], true);
+
+    // Invalid parameters are reported
+    let variables = Variables::from_json(json!({
+        "input": {
+            "startingStack": 20000,
+            "expectedPlayers": 1,
+            "targetDurationMinutes": 300,
+            "levelDurationMinutes": 20
+        }
+    }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(!response.errors.is_empty());
+}
@@ -0,0 +1,348 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use serde_json::json;
+use uuid::Uuid;
+
+const REGISTER: &str = r#"
+    mutation RegisterForTournament($input: RegisterForTournamentInput!) {
+        registerForTournament(input: $input) { id }
+    }
+"#;
+
+const ELIMINATE: &str = r#"
+    mutation EliminatePlayer($tournamentId: ID!, $userId: ID!, $eliminatedBy: ID) {
+        eliminatePlayer(tournamentId: $tournamentId, userId: $userId, eliminatedBy: $eliminatedBy)
+    }
+"#;
+
+type Schema =
+    async_graphql::Schema<api::gql::QueryRoot, api::gql::MutationRoot, api::gql::SubscriptionRoot>;
+
+/// Creates a running bounty tournament with a manager for its club
+async fn setup_bounty_tournament(
+    app_state: &api::AppState,
+    prefix: &str,
+    bounty_format: &str,
+) -> (Uuid, Uuid, api::auth::Claims) {
+    let (manager_id, manager_claims) =
+        create_test_user(app_state, &format!("{}manager@test.com", prefix), "manager").await;
+    let club_id = create_test_club(app_state, &format!("{} Club", prefix)).await;
+    create_club_manager(app_state, manager_id, club_id).await;
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    sqlx::query(
+        r#"
+        UPDATE tournaments
+        SET bounty_format = $2, bounty_cents = 2000, live_status = 'in_progress'
+        WHERE id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(bounty_format)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to set bounty format");
+
+    (tournament_id, club_id, manager_claims)
+}
+
+/// Registers players and seats them at one table
+async fn seat_players(
+    schema: &Schema,
+    app_state: &api::AppState,
+    club_id: Uuid,
+    tournament_id: Uuid,
+    emails: &[&str],
+) -> Vec<Uuid> {
+    let club_table_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO club_tables (id, club_id, table_number, max_seats) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(club_table_id)
+    .bind(club_id)
+    .bind(1)
+    .bind(9)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create club table");
+
+    let mut user_ids = Vec::new();
+    for (seat, email) in emails.iter().enumerate() {
+        let (user_id, claims) = create_test_user(app_state, email, "player").await;
+        let variables = Variables::from_json(json!({
+            "input": { "tournamentId": tournament_id.to_string() }
+        }));
+        let response = execute_graphql(schema, REGISTER, Some(variables), Some(claims)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        sqlx::query(
+            "INSERT INTO table_seat_assignments (tournament_id, club_table_id, user_id, seat_number, stack_size) VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(tournament_id)
+        .bind(club_table_id)
+        .bind(user_id)
+        .bind(seat as i32 + 1)
+        .bind(10000)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to seat player");
+
+        user_ids.push(user_id);
+    }
+    user_ids
+}
+
+async fn eliminate(
+    schema: &Schema,
+    claims: &api::auth::Claims,
+    tournament_id: Uuid,
+    user_id: Uuid,
+    eliminated_by: Option<Uuid>,
+) -> async_graphql::Response {
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "userId": user_id.to_string(),
+        "eliminatedBy": eliminated_by.map(|id| id.to_string()),
+    }));
+    execute_graphql(schema, ELIMINATE, Some(variables), Some(claims.clone())).await
+}
+
+async fn current_bounties(schema: &Schema, tournament_id: Uuid) -> Vec<(String, i64)> {
+    let query = r#"
+        query TournamentPlayers($tournamentId: ID!) {
+            tournamentPlayers(tournamentId: $tournamentId) {
+                registration { userId currentBountyCents }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    data["tournamentPlayers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|player| {
+            (
+                player["registration"]["userId"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                player["registration"]["currentBountyCents"]
+                    .as_i64()
+                    .unwrap(),
+            )
+        })
+        .collect()
+}
+
+fn bounty_of(bounties: &[(String, i64)], user_id: Uuid) -> i64 {
+    bounties
+        .iter()
+        .find(|(id, _)| *id == user_id.to_string())
+        .map(|(_, cents)| *cents)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_progressive_knockouts_split_and_grow_bounties() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) =
+        setup_bounty_tournament(&app_state, "pko", "progressive").await;
+    let players = seat_players(
+        &schema,
+        &app_state,
+        club_id,
+        tournament_id,
+        &["pkoalice@test.com", "pkobob@test.com", "pkocarol@test.com"],
+    )
+    .await;
+    let (alice, bob, carol) = (players[0], players[1], players[2]);
+
+    // Bounties stay out of the prize pool
+    let query = r#"
+        query Tournament($id: UUID!) {
+            tournament(id: $id) {
+                bountyFormat
+                bountyCents
+                prizeContributionCents
+                accounting { prizePoolCents bountyCents }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "id": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["tournament"]["bountyFormat"], "PROGRESSIVE");
+    assert_eq!(data["tournament"]["bountyCents"], 2000);
+    assert_eq!(data["tournament"]["prizeContributionCents"], 3000);
+    assert_eq!(data["tournament"]["accounting"]["prizePoolCents"], 9000);
+    assert_eq!(data["tournament"]["accounting"]["bountyCents"], 6000);
+
+    // Nobody can knock themselves out
+    let response = eliminate(&schema, &claims, tournament_id, carol, Some(carol)).await;
+    assert!(response.errors[0]
+        .message
+        .contains("cannot eliminate themselves"));
+
+    // Bob knocks out Carol: half of her bounty is paid, half goes on his head
+    let response = eliminate(&schema, &claims, tournament_id, carol, Some(bob)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let bounties = current_bounties(&schema, tournament_id).await;
+    assert_eq!(bounty_of(&bounties, bob), 3000);
+    assert_eq!(bounty_of(&bounties, carol), 0);
+
+    // Carol is no longer at a table to win a hand
+    let response = eliminate(&schema, &claims, tournament_id, bob, Some(carol)).await;
+    assert!(response.errors[0]
+        .message
+        .contains("Eliminator not currently"));
+
+    // Alice knocks out Bob and his grown bounty
+    let response = eliminate(&schema, &claims, tournament_id, bob, Some(alice)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let bounties = current_bounties(&schema, tournament_id).await;
+    assert_eq!(bounty_of(&bounties, alice), 3500);
+
+    let query = r#"
+        query TournamentEliminations($tournamentId: ID!) {
+            tournamentEliminations(tournamentId: $tournamentId) {
+                userId
+                eliminatedBy
+                bountyCents
+                bountyAddedCents
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let eliminations = data["tournamentEliminations"].as_array().unwrap();
+    assert_eq!(eliminations.len(), 2);
+    assert_eq!(eliminations[0]["userId"], carol.to_string());
+    assert_eq!(eliminations[0]["eliminatedBy"], bob.to_string());
+    assert_eq!(eliminations[0]["bountyCents"], 1000);
+    assert_eq!(eliminations[0]["bountyAddedCents"], 1000);
+    assert_eq!(eliminations[1]["bountyCents"], 1500);
+    assert_eq!(eliminations[1]["bountyAddedCents"], 1500);
+
+    // Alice is the last player standing: the results are in and she also collects her
+    // own bounty. Results keep bounties apart from prizes.
+    let bounties: Vec<i64> = sqlx::query_scalar(
+        r#"
+        SELECT bounty_cents::BIGINT FROM tournament_results
+        WHERE tournament_id = $1
+        ORDER BY final_position
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_all(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(bounties, vec![5000, 1000, 0]);
+    assert_eq!(bounties.iter().sum::<i64>(), 6000);
+
+    // The leaderboard counts bounty winnings separately and in the profit
+    let query = r#"
+        query Leaderboard($clubId: UUID) {
+            leaderboard(clubId: $clubId) {
+                entries {
+                    user { id }
+                    totalWinnings
+                    totalBountyWinnings
+                    netProfit
+                }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let entry = data["leaderboard"]["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["user"]["id"] == alice.to_string())
+        .cloned()
+        .unwrap();
+    assert_eq!(entry["totalBountyWinnings"], 5000);
+    assert_eq!(
+        entry["netProfit"].as_i64().unwrap(),
+        entry["totalWinnings"].as_i64().unwrap() + 5000 - 5000
+    );
+}
+
+#[tokio::test]
+async fn test_regular_bounty_pays_in_full_and_resets_on_reentry() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) =
+        setup_bounty_tournament(&app_state, "ko", "regular").await;
+    sqlx::query("UPDATE tournaments SET max_reentries = 1 WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to allow re-entries");
+    let players = seat_players(
+        &schema,
+        &app_state,
+        club_id,
+        tournament_id,
+        &["koalice@test.com", "kobob@test.com", "kocarol@test.com"],
+    )
+    .await;
+    let (alice, bob) = (players[0], players[1]);
+
+    let response = eliminate(&schema, &claims, tournament_id, bob, Some(alice)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    // A regular bounty never grows
+    let bounties = current_bounties(&schema, tournament_id).await;
+    assert_eq!(bounty_of(&bounties, alice), 2000);
+    assert_eq!(bounty_of(&bounties, bob), 0);
+
+    // A re-entry buys a fresh bounty
+    let query = r#"
+        mutation RecordTournamentEntry($input: RecordTournamentEntryInput!) {
+            recordTournamentEntry(input: $input) { bountyCents prizeCents }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "input": {
+            "tournamentId": tournament_id.to_string(),
+            "userId": bob.to_string(),
+            "entryType": "REENTRY",
+        }
+    }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(claims.clone())).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["recordTournamentEntry"]["bountyCents"], 2000);
+    assert_eq!(data["recordTournamentEntry"]["prizeCents"], 3000);
+
+    let bounties = current_bounties(&schema, tournament_id).await;
+    assert_eq!(bounty_of(&bounties, bob), 2000);
+
+    let query = r#"
+        query TournamentEliminations($tournamentId: ID!) {
+            tournamentEliminations(tournamentId: $tournamentId) { bountyCents bountyAddedCents }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["tournamentEliminations"][0]["bountyCents"], 2000);
+    assert_eq!(data["tournamentEliminations"][0]["bountyAddedCents"], 0);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Seat players at a fresh table with the given stacks and store a 50/30/20 payout
+async fn setup_final_table(
+    app_state: &api::AppState,
+    club_id: Uuid,
+    tournament_id: Uuid,
+    players: &[(Uuid, i32)],
+) {
+    let club_table_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO club_tables (id, club_id, table_number, max_seats) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(club_table_id)
+    .bind(club_id)
+    .bind(1)
+    .bind(9)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create club table");
+
+    for (seat, (user_id, stack)) in players.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO table_seat_assignments (tournament_id, club_table_id, user_id, seat_number, stack_size) VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(tournament_id)
+        .bind(club_table_id)
+        .bind(user_id)
+        .bind(seat as i32 + 1)
+        .bind(stack)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to seat player");
+    }
+
+    sqlx::query(
+        "INSERT INTO tournament_payouts (tournament_id, player_count, total_prize_pool, payout_positions) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(tournament_id)
+    .bind(20)
+    .bind(10000)
+    .bind(json!([
+        {"position": 1, "amount_cents": 5000, "percentage": 50.0},
+        {"position": 2, "amount_cents": 3000, "percentage": 30.0},
+        {"position": 3, "amount_cents": 2000, "percentage": 20.0}
+    ]))
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tournament payout");
+}
+
+#[tokio::test]
+async fn test_preview_deal() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "dealmanager@test.com", "manager").await;
+    let (leader_id, _) = create_test_user(&app_state, "dealleader@test.com", "player").await;
+    let (middle_id, _) = create_test_user(&app_state, "dealmiddle@test.com", "player").await;
+    let (short_id, _) = create_test_user(&app_state, "dealshort@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Deal Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Deal Tournament").await;
+
+    setup_final_table(
+        &app_state,
+        club_id,
+        tournament_id,
+        &[(short_id, 2000), (leader_id, 5000), (middle_id, 3000)],
+    )
+    .await;
+
+    let query = r#"
+        query PreviewDeal($tournamentId: ID!) {
+            previewDeal(tournamentId: $tournamentId) {
+                totalAmountCents
+                ladderCents
+                players {
+                    userId
+                    stackSize
+                    icmAmountCents
+                    chipChopAmountCents
+                    evenSplitAmountCents
+                }
+            }
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string()
+    }));
+
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Deal preview should succeed: {:?}",
+        response.errors
+    );
+
+    let data = response.data.into_json().unwrap();
+    let preview = &data["previewDeal"];
+
+    assert_eq!(preview["totalAmountCents"], 10000);
+    assert_eq!(preview["ladderCents"], json!([5000, 3000, 2000]));
+
+    let players = preview["players"].as_array().unwrap();
+    assert_eq!(players.len(), 3);
+
+    // Biggest stack first
+    assert_eq!(players[0]["userId"], leader_id.to_string());
+    assert_eq!(players[0]["icmAmountCents"], 3839);
+    assert_eq!(players[0]["chipChopAmountCents"], 4000);
+    assert_eq!(players[0]["evenSplitAmountCents"], 3334);
+
+    assert_eq!(players[1]["userId"], middle_id.to_string());
+    assert_eq!(players[1]["icmAmountCents"], 3275);
+    assert_eq!(players[1]["chipChopAmountCents"], 3200);
+
+    assert_eq!(players[2]["userId"], short_id.to_string());
+    assert_eq!(players[2]["icmAmountCents"], 2886);
+    assert_eq!(players[2]["chipChopAmountCents"], 2800);
+}
+
+#[tokio::test]
+async fn test_preview_deal_requires_club_manager() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (_, player_claims) = create_test_user(&app_state, "dealplayer@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Deal Permission Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Deal Permission Tournament").await;
+
+    let query = r#"
+        query PreviewDeal($tournamentId: ID!) {
+            previewDeal(tournamentId: $tournamentId) {
+                totalAmountCents
+            }
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string()
+    }));
+
+    let response = execute_graphql(&schema, query, Some(variables), Some(player_claims)).await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "Players should not be able to preview deals"
+    );
+}
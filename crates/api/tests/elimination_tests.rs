@@ -0,0 +1,777 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use infra::repos::TournamentEliminationRepo;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const REGISTER: &str = r#"
+    mutation RegisterForTournament($input: RegisterForTournamentInput!) {
+        registerForTournament(input: $input) { id }
+    }
+"#;
+
+const ELIMINATE_PLAYERS: &str = r#"
+    mutation EliminatePlayers($input: EliminatePlayersInput!) {
+        eliminatePlayers(input: $input) {
+            userId
+            eliminatedBy
+            level
+            finishingPosition
+        }
+    }
+"#;
+
+const UNDO_LAST_ELIMINATION: &str = r#"
+    mutation UndoLastElimination($tournamentId: ID!) {
+        undoLastElimination(tournamentId: $tournamentId) {
+            userId
+            finishingPosition
+        }
+    }
+"#;
+
+type Schema =
+    async_graphql::Schema<api::gql::QueryRoot, api::gql::MutationRoot, api::gql::SubscriptionRoot>;
+
+/// Creates a running tournament at level 3 with a manager for its club
+async fn setup_running_tournament(
+    app_state: &api::AppState,
+    prefix: &str,
+) -> (Uuid, Uuid, api::auth::Claims) {
+    let (manager_id, manager_claims) =
+        create_test_user(app_state, &format!("{}manager@test.com", prefix), "manager").await;
+    let club_id = create_test_club(app_state, &format!("{} Club", prefix)).await;
+    create_club_manager(app_state, manager_id, club_id).await;
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    sqlx::query("UPDATE tournaments SET live_status = 'in_progress' WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to start tournament");
+    sqlx::query("UPDATE tournament_clocks SET current_level = 3 WHERE tournament_id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to set clock level");
+
+    (tournament_id, club_id, manager_claims)
+}
+
+async fn create_table(app_state: &api::AppState, club_id: Uuid, table_number: i32) -> Uuid {
+    let club_table_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO club_tables (id, club_id, table_number, max_seats) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(club_table_id)
+    .bind(club_id)
+    .bind(table_number)
+    .bind(9)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create club table");
+    club_table_id
+}
+
+/// Registers a player and seats them with the given stack
+async fn seat_player(
+    schema: &Schema,
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+    club_table_id: Uuid,
+    seat_number: i32,
+    email: &str,
+    stack_size: i32,
+) -> Uuid {
+    let (user_id, claims) = create_test_user(app_state, email, "player").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string() }
+    }));
+    let response = execute_graphql(schema, REGISTER, Some(variables), Some(claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    sqlx::query(
+        "INSERT INTO table_seat_assignments (tournament_id, club_table_id, user_id, seat_number, stack_size) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(tournament_id)
+    .bind(club_table_id)
+    .bind(user_id)
+    .bind(seat_number)
+    .bind(stack_size)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to seat player");
+
+    user_id
+}
+
+async fn eliminate_players(
+    schema: &Schema,
+    claims: &api::auth::Claims,
+    tournament_id: Uuid,
+    eliminations: &[(Uuid, Uuid)],
+) -> async_graphql::Response {
+    let eliminations: Vec<Value> = eliminations
+        .iter()
+        .map(|(user_id, eliminated_by)| {
+            json!({ "userId": user_id.to_string(), "eliminatedBy": eliminated_by.to_string() })
+        })
+        .collect();
+    let variables = Variables::from_json(json!({
+        "input": {
+            "tournamentId": tournament_id.to_string(),
+            "eliminations": eliminations,
+        }
+    }));
+    execute_graphql(
+        schema,
+        ELIMINATE_PLAYERS,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await
+}
+
+fn position_of(data: &Value, user_id: Uuid) -> i64 {
+    data["eliminatePlayers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|elimination| elimination["userId"] == user_id.to_string())
+        .map(|elimination| elimination["finishingPosition"].as_i64().unwrap())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_finishing_positions_follow_the_players_remaining() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimlog").await;
+    let table_1 = create_table(&app_state, club_id, 1).await;
+    let table_2 = create_table(&app_state, club_id, 2).await;
+    let alice = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_1,
+        1,
+        "elimlogalice@test.com",
+        5000,
+    )
+    .await;
+    let bob = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_1,
+        2,
+        "elimlogbob@test.com",
+        2000,
+    )
+    .await;
+    let carol = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_1,
+        3,
+        "elimlogcarol@test.com",
+        3000,
+    )
+    .await;
+    let dave = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_2,
+        1,
+        "elimlogdave@test.com",
+        4000,
+    )
+    .await;
+    let erin = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_2,
+        2,
+        "elimlogerin@test.com",
+        3000,
+    )
+    .await;
+
+    // The single eliminatePlayer mutation goes through the same log
+    let query = r#"
+        mutation EliminatePlayer($tournamentId: ID!, $userId: ID!, $eliminatedBy: ID) {
+            eliminatePlayer(tournamentId: $tournamentId, userId: $userId, eliminatedBy: $eliminatedBy)
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "userId": erin.to_string(),
+        "eliminatedBy": dave.to_string(),
+    }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(claims.clone())).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    // Two players bust in the same hand at the same table: the bigger stack finishes higher
+    let response = eliminate_players(
+        &schema,
+        &claims,
+        tournament_id,
+        &[(bob, alice), (carol, alice)],
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(position_of(&data, carol), 3);
+    assert_eq!(position_of(&data, bob), 4);
+    assert_eq!(data["eliminatePlayers"][0]["level"], 3);
+
+    let query = r#"
+        query TournamentEliminations($tournamentId: ID!) {
+            tournamentEliminations(tournamentId: $tournamentId) {
+                userId
+                eliminatedBy
+                level
+                finishingPosition
+                createdAt
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables.clone()), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let log = data["tournamentEliminations"].as_array().unwrap();
+    assert_eq!(log.len(), 3);
+    assert_eq!(log[0]["userId"], erin.to_string());
+    assert_eq!(log[0]["eliminatedBy"], dave.to_string());
+    assert_eq!(log[0]["finishingPosition"], 5);
+    assert_eq!(log[0]["level"], 3);
+    assert_eq!(log[1]["finishingPosition"], 4);
+    assert_eq!(log[2]["finishingPosition"], 3);
+
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(bob)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(status, "busted");
+
+    // Undoing the last elimination gives the player their seat and stack back
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        UNDO_LAST_ELIMINATION,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["undoLastElimination"]["userId"], carol.to_string());
+
+    let (is_current, stack_size): (bool, Option<i32>) = sqlx::query_as(
+        r#"
+        SELECT is_current, stack_size FROM table_seat_assignments
+        WHERE tournament_id = $1 AND user_id = $2
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(carol)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert!(is_current);
+    assert_eq!(stack_size, Some(3000));
+
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(carol)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(status, "registered");
+
+    // Carol busts again later, with one more player remaining than before
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(carol, dave)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(position_of(&data, carol), 3);
+}
+
+#[tokio::test]
+async fn test_concurrent_undos_restore_each_elimination_once() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimundo").await;
+    let table = create_table(&app_state, club_id, 1).await;
+    let alice = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        1,
+        "elimundoalice@test.com",
+        5000,
+    )
+    .await;
+    let bob = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        2,
+        "elimundobob@test.com",
+        2000,
+    )
+    .await;
+    let carol = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        3,
+        "elimundocarol@test.com",
+        3000,
+    )
+    .await;
+    seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        4,
+        "elimundodave@test.com",
+        3000,
+    )
+    .await;
+    for busted in [bob, carol] {
+        let response = eliminate_players(&schema, &claims, tournament_id, &[(busted, alice)]).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+    }
+
+    // Two managers undo at the same time: no elimination is undone twice
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let (first, second) = tokio::join!(
+        execute_graphql(
+            &schema,
+            UNDO_LAST_ELIMINATION,
+            Some(variables.clone()),
+            Some(claims.clone()),
+        ),
+        execute_graphql(
+            &schema,
+            UNDO_LAST_ELIMINATION,
+            Some(variables),
+            Some(claims.clone()),
+        ),
+    );
+    let undone: Vec<Value> = [first, second]
+        .into_iter()
+        .filter(|response| response.errors.is_empty())
+        .map(|response| response.data.into_json().unwrap()["undoLastElimination"]["userId"].clone())
+        .collect();
+    assert!(!undone.is_empty());
+    if undone.len() == 2 {
+        assert_ne!(undone[0], undone[1]);
+    }
+
+    let (eliminations, busted): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM tournament_eliminations WHERE tournament_id = $1),
+            (SELECT COUNT(*) FROM tournament_registrations WHERE tournament_id = $1 AND status = 'busted')
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(eliminations, 2 - undone.len() as i64);
+    assert_eq!(busted, eliminations);
+}
+
+#[tokio::test]
+async fn test_simultaneous_busts_at_different_tables_tie() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimtie").await;
+    let table_1 = create_table(&app_state, club_id, 1).await;
+    let table_2 = create_table(&app_state, club_id, 2).await;
+    let alice = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_1,
+        1,
+        "elimtiealice@test.com",
+        5000,
+    )
+    .await;
+    let bob = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_1,
+        2,
+        "elimtiebob@test.com",
+        1000,
+    )
+    .await;
+    let carol = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_2,
+        1,
+        "elimtiecarol@test.com",
+        5000,
+    )
+    .await;
+    let dave = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table_2,
+        2,
+        "elimtiedave@test.com",
+        9000,
+    )
+    .await;
+
+    // The eliminator has to survive the hand
+    let response = eliminate_players(
+        &schema,
+        &claims,
+        tournament_id,
+        &[(bob, alice), (alice, carol)],
+    )
+    .await;
+    assert!(response.errors[0]
+        .message
+        .contains("cannot bust in the same hand"));
+
+    let response = eliminate_players(
+        &schema,
+        &claims,
+        tournament_id,
+        &[(bob, alice), (dave, carol)],
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(position_of(&data, bob), 3);
+    assert_eq!(position_of(&data, dave), 3);
+
+    // Busted players can't be eliminated twice
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(bob, alice)]).await;
+    assert!(response.errors[0]
+        .message
+        .contains("not currently assigned to a seat"));
+}
+
+#[tokio::test]
+async fn test_undo_restores_progressive_bounties() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimundo").await;
+    sqlx::query(
+        "UPDATE tournaments SET bounty_format = 'progressive', bounty_cents = 2000 WHERE id = $1",
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to set bounty format");
+    let table = create_table(&app_state, club_id, 1).await;
+    let alice = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        1,
+        "elimundoalice@test.com",
+        5000,
+    )
+    .await;
+    let bob = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        2,
+        "elimundobob@test.com",
+        5000,
+    )
+    .await;
+    // A third player keeps the tournament going after Bob busts
+    seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        3,
+        "elimundocarol@test.com",
+        5000,
+    )
+    .await;
+
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        UNDO_LAST_ELIMINATION,
+        Some(variables.clone()),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors[0]
+        .message
+        .contains("No elimination to undo"));
+
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(bob, alice)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let response = execute_graphql(
+        &schema,
+        UNDO_LAST_ELIMINATION,
+        Some(variables.clone()),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let bounties: Vec<(Uuid, Option<i32>)> = sqlx::query_as(
+        "SELECT user_id, bounty_cents FROM tournament_registrations WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .fetch_all(&app_state.db)
+    .await
+    .unwrap();
+    for (user_id, bounty_cents) in bounties {
+        assert_eq!(bounty_cents.unwrap_or(2000), 2000, "bounty of {}", user_id);
+    }
+
+    // Nothing left to undo
+    let response = execute_graphql(
+        &schema,
+        UNDO_LAST_ELIMINATION,
+        Some(variables),
+        Some(claims),
+    )
+    .await;
+    assert!(response.errors[0]
+        .message
+        .contains("No elimination to undo"));
+}
+
+/// Final results of a tournament as `(user_id, final_position, prize_cents, points)`
+async fn final_results(
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+) -> Vec<(Uuid, i32, i32, i32)> {
+    sqlx::query_as(
+        r#"
+        SELECT user_id, final_position, prize_cents, points
+        FROM tournament_results
+        WHERE tournament_id = $1
+        ORDER BY final_position
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_all(&app_state.db)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_last_player_standing_finalises_the_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimfinal").await;
+    let table = create_table(&app_state, club_id, 1).await;
+    let alice = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        1,
+        "elimfinalalice@test.com",
+        5000,
+    )
+    .await;
+    let bob = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        2,
+        "elimfinalbob@test.com",
+        5000,
+    )
+    .await;
+    let carol = seat_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        table,
+        3,
+        "elimfinalcarol@test.com",
+        5000,
+    )
+    .await;
+    // A registered player who never sat down doesn't keep the tournament going
+    let (_, no_show_claims) =
+        create_test_user(&app_state, "elimfinalnoshow@test.com", "player").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string() }
+    }));
+    let response = execute_graphql(&schema, REGISTER, Some(variables), Some(no_show_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let prize_pool: i32 = sqlx::query_scalar("SELECT tournament_prize_pool($1)")
+        .bind(tournament_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap();
+
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(carol, bob)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert!(final_results(&app_state, tournament_id).await.is_empty());
+
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(bob, alice)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    // Paid 70/30 from the ladder, in the order the players busted
+    let results = final_results(&app_state, tournament_id).await;
+    let standings: Vec<(Uuid, i32, i32)> = results
+        .iter()
+        .map(|(user_id, position, prize_cents, _)| (*user_id, *position, *prize_cents))
+        .collect();
+    assert_eq!(
+        standings,
+        vec![
+            (alice, 1, prize_pool * 70 / 100),
+            (bob, 2, prize_pool * 30 / 100),
+            (carol, 3, 0),
+        ]
+    );
+    // Finishing the tournament awards the points
+    assert!(results.iter().all(|(_, _, _, points)| *points > 0));
+
+    let (live_status, clock_status): (String, String) = sqlx::query_as(
+        r#"
+        SELECT t.live_status::TEXT, c.clock_status
+        FROM tournaments t
+        JOIN tournament_clocks c ON c.tournament_id = t.id
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(live_status, "finished");
+    assert_eq!(clock_status, "stopped");
+
+    // Finalising again changes nothing
+    let finalised = TournamentEliminationRepo::new(app_state.db.clone())
+        .finalise(tournament_id, Vec::new(), None)
+        .await
+        .unwrap();
+    assert!(!finalised);
+    assert_eq!(final_results(&app_state, tournament_id).await, results);
+
+    // Undoing the last elimination reopens the tournament
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        UNDO_LAST_ELIMINATION,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["undoLastElimination"]["userId"], bob.to_string());
+    assert!(final_results(&app_state, tournament_id).await.is_empty());
+    let live_status: String =
+        sqlx::query_scalar("SELECT live_status::TEXT FROM tournaments WHERE id = $1")
+            .bind(tournament_id)
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(live_status, "in_progress");
+
+    // Busting the player again finishes it with the same results
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(bob, alice)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(final_results(&app_state, tournament_id).await, results);
+}
+
+#[tokio::test]
+async fn test_finalised_results_follow_the_recorded_deal() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, club_id, claims) = setup_running_tournament(&app_state, "elimdeal").await;
+    let table = create_table(&app_state, club_id, 1).await;
+    let mut players = Vec::new();
+    for (seat, name) in ["alice", "bob", "carol"].iter().enumerate() {
+        players.push(
+            seat_player(
+                &schema,
+                &app_state,
+                tournament_id,
+                table,
+                seat as i32 + 1,
+                &format!("elimdeal{}@test.com", name),
+                5000,
+            )
+            .await,
+        );
+    }
+    let (alice, bob, carol) = (players[0], players[1], players[2]);
+
+    // Alice and Bob agreed to chop what's left, Alice taking a bit more
+    sqlx::query(
+        r#"
+        INSERT INTO player_deals (tournament_id, deal_type, affected_positions, custom_payouts,
+                                  total_amount_cents, created_by)
+        VALUES ($1, 'custom', $2, $3, $4, $5)
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(vec![1, 2])
+    .bind(json!({ alice.to_string(): 8000, bob.to_string(): 7000 }))
+    .bind(15000)
+    .bind(Uuid::parse_str(&claims.sub).unwrap())
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to record deal");
+
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(carol, alice)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let response = eliminate_players(&schema, &claims, tournament_id, &[(bob, alice)]).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let prizes: Vec<(Uuid, i32)> = final_results(&app_state, tournament_id)
+        .await
+        .into_iter()
+        .map(|(user_id, _, prize_cents, _)| (user_id, prize_cents))
+        .collect();
+    assert_eq!(prizes, vec![(alice, 8000), (bob, 7000), (carol, 0)]);
+}
@@ -0,0 +1,491 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use infra::repos::TournamentPayoutRepo;
+use serde_json::json;
+use uuid::Uuid;
+
+const REGISTER: &str = r#"
+    mutation RegisterForTournament($input: RegisterForTournamentInput!) {
+        registerForTournament(input: $input) { id }
+    }
+"#;
+
+const RECORD_ENTRY: &str = r#"
+    mutation RecordTournamentEntry($input: RecordTournamentEntryInput!) {
+        recordTournamentEntry(input: $input) {
+            entryType
+            amountCents
+        }
+    }
+"#;
+
+const TOURNAMENT_ENTRIES: &str = r#"
+    query TournamentEntries($id: UUID!, $tournamentId: ID!) {
+        tournament(id: $id) {
+            entryCount
+            prizePoolCents
+        }
+        tournamentEntries(tournamentId: $tournamentId) {
+            userId
+            entryType
+            amountCents
+        }
+    }
+"#;
+
+type Schema =
+    async_graphql::Schema<api::gql::QueryRoot, api::gql::MutationRoot, api::gql::SubscriptionRoot>;
+
+async fn register_player(
+    schema: &Schema,
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+    email: &str,
+) -> Uuid {
+    let (user_id, claims) = create_test_user(app_state, email, "player").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string() }
+    }));
+    let response = execute_graphql(schema, REGISTER, Some(variables), Some(claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    user_id
+}
+
+async fn set_registration_status(
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+    user_id: Uuid,
+    status: &str,
+) {
+    sqlx::query(
+        "UPDATE tournament_registrations SET status = $3 WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(user_id)
+    .bind(status)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to set registration status");
+}
+
+async fn record_entry(
+    schema: &Schema,
+    claims: &api::auth::Claims,
+    tournament_id: Uuid,
+    user_id: Uuid,
+    entry_type: &str,
+) -> async_graphql::Response {
+    let variables = Variables::from_json(json!({
+        "input": {
+            "tournamentId": tournament_id.to_string(),
+            "userId": user_id.to_string(),
+            "entryType": entry_type,
+        }
+    }));
+    execute_graphql(schema, RECORD_ENTRY, Some(variables), Some(claims.clone())).await
+}
+
+/// Creates a running tournament with the given entry rules and a manager for its club
+async fn setup_rebuy_tournament(
+    app_state: &api::AppState,
+    prefix: &str,
+    rules: &str,
+) -> (Uuid, api::auth::Claims) {
+    let (manager_id, manager_claims) =
+        create_test_user(app_state, &format!("{}manager@test.com", prefix), "manager").await;
+    let club_id = create_test_club(app_state, &format!("{} Club", prefix)).await;
+    create_club_manager(app_state, manager_id, club_id).await;
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    sqlx::query(&format!(
+        "UPDATE tournaments SET {}, live_status = 'in_progress' WHERE id = $1",
+        rules
+    ))
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to set entry rules");
+
+    (tournament_id, manager_claims)
+}
+
+#[tokio::test]
+async fn test_reentries_rebuys_and_add_ons_grow_the_prize_pool() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims) = setup_rebuy_tournament(
+        &app_state,
+        "rebuy",
+        "max_reentries = 1, max_rebuys = 2, rebuy_cents = 3000, add_on_cents = 2000",
+    )
+    .await;
+    let alice = register_player(&schema, &app_state, tournament_id, "rebuyalice@test.com").await;
+    let bob = register_player(&schema, &app_state, tournament_id, "rebuybob@test.com").await;
+
+    // Only busted players re-enter, up to the limit
+    set_registration_status(&app_state, tournament_id, alice, "checked_in").await;
+    let response = record_entry(&schema, &claims, tournament_id, alice, "REENTRY").await;
+    assert!(response.errors[0].message.contains("Only busted players"));
+
+    set_registration_status(&app_state, tournament_id, alice, "busted").await;
+    let response = record_entry(&schema, &claims, tournament_id, alice, "REENTRY").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["recordTournamentEntry"]["amountCents"], 5000);
+
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(alice)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(status, "checked_in");
+
+    set_registration_status(&app_state, tournament_id, alice, "busted").await;
+    let response = record_entry(&schema, &claims, tournament_id, alice, "REENTRY").await;
+    assert!(response.errors[0].message.contains("1 re-entries allowed"));
+
+    // Rebuys are priced separately and capped per player
+    set_registration_status(&app_state, tournament_id, bob, "seated").await;
+    for _ in 0..2 {
+        let response = record_entry(&schema, &claims, tournament_id, bob, "REBUY").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["recordTournamentEntry"]["amountCents"], 3000);
+    }
+    let response = record_entry(&schema, &claims, tournament_id, bob, "REBUY").await;
+    assert!(response.errors[0].message.contains("2 rebuys allowed"));
+
+    // One add-on per player
+    let response = record_entry(&schema, &claims, tournament_id, bob, "ADD_ON").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let response = record_entry(&schema, &claims, tournament_id, bob, "ADD_ON").await;
+    assert!(response.errors[0]
+        .message
+        .contains("already took the add-on"));
+
+    let variables = Variables::from_json(json!({
+        "id": tournament_id.to_string(),
+        "tournamentId": tournament_id.to_string(),
+    }));
+    let response = execute_graphql(&schema, TOURNAMENT_ENTRIES, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+
+    // Two buy-ins and a re-entry put three entries in the field
+    assert_eq!(data["tournament"]["entryCount"], 3);
+    assert_eq!(
+        data["tournament"]["prizePoolCents"],
+        3 * 5000 + 2 * 3000 + 2000
+    );
+    let entry_types: Vec<_> = data["tournamentEntries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["entryType"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        entry_types,
+        vec!["BUY_IN", "BUY_IN", "REENTRY", "REBUY", "REBUY", "ADD_ON"]
+    );
+}
+
+#[tokio::test]
+async fn test_entries_follow_tournament_rules() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims) =
+        setup_rebuy_tournament(&app_state, "freezeout", "rebuy_until_level = 2").await;
+    let player = register_player(&schema, &app_state, tournament_id, "freezeout1@test.com").await;
+    set_registration_status(&app_state, tournament_id, player, "seated").await;
+
+    // Freezeouts have no rebuys or add-ons
+    let response = record_entry(&schema, &claims, tournament_id, player, "REBUY").await;
+    assert!(response.errors[0].message.contains("0 rebuys allowed"));
+    let response = record_entry(&schema, &claims, tournament_id, player, "ADD_ON").await;
+    assert!(response.errors[0]
+        .message
+        .contains("doesn't offer an add-on"));
+
+    // Nothing is sold after the rebuy period
+    sqlx::query("UPDATE tournaments SET max_rebuys = 1 WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE tournament_clocks SET current_level = 3 WHERE tournament_id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    let response = record_entry(&schema, &claims, tournament_id, player, "REBUY").await;
+    assert!(response.errors[0].message.contains("closed after level 2"));
+
+    // Players can't record their own entries
+    let (_, player_claims) = create_test_user(&app_state, "freezeout2@test.com", "player").await;
+    let response = record_entry(&schema, &player_claims, tournament_id, player, "REBUY").await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_concurrent_entries_stay_within_the_limits() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims) = setup_rebuy_tournament(
+        &app_state,
+        "concurrententry",
+        "max_reentries = 1, add_on_cents = 2000",
+    )
+    .await;
+    let alice = register_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        "concurrententryalice@test.com",
+    )
+    .await;
+    let bob = register_player(
+        &schema,
+        &app_state,
+        tournament_id,
+        "concurrententrybob@test.com",
+    )
+    .await;
+    set_registration_status(&app_state, tournament_id, alice, "seated").await;
+    set_registration_status(&app_state, tournament_id, bob, "busted").await;
+
+    // Only one of two add-ons recorded at the same time goes through
+    let (first, second) = tokio::join!(
+        record_entry(&schema, &claims, tournament_id, alice, "ADD_ON"),
+        record_entry(&schema, &claims, tournament_id, alice, "ADD_ON"),
+    );
+    assert_eq!(
+        [first.errors.is_empty(), second.errors.is_empty()]
+            .iter()
+            .filter(|ok| **ok)
+            .count(),
+        1
+    );
+
+    // Same for the only re-entry allowed
+    let (first, second) = tokio::join!(
+        record_entry(&schema, &claims, tournament_id, bob, "REENTRY"),
+        record_entry(&schema, &claims, tournament_id, bob, "REENTRY"),
+    );
+    assert_eq!(
+        [first.errors.is_empty(), second.errors.is_empty()]
+            .iter()
+            .filter(|ok| **ok)
+            .count(),
+        1
+    );
+
+    let entries: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tournament_entries WHERE tournament_id = $1 AND entry_type <> 'buy_in'",
+    )
+    .bind(tournament_id)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(entries, 2);
+}
+
+#[tokio::test]
+async fn test_cancelled_registrations_give_their_buy_in_back() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Refund Club").await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Refund Tournament").await;
+    let player = register_player(&schema, &app_state, tournament_id, "refund1@test.com").await;
+    register_player(&schema, &app_state, tournament_id, "refund2@test.com").await;
+
+    set_registration_status(&app_state, tournament_id, player, "cancelled").await;
+
+    let variables = Variables::from_json(json!({
+        "id": tournament_id.to_string(),
+        "tournamentId": tournament_id.to_string(),
+    }));
+    let response = execute_graphql(&schema, TOURNAMENT_ENTRIES, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["tournament"]["entryCount"], 1);
+    assert_eq!(data["tournament"]["prizePoolCents"], 5000);
+}
+
+#[tokio::test]
+async fn test_rake_and_staff_fees_stay_out_of_the_prize_pool() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims) = setup_rebuy_tournament(
+        &app_state,
+        "rake",
+        "rake_cents = 500, staff_fee_cents = 250, max_reentries = 1, max_rebuys = 1, rebuy_cents = 3000",
+    )
+    .await;
+    let alice = register_player(&schema, &app_state, tournament_id, "rakealice@test.com").await;
+    let bob = register_player(&schema, &app_state, tournament_id, "rakebob@test.com").await;
+
+    // Re-entries pay the fees again, rebuys go to the prize pool in full
+    set_registration_status(&app_state, tournament_id, alice, "busted").await;
+    let response = record_entry(&schema, &claims, tournament_id, alice, "REENTRY").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    set_registration_status(&app_state, tournament_id, bob, "seated").await;
+    let response = record_entry(&schema, &claims, tournament_id, bob, "REBUY").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let query = r#"
+        query Accounting($id: UUID!) {
+            tournament(id: $id) {
+                prizeContributionCents
+                prizePoolCents
+                accounting { paidCents prizePoolCents rakeCents staffFeeCents }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "id": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let tournament = &data["tournament"];
+    assert_eq!(tournament["prizeContributionCents"], 4250);
+    assert_eq!(tournament["prizePoolCents"], 3 * 4250 + 3000);
+    assert_eq!(
+        tournament["accounting"],
+        json!({
+            "paidCents": 3 * 5000 + 3000,
+            "prizePoolCents": 3 * 4250 + 3000,
+            "rakeCents": 3 * 500,
+            "staffFeeCents": 3 * 250,
+        })
+    );
+
+    // Changing the price before the start updates the buy-ins already taken
+    sqlx::query("UPDATE tournaments SET rake_cents = 1000 WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    let prize_pool: i32 = sqlx::query_scalar("SELECT tournament_prize_pool($1)")
+        .bind(tournament_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap();
+    assert_eq!(prize_pool, 2 * 3750 + 4250 + 3000);
+
+    // Payouts share the prize pool, not the fees
+    let payout = TournamentPayoutRepo::new(app_state.db.clone())
+        .recalculate(tournament_id)
+        .await
+        .unwrap()
+        .expect("Payouts should be calculated");
+    assert_eq!(payout.player_count, 3);
+    assert_eq!(payout.total_prize_pool, prize_pool);
+
+    // The leaderboard counts everything a player paid, fees included
+    sqlx::query(
+        "INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents) VALUES ($1, $2, 1, 12000)",
+    )
+    .bind(tournament_id)
+    .bind(bob)
+    .execute(&app_state.db)
+    .await
+    .unwrap();
+
+    let club_id: Uuid = sqlx::query_scalar("SELECT club_id FROM tournaments WHERE id = $1")
+        .bind(tournament_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap();
+    let query = r#"
+        query Leaderboard($clubId: UUID) {
+            leaderboard(clubId: $clubId) {
+                entries { user { id } totalBuyIns roiPercentage }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let entry = |user_id: Uuid| {
+        data["leaderboard"]["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["user"]["id"] == user_id.to_string())
+            .cloned()
+            .unwrap()
+    };
+    assert_eq!(entry(alice)["totalBuyIns"], 10000);
+    assert_eq!(entry(alice)["roiPercentage"], -100.0);
+    assert_eq!(entry(bob)["totalBuyIns"], 8000);
+    assert_eq!(entry(bob)["roiPercentage"], 50.0);
+}
+
+#[tokio::test]
+async fn test_guarantee_overlay_is_covered_by_the_club() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, _) =
+        setup_rebuy_tournament(&app_state, "guarantee", "guarantee_cents = 50000").await;
+    register_player(&schema, &app_state, tournament_id, "guarantee1@test.com").await;
+    register_player(&schema, &app_state, tournament_id, "guarantee2@test.com").await;
+
+    TournamentPayoutRepo::new(app_state.db.clone())
+        .recalculate(tournament_id)
+        .await
+        .unwrap()
+        .expect("Payouts should be calculated");
+
+    let query = r#"
+        query Payout($tournamentId: ID!) {
+            tournamentPayout(tournamentId: $tournamentId) {
+                totalPrizePool
+                collectedPrizePool
+                overlayCents
+                positions { amountCents }
+            }
+        }
+    "#;
+    let payout = |schema: Schema| async move {
+        let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+        let response = execute_graphql(&schema, query, Some(variables), None).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()["tournamentPayout"].clone()
+    };
+
+    // Two buy-ins don't cover the guarantee, the club pays the rest
+    let data = payout(schema.clone()).await;
+    assert_eq!(data["totalPrizePool"], 50000);
+    assert_eq!(data["collectedPrizePool"], 10000);
+    assert_eq!(data["overlayCents"], 40000);
+    let paid_out: i64 = data["positions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|position| position["amountCents"].as_i64().unwrap())
+        .sum();
+    assert!(paid_out > 10000 && paid_out <= 50000);
+
+    // Lowering the guarantee below the collected pool removes the overlay
+    sqlx::query("UPDATE tournaments SET guarantee_cents = 5000 WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    let data = payout(schema.clone()).await;
+    assert_eq!(data["totalPrizePool"], 10000);
+    assert_eq!(data["overlayCents"], 0);
+}
@@ -69,6 +69,7 @@ async fn test_tournament_live_status_enum() {
                     | "BREAK"
                     | "FINAL_TABLE"
                     | "FINISHED"
+                    | "CANCELLED"
             ),
             "liveStatus should be a valid enum value, got: {}",
             status_str
@@ -171,7 +172,10 @@ async fn test_tournament_business_status_vs_live_status() {
 
         // Verify both status fields are valid
         assert!(
-            matches!(status, "UPCOMING" | "IN_PROGRESS" | "COMPLETED"),
+            matches!(
+                status,
+                "UPCOMING" | "IN_PROGRESS" | "COMPLETED" | "CANCELLED"
+            ),
             "Business status should be a valid enum value, got: {}",
             status
         );
@@ -186,6 +190,7 @@ async fn test_tournament_business_status_vs_live_status() {
                     | "BREAK"
                     | "FINAL_TABLE"
                     | "FINISHED"
+                    | "CANCELLED"
             ),
             "Live status should be a valid enum value, got: {}",
             live_status
@@ -211,6 +216,12 @@ async fn test_tournament_business_status_vs_live_status() {
                     "FINISHED should map to COMPLETED business status"
                 );
             }
+            "CANCELLED" => {
+                assert_eq!(
+                    status, "CANCELLED",
+                    "CANCELLED should map to CANCELLED business status"
+                );
+            }
             _ => panic!("Unknown live status: {}", live_status),
         }
 
@@ -0,0 +1,204 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use serde_json::json;
+use uuid::Uuid;
+
+const LEADERBOARD: &str = r#"
+    query Leaderboard($clubId: UUID, $limit: Int, $offset: Int) {
+        leaderboard(clubId: $clubId, limit: $limit, offset: $offset) {
+            entries {
+                user { id }
+                rank
+                denseRank
+                points
+            }
+            totalPlayers
+            hasNextPage
+        }
+    }
+"#;
+
+const MY_LEADERBOARD_POSITION: &str = r#"
+    query MyLeaderboardPosition($clubId: UUID, $around: Int) {
+        myLeaderboardPosition(clubId: $clubId, around: $around) {
+            entry { user { id } rank points }
+            above { rank points }
+            below { rank points }
+            totalPlayers
+        }
+    }
+"#;
+
+/// Creates a club whose only tournament gave the players these points, returning the
+/// club and the players' claims in the same order
+async fn setup_leaderboard(
+    app_state: &api::AppState,
+    prefix: &str,
+    points: &[i32],
+) -> (Uuid, Vec<(Uuid, api::auth::Claims)>) {
+    let club_id = create_test_club(app_state, &format!("{} Club", prefix)).await;
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    let mut players = Vec::new();
+    for (index, points) in points.iter().enumerate() {
+        let (user_id, claims) = create_test_user(
+            app_state,
+            &format!("{}player{}@test.com", prefix, index),
+            "player",
+        )
+        .await;
+        sqlx::query(
+            "INSERT INTO tournament_registrations (tournament_id, user_id) VALUES ($1, $2)",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to register player");
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents, points)
+            VALUES ($1, $2, $3, 0, $4)
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(index as i32 + 1)
+        .bind(points)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to create result");
+        players.push((user_id, claims));
+    }
+
+    (club_id, players)
+}
+
+fn ranks(entries: &serde_json::Value, field: &str) -> Vec<i64> {
+    entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry[field].as_f64().unwrap() as i64)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_leaderboard_pages_share_ranks_on_ties() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (club_id, _) = setup_leaderboard(&app_state, "lbpages", &[30, 20, 20, 10, 5]).await;
+
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string(), "limit": 2 }));
+    let response = execute_graphql(&schema, LEADERBOARD, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(ranks(&data["leaderboard"]["entries"], "rank"), vec![1, 2]);
+    assert_eq!(data["leaderboard"]["totalPlayers"], 5);
+    assert_eq!(data["leaderboard"]["hasNextPage"], true);
+
+    // The second player on 20 points shares 2nd place; 10 points is 4th, or 3rd densely
+    let variables = Variables::from_json(json!({
+        "clubId": club_id.to_string(),
+        "limit": 2,
+        "offset": 2,
+    }));
+    let response = execute_graphql(&schema, LEADERBOARD, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(ranks(&data["leaderboard"]["entries"], "rank"), vec![2, 4]);
+    assert_eq!(
+        ranks(&data["leaderboard"]["entries"], "denseRank"),
+        vec![2, 3]
+    );
+    assert_eq!(
+        ranks(&data["leaderboard"]["entries"], "points"),
+        vec![20, 10]
+    );
+    assert_eq!(data["leaderboard"]["hasNextPage"], true);
+
+    // Past the end the total is still known
+    let variables = Variables::from_json(json!({
+        "clubId": club_id.to_string(),
+        "offset": 10,
+    }));
+    let response = execute_graphql(&schema, LEADERBOARD, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert!(data["leaderboard"]["entries"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+    assert_eq!(data["leaderboard"]["totalPlayers"], 5);
+    assert_eq!(data["leaderboard"]["hasNextPage"], false);
+}
+
+#[tokio::test]
+async fn test_my_leaderboard_position() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (club_id, players) = setup_leaderboard(&app_state, "lbmine", &[30, 20, 20, 10, 5]).await;
+    let (user_id, claims) = players[3].clone();
+
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string(), "around": 1 }));
+    let response = execute_graphql(
+        &schema,
+        MY_LEADERBOARD_POSITION,
+        Some(variables),
+        Some(claims),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let position = &data["myLeaderboardPosition"];
+    assert_eq!(position["entry"]["user"]["id"], user_id.to_string());
+    assert_eq!(position["entry"]["rank"], 4);
+    assert_eq!(ranks(&position["above"], "rank"), vec![2]);
+    assert_eq!(ranks(&position["below"], "rank"), vec![5]);
+    assert_eq!(position["totalPlayers"], 5);
+
+    // The leader has nobody above
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        MY_LEADERBOARD_POSITION,
+        Some(variables),
+        Some(players[0].1.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let position = &data["myLeaderboardPosition"];
+    assert_eq!(position["entry"]["rank"], 1);
+    assert!(position["above"].as_array().unwrap().is_empty());
+    assert_eq!(ranks(&position["below"], "points"), vec![20, 20]);
+
+    // Players who haven't played aren't on the leaderboard
+    let (_, newcomer_claims) = create_test_user(&app_state, "lbnewcomer@test.com", "player").await;
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        MY_LEADERBOARD_POSITION,
+        Some(variables),
+        Some(newcomer_claims),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert!(data["myLeaderboardPosition"]["entry"].is_null());
+    assert!(data["myLeaderboardPosition"]["above"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+    assert_eq!(data["myLeaderboardPosition"]["totalPlayers"], 5);
+
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, MY_LEADERBOARD_POSITION, Some(variables), None).await;
+    assert!(!response.errors.is_empty());
+}
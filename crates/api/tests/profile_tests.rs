@@ -0,0 +1,354 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use chrono::{DateTime, TimeZone, Utc};
+use common::*;
+use serde_json::json;
+use uuid::Uuid;
+
+const PLAYER_PROFILE: &str = r#"
+    query PlayerProfile($userId: ID!) {
+        playerProfile(userId: $userId) {
+            winningsHidden
+            user { id firstName }
+            lifetime {
+                tournamentsPlayed
+                totalItm
+                wins
+                points
+                totalBuyIns
+                totalWinnings
+                totalBountyWinnings
+                netProfit
+            }
+            clubs { clubName statistics { tournamentsPlayed netProfit } }
+            profitOverTime { cumulativeProfitCents }
+            biggestCashes { prizeCents }
+            finishPositions { position count }
+            pointsByYear { year points tournamentsPlayed }
+            recentResults { tournamentName finalPosition prizeCents }
+            topRivals { opponent { id } }
+        }
+    }
+"#;
+
+const UPDATE_MY_PRIVACY_SETTINGS: &str = r#"
+    mutation UpdateMyPrivacySettings($input: UpdatePrivacySettingsInput!) {
+        updateMyPrivacySettings(input: $input) {
+            hideWinnings
+        }
+    }
+"#;
+
+/// Gives a player a result in a new tournament of the club. Registering pays the 50.00
+/// buy-in, each rebuy another 50.00.
+#[allow(clippy::too_many_arguments)]
+async fn add_result(
+    app_state: &api::AppState,
+    club_id: Uuid,
+    name: &str,
+    start_time: DateTime<Utc>,
+    user_id: Uuid,
+    registered: bool,
+    rebuys: usize,
+    position: i32,
+    prize_cents: i32,
+    bounty_cents: i32,
+    points: i32,
+) -> Uuid {
+    let tournament_id = create_test_tournament(app_state, club_id, name).await;
+    sqlx::query("UPDATE tournaments SET start_time = $2 WHERE id = $1")
+        .bind(tournament_id)
+        .bind(start_time)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to move tournament");
+
+    if registered {
+        let registration_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tournament_registrations (tournament_id, user_id) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .fetch_one(&app_state.db)
+        .await
+        .expect("Failed to register player");
+        for _ in 0..rebuys {
+            sqlx::query(
+                r#"
+                INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type, amount_cents)
+                VALUES ($1, $2, $3, 'rebuy', 5000)
+                "#,
+            )
+            .bind(tournament_id)
+            .bind(registration_id)
+            .bind(user_id)
+            .execute(&app_state.db)
+            .await
+            .expect("Failed to record rebuy");
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents, bounty_cents, points)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(user_id)
+    .bind(position)
+    .bind(prize_cents)
+    .bind(bounty_cents)
+    .bind(points)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create result");
+
+    tournament_id
+}
+
+fn values(items: &serde_json::Value, field: &str) -> Vec<serde_json::Value> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item[field].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_player_profile_and_hidden_winnings() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let home_club = create_test_club(&app_state, "Profile Home Club").await;
+    let away_club = create_test_club(&app_state, "Profile Away Club").await;
+    let (player_id, player_claims) =
+        create_test_user(&app_state, "profileplayer@test.com", "player").await;
+    let (rival_id, rival_claims) =
+        create_test_user(&app_state, "profilerival@test.com", "player").await;
+
+    let opener = add_result(
+        &app_state,
+        home_club,
+        "Profile Opener",
+        Utc.with_ymd_and_hms(2024, 6, 1, 19, 0, 0).unwrap(),
+        player_id,
+        true,
+        0,
+        1,
+        20000,
+        1000,
+        30,
+    )
+    .await;
+    add_result(
+        &app_state,
+        home_club,
+        "Profile Rebuy Night",
+        Utc.with_ymd_and_hms(2025, 2, 1, 19, 0, 0).unwrap(),
+        player_id,
+        true,
+        1,
+        4,
+        0,
+        0,
+        10,
+    )
+    .await;
+    add_result(
+        &app_state,
+        away_club,
+        "Profile Away Game",
+        Utc.with_ymd_and_hms(2025, 3, 1, 19, 0, 0).unwrap(),
+        player_id,
+        false,
+        0,
+        2,
+        8000,
+        0,
+        20,
+    )
+    .await;
+    sqlx::query(
+        "INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents) VALUES ($1, $2, 2, 0)",
+    )
+    .bind(opener)
+    .bind(rival_id)
+    .execute(&app_state.db)
+    .await
+    .unwrap();
+
+    let variables = Variables::from_json(json!({ "userId": player_id.to_string() }));
+    let response = execute_graphql(&schema, PLAYER_PROFILE, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let profile = &data["playerProfile"];
+    assert_eq!(profile["winningsHidden"], false);
+    assert_eq!(profile["user"]["id"], player_id.to_string());
+    assert_eq!(profile["user"]["firstName"], "Test");
+
+    // 15000 paid for 28000 in prizes and 1000 in bounties
+    let lifetime = &profile["lifetime"];
+    assert_eq!(lifetime["tournamentsPlayed"], 3);
+    assert_eq!(lifetime["totalItm"], 2);
+    assert_eq!(lifetime["wins"], 1);
+    assert_eq!(lifetime["points"], 60);
+    assert_eq!(lifetime["totalBuyIns"], 15000);
+    assert_eq!(lifetime["totalWinnings"], 28000);
+    assert_eq!(lifetime["totalBountyWinnings"], 1000);
+    assert_eq!(lifetime["netProfit"], 14000);
+
+    assert_eq!(
+        values(&profile["clubs"], "clubName"),
+        vec!["Profile Home Club", "Profile Away Club"]
+    );
+    assert_eq!(profile["clubs"][0]["statistics"]["netProfit"], 6000);
+    assert_eq!(
+        values(&profile["profitOverTime"], "cumulativeProfitCents"),
+        vec![16000, 6000, 14000]
+    );
+    assert_eq!(
+        values(&profile["biggestCashes"], "prizeCents"),
+        vec![20000, 8000]
+    );
+    assert_eq!(
+        values(&profile["finishPositions"], "position"),
+        vec![1, 2, 4]
+    );
+    assert_eq!(values(&profile["pointsByYear"], "year"), vec![2024, 2025]);
+    assert_eq!(values(&profile["pointsByYear"], "points"), vec![30, 30]);
+    assert_eq!(
+        profile["recentResults"][0]["tournamentName"],
+        "Profile Away Game"
+    );
+    assert_eq!(
+        profile["topRivals"][0]["opponent"]["id"],
+        rival_id.to_string()
+    );
+
+    let variables = Variables::from_json(json!({ "input": { "hideWinnings": true } }));
+    let response = execute_graphql(
+        &schema,
+        UPDATE_MY_PRIVACY_SETTINGS,
+        Some(variables),
+        Some(player_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["updateMyPrivacySettings"]["hideWinnings"], true);
+
+    // Other players no longer see amounts, the rest of the profile stays public
+    let variables = Variables::from_json(json!({ "userId": player_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        PLAYER_PROFILE,
+        Some(variables),
+        Some(rival_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let profile = &data["playerProfile"];
+    assert_eq!(profile["winningsHidden"], true);
+    assert!(profile["lifetime"]["netProfit"].is_null());
+    assert!(profile["lifetime"]["totalWinnings"].is_null());
+    assert!(profile["clubs"][0]["statistics"]["netProfit"].is_null());
+    assert!(profile["profitOverTime"].as_array().unwrap().is_empty());
+    assert!(profile["biggestCashes"].as_array().unwrap().is_empty());
+    assert!(profile["recentResults"][0]["prizeCents"].is_null());
+    assert_eq!(profile["lifetime"]["wins"], 1);
+    assert_eq!(profile["recentResults"][0]["finalPosition"], 2);
+
+    // Nor on the leaderboard
+    let leaderboard = r#"
+        query Leaderboard($clubId: UUID) {
+            leaderboard(clubId: $clubId) {
+                entries { user { id } points totalBuyIns totalWinnings netProfit roiPercentage }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "clubId": home_club.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        leaderboard,
+        Some(variables.clone()),
+        Some(rival_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let entry = &data["leaderboard"]["entries"][0];
+    assert_eq!(entry["user"]["id"], player_id.to_string());
+    assert_eq!(entry["points"], 40.0);
+    assert!(entry["totalBuyIns"].is_null());
+    assert!(entry["totalWinnings"].is_null());
+    assert!(entry["netProfit"].is_null());
+    assert!(entry["roiPercentage"].is_null());
+
+    let response = execute_graphql(
+        &schema,
+        leaderboard,
+        Some(variables),
+        Some(player_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["leaderboard"]["entries"][0]["totalWinnings"], 20000);
+
+    // The player still sees their own winnings
+    let variables = Variables::from_json(json!({ "userId": player_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        PLAYER_PROFILE,
+        Some(variables),
+        Some(player_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["playerProfile"]["winningsHidden"], true);
+    assert_eq!(data["playerProfile"]["lifetime"]["netProfit"], 14000);
+
+    let query = "query { myPrivacySettings { hideWinnings } }";
+    let response = execute_graphql(&schema, query, None, Some(player_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["myPrivacySettings"]["hideWinnings"], true);
+}
+
+#[tokio::test]
+async fn test_player_profile_without_results() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (user_id, _) = create_test_user(&app_state, "profilenewcomer@test.com", "player").await;
+
+    let variables = Variables::from_json(json!({ "userId": user_id.to_string() }));
+    let response = execute_graphql(&schema, PLAYER_PROFILE, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let profile = &data["playerProfile"];
+    assert_eq!(profile["lifetime"]["tournamentsPlayed"], 0);
+    assert_eq!(profile["lifetime"]["netProfit"], 0);
+    assert!(profile["recentResults"].as_array().unwrap().is_empty());
+
+    let variables = Variables::from_json(json!({ "userId": Uuid::new_v4().to_string() }));
+    let response = execute_graphql(&schema, PLAYER_PROFILE, Some(variables), None).await;
+    assert!(response.errors[0].message.contains("not found"));
+
+    let response = execute_graphql(
+        &schema,
+        UPDATE_MY_PRIVACY_SETTINGS,
+        Some(Variables::from_json(
+            json!({ "input": { "hideWinnings": true } }),
+        )),
+        None,
+    )
+    .await;
+    assert!(!response.errors.is_empty());
+}
@@ -0,0 +1,480 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use infra::repos::TournamentRegistrationRepo;
+use serde_json::json;
+use uuid::Uuid;
+
+const REGISTER: &str = r#"
+    mutation RegisterForTournament($input: RegisterForTournamentInput!) {
+        registerForTournament(input: $input) {
+            id
+            status
+            waitlistPosition
+        }
+    }
+"#;
+
+const WAITLIST: &str = r#"
+    query TournamentWaitlist($tournamentId: ID!) {
+        tournamentWaitlist(tournamentId: $tournamentId) {
+            position
+            player {
+                user { id }
+            }
+        }
+    }
+"#;
+
+async fn set_seat_cap(app_state: &api::AppState, tournament_id: Uuid, seat_cap: i32) {
+    sqlx::query("UPDATE tournaments SET seat_cap = $2 WHERE id = $1")
+        .bind(tournament_id)
+        .bind(seat_cap)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to set seat cap");
+}
+
+/// Registers the given players in order and returns their user ids and registrations
+async fn register_players(
+    schema: &async_graphql::Schema<
+        api::gql::QueryRoot,
+        api::gql::MutationRoot,
+        api::gql::SubscriptionRoot,
+    >,
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+    emails: &[&str],
+) -> Vec<(Uuid, serde_json::Value)> {
+    let mut registrations = Vec::new();
+    for email in emails {
+        let (user_id, claims) = create_test_user(app_state, email, "player").await;
+        let variables = Variables::from_json(json!({
+            "input": { "tournamentId": tournament_id.to_string() }
+        }));
+        let response = execute_graphql(schema, REGISTER, Some(variables), Some(claims)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        registrations.push((user_id, data["registerForTournament"].clone()));
+    }
+    registrations
+}
+
+async fn waitlisted_users(
+    schema: &async_graphql::Schema<
+        api::gql::QueryRoot,
+        api::gql::MutationRoot,
+        api::gql::SubscriptionRoot,
+    >,
+    tournament_id: Uuid,
+) -> Vec<String> {
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(schema, WAITLIST, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    data["tournamentWaitlist"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            assert_eq!(entry["position"], i as i64 + 1);
+            entry["player"]["user"]["id"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_registrations_beyond_seat_cap_are_waitlisted() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Waitlist Club").await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Waitlist Tournament").await;
+    set_seat_cap(&app_state, tournament_id, 2).await;
+
+    let registrations = register_players(
+        &schema,
+        &app_state,
+        tournament_id,
+        &[
+            "waitlist1@test.com",
+            "waitlist2@test.com",
+            "waitlist3@test.com",
+            "waitlist4@test.com",
+        ],
+    )
+    .await;
+
+    let statuses: Vec<_> = registrations
+        .iter()
+        .map(|(_, r)| r["status"].clone())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec!["REGISTERED", "REGISTERED", "WAITLISTED", "WAITLISTED"]
+    );
+    assert!(registrations[1].1["waitlistPosition"].is_null());
+    assert_eq!(registrations[2].1["waitlistPosition"], 1);
+    assert_eq!(registrations[3].1["waitlistPosition"], 2);
+
+    assert_eq!(
+        waitlisted_users(&schema, tournament_id).await,
+        vec![
+            registrations[2].0.to_string(),
+            registrations[3].0.to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_freed_seats_promote_the_waitlist_in_order() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Promotion Club").await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Promotion Tournament").await;
+    set_seat_cap(&app_state, tournament_id, 2).await;
+
+    let registrations = register_players(
+        &schema,
+        &app_state,
+        tournament_id,
+        &[
+            "promote1@test.com",
+            "promote2@test.com",
+            "promote3@test.com",
+            "promote4@test.com",
+        ],
+    )
+    .await;
+    let registration_id =
+        |i: usize| Uuid::parse_str(registrations[i].1["id"].as_str().unwrap()).unwrap();
+
+    let repo = TournamentRegistrationRepo::new(app_state.db.clone());
+
+    // A cancellation lets the first waitlisted player in
+    let change = repo
+        .update_status(registration_id(0), "registered", "cancelled", None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.registration.status, "cancelled");
+    assert_eq!(change.promoted.len(), 1);
+    assert_eq!(change.promoted[0].id, registration_id(2));
+    assert_eq!(change.promoted[0].status, "registered");
+    assert_eq!(
+        waitlisted_users(&schema, tournament_id).await,
+        vec![registrations[3].0.to_string()]
+    );
+
+    // Waitlisted players leaving don't free a seat
+    let (_, late) =
+        &register_players(&schema, &app_state, tournament_id, &["promote5@test.com"]).await[0];
+    let late_id = Uuid::parse_str(late["id"].as_str().unwrap()).unwrap();
+    let change = repo
+        .update_status(late_id, "waitlisted", "cancelled", None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(change.promoted.is_empty());
+
+    // Neither does moving between seated statuses
+    let change = repo
+        .update_status(registration_id(1), "registered", "checked_in", None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(change.promoted.is_empty());
+
+    // A no-show does
+    let change = repo
+        .update_status(registration_id(1), "checked_in", "no_show", None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.promoted.len(), 1);
+    assert_eq!(change.promoted[0].id, registration_id(3));
+    assert!(waitlisted_users(&schema, tournament_id).await.is_empty());
+
+    // A change based on a status that moved on in the meantime is refused
+    let change = repo
+        .update_status(registration_id(1), "checked_in", "seated", None)
+        .await
+        .unwrap();
+    assert!(change.is_none());
+    let registration = repo.get_by_id(registration_id(1)).await.unwrap().unwrap();
+    assert_eq!(registration.status, "no_show");
+}
+
+#[tokio::test]
+async fn test_raising_seat_cap_promotes_waitlist() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "waitlistmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Seat Cap Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Seat Cap Tournament").await;
+    set_seat_cap(&app_state, tournament_id, 1).await;
+
+    let registrations = register_players(
+        &schema,
+        &app_state,
+        tournament_id,
+        &[
+            "seatcap1@test.com",
+            "seatcap2@test.com",
+            "seatcap3@test.com",
+        ],
+    )
+    .await;
+
+    let query = r#"
+        mutation UpdateTournament($input: UpdateTournamentInput!) {
+            updateTournament(input: $input) { seatCap }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "input": { "id": tournament_id.to_string(), "seatCap": 2 }
+    }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    assert_eq!(
+        waitlisted_users(&schema, tournament_id).await,
+        vec![registrations[2].0.to_string()]
+    );
+}
+
+const UNREGISTER: &str = r#"
+    mutation Unregister($tournamentId: ID!) {
+        unregisterFromTournament(tournamentId: $tournamentId) {
+            status
+        }
+    }
+"#;
+
+const UPDATE_STATUS: &str = r#"
+    mutation UpdateRegistrationStatus($input: UpdateRegistrationStatusInput!) {
+        updateRegistrationStatus(input: $input) {
+            status
+            notes
+        }
+    }
+"#;
+
+#[tokio::test]
+async fn test_unregister_from_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Unregister Club").await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Unregister Tournament").await;
+    set_seat_cap(&app_state, tournament_id, 1).await;
+    sqlx::query("UPDATE tournaments SET start_time = NOW() + INTERVAL '2 hours' WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to move start time");
+
+    let registrations = register_players(
+        &schema,
+        &app_state,
+        tournament_id,
+        &["unregister1@test.com", "unregister2@test.com"],
+    )
+    .await;
+    let (_, claims) = create_test_user(&app_state, "unregister1@test.com", "player").await;
+
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, UNREGISTER, Some(variables), Some(claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["unregisterFromTournament"]["status"], "CANCELLED");
+
+    // The waitlisted player got the seat
+    assert!(waitlisted_users(&schema, tournament_id).await.is_empty());
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(registrations[1].0)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(status, "registered");
+
+    // Players who left can register again, behind the players already in
+    let register = || {
+        Variables::from_json(json!({
+            "input": { "tournamentId": tournament_id.to_string() }
+        }))
+    };
+    let (_, claims) = create_test_user(&app_state, "unregister1@test.com", "player").await;
+    let response = execute_graphql(&schema, REGISTER, Some(register()), Some(claims.clone())).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["registerForTournament"]["id"],
+        registrations[0].1["id"]
+    );
+    assert_eq!(data["registerForTournament"]["status"], "WAITLISTED");
+
+    // But only once
+    let response = execute_graphql(&schema, REGISTER, Some(register()), Some(claims)).await;
+    assert!(
+        response.errors[0].message.contains("already registered"),
+        "{:?}",
+        response.errors
+    );
+
+    // Within the cutoff (an hour by default) players can't leave anymore
+    sqlx::query("UPDATE tournaments SET start_time = NOW() + INTERVAL '30 minutes' WHERE id = $1")
+        .bind(tournament_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to move start time");
+    let (_, claims) = create_test_user(&app_state, "unregister2@test.com", "player").await;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, UNREGISTER, Some(variables), Some(claims)).await;
+    assert!(!response.errors.is_empty());
+    assert!(response.errors[0].message.contains("60 minutes"));
+}
+
+#[tokio::test]
+async fn test_update_registration_status_transitions() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "statusmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Status Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Status Tournament").await;
+
+    let registrations = register_players(
+        &schema,
+        &app_state,
+        tournament_id,
+        &["statusplayer@test.com"],
+    )
+    .await;
+    let (user_id, _) = registrations[0];
+
+    let update = |status: &str| {
+        Variables::from_json(json!({
+            "input": {
+                "tournamentId": tournament_id.to_string(),
+                "userId": user_id.to_string(),
+                "status": status,
+                "notes": format!("Moved to {}", status)
+            }
+        }))
+    };
+
+    // Players can't skip straight to busted
+    let response = execute_graphql(
+        &schema,
+        UPDATE_STATUS,
+        Some(update("BUSTED")),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(!response.errors.is_empty());
+    assert!(response.errors[0].message.contains("allowed: CheckedIn"));
+
+    for status in ["CHECKED_IN", "SEATED", "BUSTED"] {
+        let response = execute_graphql(
+            &schema,
+            UPDATE_STATUS,
+            Some(update(status)),
+            Some(manager_claims.clone()),
+        )
+        .await;
+        assert!(
+            response.errors.is_empty(),
+            "{}: {:?}",
+            status,
+            response.errors
+        );
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["updateRegistrationStatus"]["status"], status);
+        assert_eq!(
+            data["updateRegistrationStatus"]["notes"],
+            format!("Moved to {}", status)
+        );
+    }
+
+    // Busted is final
+    let response = execute_graphql(
+        &schema,
+        UPDATE_STATUS,
+        Some(update("REGISTERED")),
+        Some(manager_claims),
+    )
+    .await;
+    assert!(!response.errors.is_empty());
+    assert!(response.errors[0].message.contains("is final"));
+
+    // Players can't change statuses themselves
+    let (_, player_claims) = create_test_user(&app_state, "statusplayer@test.com", "player").await;
+    let response = execute_graphql(
+        &schema,
+        UPDATE_STATUS,
+        Some(update("NO_SHOW")),
+        Some(player_claims),
+    )
+    .await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_cancelled_tournaments_take_no_registrations() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Cancelled Registration Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Cancelled Registration Tournament").await;
+    infra::repos::TournamentRepo::new(app_state.db.clone())
+        .cancel(tournament_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (user_id, claims) =
+        create_test_user(&app_state, "cancelledregistration@test.com", "player").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string() }
+    }));
+    let response = execute_graphql(&schema, REGISTER, Some(variables), Some(claims)).await;
+    assert!(
+        response.errors[0]
+            .message
+            .contains("don't take registrations"),
+        "{:?}",
+        response.errors
+    );
+
+    // Nothing was recorded, not even a buy-in
+    let registrations: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(tournament_id)
+    .bind(user_id)
+    .fetch_one(&app_state.db)
+    .await
+    .unwrap();
+    assert_eq!(registrations, 0);
+    let entries: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tournament_entries WHERE tournament_id = $1")
+            .bind(tournament_id)
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(entries, 0);
+}
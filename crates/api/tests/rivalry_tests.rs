@@ -0,0 +1,259 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use chrono::{DateTime, Duration, Utc};
+use common::*;
+use serde_json::json;
+use uuid::Uuid;
+
+const HEAD_TO_HEAD: &str = r#"
+    query HeadToHead($userA: ID!, $userB: ID!) {
+        headToHead(userA: $userA, userB: $userB) {
+            opponentId
+            sharedTournaments
+            userFinishedHigher
+            opponentFinishedHigher
+            hoursTogether
+        }
+    }
+"#;
+
+async fn create_table(app_state: &api::AppState, club_id: Uuid, table_number: i32) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO club_tables (club_id, table_number, max_seats) VALUES ($1, $2, 9) RETURNING id",
+    )
+    .bind(club_id)
+    .bind(table_number)
+    .fetch_one(&app_state.db)
+    .await
+    .expect("Failed to create club table")
+}
+
+async fn add_result(app_state: &api::AppState, tournament_id: Uuid, user_id: Uuid, position: i32) {
+    sqlx::query(
+        r#"
+        INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents)
+        VALUES ($1, $2, $3, 0)
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(user_id)
+    .bind(position)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create result");
+}
+
+/// Seats a player at a table between two times
+async fn add_seat(
+    app_state: &api::AppState,
+    tournament_id: Uuid,
+    club_table_id: Uuid,
+    user_id: Uuid,
+    seat_number: i32,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO table_seat_assignments (
+            tournament_id, club_table_id, user_id, seat_number, is_current, assigned_at, unassigned_at
+        )
+        VALUES ($1, $2, $3, $4, false, $5, $6)
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(club_table_id)
+    .bind(user_id)
+    .bind(seat_number)
+    .bind(from)
+    .bind(to)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to seat player");
+}
+
+#[tokio::test]
+async fn test_head_to_head_and_top_rivals() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Rivalry Club").await;
+    let table_one = create_table(&app_state, club_id, 1).await;
+    let table_two = create_table(&app_state, club_id, 2).await;
+    let first = create_test_tournament(&app_state, club_id, "Rivalry Opener").await;
+    let second = create_test_tournament(&app_state, club_id, "Rivalry Rematch").await;
+
+    let (alice, _) = create_test_user(&app_state, "rivalalice@test.com", "player").await;
+    let (bob, _) = create_test_user(&app_state, "rivalbob@test.com", "player").await;
+    let (carol, _) = create_test_user(&app_state, "rivalcarol@test.com", "player").await;
+    let (stranger, _) = create_test_user(&app_state, "rivalstranger@test.com", "player").await;
+
+    add_result(&app_state, first, alice, 1).await;
+    add_result(&app_state, first, bob, 2).await;
+    add_result(&app_state, first, carol, 3).await;
+    add_result(&app_state, second, bob, 1).await;
+    add_result(&app_state, second, alice, 2).await;
+
+    // Alice and Bob share table one for 1.5 hours, then 30 minutes in the rematch where
+    // Carol joins Alice for 15 minutes. Carol's first table is never Alice's.
+    let start = Utc::now() - Duration::days(2);
+    let hours = |h: f64| start + Duration::minutes((h * 60.0) as i64);
+    add_seat(
+        &app_state,
+        first,
+        table_one,
+        alice,
+        1,
+        hours(0.0),
+        hours(3.0),
+    )
+    .await;
+    add_seat(&app_state, first, table_one, bob, 2, hours(1.0), hours(2.5)).await;
+    add_seat(
+        &app_state,
+        first,
+        table_two,
+        carol,
+        1,
+        hours(0.0),
+        hours(1.0),
+    )
+    .await;
+    add_seat(
+        &app_state,
+        second,
+        table_one,
+        alice,
+        1,
+        hours(24.0),
+        hours(25.0),
+    )
+    .await;
+    add_seat(
+        &app_state,
+        second,
+        table_one,
+        bob,
+        2,
+        hours(24.5),
+        hours(25.0),
+    )
+    .await;
+    add_seat(
+        &app_state,
+        second,
+        table_one,
+        carol,
+        3,
+        hours(24.0),
+        hours(24.25),
+    )
+    .await;
+
+    for (opponent, shared, higher, lower, together) in [
+        (bob, 2, 1, 1, 2.0),
+        (carol, 1, 1, 0, 0.25),
+        (stranger, 0, 0, 0, 0.0),
+    ] {
+        let variables = Variables::from_json(json!({
+            "userA": alice.to_string(),
+            "userB": opponent.to_string(),
+        }));
+        let response = execute_graphql(&schema, HEAD_TO_HEAD, Some(variables), None).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let head_to_head = &data["headToHead"];
+        assert_eq!(head_to_head["opponentId"], opponent.to_string());
+        assert_eq!(head_to_head["sharedTournaments"], shared);
+        assert_eq!(head_to_head["userFinishedHigher"], higher);
+        assert_eq!(head_to_head["opponentFinishedHigher"], lower);
+        assert_eq!(head_to_head["hoursTogether"], together);
+    }
+
+    // From Carol's side, Alice finished higher
+    let variables = Variables::from_json(json!({
+        "userA": carol.to_string(),
+        "userB": alice.to_string(),
+    }));
+    let response = execute_graphql(&schema, HEAD_TO_HEAD, Some(variables), None).await;
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["headToHead"]["userFinishedHigher"], 0);
+    assert_eq!(data["headToHead"]["opponentFinishedHigher"], 1);
+
+    let variables = Variables::from_json(json!({
+        "userA": alice.to_string(),
+        "userB": alice.to_string(),
+    }));
+    let response = execute_graphql(&schema, HEAD_TO_HEAD, Some(variables), None).await;
+    assert!(!response.errors.is_empty());
+
+    let query = r#"
+        query TopRivals($userId: ID!) {
+            topRivals(userId: $userId) {
+                sharedTournaments
+                opponent { id }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "userId": alice.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let rivals: Vec<_> = data["topRivals"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rival| rival["opponent"]["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(rivals, vec![bob.to_string(), carol.to_string()]);
+}
+
+#[tokio::test]
+async fn test_open_seats_end_with_the_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let club_id = create_test_club(&app_state, "Open Seat Club").await;
+    let table = create_table(&app_state, club_id, 1).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Open Seat Finale").await;
+    let (alice, _) = create_test_user(&app_state, "openseatalice@test.com", "player").await;
+    let (bob, _) = create_test_user(&app_state, "openseatbob@test.com", "player").await;
+
+    // Both seats were never closed, the tournament ended an hour after they sat down
+    let start = Utc::now() - Duration::days(2);
+    for (seat_number, user_id) in [(1, alice), (2, bob)] {
+        sqlx::query(
+            r#"
+            INSERT INTO table_seat_assignments (
+                tournament_id, club_table_id, user_id, seat_number, is_current, assigned_at
+            )
+            VALUES ($1, $2, $3, $4, true, $5)
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(table)
+        .bind(user_id)
+        .bind(seat_number)
+        .bind(start)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to seat player");
+    }
+    sqlx::query("UPDATE tournaments SET live_status = 'finished', end_time = $2 WHERE id = $1")
+        .bind(tournament_id)
+        .bind(start + Duration::hours(1))
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to finish tournament");
+
+    let variables = Variables::from_json(json!({
+        "userA": alice.to_string(),
+        "userB": bob.to_string(),
+    }));
+    let response = execute_graphql(&schema, HEAD_TO_HEAD, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["headToHead"]["hoursTogether"], 1.0);
+}
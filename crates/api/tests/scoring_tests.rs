@@ -0,0 +1,214 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use infra::scoring::event_points;
+use serde_json::json;
+use uuid::Uuid;
+
+const CLUB_SCORING_SETTINGS: &str = r#"
+    query ClubScoringSettings($clubId: ID!) {
+        clubScoringSettings(clubId: $clubId) {
+            formula
+            cap
+            multiplier
+            updatedAt
+        }
+    }
+"#;
+
+const UPDATE_CLUB_SCORING_SETTINGS: &str = r#"
+    mutation UpdateClubScoringSettings($input: UpdateClubScoringSettingsInput!) {
+        updateClubScoringSettings(input: $input) {
+            formula
+            cap
+            multiplier
+            updatedAt
+        }
+    }
+"#;
+
+const FINISH_TOURNAMENT: &str = r#"
+    mutation UpdateTournamentStatus($input: UpdateTournamentStatusInput!) {
+        updateTournamentStatus(input: $input) {
+            id
+        }
+    }
+"#;
+
+/// Creates a tournament with three results (no points yet), returning its ID
+async fn create_tournament_with_results(
+    app_state: &api::AppState,
+    club_id: Uuid,
+    prefix: &str,
+) -> Uuid {
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    for position in 1..=3 {
+        let (user_id, _) = create_test_user(
+            app_state,
+            &format!("{}player{}@test.com", prefix, position),
+            "player",
+        )
+        .await;
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents)
+            VALUES ($1, $2, $3, 0)
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(position)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to create result");
+    }
+
+    tournament_id
+}
+
+/// Points of the results of a tournament, best finish first
+async fn points_by_position(app_state: &api::AppState, tournament_id: Uuid) -> Vec<i32> {
+    sqlx::query_scalar(
+        "SELECT points FROM tournament_results WHERE tournament_id = $1 ORDER BY final_position",
+    )
+    .bind(tournament_id)
+    .fetch_all(&app_state.db)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_finishing_a_tournament_scores_with_the_club_formula() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "scoringmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Scoring Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    // Clubs start with the authoritative formula
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, CLUB_SCORING_SETTINGS, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["clubScoringSettings"]["formula"], "AUTHORITATIVE");
+    assert_eq!(data["clubScoringSettings"]["cap"], 60);
+    assert!(data["clubScoringSettings"]["updatedAt"].is_null());
+
+    let tournament_id = create_tournament_with_results(&app_state, club_id, "scoringauth").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string(), "liveStatus": "FINISHED" }
+    }));
+    let response = execute_graphql(
+        &schema,
+        FINISH_TOURNAMENT,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(
+        points_by_position(&app_state, tournament_id).await,
+        (1..=3)
+            .map(|rank| event_points(3, rank, 50.0) as i32)
+            .collect::<Vec<_>>()
+    );
+
+    // Doubled linear points table
+    let variables = Variables::from_json(json!({
+        "input": {
+            "clubId": club_id.to_string(),
+            "formula": "LINEAR",
+            "cap": 100,
+            "multiplier": 2.0,
+        }
+    }));
+    let response = execute_graphql(
+        &schema,
+        UPDATE_CLUB_SCORING_SETTINGS,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["updateClubScoringSettings"]["formula"], "LINEAR");
+    assert_eq!(data["updateClubScoringSettings"]["cap"], 100);
+    assert_eq!(data["updateClubScoringSettings"]["multiplier"], 2.0);
+    assert!(!data["updateClubScoringSettings"]["updatedAt"].is_null());
+
+    let tournament_id = create_tournament_with_results(&app_state, club_id, "scoringlinear").await;
+    let variables = Variables::from_json(json!({
+        "input": { "tournamentId": tournament_id.to_string(), "liveStatus": "FINISHED" }
+    }));
+    let response = execute_graphql(
+        &schema,
+        FINISH_TOURNAMENT,
+        Some(variables),
+        Some(manager_claims),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(
+        points_by_position(&app_state, tournament_id).await,
+        vec![6, 4, 2]
+    );
+}
+
+#[tokio::test]
+async fn test_update_club_scoring_settings_validation_and_permissions() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "scoringvalidmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Scoring Validation Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let (_, other_manager_claims) =
+        create_test_user(&app_state, "scoringothermanager@test.com", "manager").await;
+    let (_, player_claims) = create_test_user(&app_state, "scoringplayer@test.com", "player").await;
+
+    for (input, message) in [
+        (json!({ "cap": 0 }), "Cap"),
+        (json!({ "multiplier": 0.0 }), "Multiplier"),
+        (json!({ "multiplier": -1.5 }), "Multiplier"),
+    ] {
+        let mut input = input;
+        input["clubId"] = json!(club_id.to_string());
+        input["formula"] = json!("FIELD_SIZE");
+        let variables = Variables::from_json(json!({ "input": input }));
+        let response = execute_graphql(
+            &schema,
+            UPDATE_CLUB_SCORING_SETTINGS,
+            Some(variables),
+            Some(manager_claims.clone()),
+        )
+        .await;
+        assert!(response.errors[0].message.contains(message));
+    }
+
+    for claims in [other_manager_claims, player_claims] {
+        let variables = Variables::from_json(json!({
+            "input": { "clubId": club_id.to_string(), "formula": "FIELD_SIZE" }
+        }));
+        let response = execute_graphql(
+            &schema,
+            UPDATE_CLUB_SCORING_SETTINGS,
+            Some(variables),
+            Some(claims),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+    }
+
+    // Nothing was saved
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, CLUB_SCORING_SETTINGS, Some(variables), None).await;
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["clubScoringSettings"]["formula"], "AUTHORITATIVE");
+}
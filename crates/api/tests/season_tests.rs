@@ -0,0 +1,340 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use chrono::{Duration, Utc};
+use common::*;
+use infra::repos::{TournamentLiveStatus, TournamentRepo};
+use serde_json::json;
+use uuid::Uuid;
+
+const CREATE_SEASON: &str = r#"
+    mutation CreateSeason($input: CreateSeasonInput!) {
+        createSeason(input: $input) {
+            id
+            name
+            minEvents
+            scoringFormula
+            isClosed
+        }
+    }
+"#;
+
+const SEASON_STANDINGS: &str = r#"
+    query SeasonStandings($seasonId: ID!) {
+        seasonStandings(seasonId: $seasonId) {
+            isFinal
+            season { id isClosed }
+            entries {
+                userId
+                rank
+                points
+                eventsPlayed
+                resultsCounted
+                user { email }
+            }
+        }
+    }
+"#;
+
+const CLOSE_SEASON: &str = r#"
+    mutation CloseSeason($seasonId: ID!) {
+        closeSeason(seasonId: $seasonId) {
+            isClosed
+            closedAt
+        }
+    }
+"#;
+
+/// Finishes a tournament of the club with the players in finishing order
+async fn finish_tournament(
+    app_state: &api::AppState,
+    club_id: Uuid,
+    name: &str,
+    players: &[Uuid],
+) -> Uuid {
+    let tournament_id = create_test_tournament(app_state, club_id, name).await;
+    for (index, user_id) in players.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents)
+            VALUES ($1, $2, $3, 0)
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(index as i32 + 1)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to create result");
+    }
+
+    TournamentRepo::new(app_state.db.clone())
+        .update_live_status(tournament_id, TournamentLiveStatus::Finished)
+        .await
+        .expect("Failed to finish tournament");
+
+    tournament_id
+}
+
+fn season_input(club_id: Uuid, name: &str, from_days: i64, to_days: i64) -> serde_json::Value {
+    let today = Utc::now().date_naive();
+    json!({
+        "clubId": club_id.to_string(),
+        "name": name,
+        "startDate": (today + Duration::days(from_days)).to_string(),
+        "endDate": (today + Duration::days(to_days)).to_string(),
+    })
+}
+
+#[tokio::test]
+async fn test_season_standings_follow_the_rules_and_freeze_on_close() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seasonmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Season Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let mut players = Vec::new();
+    for name in ["alice", "bob", "carol"] {
+        let (user_id, _) =
+            create_test_user(&app_state, &format!("season{}@test.com", name), "player").await;
+        players.push(user_id);
+    }
+    let (alice, bob, carol) = (players[0], players[1], players[2]);
+
+    // Finished before the season exists, scored again with the season's formula
+    let first_tournament =
+        finish_tournament(&app_state, club_id, "Season Opener", &[alice, bob, carol]).await;
+
+    let mut input = season_input(club_id, "Spring League", -30, 30);
+    input["minEvents"] = json!(2);
+    input["scoringFormula"] = json!("LINEAR");
+    let variables = Variables::from_json(json!({ "input": input }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SEASON,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["createSeason"]["scoringFormula"], "LINEAR");
+    let season_id = data["createSeason"]["id"].as_str().unwrap().to_string();
+
+    finish_tournament(&app_state, club_id, "Season Finale", &[bob, alice]).await;
+
+    // Alice 3 + 1, Bob 2 + 2, Carol only played once
+    let variables = Variables::from_json(json!({ "seasonId": season_id }));
+    let response = execute_graphql(&schema, SEASON_STANDINGS, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["seasonStandings"]["isFinal"], false);
+    let entries = data["seasonStandings"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+    for entry in &entries[..2] {
+        assert_eq!(entry["rank"], 1);
+        assert_eq!(entry["points"], 4);
+        assert_eq!(entry["eventsPlayed"], 2);
+    }
+    assert_eq!(entries[2]["userId"], carol.to_string());
+    assert!(entries[2]["rank"].is_null());
+    assert_eq!(entries[2]["points"], 1);
+    assert_eq!(entries[2]["user"]["email"], "seasoncarol@test.com");
+
+    // A season can't close while one of its tournaments is still running
+    let running = create_test_tournament(&app_state, club_id, "Season Late Game").await;
+    let variables = Variables::from_json(json!({ "seasonId": season_id }));
+    let response = execute_graphql(
+        &schema,
+        CLOSE_SEASON,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert_eq!(
+        response.errors[0].message,
+        "1 tournament(s) of the season are not finished yet"
+    );
+    TournamentRepo::new(app_state.db.clone())
+        .update_live_status(running, TournamentLiveStatus::Cancelled)
+        .await
+        .unwrap();
+
+    let variables = Variables::from_json(json!({ "seasonId": season_id }));
+    let response = execute_graphql(
+        &schema,
+        CLOSE_SEASON,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["closeSeason"]["isClosed"], true);
+
+    // Later changes to the results no longer move the final standings
+    sqlx::query(
+        "UPDATE tournament_results SET points = 50 WHERE tournament_id = $1 AND user_id = $2",
+    )
+    .bind(first_tournament)
+    .bind(carol)
+    .execute(&app_state.db)
+    .await
+    .unwrap();
+
+    let variables = Variables::from_json(json!({ "seasonId": season_id }));
+    let response = execute_graphql(&schema, SEASON_STANDINGS, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["seasonStandings"]["isFinal"], true);
+    assert_eq!(data["seasonStandings"]["season"]["isClosed"], true);
+    let entries = data["seasonStandings"]["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["rank"], 1);
+    assert_eq!(entries[1]["rank"], 1);
+    assert_eq!(entries[2]["userId"], carol.to_string());
+    assert_eq!(entries[2]["points"], 1);
+
+    // A closed season stays closed
+    let variables = Variables::from_json(json!({ "seasonId": season_id }));
+    let response =
+        execute_graphql(&schema, CLOSE_SEASON, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors[0].message.contains("closed"));
+}
+
+#[tokio::test]
+async fn test_create_season_validation_and_permissions() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seasonvalidmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Season Validation Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let (_, player_claims) =
+        create_test_user(&app_state, "seasonvalidplayer@test.com", "player").await;
+
+    let variables = Variables::from_json(json!({
+        "input": season_input(club_id, "Winter League", 0, 90)
+    }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SEASON,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let mut best_results = season_input(club_id, "Summer League", 100, 190);
+    best_results["bestResults"] = json!(0);
+    for (input, message) in [
+        (
+            season_input(club_id, "Overlapping League", 60, 120),
+            "already has a season",
+        ),
+        (
+            season_input(club_id, "Backwards League", 200, 100),
+            "cannot end before",
+        ),
+        (best_results, "at least 1 result"),
+    ] {
+        let variables = Variables::from_json(json!({ "input": input }));
+        let response = execute_graphql(
+            &schema,
+            CREATE_SEASON,
+            Some(variables),
+            Some(manager_claims.clone()),
+        )
+        .await;
+        assert!(
+            response.errors[0].message.contains(message),
+            "{:?}",
+            response.errors
+        );
+    }
+
+    let variables = Variables::from_json(json!({
+        "input": season_input(club_id, "Player League", 300, 400)
+    }));
+    let response =
+        execute_graphql(&schema, CREATE_SEASON, Some(variables), Some(player_claims)).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_update_season_clears_overrides() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seasonclearmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Season Clear Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let mut input = season_input(club_id, "Override League", 0, 90);
+    input["bestResults"] = json!(5);
+    input["scoringFormula"] = json!("LINEAR");
+    input["scoringCap"] = json!(100);
+    let variables = Variables::from_json(json!({ "input": input }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SEASON,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let season_id = data["createSeason"]["id"].as_str().unwrap().to_string();
+
+    let query = r#"
+        mutation UpdateSeason($input: UpdateSeasonInput!) {
+            updateSeason(input: $input) {
+                name
+                bestResults
+                scoringFormula
+                scoringCap
+            }
+        }
+    "#;
+
+    // Fields left out keep their value
+    let variables = Variables::from_json(json!({
+        "input": { "seasonId": season_id, "name": "Renamed League" }
+    }));
+    let response = execute_graphql(
+        &schema,
+        query,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let season = &data["updateSeason"];
+    assert_eq!(season["bestResults"], 5);
+    assert_eq!(season["scoringFormula"], "LINEAR");
+    assert_eq!(season["scoringCap"], 100);
+
+    // Null clears the overrides
+    let variables = Variables::from_json(json!({
+        "input": {
+            "seasonId": season_id,
+            "bestResults": null,
+            "scoringFormula": null,
+            "scoringCap": null
+        }
+    }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let season = &data["updateSeason"];
+    assert_eq!(season["name"], "Renamed League");
+    assert!(season["bestResults"].is_null());
+    assert!(season["scoringFormula"].is_null());
+    assert!(season["scoringCap"].is_null());
+}
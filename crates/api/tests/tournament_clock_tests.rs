@@ -1,9 +1,11 @@
 mod common;
 
 use api::gql::build_schema;
-use async_graphql::Variables;
+use async_graphql::{Request, Variables};
 use common::*;
+use futures_util::{Stream, StreamExt};
 use serde_json::json;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_create_tournament_clock() {
@@ -330,3 +332,368 @@ async fn test_tournament_clock_unauthorized() {
         .message
         .contains("Manager privileges required"));
 }
+
+/// Read clock updates until one matches, failing after a few seconds
+async fn wait_for_clock(
+    stream: &mut (impl Stream<Item = async_graphql::Response> + Unpin),
+    matches: impl Fn(&serde_json::Value) -> bool,
+) -> serde_json::Value {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let response = stream.next().await.expect("Subscription ended");
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            let clock = response.data.into_json().unwrap()["tournamentClockUpdates"].clone();
+            if matches(&clock) {
+                return clock;
+            }
+        }
+    })
+    .await
+    .expect("No matching clock update")
+}
+
+#[tokio::test]
+async fn test_clock_updates_follow_clock_changes() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "clockbusmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Clock Bus Club").await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Clock Bus Tournament").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    sqlx::query(
+        r#"INSERT INTO tournament_structures (tournament_id, level_number, small_blind, big_blind, ante, duration_minutes)
+           VALUES ($1, 1, 25, 50, 0, 20), ($1, 2, 50, 100, 0, 20)"#,
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tournament structures");
+
+    // Creating the tournament created its stopped clock
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let subscription = r#"
+        subscription ClockUpdates($tournamentId: ID!) {
+            tournamentClockUpdates(tournamentId: $tournamentId) {
+                status
+                currentLevel
+                timeRemainingSeconds
+                smallBlind
+            }
+        }
+    "#;
+    let mut stream = schema.execute_stream(Request::new(subscription).variables(variables.clone()));
+
+    let clock = wait_for_clock(&mut stream, |_| true).await;
+    assert_eq!(clock["status"], "STOPPED");
+    assert_eq!(clock["timeRemainingSeconds"], 20 * 60);
+
+    for (mutation, status, level) in [
+        ("startTournamentClock", "RUNNING", 1),
+        ("advanceTournamentLevel", "RUNNING", 2),
+        ("pauseTournamentClock", "PAUSED", 2),
+    ] {
+        let query = format!(
+            "mutation Change($tournamentId: ID!) {{ {}(tournamentId: $tournamentId) {{ id }} }}",
+            mutation
+        );
+        let response = execute_graphql(
+            &schema,
+            &query,
+            Some(variables.clone()),
+            Some(manager_claims.clone()),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let clock = wait_for_clock(&mut stream, |clock| {
+            clock["status"] == status && clock["currentLevel"] == level
+        })
+        .await;
+        let remaining = clock["timeRemainingSeconds"].as_i64().unwrap();
+        assert!(remaining > 19 * 60 && remaining <= 20 * 60);
+    }
+}
+
+#[tokio::test]
+async fn test_advance_level_from_expected_level_is_idempotent() {
+    let app_state = setup_test_db().await;
+    let club_id = create_test_club(&app_state, "Idempotent Advance Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Idempotent Advance Tournament").await;
+
+    sqlx::query(
+        r#"INSERT INTO tournament_structures (tournament_id, level_number, small_blind, big_blind, ante, duration_minutes)
+           VALUES ($1, 1, 25, 50, 0, 20), ($1, 2, 50, 100, 0, 20), ($1, 3, 75, 150, 0, 20)
+           ON CONFLICT DO NOTHING"#,
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tournament structures");
+
+    // The first level ended a minute ago
+    let now = chrono::Utc::now();
+    sqlx::query(
+        "UPDATE tournament_clocks SET clock_status = 'running', level_end_time = $2 WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .bind(now - chrono::Duration::minutes(1))
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to start clock");
+
+    // Two instances advancing the level they both saw move the clock once
+    let repo = infra::repos::TournamentClockRepo::new(app_state.db.clone());
+    let (first, second) = tokio::join!(
+        repo.advance_level(tournament_id, Some(1), true, None, now),
+        repo.advance_level(tournament_id, Some(1), true, None, now),
+    );
+    let advanced: Vec<_> = [first.unwrap(), second.unwrap()]
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(advanced.len(), 1);
+    assert_eq!(advanced[0].current_level, 2);
+
+    let clock = repo.get_clock(tournament_id).await.unwrap().unwrap();
+    assert_eq!(clock.current_level, 2);
+
+    // The second level isn't over yet
+    assert!(repo
+        .advance_level(tournament_id, Some(2), true, None, now)
+        .await
+        .unwrap()
+        .is_none());
+
+    // A pause taken once the level is over wins over the automatic advance
+    sqlx::query(
+        "UPDATE tournament_clocks SET clock_status = 'paused', pause_started_at = $2 WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .bind(now)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to pause clock");
+    let later = now + chrono::Duration::hours(1);
+    assert!(repo
+        .advance_level(tournament_id, Some(2), true, None, later)
+        .await
+        .unwrap()
+        .is_none());
+    let clock = repo.get_clock(tournament_id).await.unwrap().unwrap();
+    assert_eq!(clock.current_level, 2);
+    assert_eq!(clock.clock_status, "paused");
+
+    // Without an expected level the clock always advances
+    let clock = repo
+        .advance_level(tournament_id, None, false, None, now)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(clock.current_level, 3);
+
+    // Past the last level nothing changes
+    assert!(repo
+        .advance_level(tournament_id, None, false, None, now)
+        .await
+        .is_err());
+    let clock = repo.get_clock(tournament_id).await.unwrap().unwrap();
+    assert_eq!(clock.current_level, 3);
+}
+
+#[tokio::test]
+async fn test_manual_level_changes_from_expected_level_apply_once() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "expectedlevelmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Expected Level Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Expected Level Tournament").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    sqlx::query(
+        r#"INSERT INTO tournament_structures (tournament_id, level_number, small_blind, big_blind, ante, duration_minutes)
+           VALUES ($1, 1, 25, 50, 0, 20), ($1, 2, 50, 100, 0, 20), ($1, 3, 75, 150, 0, 20)
+           ON CONFLICT DO NOTHING"#,
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tournament structures");
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "expectedLevel": 1
+    }));
+    let advance_query = r#"
+        mutation AdvanceTournamentLevel($tournamentId: ID!, $expectedLevel: Int) {
+            advanceTournamentLevel(tournamentId: $tournamentId, expectedLevel: $expectedLevel) {
+                currentLevel
+            }
+        }
+    "#;
+
+    // A double-click advances the level once
+    let response = execute_graphql(
+        &schema,
+        advance_query,
+        Some(variables.clone()),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["advanceTournamentLevel"]["currentLevel"], 2);
+
+    let response = execute_graphql(
+        &schema,
+        advance_query,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors[0].message.contains("no longer on level 1"));
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string(),
+        "expectedLevel": 2
+    }));
+    let revert_query = r#"
+        mutation RevertTournamentLevel($tournamentId: ID!, $expectedLevel: Int) {
+            revertTournamentLevel(tournamentId: $tournamentId, expectedLevel: $expectedLevel) {
+                currentLevel
+            }
+        }
+    "#;
+
+    // And so does a repeated revert
+    let response = execute_graphql(
+        &schema,
+        revert_query,
+        Some(variables.clone()),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["revertTournamentLevel"]["currentLevel"], 1);
+
+    let response =
+        execute_graphql(&schema, revert_query, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors[0].message.contains("no longer on level 2"));
+
+    let clock = infra::repos::TournamentClockRepo::new(app_state.db.clone())
+        .get_clock(tournament_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(clock.current_level, 1);
+}
+
+#[tokio::test]
+async fn test_clock_service_leader_lock_fails_over() {
+    use api::services::LeaderLock;
+
+    let app_state = setup_test_db().await;
+    let key = 0x7465_7374_0001;
+
+    let mut first = LeaderLock::new(&app_state.db, key);
+    let mut second = LeaderLock::new(&app_state.db, key);
+
+    assert!(first.acquire().await);
+    assert!(!second.acquire().await);
+    // The leader keeps the lock on later ticks
+    assert!(first.acquire().await);
+    assert!(!second.acquire().await);
+
+    // Once the leader is gone another instance takes over
+    first.release().await;
+    assert!(!first.is_leader());
+    assert!(second.acquire().await);
+    assert!(!first.acquire().await);
+
+    second.release().await;
+}
+
+#[tokio::test]
+async fn test_clock_service_advances_at_the_level_boundary() {
+    use api::services::{ClockService, ManualTime};
+    use chrono::Utc;
+    use infra::repos::TournamentClockRepo;
+
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "clockservicemanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Clock Service Club").await;
+    let tournament_id =
+        create_test_tournament(&app_state, club_id, "Clock Service Tournament").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    sqlx::query(
+        r#"INSERT INTO tournament_structures (tournament_id, level_number, small_blind, big_blind, ante, duration_minutes)
+           VALUES ($1, 1, 25, 50, 0, 1), ($1, 2, 50, 100, 0, 1)"#,
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tournament structures");
+
+    // The service waits on time that only moves when the test says so
+    let time = ManualTime::new(Utc::now());
+    let mut service =
+        ClockService::with_time_source(app_state.clone(), std::sync::Arc::new(time.clone()));
+    let service = tokio::spawn(async move { service.run().await });
+
+    // Starting the clock wakes the service to plan the end of the level
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        "mutation Start($tournamentId: ID!) { startTournamentClock(tournamentId: $tournamentId) { id } }",
+        Some(variables),
+        Some(manager_claims),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let repo = TournamentClockRepo::new(app_state.db.clone());
+    let level_end = repo
+        .get_clock(tournament_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .level_end_time
+        .unwrap();
+
+    time.set(level_end - chrono::Duration::milliseconds(1));
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let clock = repo.get_clock(tournament_id).await.unwrap().unwrap();
+    assert_eq!(clock.current_level, 1);
+
+    // At the boundary the next level starts exactly when the previous one ended
+    time.set(level_end);
+    let clock = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let clock = repo.get_clock(tournament_id).await.unwrap().unwrap();
+            if clock.current_level == 2 {
+                return clock;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Level did not advance at the boundary");
+    assert_eq!(clock.level_started_at, Some(level_end));
+    assert_eq!(
+        clock.level_end_time,
+        Some(level_end + chrono::Duration::minutes(1))
+    );
+
+    service.abort();
+}
@@ -0,0 +1,288 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use infra::repos::{TournamentLiveStatus, TournamentRepo};
+use serde_json::json;
+use uuid::Uuid;
+
+const CORRECT_RESULT: &str = r#"
+    mutation CorrectTournamentResult($input: CorrectTournamentResultInput!) {
+        correctTournamentResult(input: $input) {
+            id
+            finalPosition
+            prizeCents
+            points
+        }
+    }
+"#;
+
+const DELETE_RESULT: &str = r#"
+    mutation DeleteTournamentResult($resultId: ID!, $reason: String) {
+        deleteTournamentResult(resultId: $resultId, reason: $reason)
+    }
+"#;
+
+/// Creates a finished tournament with three results, returning the tournament, the
+/// manager's claims and the result IDs best first
+async fn setup_finished_tournament(
+    app_state: &api::AppState,
+    prefix: &str,
+) -> (Uuid, api::auth::Claims, Vec<Uuid>) {
+    let (manager_id, manager_claims) =
+        create_test_user(app_state, &format!("{}manager@test.com", prefix), "manager").await;
+    let club_id = create_test_club(app_state, &format!("{} Club", prefix)).await;
+    create_club_manager(app_state, manager_id, club_id).await;
+    let tournament_id =
+        create_test_tournament(app_state, club_id, &format!("{} Tournament", prefix)).await;
+
+    let mut result_ids = Vec::new();
+    for (i, prize_cents) in [7000, 3000, 0].iter().enumerate() {
+        let (user_id, _) = create_test_user(
+            app_state,
+            &format!("{}player{}@test.com", prefix, i),
+            "player",
+        )
+        .await;
+        let result_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(i as i32 + 1)
+        .bind(prize_cents)
+        .fetch_one(&app_state.db)
+        .await
+        .expect("Failed to create result");
+        result_ids.push(result_id);
+    }
+
+    // Finishing the tournament awards the points
+    TournamentRepo::new(app_state.db.clone())
+        .update_live_status(tournament_id, TournamentLiveStatus::Finished)
+        .await
+        .expect("Failed to finish tournament");
+
+    (tournament_id, manager_claims, result_ids)
+}
+
+async fn points_of(app_state: &api::AppState, result_id: Uuid) -> i32 {
+    sqlx::query_scalar("SELECT points FROM tournament_results WHERE id = $1")
+        .bind(result_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_correct_and_delete_tournament_results() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims, result_ids) =
+        setup_finished_tournament(&app_state, "results").await;
+    let second_place_points = points_of(&app_state, result_ids[1]).await;
+    let third_place_points = points_of(&app_state, result_ids[2]).await;
+    assert!(second_place_points > third_place_points);
+
+    let variables = Variables::from_json(json!({
+        "input": { "resultId": result_ids[2].to_string(), "finalPosition": 0 }
+    }));
+    let response = execute_graphql(
+        &schema,
+        CORRECT_RESULT,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors[0].message.contains("at least 1"));
+
+    // The 3rd place was actually a tie for 2nd: the points follow the new position
+    let variables = Variables::from_json(json!({
+        "input": {
+            "resultId": result_ids[2].to_string(),
+            "finalPosition": 2,
+            "prizeCents": 1500,
+            "reason": "Tied for second",
+        }
+    }));
+    let response = execute_graphql(
+        &schema,
+        CORRECT_RESULT,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["correctTournamentResult"]["finalPosition"], 2);
+    assert_eq!(data["correctTournamentResult"]["prizeCents"], 1500);
+    assert_eq!(
+        data["correctTournamentResult"]["points"],
+        second_place_points
+    );
+
+    let variables = Variables::from_json(json!({
+        "resultId": result_ids[0].to_string(),
+        "reason": "Entered twice",
+    }));
+    let response = execute_graphql(
+        &schema,
+        DELETE_RESULT,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["deleteTournamentResult"], true);
+
+    // Both changes are in the audit trail
+    let query = r#"
+        query TournamentResultCorrections($tournamentId: ID!) {
+            tournamentResultCorrections(tournamentId: $tournamentId) {
+                resultId
+                action
+                oldFinalPosition
+                newFinalPosition
+                oldPrizeCents
+                newPrizeCents
+                reason
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let corrections = data["tournamentResultCorrections"].as_array().unwrap();
+    assert_eq!(corrections.len(), 2);
+    assert_eq!(corrections[0]["resultId"], result_ids[2].to_string());
+    assert_eq!(corrections[0]["action"], "CORRECT");
+    assert_eq!(corrections[0]["oldFinalPosition"], 3);
+    assert_eq!(corrections[0]["newFinalPosition"], 2);
+    assert_eq!(corrections[0]["oldPrizeCents"], 0);
+    assert_eq!(corrections[0]["newPrizeCents"], 1500);
+    assert_eq!(corrections[0]["reason"], "Tied for second");
+    assert_eq!(corrections[1]["action"], "DELETE");
+    assert_eq!(corrections[1]["oldFinalPosition"], 1);
+    assert!(corrections[1]["newFinalPosition"].is_null());
+
+    let remaining: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tournament_results WHERE tournament_id = $1")
+            .bind(tournament_id)
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(remaining, 2);
+}
+
+#[tokio::test]
+async fn test_correct_tournament_result_requires_club_manager() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (_, _, result_ids) = setup_finished_tournament(&app_state, "resultsauth").await;
+    let (_, other_manager_claims) =
+        create_test_user(&app_state, "resultsothermanager@test.com", "manager").await;
+    let (_, player_claims) = create_test_user(&app_state, "resultsplayer@test.com", "player").await;
+
+    for claims in [other_manager_claims, player_claims] {
+        let variables = Variables::from_json(json!({
+            "input": { "resultId": result_ids[0].to_string(), "prizeCents": 1 }
+        }));
+        let response = execute_graphql(
+            &schema,
+            CORRECT_RESULT,
+            Some(variables),
+            Some(claims.clone()),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let variables = Variables::from_json(json!({ "resultId": result_ids[0].to_string() }));
+        let response = execute_graphql(&schema, DELETE_RESULT, Some(variables), Some(claims)).await;
+        assert!(!response.errors.is_empty());
+    }
+
+    let prize_cents: i32 =
+        sqlx::query_scalar("SELECT prize_cents FROM tournament_results WHERE id = $1")
+            .bind(result_ids[0])
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(prize_cents, 7000);
+}
+
+#[tokio::test]
+async fn test_correct_tournament_result_clears_notes() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (tournament_id, claims, result_ids) =
+        setup_finished_tournament(&app_state, "resultsnotes").await;
+    sqlx::query("UPDATE tournament_results SET notes = 'Chopped heads-up' WHERE id = $1")
+        .bind(result_ids[0])
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to set notes");
+
+    // Leaving the notes out keeps them
+    let variables = Variables::from_json(json!({
+        "input": { "resultId": result_ids[0].to_string(), "prizeCents": 6500 }
+    }));
+    let response = execute_graphql(
+        &schema,
+        CORRECT_RESULT,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    // Null removes them
+    let variables = Variables::from_json(json!({
+        "input": { "resultId": result_ids[0].to_string(), "notes": null }
+    }));
+    let response = execute_graphql(
+        &schema,
+        CORRECT_RESULT,
+        Some(variables),
+        Some(claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let notes: Option<String> =
+        sqlx::query_scalar("SELECT notes FROM tournament_results WHERE id = $1")
+            .bind(result_ids[0])
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert!(notes.is_none());
+
+    // The audit trail shows the notes being removed
+    let query = r#"
+        query TournamentResultCorrections($tournamentId: ID!) {
+            tournamentResultCorrections(tournamentId: $tournamentId) {
+                oldNotes
+                newNotes
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "tournamentId": tournament_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let corrections = data["tournamentResultCorrections"].as_array().unwrap();
+    assert_eq!(corrections.len(), 2);
+    assert_eq!(corrections[0]["oldNotes"], "Chopped heads-up");
+    assert_eq!(corrections[0]["newNotes"], "Chopped heads-up");
+    assert_eq!(corrections[1]["oldNotes"], "Chopped heads-up");
+    assert!(corrections[1]["newNotes"].is_null());
+}
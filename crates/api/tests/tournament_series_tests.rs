@@ -0,0 +1,539 @@
+mod common;
+
+use api::gql::build_schema;
+use async_graphql::Variables;
+use common::*;
+use serde_json::json;
+
+const ALL_WEEKDAYS: [&str; 7] = [
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+    "SUNDAY",
+];
+
+const CREATE_SERIES: &str = r#"
+    mutation CreateTournamentSeries($input: CreateTournamentSeriesInput!) {
+        createTournamentSeries(input: $input) {
+            id
+            name
+            buyInCents
+            weekdays
+            localStartTime
+            timezone
+            weeksAhead
+            isPaused
+            blindStructure {
+                levelNumber
+                smallBlind
+                bigBlind
+            }
+            upcomingTournaments {
+                id
+                title
+                buyInCents
+                liveStatus
+                structure {
+                    levelNumber
+                    smallBlind
+                    bigBlind
+                }
+                clock {
+                    status
+                }
+            }
+        }
+    }
+"#;
+
+fn series_input(club_id: uuid::Uuid, weekdays: &[&str]) -> serde_json::Value {
+    json!({
+        "clubId": club_id.to_string(),
+        "name": "Weekly Deepstack",
+        "buyInCents": 5000,
+        "seatCap": 60,
+        "durationMinutes": 300,
+        "blindStructure": [
+            { "smallBlind": 100, "bigBlind": 200, "durationMinutes": 20 },
+            { "smallBlind": 200, "bigBlind": 400, "ante": 400, "durationMinutes": 20 }
+        ],
+        "weekdays": weekdays,
+        "localStartTime": "20:00:00",
+        "timezone": "Europe/Brussels",
+        "weeksAhead": 1
+    })
+}
+
+#[tokio::test]
+async fn test_create_tournament_series() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesmanager@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let variables = Variables::from_json(json!({
+        "input": series_input(club_id, &ALL_WEEKDAYS)
+    }));
+
+    let response = execute_graphql(
+        &schema,
+        CREATE_SERIES,
+        Some(variables),
+        Some(manager_claims),
+    )
+    .await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Series creation should succeed: {:?}",
+        response.errors
+    );
+
+    let data = response.data.into_json().unwrap();
+    let series = &data["createTournamentSeries"];
+
+    assert_eq!(series["name"], "Weekly Deepstack");
+    assert_eq!(series["weekdays"].as_array().unwrap().len(), 7);
+    assert_eq!(series["localStartTime"], "20:00:00");
+    assert_eq!(series["isPaused"], false);
+    assert_eq!(series["blindStructure"][1]["levelNumber"], 2);
+
+    // One instance per day for the week ahead, each with the series structure and a clock
+    let upcoming = series["upcomingTournaments"].as_array().unwrap();
+    assert_eq!(upcoming.len(), 7);
+    for tournament in upcoming {
+        assert_eq!(tournament["title"], "Weekly Deepstack");
+        assert_eq!(tournament["buyInCents"], 5000);
+        assert_eq!(tournament["liveStatus"], "NOT_STARTED");
+        assert_eq!(tournament["structure"].as_array().unwrap().len(), 2);
+        assert_eq!(tournament["structure"][1]["bigBlind"], 400);
+        assert_eq!(tournament["clock"]["status"], "STOPPED");
+    }
+
+    // Materializing again doesn't create duplicates
+    let series_id = uuid::Uuid::parse_str(series["id"].as_str().unwrap()).unwrap();
+    let created = infra::repos::TournamentSeriesRepo::new(app_state.db.clone())
+        .materialize(series_id)
+        .await
+        .unwrap();
+    assert!(created.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_tournament_series_validation() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesvalidation@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Validation Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let mut no_weekdays = series_input(club_id, &[]);
+    no_weekdays["weekdays"] = json!([]);
+    let mut bad_timezone = series_input(club_id, &["FRIDAY"]);
+    bad_timezone["timezone"] = json!("Mars/Olympus_Mons");
+    let mut bad_blinds = series_input(club_id, &["FRIDAY"]);
+    bad_blinds["blindStructure"] = json!([
+        { "smallBlind": 200, "bigBlind": 100, "durationMinutes": 20 }
+    ]);
+
+    for input in [no_weekdays, bad_timezone, bad_blinds] {
+        let variables = Variables::from_json(json!({ "input": input }));
+        let response = execute_graphql(
+            &schema,
+            CREATE_SERIES,
+            Some(variables),
+            Some(manager_claims.clone()),
+        )
+        .await;
+
+        assert!(
+            !response.errors.is_empty(),
+            "Invalid series should be rejected: {}",
+            input
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_create_tournament_series_requires_club_manager() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (_, player_claims) = create_test_user(&app_state, "seriesplayer@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Series Permission Club").await;
+
+    let variables = Variables::from_json(json!({
+        "input": series_input(club_id, &["FRIDAY"])
+    }));
+
+    let response =
+        execute_graphql(&schema, CREATE_SERIES, Some(variables), Some(player_claims)).await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "Players should not be able to create series"
+    );
+}
+
+#[tokio::test]
+async fn test_update_and_pause_tournament_series() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesupdate@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Update Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let variables = Variables::from_json(json!({
+        "input": series_input(club_id, &ALL_WEEKDAYS)
+    }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SERIES,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series_id = data["createTournamentSeries"]["id"].clone();
+
+    let update = r#"
+        mutation UpdateTournamentSeries($input: UpdateTournamentSeriesInput!) {
+            updateTournamentSeries(input: $input) {
+                buyInCents
+                upcomingTournaments {
+                    buyInCents
+                    structure {
+                        smallBlind
+                    }
+                }
+            }
+        }
+    "#;
+
+    // Without applying to instances only the series changes
+    let variables = Variables::from_json(json!({
+        "input": { "id": series_id, "buyInCents": 7500 }
+    }));
+    let response = execute_graphql(
+        &schema,
+        update,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series = &data["updateTournamentSeries"];
+    assert_eq!(series["buyInCents"], 7500);
+    assert!(series["upcomingTournaments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|t| t["buyInCents"] == 5000));
+
+    // Applying to future instances updates their details and structure
+    let variables = Variables::from_json(json!({
+        "input": {
+            "id": series_id,
+            "blindStructure": [
+                { "smallBlind": 50, "bigBlind": 100, "durationMinutes": 30 }
+            ],
+            "applyToFutureInstances": true
+        }
+    }));
+    let response = execute_graphql(
+        &schema,
+        update,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let upcoming = data["updateTournamentSeries"]["upcomingTournaments"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(upcoming.len(), 7);
+    for tournament in &upcoming {
+        assert_eq!(tournament["buyInCents"], 7500);
+        assert_eq!(tournament["structure"], json!([{ "smallBlind": 50 }]));
+    }
+
+    // Paused series stay paused until resumed
+    let pause = r#"
+        mutation PauseTournamentSeries($seriesId: ID!) {
+            pauseTournamentSeries(seriesId: $seriesId) {
+                isPaused
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "seriesId": series_id }));
+    let response = execute_graphql(
+        &schema,
+        pause,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["pauseTournamentSeries"]["isPaused"], true);
+
+    let query = r#"
+        query TournamentSeries($clubId: ID!) {
+            tournamentSeries(clubId: $clubId) {
+                id
+                isPaused
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "clubId": club_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), None).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["tournamentSeries"][0]["id"], series_id);
+    assert_eq!(data["tournamentSeries"][0]["isPaused"], true);
+
+    let resume = r#"
+        mutation ResumeTournamentSeries($seriesId: ID!) {
+            resumeTournamentSeries(seriesId: $seriesId) {
+                isPaused
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({ "seriesId": series_id }));
+    let response = execute_graphql(&schema, resume, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["resumeTournamentSeries"]["isPaused"], false);
+}
+
+#[tokio::test]
+async fn test_update_tournament_series_clears_optional_fields() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesclear@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Clear Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let template_id: uuid::Uuid = sqlx::query_scalar(
+        r#"INSERT INTO payout_templates (name, payout_structure)
+           VALUES ('Series Clear Template', '[{"position": 1, "percentage": 100.0}]')
+           RETURNING id"#,
+    )
+    .fetch_one(&app_state.db)
+    .await
+    .expect("Failed to create payout template");
+
+    let mut input = series_input(club_id, &["MONDAY"]);
+    input["description"] = json!("Every Monday");
+    input["payoutTemplateId"] = json!(template_id.to_string());
+    let variables = Variables::from_json(json!({ "input": input }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SERIES,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series_id = data["createTournamentSeries"]["id"].clone();
+
+    let update = r#"
+        mutation UpdateTournamentSeries($input: UpdateTournamentSeriesInput!) {
+            updateTournamentSeries(input: $input) {
+                name
+                description
+                seatCap
+                durationMinutes
+                payoutTemplateId
+            }
+        }
+    "#;
+
+    // Null clears the optional fields
+    let variables = Variables::from_json(json!({
+        "input": {
+            "id": series_id,
+            "description": null,
+            "seatCap": null,
+            "durationMinutes": null,
+            "payoutTemplateId": null
+        }
+    }));
+    let response = execute_graphql(&schema, update, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series = &data["updateTournamentSeries"];
+    assert_eq!(series["name"], "Weekly Deepstack");
+    assert!(series["description"].is_null());
+    assert!(series["seatCap"].is_null());
+    assert!(series["durationMinutes"].is_null());
+    assert!(series["payoutTemplateId"].is_null());
+}
+
+#[tokio::test]
+async fn test_rescheduled_series_keeps_one_instance_per_day() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesslots@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Slots Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let variables = Variables::from_json(json!({
+        "input": series_input(club_id, &["MONDAY", "THURSDAY"])
+    }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SERIES,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series = &data["createTournamentSeries"];
+    let series_id = series["id"].clone();
+    let upcoming = series["upcomingTournaments"].as_array().unwrap();
+    assert_eq!(upcoming.len(), 2);
+
+    // Someone registered for one of the instances
+    let kept_id = upcoming[0]["id"].as_str().unwrap().to_string();
+    let (player_id, _) = create_test_user(&app_state, "seriesslotsplayer@test.com", "player").await;
+    sqlx::query("INSERT INTO tournament_registrations (tournament_id, user_id, status) VALUES ($1, $2, 'registered')")
+        .bind(uuid::Uuid::parse_str(&kept_id).unwrap())
+        .bind(player_id)
+        .execute(&app_state.db)
+        .await
+        .expect("Failed to register player");
+
+    let update = r#"
+        mutation UpdateTournamentSeries($input: UpdateTournamentSeriesInput!) {
+            updateTournamentSeries(input: $input) {
+                upcomingTournaments {
+                    id
+                }
+            }
+        }
+    "#;
+
+    // Without applying to instances a new start time leaves the schedule alone
+    let variables = Variables::from_json(json!({
+        "input": { "id": series_id, "localStartTime": "21:00:00" }
+    }));
+    let response = execute_graphql(
+        &schema,
+        update,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["updateTournamentSeries"]["upcomingTournaments"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // Applying it moves the other instance, while the registered one keeps its day
+    let variables = Variables::from_json(json!({
+        "input": { "id": series_id, "localStartTime": "21:00:00", "applyToFutureInstances": true }
+    }));
+    let response = execute_graphql(&schema, update, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let upcoming = data["updateTournamentSeries"]["upcomingTournaments"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(upcoming.len(), 2);
+    assert!(upcoming.iter().any(|t| t["id"] == kept_id.as_str()));
+}
+
+#[tokio::test]
+async fn test_update_tournament_series_duration_keeps_instances() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "seriesduration@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Series Duration Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let variables = Variables::from_json(json!({
+        "input": series_input(club_id, &["MONDAY", "THURSDAY"])
+    }));
+    let response = execute_graphql(
+        &schema,
+        CREATE_SERIES,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let series_id = data["createTournamentSeries"]["id"].clone();
+    let mut ids: Vec<String> = data["createTournamentSeries"]["upcomingTournaments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+
+    // A new duration moves the end times of the same instances
+    let update = r#"
+        mutation UpdateTournamentSeries($input: UpdateTournamentSeriesInput!) {
+            updateTournamentSeries(input: $input) {
+                upcomingTournaments {
+                    id
+                    startTime
+                    endTime
+                }
+            }
+        }
+    "#;
+    let variables = Variables::from_json(json!({
+        "input": { "id": series_id, "durationMinutes": 120, "applyToFutureInstances": true }
+    }));
+    let response = execute_graphql(&schema, update, Some(variables), Some(manager_claims)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let upcoming = data["updateTournamentSeries"]["upcomingTournaments"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let mut updated_ids: Vec<String> = upcoming
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect();
+    updated_ids.sort();
+    assert_eq!(updated_ids, ids);
+    for tournament in &upcoming {
+        let start: chrono::DateTime<chrono::Utc> =
+            tournament["startTime"].as_str().unwrap().parse().unwrap();
+        let end: chrono::DateTime<chrono::Utc> =
+            tournament["endTime"].as_str().unwrap().parse().unwrap();
+        assert_eq!(end - start, chrono::Duration::minutes(120));
+    }
+}
@@ -596,3 +596,466 @@ async fn test_regular_player_has_no_managed_club() {
         "Regular player should not have a managedClub"
     );
 }
+
+#[tokio::test]
+async fn test_create_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "createtournament@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Create Tournament Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    sqlx::query(
+        "INSERT INTO tags (slug, label) VALUES ('test-deepstack', 'Test Deepstack') ON CONFLICT (slug) DO NOTHING",
+    )
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create tag");
+
+    let query = r#"
+        mutation CreateTournament($input: CreateTournamentInput!) {
+            createTournament(input: $input) {
+                id
+                title
+                description
+                clubId
+                buyInCents
+                seatCap
+                liveStatus
+                tags {
+                    slug
+                }
+                clock {
+                    status
+                    currentLevel
+                }
+            }
+        }
+    "#;
+
+    let start_time = chrono::Utc::now() + chrono::Duration::days(7);
+    let variables = Variables::from_json(json!({
+        "input": {
+            "title": "Sunday Deepstack",
+            "clubId": club_id.to_string(),
+            "description": "Weekly deepstack",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 10000,
+            "seatCap": 60,
+            "tags": ["test-deepstack"]
+        }
+    }));
+
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Tournament creation should succeed: {:?}",
+        response.errors
+    );
+
+    let data = response.data.into_json().unwrap();
+    let tournament = &data["createTournament"];
+
+    assert_eq!(tournament["title"], "Sunday Deepstack");
+    assert_eq!(tournament["clubId"], club_id.to_string());
+    assert_eq!(tournament["buyInCents"], 10000);
+    assert_eq!(tournament["seatCap"], 60);
+    assert_eq!(tournament["liveStatus"], "NOT_STARTED");
+    assert_eq!(tournament["tags"][0]["slug"], "test-deepstack");
+    assert_eq!(tournament["clock"]["status"], "STOPPED");
+    assert_eq!(tournament["clock"]["currentLevel"], 1);
+
+    // The tournament is persisted
+    let tournament_id = uuid::Uuid::parse_str(tournament["id"].as_str().unwrap()).unwrap();
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tournaments WHERE id = $1")
+        .bind(tournament_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn test_create_tournament_validation() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "createinvalid@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Create Invalid Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+
+    let query = r#"
+        mutation CreateTournament($input: CreateTournamentInput!) {
+            createTournament(input: $input) {
+                id
+            }
+        }
+    "#;
+
+    let start_time = chrono::Utc::now() + chrono::Duration::days(7);
+    let invalid_inputs = [
+        json!({ "title": "  ", "startTime": start_time.to_rfc3339(), "buyInCents": 1000 }),
+        json!({
+            "title": "Ends before it starts",
+            "startTime": start_time.to_rfc3339(),
+            "endTime": (start_time - chrono::Duration::hours(1)).to_rfc3339(),
+            "buyInCents": 1000
+        }),
+        json!({ "title": "Negative", "startTime": start_time.to_rfc3339(), "buyInCents": -1 }),
+        json!({
+            "title": "No seats",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "seatCap": 0
+        }),
+        json!({
+            "title": "Fees above the buy-in",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "rakeCents": 800,
+            "staffFeeCents": 300
+        }),
+        json!({
+            "title": "Fees overflowing",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "rakeCents": i32::MAX,
+            "staffFeeCents": i32::MAX
+        }),
+        json!({
+            "title": "Bounty without a format",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "bountyCents": 500
+        }),
+        json!({
+            "title": "Bounty and fees above the buy-in",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "rakeCents": 200,
+            "bountyFormat": "PROGRESSIVE",
+            "bountyCents": 900
+        }),
+        json!({
+            "title": "Bounty and fees overflowing",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": i32::MAX,
+            "rakeCents": i32::MAX,
+            "bountyFormat": "REGULAR",
+            "bountyCents": i32::MAX
+        }),
+        json!({
+            "title": "Unknown tag",
+            "startTime": start_time.to_rfc3339(),
+            "buyInCents": 1000,
+            "tags": ["no-such-tag"]
+        }),
+    ];
+
+    for mut input in invalid_inputs {
+        input["clubId"] = json!(club_id.to_string());
+        let variables = Variables::from_json(json!({ "input": input }));
+        let response = execute_graphql(
+            &schema,
+            query,
+            Some(variables),
+            Some(manager_claims.clone()),
+        )
+        .await;
+
+        assert!(
+            !response.errors.is_empty(),
+            "Invalid input should be rejected: {}",
+            input
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_create_tournament_requires_club_manager() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (_, manager_claims) =
+        create_test_user(&app_state, "createotherclub@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Someone Else's Club").await;
+
+    let query = r#"
+        mutation CreateTournament($input: CreateTournamentInput!) {
+            createTournament(input: $input) {
+                id
+            }
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({
+        "input": {
+            "title": "Not my club",
+            "clubId": club_id.to_string(),
+            "startTime": chrono::Utc::now().to_rfc3339(),
+            "buyInCents": 1000
+        }
+    }));
+
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "Managers should not create tournaments for other clubs"
+    );
+}
+
+#[tokio::test]
+async fn test_update_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "updatetournament@test.com", "manager").await;
+    let club_id = create_test_club(&app_state, "Update Tournament Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "Before Update").await;
+
+    let query = r#"
+        mutation UpdateTournament($input: UpdateTournamentInput!) {
+            updateTournament(input: $input) {
+                id
+                title
+                description
+                buyInCents
+                seatCap
+                guaranteeCents
+            }
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({
+        "input": {
+            "id": tournament_id.to_string(),
+            "title": "After Update",
+            "buyInCents": 7500,
+            "guaranteeCents": 100000
+        }
+    }));
+
+    let response = execute_graphql(
+        &schema,
+        query,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Tournament update should succeed: {:?}",
+        response.errors
+    );
+
+    let data = response.data.into_json().unwrap();
+    let tournament = &data["updateTournament"];
+
+    assert_eq!(tournament["title"], "After Update");
+    assert_eq!(tournament["buyInCents"], 7500);
+    assert_eq!(tournament["guaranteeCents"], 100000);
+    // Untouched fields keep their values
+    assert_eq!(tournament["description"], "Test tournament description");
+    assert_eq!(tournament["seatCap"], 100);
+
+    // Invalid edits are rejected
+    let variables = Variables::from_json(json!({
+        "input": {
+            "id": tournament_id.to_string(),
+            "seatCap": -5
+        }
+    }));
+
+    let response = execute_graphql(
+        &schema,
+        query,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+    assert!(!response.errors.is_empty(), "Negative seat cap should fail");
+
+    // Null clears the optional fields
+    let variables = Variables::from_json(json!({
+        "input": {
+            "id": tournament_id.to_string(),
+            "description": null,
+            "seatCap": null,
+            "guaranteeCents": null
+        }
+    }));
+
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+    assert!(
+        response.errors.is_empty(),
+        "Clearing fields should succeed: {:?}",
+        response.errors
+    );
+    let data = response.data.into_json().unwrap();
+    let tournament = &data["updateTournament"];
+    assert!(tournament["description"].is_null());
+    assert!(tournament["seatCap"].is_null());
+    assert!(tournament["guaranteeCents"].is_null());
+    assert_eq!(tournament["title"], "After Update");
+}
+
+#[tokio::test]
+async fn test_cancel_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "canceltournament@test.com", "manager").await;
+    let (player_id, _) = create_test_user(&app_state, "cancelplayer@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Cancel Tournament Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let tournament_id = create_test_tournament(&app_state, club_id, "To Be Cancelled").await;
+
+    sqlx::query(
+        "INSERT INTO tournament_registrations (tournament_id, user_id, status) VALUES ($1, $2, 'registered')",
+    )
+    .bind(tournament_id)
+    .bind(player_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to register player");
+
+    // The clock was already running
+    sqlx::query(
+        "UPDATE tournament_clocks SET clock_status = 'running', level_end_time = NOW() + INTERVAL '20 minutes' WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to start clock");
+
+    let query = r#"
+        mutation CancelTournament($tournamentId: ID!) {
+            cancelTournament(tournamentId: $tournamentId) {
+                id
+                liveStatus
+                status
+                registrations {
+                    status
+                }
+            }
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({
+        "tournamentId": tournament_id.to_string()
+    }));
+
+    let response = execute_graphql(
+        &schema,
+        query,
+        Some(variables.clone()),
+        Some(manager_claims.clone()),
+    )
+    .await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Tournament cancellation should succeed: {:?}",
+        response.errors
+    );
+
+    let data = response.data.into_json().unwrap();
+    let tournament = &data["cancelTournament"];
+
+    assert_eq!(tournament["liveStatus"], "CANCELLED");
+    assert_eq!(tournament["status"], "CANCELLED");
+    assert_eq!(tournament["registrations"][0]["status"], "CANCELLED");
+
+    let clock_status: String =
+        sqlx::query_scalar("SELECT clock_status FROM tournament_clocks WHERE tournament_id = $1")
+            .bind(tournament_id)
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(clock_status, "stopped");
+
+    // Cancelling twice is an error
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+    assert!(
+        response.errors[0].message.contains("already cancelled"),
+        "Second cancel should fail: {:?}",
+        response.errors
+    );
+
+    // The repository refuses it too, whatever the caller read before
+    let repo = infra::repos::TournamentRepo::new(app_state.db.clone());
+    assert!(repo.cancel(tournament_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_tournament() {
+    let app_state = setup_test_db().await;
+    let schema = build_schema(app_state.clone());
+
+    let (manager_id, manager_claims) =
+        create_test_user(&app_state, "deletetournament@test.com", "manager").await;
+    let (player_id, _) = create_test_user(&app_state, "deleteplayer@test.com", "player").await;
+    let club_id = create_test_club(&app_state, "Delete Tournament Club").await;
+    create_club_manager(&app_state, manager_id, club_id).await;
+    let unplayed_id = create_test_tournament(&app_state, club_id, "Never Played").await;
+    let played_id = create_test_tournament(&app_state, club_id, "Already Played").await;
+
+    // Seat a player in the second tournament
+    let club_table_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO club_tables (id, club_id, table_number, max_seats) VALUES ($1, $2, 1, 9)",
+    )
+    .bind(club_table_id)
+    .bind(club_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to create club table");
+    sqlx::query(
+        "INSERT INTO table_seat_assignments (tournament_id, club_table_id, user_id, seat_number, stack_size) VALUES ($1, $2, $3, 1, 20000)",
+    )
+    .bind(played_id)
+    .bind(club_table_id)
+    .bind(player_id)
+    .execute(&app_state.db)
+    .await
+    .expect("Failed to seat player");
+
+    let query = r#"
+        mutation DeleteTournament($tournamentId: ID!) {
+            deleteTournament(tournamentId: $tournamentId)
+        }
+    "#;
+
+    let variables = Variables::from_json(json!({ "tournamentId": unplayed_id.to_string() }));
+    let response = execute_graphql(
+        &schema,
+        query,
+        Some(variables),
+        Some(manager_claims.clone()),
+    )
+    .await;
+
+    assert!(
+        response.errors.is_empty(),
+        "Deleting an unplayed tournament should succeed: {:?}",
+        response.errors
+    );
+    assert_eq!(response.data.into_json().unwrap()["deleteTournament"], true);
+
+    let variables = Variables::from_json(json!({ "tournamentId": played_id.to_string() }));
+    let response = execute_graphql(&schema, query, Some(variables), Some(manager_claims)).await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "Deleting a played tournament should fail"
+    );
+}
@@ -0,0 +1,489 @@
+//! Blind structure generation from a starting stack and a target tournament length
+//!
+//! Big blinds grow geometrically from about 1/100 of the starting stack to the point
+//! where the whole field's chips are roughly 20 big blinds, which is when a tournament
+//! usually ends. Blinds are rounded to values that can be paid with the smallest chip
+//! still in play, and levels are added until the target length (breaks included) is
+//! reached.
+//!
+//! # Examples
+//!
+//! ```
+//! use infra::blind_structure::{generate_blind_structure, BlindStructureParams};
+//!
+//! let structure = generate_blind_structure(&BlindStructureParams {
+//!     starting_stack: 20_000,
+//!     expected_players: 50,
+//!     target_duration_minutes: 300,
+//!     level_duration_minutes: 20,
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(structure.levels[0].small_blind, 100);
+//! assert_eq!(structure.levels[0].big_blind, 200);
+//! ```
+
+use crate::repos::tournament_clock::TournamentStructureLevel;
+
+/// Chip values found in most tournament sets, smallest first
+pub const STANDARD_DENOMINATIONS: [i32; 7] = [25, 100, 500, 1_000, 5_000, 25_000, 100_000];
+
+/// Longest structure the generator produces, breaks included
+pub const MAX_LEVELS: usize = 100;
+
+/// Largest starting stack accepted
+pub const MAX_STARTING_STACK: i32 = 10_000_000;
+
+/// Largest field accepted
+pub const MAX_EXPECTED_PLAYERS: i32 = 10_000;
+
+/// Small blinds are rounded to one of these multiples of a power of ten first
+const NICE_STEPS: [f64; 9] = [1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0, 6.0, 8.0];
+
+#[derive(Debug, Clone)]
+pub struct BlindStructureParams {
+    pub starting_stack: i32,
+    pub expected_players: i32,
+    /// Total length including breaks
+    pub target_duration_minutes: i32,
+    pub level_duration_minutes: i32,
+    /// First level (counting playing levels only) with a big blind ante
+    pub ante_from_level: Option<i32>,
+    /// Insert a break after this many playing levels
+    pub break_every_levels: Option<i32>,
+    pub break_duration_minutes: i32,
+    /// Chip values in the set; [`STANDARD_DENOMINATIONS`] by default
+    pub denominations: Vec<i32>,
+}
+
+impl Default for BlindStructureParams {
+    fn default() -> Self {
+        Self {
+            starting_stack: 20_000,
+            expected_players: 50,
+            target_duration_minutes: 300,
+            level_duration_minutes: 20,
+            ante_from_level: None,
+            break_every_levels: None,
+            break_duration_minutes: 10,
+            denominations: STANDARD_DENOMINATIONS.to_vec(),
+        }
+    }
+}
+
+/// Chips that are no longer needed once a break is over and can be raced off
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorUp {
+    /// Level number of the break
+    pub level_number: i32,
+    pub denominations: Vec<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedBlindStructure {
+    pub levels: Vec<TournamentStructureLevel>,
+    pub color_ups: Vec<ColorUp>,
+}
+
+impl GeneratedBlindStructure {
+    pub fn total_duration_minutes(&self) -> i32 {
+        self.levels.iter().map(|level| level.duration_minutes).sum()
+    }
+}
+
+/// Generate a blind structure
+///
+/// # Returns
+///
+/// The levels numbered from 1, with a break level (blinds 0) after every
+/// `break_every_levels` playing levels except the last, and the colour-ups that can
+/// happen during each break. Antes are big blind antes. An error describes the first
+/// invalid parameter.
+pub fn generate_blind_structure(
+    params: &BlindStructureParams,
+) -> Result<GeneratedBlindStructure, String> {
+    let mut denominations = params.denominations.clone();
+    denominations.sort();
+    denominations.dedup();
+
+    if params.starting_stack <= 0 {
+        return Err("Starting stack must be positive".to_string());
+    }
+    if params.starting_stack > MAX_STARTING_STACK {
+        return Err(format!(
+            "Starting stack cannot exceed {}",
+            MAX_STARTING_STACK
+        ));
+    }
+    if params.expected_players < 2 {
+        return Err("At least two players are needed".to_string());
+    }
+    if params.expected_players > MAX_EXPECTED_PLAYERS {
+        return Err(format!(
+            "Expected players cannot exceed {}",
+            MAX_EXPECTED_PLAYERS
+        ));
+    }
+    if params.level_duration_minutes <= 0 {
+        return Err("Level duration must be positive".to_string());
+    }
+    if params.target_duration_minutes < params.level_duration_minutes {
+        return Err("Target duration must be at least one level long".to_string());
+    }
+    if matches!(params.ante_from_level, Some(level) if level < 1) {
+        return Err("Antes must start from level 1 or later".to_string());
+    }
+    if let Some(every) = params.break_every_levels {
+        if every < 1 {
+            return Err("Breaks must come after at least one level".to_string());
+        }
+        if params.break_duration_minutes <= 0 {
+            return Err("Break duration must be positive".to_string());
+        }
+    }
+    if denominations.first().is_none_or(|d| *d <= 0) {
+        return Err("Chip denominations must be positive".to_string());
+    }
+
+    let break_after = params.break_every_levels.map(|every| every as usize);
+    let playing_levels = playing_level_count(params, break_after);
+
+    // Blinds from ~100 big blinds deep to the field's chips being ~20 big blinds
+    let start_big_blind = (params.starting_stack as f64 / 100.0).max(2.0 * denominations[0] as f64);
+    let end_big_blind =
+        (params.starting_stack as f64 * params.expected_players as f64 / 20.0).max(start_big_blind);
+    let growth = if playing_levels > 1 {
+        (end_big_blind / start_big_blind).powf(1.0 / (playing_levels - 1) as f64)
+    } else {
+        1.0
+    };
+
+    let mut small_blinds: Vec<i32> = Vec::with_capacity(playing_levels);
+    let mut target = start_big_blind / 2.0;
+    for _ in 0..playing_levels {
+        let mut small_blind = round_small_blind(target, &denominations);
+        let mut bumped = target;
+        while small_blinds
+            .last()
+            .is_some_and(|last| small_blind <= *last as i64)
+        {
+            bumped *= 1.05;
+            small_blind = round_small_blind(bumped, &denominations);
+        }
+        // The big blind and the ante are twice the small blind
+        let small_blind = i32::try_from(small_blind)
+            .ok()
+            .filter(|small_blind| small_blind.checked_mul(2).is_some())
+            .ok_or_else(|| {
+                "Blinds grow too large, lower the starting stack or the chip values".to_string()
+            })?;
+        small_blinds.push(small_blind);
+        target *= growth;
+    }
+
+    let mut levels = Vec::new();
+    for (i, small_blind) in small_blinds.iter().enumerate() {
+        let playing_level = i as i32 + 1;
+        let big_blind = small_blind * 2;
+        let ante = match params.ante_from_level {
+            Some(from) if playing_level >= from => big_blind,
+            _ => 0,
+        };
+
+        levels.push(TournamentStructureLevel {
+            level_number: levels.len() as i32 + 1,
+            small_blind: *small_blind,
+            big_blind,
+            ante,
+            duration_minutes: params.level_duration_minutes,
+            is_break: false,
+            break_duration_minutes: None,
+        });
+
+        let is_last = i + 1 == playing_levels;
+        if break_after.is_some_and(|every| (i + 1) % every == 0) && !is_last {
+            levels.push(TournamentStructureLevel {
+                level_number: levels.len() as i32 + 1,
+                small_blind: 0,
+                big_blind: 0,
+                ante: 0,
+                duration_minutes: params.break_duration_minutes,
+                is_break: true,
+                break_duration_minutes: Some(params.break_duration_minutes),
+            });
+        }
+    }
+
+    let color_ups = color_ups(&levels, &denominations);
+
+    Ok(GeneratedBlindStructure { levels, color_ups })
+}
+
+/// Most playing levels (and the breaks between them) that fit in the target duration
+fn playing_level_count(params: &BlindStructureParams, break_after: Option<usize>) -> usize {
+    let breaks = |levels: usize| break_after.map_or(0, |every| (levels - 1) / every);
+    let duration = |levels: usize| {
+        levels as i64 * params.level_duration_minutes as i64
+            + breaks(levels) as i64 * params.break_duration_minutes as i64
+    };
+
+    let mut count = 1;
+    while count + 1 + breaks(count + 1) <= MAX_LEVELS
+        && duration(count + 1) <= params.target_duration_minutes as i64
+    {
+        count += 1;
+    }
+    count
+}
+
+/// Round a small blind to a "nice" value that can be paid with chips of its size
+///
+/// The value is first rounded to the nearest of 1, 1.5, 2, 2.5, 3, 4, 5, 6 or 8 times a
+/// power of ten, then to a multiple of the largest chip that still takes four or more
+/// to make up the small blind.
+fn round_small_blind(value: f64, denominations: &[i32]) -> i64 {
+    let smallest = denominations[0];
+    let value = value.max(smallest as f64);
+
+    let magnitude = 10f64.powf(value.log10().floor());
+    let nice = NICE_STEPS
+        .iter()
+        .chain(std::iter::once(&10.0))
+        .map(|step| step * magnitude)
+        .min_by(|a, b| {
+            let da = (a / value).ln().abs();
+            let db = (b / value).ln().abs();
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(value);
+
+    let chip = denominations
+        .iter()
+        .rev()
+        .find(|chip| (**chip as f64) * 4.0 <= nice)
+        .copied()
+        .unwrap_or(smallest);
+
+    ((nice / chip as f64).round() as i64)
+        .max(1)
+        .saturating_mul(chip as i64)
+}
+
+/// Smallest chip needed to pay every blind and ante from `levels` onwards
+fn smallest_chip_needed(levels: &[TournamentStructureLevel], denominations: &[i32]) -> i32 {
+    let divisor = levels
+        .iter()
+        .filter(|level| !level.is_break)
+        .flat_map(|level| [level.small_blind, level.big_blind, level.ante])
+        .filter(|amount| *amount > 0)
+        .fold(0, gcd);
+
+    denominations
+        .iter()
+        .rev()
+        .find(|chip| divisor % **chip == 0)
+        .copied()
+        .unwrap_or(denominations[0])
+}
+
+/// Colour-ups during each break: every chip smaller than the smallest one still needed
+fn color_ups(levels: &[TournamentStructureLevel], denominations: &[i32]) -> Vec<ColorUp> {
+    let mut in_play = smallest_chip_needed(levels, denominations);
+    let mut color_ups = Vec::new();
+
+    for (i, level) in levels.iter().enumerate() {
+        if !level.is_break {
+            continue;
+        }
+        let needed = smallest_chip_needed(&levels[i + 1..], denominations);
+        if needed > in_play {
+            color_ups.push(ColorUp {
+                level_number: level.level_number,
+                denominations: denominations
+                    .iter()
+                    .filter(|chip| **chip >= in_play && **chip < needed)
+                    .copied()
+                    .collect(),
+            });
+            in_play = needed;
+        }
+    }
+
+    color_ups
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blinds(structure: &GeneratedBlindStructure) -> Vec<i32> {
+        structure
+            .levels
+            .iter()
+            .map(|level| level.small_blind)
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_blind_structure() {
+        let structure = generate_blind_structure(&BlindStructureParams::default()).unwrap();
+
+        // 15 levels of 20 minutes fit in 5 hours, ending with 50 players' chips at 20 big blinds
+        assert_eq!(
+            blinds(&structure),
+            vec![
+                100, 150, 200, 300, 500, 800, 1000, 1500, 2500, 4000, 5000, 8000, 10000, 15000,
+                25000
+            ]
+        );
+        assert!(structure
+            .levels
+            .iter()
+            .all(|l| l.big_blind == 2 * l.small_blind));
+        assert_eq!(structure.total_duration_minutes(), 300);
+        assert!(structure.color_ups.is_empty());
+    }
+
+    #[test]
+    fn test_breaks_antes_and_color_ups() {
+        let structure = generate_blind_structure(&BlindStructureParams {
+            ante_from_level: Some(5),
+            break_every_levels: Some(4),
+            break_duration_minutes: 10,
+            ..Default::default()
+        })
+        .unwrap();
+
+        // 13 levels and 3 breaks fit in 5 hours
+        assert_eq!(structure.levels.len(), 16);
+        assert_eq!(structure.total_duration_minutes(), 290);
+
+        let breaks: Vec<i32> = structure
+            .levels
+            .iter()
+            .filter(|level| level.is_break)
+            .map(|level| level.level_number)
+            .collect();
+        assert_eq!(breaks, vec![5, 10, 15]);
+        assert_eq!(structure.levels[4].break_duration_minutes, Some(10));
+
+        // Big blind antes from the fifth playing level, which comes after the first break
+        assert_eq!(structure.levels[3].ante, 0);
+        assert_eq!(structure.levels[5].ante, structure.levels[5].big_blind);
+
+        assert_eq!(
+            structure.color_ups,
+            vec![
+                ColorUp {
+                    level_number: 5,
+                    denominations: vec![25]
+                },
+                ColorUp {
+                    level_number: 10,
+                    denominations: vec![100, 500]
+                },
+                ColorUp {
+                    level_number: 15,
+                    denominations: vec![1000, 5000]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_small_blind() {
+        let chips = STANDARD_DENOMINATIONS;
+        assert_eq!(round_small_blind(10.0, &chips), 25);
+        assert_eq!(round_small_blind(158.0, &chips), 150);
+        assert_eq!(round_small_blind(630.0, &chips), 600);
+        assert_eq!(round_small_blind(1584.0, &chips), 1500);
+        assert_eq!(round_small_blind(2510.0, &chips), 2500);
+
+        // Values that can't be paid with the chips in play are snapped to them
+        assert_eq!(round_small_blind(60.0, &chips), 50);
+        assert_eq!(round_small_blind(15.0, &[10, 50]), 20);
+    }
+
+    #[test]
+    fn test_blinds_always_increase() {
+        // A long, slow structure would round several targets to the same value
+        let structure = generate_blind_structure(&BlindStructureParams {
+            starting_stack: 5_000,
+            expected_players: 4,
+            target_duration_minutes: 600,
+            level_duration_minutes: 10,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(structure
+            .levels
+            .windows(2)
+            .all(|pair| pair[1].small_blind > pair[0].small_blind));
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let invalid = [
+            BlindStructureParams {
+                starting_stack: 0,
+                ..Default::default()
+            },
+            BlindStructureParams {
+                starting_stack: MAX_STARTING_STACK + 1,
+                ..Default::default()
+            },
+            BlindStructureParams {
+                expected_players: 1,
+                ..Default::default()
+            },
+            BlindStructureParams {
+                expected_players: i32::MAX,
+                ..Default::default()
+            },
+            BlindStructureParams {
+                target_duration_minutes: 10,
+                ..Default::default()
+            },
+            BlindStructureParams {
+                break_every_levels: Some(0),
+                ..Default::default()
+            },
+            BlindStructureParams {
+                denominations: Vec::new(),
+                ..Default::default()
+            },
+        ];
+
+        for params in invalid {
+            assert!(generate_blind_structure(&params).is_err(), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn test_blinds_too_large_for_i32() {
+        // The largest field's chips would need a big blind above i32::MAX
+        let result = generate_blind_structure(&BlindStructureParams {
+            starting_stack: MAX_STARTING_STACK,
+            expected_players: MAX_EXPECTED_PLAYERS,
+            ..Default::default()
+        });
+        assert!(result.is_err());
+
+        // A single huge chip makes every small blind too large to double
+        let result = generate_blind_structure(&BlindStructureParams {
+            denominations: vec![1_500_000_000],
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
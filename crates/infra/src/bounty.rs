@@ -0,0 +1,128 @@
+//! Knockout bounties
+//!
+//! Part of each buy-in is put on the player's head. Whoever eliminates them collects it:
+//! in full for a regular bounty, half in cash and half on their own head for a progressive
+//! knockout (PKO). All amounts are in cents.
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BountyFormat {
+    /// No bounties
+    None,
+    /// The eliminator collects the whole bounty
+    Regular,
+    /// Progressive knockout: half is paid, half goes on the eliminator's head
+    Progressive,
+}
+
+impl BountyFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BountyFormat::None => "none",
+            BountyFormat::Regular => "regular",
+            BountyFormat::Progressive => "progressive",
+        }
+    }
+}
+
+impl FromStr for BountyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(BountyFormat::None),
+            "regular" => Ok(BountyFormat::Regular),
+            "progressive" => Ok(BountyFormat::Progressive),
+            _ => Err(format!("Unknown bounty format: {}", s)),
+        }
+    }
+}
+
+/// What a knockout pays the eliminator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KnockoutSplit {
+    /// Paid out to the eliminator
+    pub cash_cents: i32,
+    /// Added to the eliminator's own bounty
+    pub added_cents: i32,
+}
+
+/// Split the bounty of an eliminated player
+///
+/// In a PKO the odd cent goes on the eliminator's head, so the bounty never shrinks.
+///
+/// # Examples
+///
+/// ```
+/// use infra::bounty::{split_knockout, BountyFormat, KnockoutSplit};
+///
+/// assert_eq!(
+///     split_knockout(BountyFormat::Progressive, 2501),
+///     KnockoutSplit { cash_cents: 1250, added_cents: 1251 }
+/// );
+/// ```
+pub fn split_knockout(format: BountyFormat, bounty_cents: i32) -> KnockoutSplit {
+    let bounty_cents = bounty_cents.max(0);
+
+    match format {
+        BountyFormat::None => KnockoutSplit::default(),
+        BountyFormat::Regular => KnockoutSplit {
+            cash_cents: bounty_cents,
+            added_cents: 0,
+        },
+        BountyFormat::Progressive => {
+            let cash_cents = bounty_cents / 2;
+            KnockoutSplit {
+                cash_cents,
+                added_cents: bounty_cents - cash_cents,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_knockout() {
+        assert_eq!(
+            split_knockout(BountyFormat::None, 2000),
+            KnockoutSplit::default()
+        );
+        assert_eq!(
+            split_knockout(BountyFormat::Regular, 2000),
+            KnockoutSplit {
+                cash_cents: 2000,
+                added_cents: 0
+            }
+        );
+        assert_eq!(
+            split_knockout(BountyFormat::Progressive, 2000),
+            KnockoutSplit {
+                cash_cents: 1000,
+                added_cents: 1000
+            }
+        );
+    }
+
+    #[test]
+    fn test_progressive_bounties_grow() {
+        // A player who knocks out two starting bounties of 20 carries 40 on their head
+        let first = split_knockout(BountyFormat::Progressive, 2000);
+        let second = split_knockout(BountyFormat::Progressive, 2000);
+        let own = 2000 + first.added_cents + second.added_cents;
+        assert_eq!(own, 4000);
+
+        // Knocking them out pays half of it
+        assert_eq!(
+            split_knockout(BountyFormat::Progressive, own).cash_cents,
+            2000
+        );
+        assert_eq!(
+            split_knockout(BountyFormat::Progressive, -5),
+            KnockoutSplit::default()
+        );
+    }
+}
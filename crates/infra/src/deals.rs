@@ -0,0 +1,109 @@
+//! Final table deal calculations other than ICM (see [`crate::icm`])
+//!
+//! All amounts are in cents and every deal sums exactly to the prizes the remaining
+//! players can still win.
+
+use crate::icm::distribute_cents;
+
+/// Chip chop: everyone locks up the last remaining prize, the rest follows the chips
+///
+/// # Arguments
+///
+/// * `stacks` - Chip stack of each remaining player (negative stacks count as 0)
+/// * `prizes_cents` - Remaining payout ladder in cents, first place first
+///
+/// # Returns
+///
+/// One amount per player, in the order of `stacks`. Places beyond the number of
+/// players are never paid.
+///
+/// # Examples
+///
+/// ```
+/// use infra::deals::chip_chop_deal;
+///
+/// // Each player locks up 2000, the remaining 4000 goes 60/40 by chips
+/// assert_eq!(chip_chop_deal(&[6000, 4000], &[6000, 2000]), vec![4400, 3600]);
+/// ```
+pub fn chip_chop_deal(stacks: &[i32], prizes_cents: &[i32]) -> Vec<i32> {
+    let n = stacks.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let paid = &prizes_cents[..prizes_cents.len().min(n)];
+    let total: i32 = paid.iter().sum();
+
+    // The prize for the lowest remaining place, or nothing if it isn't paid
+    let guaranteed = if paid.len() == n { paid[n - 1] } else { 0 };
+    let remainder = total - guaranteed * n as i32;
+
+    let shares: Vec<f64> = stacks.iter().map(|s| (*s).max(0) as f64).collect();
+    distribute_cents(&shares, remainder)
+        .into_iter()
+        .map(|share| guaranteed + share)
+        .collect()
+}
+
+/// Even split: the prizes on offer are shared equally regardless of stacks
+///
+/// # Examples
+///
+/// ```
+/// use infra::deals::even_split_deal;
+///
+/// assert_eq!(even_split_deal(3, &[5000, 3000, 2001]), vec![3334, 3334, 3333]);
+/// ```
+pub fn even_split_deal(players: usize, prizes_cents: &[i32]) -> Vec<i32> {
+    if players == 0 {
+        return Vec::new();
+    }
+
+    let total: i32 = prizes_cents[..prizes_cents.len().min(players)].iter().sum();
+    distribute_cents(&vec![1.0; players], total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chip_chop_deal() {
+        // 3 players, 50/30/20 ladder: 2000 each locked, 4000 split by chips
+        let deal = chip_chop_deal(&[5000, 3000, 2000], &[5000, 3000, 2000]);
+        assert_eq!(deal, vec![4000, 3200, 2800]);
+        assert_eq!(deal.iter().sum::<i32>(), 10000);
+
+        // Rounding still adds up to the ladder
+        let deal = chip_chop_deal(&[1000, 1000, 1000], &[5000, 3000, 2001]);
+        assert_eq!(deal, vec![3334, 3334, 3333]);
+    }
+
+    #[test]
+    fn test_chip_chop_deal_unpaid_last_place() {
+        // Fewer paid places than players: pure chip split of the ladder
+        let deal = chip_chop_deal(&[3000, 1000], &[10000]);
+        assert_eq!(deal, vec![7500, 2500]);
+    }
+
+    #[test]
+    fn test_chip_chop_deal_edge_cases() {
+        assert!(chip_chop_deal(&[], &[10000]).is_empty());
+
+        // Unreachable places are ignored
+        assert_eq!(
+            chip_chop_deal(&[6000, 4000], &[6000, 2000, 1000]),
+            vec![4400, 3600]
+        );
+
+        // Without chip counts the remainder is shared evenly
+        assert_eq!(chip_chop_deal(&[0, 0], &[4000, 2000]), vec![3000, 3000]);
+    }
+
+    #[test]
+    fn test_even_split_deal() {
+        assert_eq!(even_split_deal(2, &[7000, 3000]), vec![5000, 5000]);
+        assert_eq!(even_split_deal(2, &[7000, 3000, 1000]), vec![5000, 5000]);
+        assert!(even_split_deal(0, &[7000]).is_empty());
+    }
+}
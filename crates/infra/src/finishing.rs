@@ -0,0 +1,238 @@
+//! Finishing positions of eliminated players
+//!
+//! A player busting with 10 players left finishes 10th. When several players bust in the
+//! same hand, players at the same table are ranked by their stack at the start of the
+//! hand (bigger stack finishes higher). Players at different tables tie with the players
+//! holding the same rank from the bottom of their own table, so the shortest stack of
+//! each table ties for the last places.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// A player busting out, with the table and the stack they started the hand with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bust {
+    pub table_id: Uuid,
+    pub stack_size: Option<i32>,
+}
+
+impl Bust {
+    /// Players busting in the same hand at this player's table with a smaller stack,
+    /// which this player finishes ahead of
+    fn rank(&self, busts: &[Bust]) -> usize {
+        busts
+            .iter()
+            .filter(|other| {
+                other.table_id == self.table_id
+                    && other.stack_size.unwrap_or(0) < self.stack_size.unwrap_or(0)
+            })
+            .count()
+    }
+}
+
+/// Finishing position of each player busting in the same hand, in the order given.
+///
+/// Tied players share the best position they compete for, so two players busting at
+/// different tables with 10 left both finish 9th. Together the positions cover exactly
+/// the places of the players busting: a tie at 9th takes 9th and 10th.
+///
+/// # Examples
+///
+/// ```
+/// use infra::finishing::{finishing_positions, Bust};
+/// use uuid::Uuid;
+///
+/// let table = Uuid::new_v4();
+/// let busts = [
+///     Bust { table_id: table, stack_size: Some(3000) },
+///     Bust { table_id: table, stack_size: Some(5000) },
+/// ];
+/// assert_eq!(finishing_positions(10, &busts), vec![10, 9]);
+/// ```
+pub fn finishing_positions(players_remaining: i32, busts: &[Bust]) -> Vec<i32> {
+    let ranks: Vec<usize> = busts.iter().map(|bust| bust.rank(busts)).collect();
+
+    ranks
+        .iter()
+        .map(|rank| {
+            let at_or_below = ranks.iter().filter(|other| *other <= rank).count() as i32;
+            (players_remaining - at_or_below + 1).max(1)
+        })
+        .collect()
+}
+
+/// Prize of each finisher, in the order of `positions`, from the prize of every paid
+/// place.
+///
+/// Tied players share the prizes of all the places they cover: two players tied for
+/// 3rd split the 3rd and 4th place prizes. Leftover cents go to the first players of
+/// the tie.
+///
+/// # Examples
+///
+/// ```
+/// use infra::finishing::split_prizes;
+/// use std::collections::HashMap;
+///
+/// let ladder = HashMap::from([(1, 5000), (2, 3000), (3, 2001)]);
+/// assert_eq!(split_prizes(&[1, 2, 2, 4], &ladder), vec![5000, 2501, 2500, 0]);
+/// ```
+pub fn split_prizes(positions: &[i32], ladder: &HashMap<i32, i32>) -> Vec<i32> {
+    let mut tied: HashMap<i32, i32> = HashMap::new();
+    for position in positions {
+        *tied.entry(*position).or_default() += 1;
+    }
+
+    let mut paid: HashMap<i32, i32> = HashMap::new();
+    positions
+        .iter()
+        .map(|position| {
+            let players = tied[position];
+            let total: i32 = (*position..*position + players)
+                .map(|place| ladder.get(&place).copied().unwrap_or(0))
+                .sum();
+            let already_paid = paid.entry(*position).or_default();
+            let share = total / players + i32::from(*already_paid < total % players);
+            *already_paid += 1;
+            share
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_bust_finishes_last_of_the_remaining() {
+        let bust = Bust {
+            table_id: Uuid::new_v4(),
+            stack_size: Some(1000),
+        };
+        assert_eq!(finishing_positions(10, &[bust]), vec![10]);
+        assert_eq!(finishing_positions(2, &[bust]), vec![2]);
+    }
+
+    #[test]
+    fn test_busts_at_different_tables_tie() {
+        let busts = [
+            Bust {
+                table_id: Uuid::new_v4(),
+                stack_size: Some(1000),
+            },
+            Bust {
+                table_id: Uuid::new_v4(),
+                stack_size: Some(8000),
+            },
+        ];
+        assert_eq!(finishing_positions(10, &busts), vec![9, 9]);
+    }
+
+    #[test]
+    fn test_mixed_busts() {
+        let table_a = Uuid::new_v4();
+        let table_b = Uuid::new_v4();
+        let busts = [
+            Bust {
+                table_id: table_a,
+                stack_size: Some(3000),
+            },
+            Bust {
+                table_id: table_b,
+                stack_size: Some(2000),
+            },
+            Bust {
+                table_id: table_a,
+                stack_size: Some(5000),
+            },
+            Bust {
+                table_id: table_a,
+                stack_size: Some(5000),
+            },
+        ];
+        // Equal stacks at the same table tie as well, the shortest stacks of both tables
+        // tie for the last places
+        assert_eq!(finishing_positions(20, &busts), vec![19, 19, 17, 17]);
+    }
+
+    #[test]
+    fn test_positions_cover_the_places_of_the_busts() {
+        let table_a = Uuid::new_v4();
+        let table_b = Uuid::new_v4();
+        let bust = |table_id, stack_size| Bust {
+            table_id,
+            stack_size: Some(stack_size),
+        };
+        let hands = [
+            vec![
+                bust(table_a, 1000),
+                bust(table_a, 5000),
+                bust(table_b, 3000),
+            ],
+            vec![
+                bust(table_a, 3000),
+                bust(table_b, 2000),
+                bust(table_a, 5000),
+                bust(table_a, 5000),
+            ],
+            vec![
+                bust(table_a, 1000),
+                bust(table_a, 2000),
+                bust(table_a, 3000),
+                bust(table_b, 500),
+                bust(table_b, 9000),
+            ],
+        ];
+
+        for busts in hands {
+            let players_remaining = 10;
+            let positions = finishing_positions(players_remaining, &busts);
+
+            // Each tie takes as many places as it has players, from its position on
+            let mut distinct = positions.clone();
+            distinct.sort();
+            distinct.dedup();
+            let places: Vec<i32> = distinct
+                .iter()
+                .flat_map(|position| {
+                    let tied = positions.iter().filter(|other| *other == position).count();
+                    *position..*position + tied as i32
+                })
+                .collect();
+            let expected: Vec<i32> =
+                (players_remaining - busts.len() as i32 + 1..=players_remaining).collect();
+            assert_eq!(places, expected, "{:?}", positions);
+        }
+
+        assert_eq!(
+            finishing_positions(
+                10,
+                &[
+                    bust(table_a, 1000),
+                    bust(table_a, 5000),
+                    bust(table_b, 3000)
+                ]
+            ),
+            vec![9, 8, 9]
+        );
+    }
+
+    #[test]
+    fn test_split_prizes() {
+        let ladder = HashMap::from([(1, 5000), (2, 3000), (3, 2000)]);
+        assert_eq!(
+            split_prizes(&[1, 2, 3, 4], &ladder),
+            vec![5000, 3000, 2000, 0]
+        );
+
+        // Players tied on the bubble share the last paid place
+        assert_eq!(
+            split_prizes(&[3, 3, 1, 2], &ladder),
+            vec![1000, 1000, 5000, 3000]
+        );
+
+        // Nothing paid without a ladder
+        assert_eq!(split_prizes(&[1, 2], &HashMap::new()), vec![0, 0]);
+    }
+}
@@ -0,0 +1,237 @@
+//! Independent Chip Model (Malmuth-Harville) deal calculation
+//!
+//! The probability that a player finishes first is their share of the chips in play.
+//! Given a first place finisher, the remaining places are resolved recursively on the
+//! remaining stacks. A player's equity is the sum over all finishing places of the
+//! probability of finishing there multiplied by the prize for that place.
+//!
+//! # Examples
+//!
+//! ```
+//! use infra::icm::icm_deal;
+//!
+//! // Stacks 5000/3000/2000 playing for 50/30/20 (in cents)
+//! assert_eq!(icm_deal(&[5000, 3000, 2000], &[5000, 3000, 2000]), vec![3839, 3275, 2886]);
+//! ```
+
+/// Largest field the model is evaluated for. The computation walks every subset of
+/// players, so this keeps a deal at a final table well under a second.
+pub const MAX_PLAYERS: usize = 20;
+
+/// Compute each player's ICM equity, in the same unit as `prizes`
+///
+/// # Arguments
+///
+/// * `stacks` - Chip stack of each remaining player (negative stacks count as 0)
+/// * `prizes` - Prize for each remaining place, first place first
+///
+/// # Returns
+///
+/// One equity per player, in the order of `stacks`. Empty if there are no players or
+/// more than [`MAX_PLAYERS`]. Places beyond the number of players are never paid.
+pub fn icm_equities(stacks: &[i32], prizes: &[f64]) -> Vec<f64> {
+    let n = stacks.len();
+    if n == 0 || n > MAX_PLAYERS {
+        return Vec::new();
+    }
+
+    let stacks: Vec<f64> = stacks.iter().map(|s| (*s).max(0) as f64).collect();
+    let paid_places = prizes.len().min(n);
+    let full = (1usize << n) - 1;
+
+    // probabilities[mask] = probability that exactly the players in `mask` are still
+    // unplaced once the places above them have been handed out
+    let mut probabilities = vec![0.0f64; full + 1];
+    probabilities[full] = 1.0;
+    let mut equities = vec![0.0f64; n];
+
+    // Removing a player always yields a smaller mask, so walking masks downwards
+    // visits every set after all of its supersets
+    for mask in (1..=full).rev() {
+        let probability = probabilities[mask];
+        if probability == 0.0 {
+            continue;
+        }
+
+        let place = n - mask.count_ones() as usize;
+        if place >= paid_places {
+            continue;
+        }
+
+        let players: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+        let chips_in_play: f64 = players.iter().map(|i| stacks[*i]).sum();
+
+        for &i in &players {
+            // When nobody left has chips the remaining places are a coin flip
+            let share = if chips_in_play > 0.0 {
+                stacks[i] / chips_in_play
+            } else {
+                1.0 / players.len() as f64
+            };
+            let finish_probability = probability * share;
+
+            equities[i] += finish_probability * prizes[place];
+            probabilities[mask & !(1 << i)] += finish_probability;
+        }
+    }
+
+    equities
+}
+
+/// Compute an ICM deal in cents that sums exactly to the prizes on offer
+///
+/// # Arguments
+///
+/// * `stacks` - Chip stack of each remaining player
+/// * `prizes_cents` - Remaining payout ladder in cents, first place first
+///
+/// # Returns
+///
+/// One amount per player, in the order of `stacks`. The amounts sum to the prizes for
+/// the places the remaining players can still finish in. Empty if there are no players
+/// or more than [`MAX_PLAYERS`].
+pub fn icm_deal(stacks: &[i32], prizes_cents: &[i32]) -> Vec<i32> {
+    let paid = &prizes_cents[..prizes_cents.len().min(stacks.len())];
+    let prizes: Vec<f64> = paid.iter().map(|p| *p as f64).collect();
+    let equities = icm_equities(stacks, &prizes);
+    if equities.is_empty() {
+        return Vec::new();
+    }
+
+    distribute_cents(
+        &equities,
+        paid.iter().map(|p| *p as i64).sum::<i64>() as i32,
+    )
+}
+
+/// Round fractional amounts to whole cents so they sum exactly to `total_cents`
+///
+/// Every amount is rounded down, then the leftover cents go one at a time to the
+/// amounts with the largest fractional remainder (earliest entry first on ties).
+/// Amounts are scaled first if they don't already sum to `total_cents`.
+///
+/// # Examples
+///
+/// ```
+/// use infra::icm::distribute_cents;
+///
+/// assert_eq!(distribute_cents(&[1.0, 1.0, 1.0], 100), vec![34, 33, 33]);
+/// ```
+pub fn distribute_cents(amounts: &[f64], total_cents: i32) -> Vec<i32> {
+    if amounts.is_empty() {
+        return Vec::new();
+    }
+
+    let sum: f64 = amounts.iter().sum();
+    let scaled: Vec<f64> = if sum > 0.0 {
+        amounts
+            .iter()
+            .map(|a| a * total_cents as f64 / sum)
+            .collect()
+    } else {
+        vec![total_cents as f64 / amounts.len() as f64; amounts.len()]
+    };
+    // Snap away floating point noise so 3275.0 isn't floored to 3274
+    let scaled: Vec<f64> = scaled.iter().map(|a| (a * 1e6).round() / 1e6).collect();
+
+    let mut cents: Vec<i32> = scaled.iter().map(|a| a.floor() as i32).collect();
+    let mut leftover = total_cents - cents.iter().sum::<i32>();
+
+    let mut by_remainder: Vec<usize> = (0..scaled.len()).collect();
+    by_remainder.sort_by(|a, b| {
+        let ra = scaled[*a] - scaled[*a].floor();
+        let rb = scaled[*b] - scaled[*b].floor();
+        rb.partial_cmp(&ra)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.cmp(b))
+    });
+
+    for i in by_remainder.iter().cycle() {
+        if leftover <= 0 {
+            break;
+        }
+        cents[*i] += 1;
+        leftover -= 1;
+    }
+
+    cents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() < 1e-9,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn test_icm_equities_reference_values() {
+        // Textbook three-handed example: 50/30/20 split on 5000/3000/2000
+        assert_close(
+            &icm_equities(&[5000, 3000, 2000], &[50.0, 30.0, 20.0]),
+            &[1075.0 / 28.0, 131.0 / 4.0, 202.0 / 7.0],
+        );
+
+        // Four players, three paid places
+        assert_close(
+            &icm_equities(&[4000, 3000, 2000, 1000], &[50.0, 30.0, 20.0]),
+            &[2117.0 / 63.0, 2477.0 / 84.0, 1486.0 / 63.0, 373.0 / 28.0],
+        );
+    }
+
+    #[test]
+    fn test_icm_equities_heads_up() {
+        // Heads-up: second prize plus chip share of the difference
+        assert_close(
+            &icm_equities(&[6000, 2000], &[650.0, 350.0]),
+            &[575.0, 425.0],
+        );
+        assert_close(&icm_equities(&[1000, 1000], &[70.0, 30.0]), &[50.0, 50.0]);
+    }
+
+    #[test]
+    fn test_icm_equities_edge_cases() {
+        assert!(icm_equities(&[], &[100.0]).is_empty());
+        assert!(icm_equities(&[1; MAX_PLAYERS + 1], &[100.0]).is_empty());
+
+        // A busted stack can only take the last place
+        assert_close(&icm_equities(&[1000, 0], &[70.0, 30.0]), &[70.0, 30.0]);
+
+        // No chips anywhere falls back to an even split
+        assert_close(&icm_equities(&[0, 0], &[70.0, 30.0]), &[50.0, 50.0]);
+    }
+
+    #[test]
+    fn test_icm_deal_sums_to_prizes() {
+        let deal = icm_deal(&[4000, 3000, 2000, 1000], &[5000, 3000, 2000]);
+        assert_eq!(deal, vec![3360, 2949, 2359, 1332]);
+        assert_eq!(deal.iter().sum::<i32>(), 10000);
+
+        // Equal stacks with an indivisible ladder still add up
+        let deal = icm_deal(&[2000, 2000, 2000], &[5000, 3000, 2001]);
+        assert_eq!(deal, vec![3334, 3334, 3333]);
+    }
+
+    #[test]
+    fn test_icm_deal_ignores_unreachable_places() {
+        // Two players left can't collect the third prize
+        let deal = icm_deal(&[6000, 2000], &[65000, 35000, 10000]);
+        assert_eq!(deal, vec![57500, 42500]);
+    }
+
+    #[test]
+    fn test_distribute_cents() {
+        assert_eq!(distribute_cents(&[33.4, 33.3, 33.3], 100), vec![34, 33, 33]);
+        assert_eq!(distribute_cents(&[0.0, 0.0], 101), vec![51, 50]);
+        assert!(distribute_cents(&[], 100).is_empty());
+    }
+}
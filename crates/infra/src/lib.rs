@@ -1,5 +1,12 @@
+pub mod blind_structure;
+pub mod bounty;
 pub mod db;
+pub mod deals;
+pub mod finishing;
+pub mod icm;
 pub mod models;
 pub mod pagination;
+pub mod profile;
 pub mod repos;
 pub mod scoring;
+pub mod standings;
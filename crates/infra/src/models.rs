@@ -1,5 +1,5 @@
 use crate::repos::tournaments::TournamentLiveStatus;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -14,6 +14,15 @@ pub struct ClubRow {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TagRow {
+    pub id: Uuid,
+    pub slug: String,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TournamentRow {
     pub id: Uuid,
@@ -25,6 +34,22 @@ pub struct TournamentRow {
     pub buy_in_cents: i32,
     pub seat_cap: Option<i32>,
     pub live_status: TournamentLiveStatus,
+    pub unregister_cutoff_minutes: i32,
+    pub max_reentries: i32,
+    pub max_rebuys: i32,
+    pub rebuy_cents: Option<i32>,
+    pub add_on_cents: Option<i32>,
+    pub rebuy_until_level: Option<i32>,
+    pub rake_cents: i32,
+    pub staff_fee_cents: i32,
+    /// Buy-in minus rake, staff fee and bounty, computed by the database
+    pub prize_contribution_cents: i32,
+    pub add_on_chips: Option<i32>,
+    pub guarantee_cents: Option<i32>,
+    /// "none", "regular" or "progressive", see [`crate::bounty::BountyFormat`]
+    pub bounty_format: String,
+    /// Part of the buy-in put on the player's head
+    pub bounty_cents: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,6 +67,7 @@ impl TournamentRow {
             | LiveStatus::Break
             | LiveStatus::FinalTable => TournamentStatus::InProgress,
             LiveStatus::Finished => TournamentStatus::Completed,
+            LiveStatus::Cancelled => TournamentStatus::Cancelled,
         }
     }
 }
@@ -72,6 +98,47 @@ pub struct TournamentRegistrationRow {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TournamentEntryRow {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub registration_id: Uuid,
+    pub user_id: Uuid,
+    pub entry_type: String,
+    pub amount_cents: i32,
+    pub rake_cents: i32,
+    pub staff_fee_cents: i32,
+    pub bounty_cents: i32,
+    /// Amount minus rake, staff fee and bounty, computed by the database
+    pub prize_cents: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TournamentEliminationRow {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub registration_id: Uuid,
+    pub user_id: Uuid,
+    pub eliminated_by: Option<Uuid>,
+    /// Bounty paid to the eliminator
+    pub bounty_cents: i32,
+    /// Bounty added to the eliminator's own bounty (progressive knockouts)
+    pub bounty_added_cents: i32,
+    /// Clock level at the bust
+    pub level: Option<i32>,
+    pub finishing_position: Option<i32>,
+    /// Seat the player lost, restored when the elimination is undone
+    pub seat_assignment_id: Option<Uuid>,
+    /// Stack at the start of the hand
+    pub stack_size: Option<i32>,
+    /// Registration status before the bust
+    pub previous_status: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TournamentResultRow {
     pub id: Uuid,
@@ -79,12 +146,35 @@ pub struct TournamentResultRow {
     pub user_id: Uuid,
     pub final_position: i32,
     pub prize_cents: i32,
+    /// Bounties won, on top of the prize
+    pub bounty_cents: i32,
     pub points: i32,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TournamentResultCorrectionRow {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub result_id: Uuid,
+    pub user_id: Uuid,
+    /// "correct" or "delete"
+    pub action: String,
+    pub old_final_position: i32,
+    pub new_final_position: Option<i32>,
+    pub old_prize_cents: i32,
+    pub new_prize_cents: Option<i32>,
+    pub old_bounty_cents: i32,
+    pub new_bounty_cents: Option<i32>,
+    pub old_notes: Option<String>,
+    pub new_notes: Option<String>,
+    pub reason: Option<String>,
+    pub corrected_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct PayoutTemplateRow {
     pub id: Uuid,
@@ -158,6 +248,28 @@ pub struct TableSeatAssignmentRow {
     pub assigned_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TournamentSeriesRow {
+    pub id: Uuid,
+    pub club_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub buy_in_cents: i32,
+    pub seat_cap: Option<i32>,
+    pub duration_minutes: Option<i32>,
+    pub blind_structure: serde_json::Value, // JSONB array of structure levels
+    pub payout_template_id: Option<Uuid>,
+    pub weekdays: Vec<i32>, // ISO weekdays, 1 = Monday
+    pub local_start_time: NaiveTime,
+    pub timezone: String,
+    pub weeks_ahead: i32,
+    pub is_paused: bool,
+    pub materialized_until: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TournamentStructureRow {
     pub id: Uuid,
@@ -188,6 +300,29 @@ pub struct TournamentClockRow {
     pub updated_at: DateTime<Utc>,
 }
 
+impl TournamentClockRow {
+    /// Highest level that has been (or is being) played; 0 before the clock first starts
+    pub fn last_played_level(&self) -> i32 {
+        if self.clock_status == "stopped" && self.level_started_at.is_none() {
+            self.current_level - 1
+        } else {
+            self.current_level
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct BlindStructureTemplateRow {
+    pub id: Uuid,
+    pub club_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub levels: serde_json::Value, // JSONB array of structure levels
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TournamentClockEventRow {
     pub id: Uuid,
@@ -220,7 +355,68 @@ pub struct TournamentPayoutRow {
     pub template_id: Option<Uuid>,
     pub player_count: i32,
     pub total_prize_pool: i32,
+    /// Part of the prize pool paid by the club to cover the guarantee
+    pub overlay_cents: i32,
     pub payout_positions: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ClubScoringSettingsRow {
+    pub club_id: Uuid,
+    pub formula: String,
+    pub cap: i32,
+    pub multiplier: f64,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SeasonRow {
+    pub id: Uuid,
+    pub club_id: Uuid,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub best_results: Option<i32>,
+    pub min_events: i32,
+    pub drop_worst: i32,
+    pub scoring_formula: Option<String>,
+    pub scoring_cap: Option<i32>,
+    pub scoring_multiplier: Option<f64>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub closed_by: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SeasonRow {
+    pub fn rules(&self) -> crate::standings::SeasonRules {
+        crate::standings::SeasonRules {
+            best_results: self.best_results.map(|best| best.max(0) as u32),
+            min_events: self.min_events.max(0) as u32,
+            drop_worst: self.drop_worst.max(0) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SeasonStandingRow {
+    pub season_id: Uuid,
+    pub user_id: Uuid,
+    pub rank: Option<i32>,
+    pub points: i32,
+    pub events_played: i32,
+    pub results_counted: i32,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UserPrivacySettingsRow {
+    pub user_id: Uuid,
+    pub hide_winnings: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
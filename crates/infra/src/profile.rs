@@ -0,0 +1,368 @@
+//! Player profile statistics
+//!
+//! A profile is built from a player's results: lifetime and per-club totals, profit
+//! over time, biggest cashes, how often they finish in each position and the points
+//! they scored each calendar year. Amounts are in cents.
+
+use chrono::{DateTime, Datelike, Utc};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Positions that make the final table
+pub const FINAL_TABLE_SIZE: i32 = 9;
+
+/// One result of a player, with what they paid to play
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ProfileResult {
+    pub tournament_id: Uuid,
+    pub tournament_name: String,
+    pub club_id: Uuid,
+    pub club_name: String,
+    pub start_time: DateTime<Utc>,
+    pub final_position: i32,
+    /// Entries in the tournament, re-entries included
+    pub field_size: i32,
+    /// Buy-ins, re-entries, rebuys and add-ons the player paid
+    pub buy_in_cents: i64,
+    pub prize_cents: i64,
+    pub bounty_cents: i64,
+    pub points: i32,
+}
+
+impl ProfileResult {
+    /// Prize and bounties
+    pub fn winnings_cents(&self) -> i64 {
+        self.prize_cents + self.bounty_cents
+    }
+
+    /// Winnings minus what the player paid
+    pub fn profit_cents(&self) -> i64 {
+        self.winnings_cents() - self.buy_in_cents
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileStats {
+    pub tournaments: u32,
+    /// Results paid a prize
+    pub itm: u32,
+    pub wins: u32,
+    pub final_tables: u32,
+    pub total_buy_ins: i64,
+    pub total_winnings: i64,
+    pub total_bounty_winnings: i64,
+    pub net_profit: i64,
+    pub itm_percentage: f64,
+    pub roi_percentage: f64,
+    pub average_finish: f64,
+    pub points: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClubStats {
+    pub club_id: Uuid,
+    pub club_name: String,
+    pub stats: ProfileStats,
+}
+
+/// Profit after one result, for graphs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfitPoint {
+    pub tournament_id: Uuid,
+    pub date: DateTime<Utc>,
+    pub profit_cents: i64,
+    pub cumulative_profit_cents: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionCount {
+    pub position: i32,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YearPoints {
+    pub year: i32,
+    pub points: i64,
+    pub tournaments: u32,
+}
+
+/// Totals over a set of results
+///
+/// # Examples
+///
+/// ```
+/// use infra::profile::profile_stats;
+///
+/// let stats = profile_stats(&[]);
+/// assert_eq!(stats.tournaments, 0);
+/// assert_eq!(stats.roi_percentage, 0.0);
+/// ```
+pub fn profile_stats(results: &[ProfileResult]) -> ProfileStats {
+    if results.is_empty() {
+        return ProfileStats::default();
+    }
+
+    let mut stats = ProfileStats {
+        tournaments: results.len() as u32,
+        ..ProfileStats::default()
+    };
+    let mut positions = 0i64;
+    for result in results {
+        if result.prize_cents > 0 {
+            stats.itm += 1;
+        }
+        if result.final_position == 1 {
+            stats.wins += 1;
+        }
+        if result.final_position <= FINAL_TABLE_SIZE {
+            stats.final_tables += 1;
+        }
+        stats.total_buy_ins += result.buy_in_cents;
+        stats.total_winnings += result.prize_cents;
+        stats.total_bounty_winnings += result.bounty_cents;
+        stats.net_profit += result.profit_cents();
+        stats.points += result.points as i64;
+        positions += result.final_position as i64;
+    }
+
+    stats.itm_percentage = stats.itm as f64 / stats.tournaments as f64 * 100.0;
+    if stats.total_buy_ins > 0 {
+        stats.roi_percentage = stats.net_profit as f64 / stats.total_buy_ins as f64 * 100.0;
+    }
+    stats.average_finish = positions as f64 / stats.tournaments as f64;
+
+    stats
+}
+
+/// Totals per club, the clubs played most first
+pub fn club_stats(results: &[ProfileResult]) -> Vec<ClubStats> {
+    let mut by_club: BTreeMap<Uuid, (String, Vec<ProfileResult>)> = BTreeMap::new();
+    for result in results {
+        by_club
+            .entry(result.club_id)
+            .or_insert_with(|| (result.club_name.clone(), Vec::new()))
+            .1
+            .push(result.clone());
+    }
+
+    let mut clubs: Vec<ClubStats> = by_club
+        .into_iter()
+        .map(|(club_id, (club_name, results))| ClubStats {
+            club_id,
+            club_name,
+            stats: profile_stats(&results),
+        })
+        .collect();
+    clubs.sort_by(|a, b| {
+        b.stats
+            .tournaments
+            .cmp(&a.stats.tournaments)
+            .then_with(|| a.club_name.cmp(&b.club_name))
+    });
+
+    clubs
+}
+
+/// Profit after each result, oldest first
+pub fn cumulative_profit(results: &[ProfileResult]) -> Vec<ProfitPoint> {
+    let mut results: Vec<&ProfileResult> = results.iter().collect();
+    results.sort_by_key(|result| (result.start_time, result.tournament_id));
+
+    let mut cumulative = 0;
+    results
+        .into_iter()
+        .map(|result| {
+            cumulative += result.profit_cents();
+            ProfitPoint {
+                tournament_id: result.tournament_id,
+                date: result.start_time,
+                profit_cents: result.profit_cents(),
+                cumulative_profit_cents: cumulative,
+            }
+        })
+        .collect()
+}
+
+/// The largest prizes, most recent first on equal prizes
+pub fn biggest_cashes(results: &[ProfileResult], limit: usize) -> Vec<ProfileResult> {
+    let mut cashes: Vec<ProfileResult> = results
+        .iter()
+        .filter(|result| result.prize_cents > 0)
+        .cloned()
+        .collect();
+    cashes.sort_by(|a, b| {
+        b.prize_cents
+            .cmp(&a.prize_cents)
+            .then(b.start_time.cmp(&a.start_time))
+    });
+    cashes.truncate(limit);
+
+    cashes
+}
+
+/// How many times the player finished in each position, best position first
+pub fn finish_positions(results: &[ProfileResult]) -> Vec<PositionCount> {
+    let mut counts: BTreeMap<i32, u32> = BTreeMap::new();
+    for result in results {
+        *counts.entry(result.final_position).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .map(|(position, count)| PositionCount { position, count })
+        .collect()
+}
+
+/// Points scored in each calendar year the tournaments started in, oldest first
+pub fn points_by_year(results: &[ProfileResult]) -> Vec<YearPoints> {
+    let mut years: BTreeMap<i32, YearPoints> = BTreeMap::new();
+    for result in results {
+        let year = result.start_time.year();
+        let entry = years.entry(year).or_insert(YearPoints {
+            year,
+            points: 0,
+            tournaments: 0,
+        });
+        entry.points += result.points as i64;
+        entry.tournaments += 1;
+    }
+
+    years.into_values().collect()
+}
+
+/// The latest results, most recent first
+pub fn recent_results(results: &[ProfileResult], limit: usize) -> Vec<ProfileResult> {
+    let mut recent = results.to_vec();
+    recent.sort_by_key(|result| std::cmp::Reverse(result.start_time));
+    recent.truncate(limit);
+
+    recent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn result(
+        club: u128,
+        date: (i32, u32, u32),
+        position: i32,
+        buy_in: i64,
+        prize: i64,
+    ) -> ProfileResult {
+        ProfileResult {
+            tournament_id: Uuid::new_v4(),
+            tournament_name: format!("Event {}-{}-{}", date.0, date.1, date.2),
+            club_id: Uuid::from_u128(club),
+            club_name: format!("Club {}", club),
+            start_time: Utc
+                .with_ymd_and_hms(date.0, date.1, date.2, 19, 0, 0)
+                .unwrap(),
+            final_position: position,
+            field_size: 20,
+            buy_in_cents: buy_in,
+            prize_cents: prize,
+            bounty_cents: 0,
+            points: 21 - position,
+        }
+    }
+
+    fn results() -> Vec<ProfileResult> {
+        vec![
+            result(1, (2024, 11, 2), 1, 5000, 40000),
+            result(1, (2025, 1, 10), 12, 5000, 0),
+            result(2, (2025, 3, 5), 3, 10000, 15000),
+            result(1, (2025, 2, 1), 12, 7500, 0),
+        ]
+    }
+
+    #[test]
+    fn test_profile_stats() {
+        let stats = profile_stats(&results());
+
+        assert_eq!(stats.tournaments, 4);
+        assert_eq!(stats.itm, 2);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.final_tables, 2);
+        assert_eq!(stats.total_buy_ins, 27500);
+        assert_eq!(stats.total_winnings, 55000);
+        assert_eq!(stats.net_profit, 27500);
+        assert_eq!(stats.itm_percentage, 50.0);
+        assert_eq!(stats.roi_percentage, 100.0);
+        assert_eq!(stats.average_finish, 7.0);
+        assert_eq!(stats.points, 20 + 9 + 18 + 9);
+    }
+
+    #[test]
+    fn test_club_stats_most_played_first() {
+        let clubs = club_stats(&results());
+
+        assert_eq!(clubs.len(), 2);
+        assert_eq!(clubs[0].club_id, Uuid::from_u128(1));
+        assert_eq!(clubs[0].stats.tournaments, 3);
+        assert_eq!(clubs[0].stats.net_profit, 22500);
+        assert_eq!(clubs[1].stats.net_profit, 5000);
+    }
+
+    #[test]
+    fn test_cumulative_profit_in_date_order() {
+        let profit: Vec<_> = cumulative_profit(&results())
+            .iter()
+            .map(|point| point.cumulative_profit_cents)
+            .collect();
+
+        assert_eq!(profit, vec![35000, 30000, 22500, 27500]);
+    }
+
+    #[test]
+    fn test_biggest_cashes_and_distribution() {
+        let cashes = biggest_cashes(&results(), 5);
+        let prizes: Vec<_> = cashes.iter().map(|cash| cash.prize_cents).collect();
+        assert_eq!(prizes, vec![40000, 15000]);
+        assert_eq!(biggest_cashes(&results(), 1).len(), 1);
+
+        assert_eq!(
+            finish_positions(&results()),
+            vec![
+                PositionCount {
+                    position: 1,
+                    count: 1
+                },
+                PositionCount {
+                    position: 3,
+                    count: 1
+                },
+                PositionCount {
+                    position: 12,
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_points_by_year_and_recent_results() {
+        assert_eq!(
+            points_by_year(&results()),
+            vec![
+                YearPoints {
+                    year: 2024,
+                    points: 20,
+                    tournaments: 1
+                },
+                YearPoints {
+                    year: 2025,
+                    points: 36,
+                    tournaments: 3
+                },
+            ]
+        );
+
+        let recent = recent_results(&results(), 2);
+        assert_eq!(recent[0].final_position, 3);
+        assert_eq!(recent[1].start_time.month(), 2);
+    }
+}
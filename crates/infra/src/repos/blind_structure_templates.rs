@@ -0,0 +1,117 @@
+use crate::{
+    db::Db, models::BlindStructureTemplateRow, repos::tournament_clock::TournamentStructureLevel,
+};
+use sqlx::Result as SqlxResult;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct CreateBlindStructureTemplate {
+    pub club_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub levels: Vec<TournamentStructureLevel>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateBlindStructureTemplate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub levels: Option<Vec<TournamentStructureLevel>>,
+}
+
+#[derive(Clone)]
+pub struct BlindStructureTemplateRepo {
+    pool: Db,
+}
+
+impl BlindStructureTemplateRepo {
+    pub fn new(pool: Db) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, id: Uuid) -> SqlxResult<Option<BlindStructureTemplateRow>> {
+        sqlx::query_as::<_, BlindStructureTemplateRow>(
+            r#"
+            SELECT id, club_id, name, description, levels, created_by, created_at, updated_at
+            FROM blind_structure_templates
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn list_by_club(&self, club_id: Uuid) -> SqlxResult<Vec<BlindStructureTemplateRow>> {
+        sqlx::query_as::<_, BlindStructureTemplateRow>(
+            r#"
+            SELECT id, club_id, name, description, levels, created_by, created_at, updated_at
+            FROM blind_structure_templates
+            WHERE club_id = $1
+            ORDER BY name ASC
+            "#,
+        )
+        .bind(club_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(
+        &self,
+        data: CreateBlindStructureTemplate,
+    ) -> SqlxResult<BlindStructureTemplateRow> {
+        sqlx::query_as::<_, BlindStructureTemplateRow>(
+            r#"
+            INSERT INTO blind_structure_templates (club_id, name, description, levels, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, club_id, name, description, levels, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(data.club_id)
+        .bind(data.name)
+        .bind(data.description)
+        .bind(serde_json::to_value(&data.levels).unwrap_or_default())
+        .bind(data.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Update a template, leaving fields that are `None` unchanged.
+    /// Tournaments the template was applied to keep their own copy of the levels.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateBlindStructureTemplate,
+    ) -> SqlxResult<Option<BlindStructureTemplateRow>> {
+        let levels = data
+            .levels
+            .map(|levels| serde_json::to_value(levels).unwrap_or_default());
+
+        sqlx::query_as::<_, BlindStructureTemplateRow>(
+            r#"
+            UPDATE blind_structure_templates
+            SET name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                levels = COALESCE($4, levels)
+            WHERE id = $1
+            RETURNING id, club_id, name, description, levels, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(data.name)
+        .bind(data.description)
+        .bind(levels)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn delete(&self, id: Uuid) -> SqlxResult<bool> {
+        let result = sqlx::query("DELETE FROM blind_structure_templates WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
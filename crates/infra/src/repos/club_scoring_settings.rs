@@ -0,0 +1,120 @@
+use sqlx::{PgConnection, PgPool, Result};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::ClubScoringSettingsRow;
+use crate::scoring::{ScoringFormulaKind, ScoringSettings};
+
+#[derive(Debug, Clone)]
+pub struct UpdateClubScoringSettings {
+    pub formula: ScoringFormulaKind,
+    pub cap: i32,
+    pub multiplier: f64,
+}
+
+impl From<&ClubScoringSettingsRow> for ScoringSettings {
+    fn from(row: &ClubScoringSettingsRow) -> Self {
+        Self {
+            formula: ScoringFormulaKind::from_str(&row.formula).unwrap_or_default(),
+            cap: row.cap.max(0) as u32,
+            multiplier: row.multiplier,
+        }
+    }
+}
+
+pub struct ClubScoringSettingsRepo {
+    db: PgPool,
+}
+
+impl ClubScoringSettingsRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Get the settings a club saved, `None` when it scores with the defaults
+    pub async fn get_by_club(&self, club_id: Uuid) -> Result<Option<ClubScoringSettingsRow>> {
+        sqlx::query_as::<_, ClubScoringSettingsRow>(
+            r#"
+            SELECT club_id, formula, cap, multiplier, updated_by, created_at, updated_at
+            FROM club_scoring_settings
+            WHERE club_id = $1
+            "#,
+        )
+        .bind(club_id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Save the scoring settings of a club. Points already awarded are kept, the new
+    /// formula applies to tournaments finished or corrected from now on.
+    pub async fn upsert(
+        &self,
+        club_id: Uuid,
+        data: UpdateClubScoringSettings,
+        updated_by: Uuid,
+    ) -> Result<ClubScoringSettingsRow> {
+        sqlx::query_as::<_, ClubScoringSettingsRow>(
+            r#"
+            INSERT INTO club_scoring_settings (club_id, formula, cap, multiplier, updated_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (club_id) DO UPDATE
+            SET formula = EXCLUDED.formula,
+                cap = EXCLUDED.cap,
+                multiplier = EXCLUDED.multiplier,
+                updated_by = EXCLUDED.updated_by
+            RETURNING club_id, formula, cap, multiplier, updated_by, created_at, updated_at
+            "#,
+        )
+        .bind(club_id)
+        .bind(data.formula.as_str())
+        .bind(data.cap)
+        .bind(data.multiplier)
+        .bind(updated_by)
+        .fetch_one(&self.db)
+        .await
+    }
+}
+
+/// Scoring settings of a tournament: those of the club that ran it, with the overrides
+/// of the season it started in. Defaults fill in whatever neither sets.
+pub(crate) async fn settings_for_tournament(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+) -> Result<ScoringSettings> {
+    let row = sqlx::query_as::<_, (Option<String>, Option<i32>, Option<f64>)>(
+        r#"
+        SELECT COALESCE(season.scoring_formula, s.formula),
+               COALESCE(season.scoring_cap, s.cap),
+               COALESCE(season.scoring_multiplier, s.multiplier)
+        FROM tournaments t
+        LEFT JOIN club_scoring_settings s ON s.club_id = t.club_id
+        LEFT JOIN LATERAL (
+            SELECT scoring_formula, scoring_cap, scoring_multiplier
+            FROM seasons
+            WHERE club_id = t.club_id
+              AND t.start_time::DATE BETWEEN start_date AND end_date
+            ORDER BY start_date DESC
+            LIMIT 1
+        ) season ON true
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_optional(conn)
+    .await?;
+
+    let mut settings = ScoringSettings::default();
+    if let Some((formula, cap, multiplier)) = row {
+        if let Some(formula) = formula.and_then(|formula| formula.parse().ok()) {
+            settings.formula = formula;
+        }
+        if let Some(cap) = cap {
+            settings.cap = cap.max(0) as u32;
+        }
+        if let Some(multiplier) = multiplier {
+            settings.multiplier = multiplier;
+        }
+    }
+
+    Ok(settings)
+}
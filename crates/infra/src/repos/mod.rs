@@ -1,31 +1,62 @@
+pub mod blind_structure_templates;
 pub mod club_managers;
+pub mod club_scoring_settings;
 pub mod club_tables;
 pub mod clubs;
 pub mod payout_templates;
 pub mod player_deals;
+pub mod player_profiles;
+pub mod rivalries;
+pub mod seasons;
 pub mod table_seat_assignments;
+pub mod tags;
 pub mod tournament_clock;
+pub mod tournament_eliminations;
+pub mod tournament_entries;
 pub mod tournament_payouts;
 pub mod tournament_registrations;
 pub mod tournament_results;
+pub mod tournament_series;
 pub mod tournaments;
+pub mod user_privacy_settings;
 pub mod users;
 
+pub use blind_structure_templates::{
+    BlindStructureTemplateRepo, CreateBlindStructureTemplate, UpdateBlindStructureTemplate,
+};
 pub use club_managers::{ClubInfo, ClubManagerRepo, CreateClubManager};
+pub use club_scoring_settings::{ClubScoringSettingsRepo, UpdateClubScoringSettings};
 pub use club_tables::{ClubTableRepo, CreateClubTable, UpdateClubTable};
 pub use clubs::ClubRepo;
 pub use payout_templates::{CreatePayoutTemplate, PayoutTemplateRepo};
 pub use player_deals::{CreatePlayerDeal, PlayerDealRepo};
+pub use player_profiles::PlayerProfileRepo;
+pub use rivalries::{HeadToHeadStats, RivalryRepo};
+pub use seasons::{CreateSeason, SeasonRepo, UpdateSeason};
 pub use table_seat_assignments::{
     CreateSeatAssignment, SeatAssignmentFilter, SeatAssignmentWithPlayer, TableSeatAssignmentRepo,
     UpdateSeatAssignment,
 };
-pub use tournament_clock::{ClockStatus, TournamentClockRepo};
+pub use tags::TagRepo;
+pub use tournament_clock::{ClockStatus, TournamentClockRepo, TournamentStructureLevel};
+pub use tournament_eliminations::{
+    CreateTournamentEliminations, EliminatedPlayer, TournamentEliminationRepo,
+};
+pub use tournament_entries::{
+    CreateTournamentEntry, TournamentEntryRepo, TournamentEntryTotals, TournamentEntryType,
+};
 pub use tournament_payouts::TournamentPayoutRepo;
-pub use tournament_registrations::{CreateTournamentRegistration, TournamentRegistrationRepo};
+pub use tournament_registrations::{
+    CreateTournamentRegistration, RegistrationStatusChange, TournamentRegistrationRepo,
+};
 pub use tournament_results::{
-    CreateTournamentResult, LeaderboardEntry, LeaderboardPeriod, TournamentResultRepo,
-    UserStatistics,
+    CreateTournamentResult, LeaderboardEntry, LeaderboardPage, LeaderboardPeriod,
+    TournamentResultRepo, UpdateTournamentResult, UserStatistics,
+};
+pub use tournament_series::{CreateTournamentSeries, TournamentSeriesRepo, UpdateTournamentSeries};
+pub use tournaments::{
+    CreateTournament, TournamentEntryRules, TournamentFilter, TournamentLiveStatus, TournamentRepo,
+    UpdateTournament,
 };
-pub use tournaments::{TournamentFilter, TournamentLiveStatus, TournamentRepo};
+pub use user_privacy_settings::UserPrivacySettingsRepo;
 pub use users::{UserFilter, UserRepo};
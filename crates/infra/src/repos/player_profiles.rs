@@ -0,0 +1,53 @@
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+use crate::profile::ProfileResult;
+
+pub struct PlayerProfileRepo {
+    db: PgPool,
+}
+
+impl PlayerProfileRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Every result of a player with the tournament, its club and what the player paid
+    /// to play it. The field size falls back to the number of results when the
+    /// tournament recorded no entries.
+    pub async fn results(&self, user_id: Uuid) -> Result<Vec<ProfileResult>> {
+        sqlx::query_as::<_, ProfileResult>(
+            r#"
+            SELECT tr.tournament_id,
+                   t.name AS tournament_name,
+                   t.club_id,
+                   c.name AS club_name,
+                   t.start_time,
+                   tr.final_position,
+                   COALESCE(
+                       NULLIF(tournament_entry_count(t.id), 0),
+                       (SELECT COUNT(*)::INTEGER FROM tournament_results other
+                        WHERE other.tournament_id = t.id)
+                   ) AS field_size,
+                   COALESCE(paid.amount_cents, 0)::BIGINT AS buy_in_cents,
+                   tr.prize_cents::BIGINT AS prize_cents,
+                   tr.bounty_cents::BIGINT AS bounty_cents,
+                   tr.points
+            FROM tournament_results tr
+            JOIN tournaments t ON t.id = tr.tournament_id
+            JOIN clubs c ON c.id = t.club_id
+            LEFT JOIN LATERAL (
+                SELECT SUM(e.amount_cents) AS amount_cents
+                FROM tournament_entries e
+                JOIN tournament_registrations reg ON reg.id = e.registration_id
+                WHERE reg.tournament_id = tr.tournament_id AND reg.user_id = tr.user_id
+            ) paid ON true
+            WHERE tr.user_id = $1
+            ORDER BY t.start_time DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await
+    }
+}
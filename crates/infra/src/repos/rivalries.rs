@@ -0,0 +1,116 @@
+use sqlx::{FromRow, PgPool, Result};
+use uuid::Uuid;
+
+/// How a player fared against one opponent
+#[derive(Debug, Clone, FromRow)]
+pub struct HeadToHeadStats {
+    pub user_id: Uuid,
+    pub opponent_id: Uuid,
+    /// Tournaments both players have a result in
+    pub shared_tournaments: i64,
+    /// Shared tournaments where the player finished ahead of the opponent
+    pub user_finished_higher: i64,
+    /// Shared tournaments where the opponent finished ahead
+    pub opponent_finished_higher: i64,
+    /// Time both players sat at the same table of the same tournament
+    pub seconds_together: f64,
+}
+
+/// Head-to-head statistics of a player against one opponent, or against everyone they
+/// met when `$2` is NULL. Seats still taken count up to the end of a finished or cancelled
+/// tournament (its end time, or when it was last updated), and up to now while it is live.
+const HEAD_TO_HEAD: &str = r#"
+    WITH seats AS (
+        SELECT tsa.tournament_id, tsa.club_table_id, tsa.user_id, tsa.assigned_at,
+               COALESCE(
+                   tsa.unassigned_at,
+                   CASE
+                       WHEN t.live_status IN ('finished', 'cancelled')
+                       THEN LEAST(t.end_time, t.updated_at)
+                       ELSE NOW()
+                   END
+               ) AS left_at
+        FROM table_seat_assignments tsa
+        JOIN tournaments t ON t.id = tsa.tournament_id
+        WHERE tsa.tournament_id IN (
+            SELECT tournament_id FROM table_seat_assignments WHERE user_id = $1
+        )
+    ),
+    shared AS (
+        SELECT other.user_id AS opponent_id,
+               COUNT(*) AS shared_tournaments,
+               COUNT(*) FILTER (WHERE me.final_position < other.final_position) AS user_finished_higher,
+               COUNT(*) FILTER (WHERE me.final_position > other.final_position) AS opponent_finished_higher
+        FROM tournament_results me
+        JOIN tournament_results other
+          ON other.tournament_id = me.tournament_id AND other.user_id <> me.user_id
+        WHERE me.user_id = $1
+          AND ($2::UUID IS NULL OR other.user_id = $2)
+        GROUP BY other.user_id
+    ),
+    together AS (
+        SELECT other.user_id AS opponent_id,
+               SUM(EXTRACT(EPOCH FROM
+                   LEAST(me.left_at, other.left_at) - GREATEST(me.assigned_at, other.assigned_at)
+               ))::DOUBLE PRECISION AS seconds_together
+        FROM seats me
+        JOIN seats other
+          ON other.tournament_id = me.tournament_id
+         AND other.club_table_id = me.club_table_id
+         AND other.user_id <> me.user_id
+         AND GREATEST(me.assigned_at, other.assigned_at) < LEAST(me.left_at, other.left_at)
+        WHERE me.user_id = $1
+          AND ($2::UUID IS NULL OR other.user_id = $2)
+        GROUP BY other.user_id
+    )
+    SELECT $1 AS user_id,
+           COALESCE(shared.opponent_id, together.opponent_id) AS opponent_id,
+           COALESCE(shared.shared_tournaments, 0) AS shared_tournaments,
+           COALESCE(shared.user_finished_higher, 0) AS user_finished_higher,
+           COALESCE(shared.opponent_finished_higher, 0) AS opponent_finished_higher,
+           COALESCE(together.seconds_together, 0) AS seconds_together
+    FROM shared
+    FULL JOIN together ON together.opponent_id = shared.opponent_id
+    ORDER BY shared_tournaments DESC, seconds_together DESC, opponent_id
+    LIMIT $3
+"#;
+
+pub struct RivalryRepo {
+    db: PgPool,
+}
+
+impl RivalryRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// How a player fared against an opponent; everything is 0 when they never met
+    pub async fn head_to_head(&self, user_id: Uuid, opponent_id: Uuid) -> Result<HeadToHeadStats> {
+        let stats = sqlx::query_as::<_, HeadToHeadStats>(HEAD_TO_HEAD)
+            .bind(user_id)
+            .bind(opponent_id)
+            .bind(1i64)
+            .fetch_optional(&self.db)
+            .await?;
+
+        Ok(stats.unwrap_or(HeadToHeadStats {
+            user_id,
+            opponent_id,
+            shared_tournaments: 0,
+            user_finished_higher: 0,
+            opponent_finished_higher: 0,
+            seconds_together: 0.0,
+        }))
+    }
+
+    /// The opponents a player met most, by shared tournaments then time at the same
+    /// table
+    pub async fn top_rivals(&self, user_id: Uuid, limit: i64) -> Result<Vec<HeadToHeadStats>> {
+        sqlx::query_as::<_, HeadToHeadStats>(HEAD_TO_HEAD)
+            .bind(user_id)
+            .bind(None::<Uuid>)
+            .bind(limit)
+            .fetch_all(&self.db)
+            .await
+    }
+}
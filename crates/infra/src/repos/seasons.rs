@@ -0,0 +1,318 @@
+use crate::{
+    db::Db,
+    models::{SeasonRow, SeasonStandingRow},
+    standings::{season_standings, PlayerSeasonResults},
+};
+use chrono::NaiveDate;
+use sqlx::{PgConnection, Result as SqlxResult};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const SEASON_COLUMNS: &str = r#"
+    id, club_id, name, start_date, end_date, best_results, min_events, drop_worst,
+    scoring_formula, scoring_cap, scoring_multiplier, closed_at, closed_by, created_by,
+    created_at, updated_at
+"#;
+
+#[derive(Debug, Clone)]
+pub struct CreateSeason {
+    pub club_id: Uuid,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub best_results: Option<i32>,
+    pub min_events: i32,
+    pub drop_worst: i32,
+    pub scoring_formula: Option<String>,
+    pub scoring_cap: Option<i32>,
+    pub scoring_multiplier: Option<f64>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateSeason {
+    pub name: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// `Some(None)` counts every result
+    pub best_results: Option<Option<i32>>,
+    pub min_events: Option<i32>,
+    pub drop_worst: Option<i32>,
+    /// `Some(None)` follows the club's formula
+    pub scoring_formula: Option<Option<String>>,
+    /// `Some(None)` follows the club's cap
+    pub scoring_cap: Option<Option<i32>>,
+    /// `Some(None)` follows the club's multiplier
+    pub scoring_multiplier: Option<Option<f64>>,
+}
+
+impl UpdateSeason {
+    /// Whether the update changes the points scored in the season's tournaments
+    pub fn changes_scoring(&self) -> bool {
+        self.start_date.is_some()
+            || self.end_date.is_some()
+            || self.scoring_formula.is_some()
+            || self.scoring_cap.is_some()
+            || self.scoring_multiplier.is_some()
+    }
+}
+
+#[derive(Clone)]
+pub struct SeasonRepo {
+    pool: Db,
+}
+
+impl SeasonRepo {
+    pub fn new(pool: Db) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, id: Uuid) -> SqlxResult<Option<SeasonRow>> {
+        sqlx::query_as::<_, SeasonRow>(&format!(
+            "SELECT {} FROM seasons WHERE id = $1",
+            SEASON_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Get the seasons of a club, latest first
+    pub async fn list_by_club(&self, club_id: Uuid) -> SqlxResult<Vec<SeasonRow>> {
+        sqlx::query_as::<_, SeasonRow>(&format!(
+            "SELECT {} FROM seasons WHERE club_id = $1 ORDER BY start_date DESC",
+            SEASON_COLUMNS
+        ))
+        .bind(club_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(&self, data: CreateSeason) -> SqlxResult<SeasonRow> {
+        sqlx::query_as::<_, SeasonRow>(&format!(
+            r#"
+            INSERT INTO seasons (
+                club_id, name, start_date, end_date, best_results, min_events, drop_worst,
+                scoring_formula, scoring_cap, scoring_multiplier, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            SEASON_COLUMNS
+        ))
+        .bind(data.club_id)
+        .bind(data.name)
+        .bind(data.start_date)
+        .bind(data.end_date)
+        .bind(data.best_results)
+        .bind(data.min_events)
+        .bind(data.drop_worst)
+        .bind(data.scoring_formula)
+        .bind(data.scoring_cap)
+        .bind(data.scoring_multiplier)
+        .bind(data.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update(&self, id: Uuid, data: UpdateSeason) -> SqlxResult<Option<SeasonRow>> {
+        sqlx::query_as::<_, SeasonRow>(&format!(
+            r#"
+            UPDATE seasons
+            SET name = COALESCE($2, name),
+                start_date = COALESCE($3, start_date),
+                end_date = COALESCE($4, end_date),
+                best_results = CASE WHEN $11 THEN $5 ELSE best_results END,
+                min_events = COALESCE($6, min_events),
+                drop_worst = COALESCE($7, drop_worst),
+                scoring_formula = CASE WHEN $12 THEN $8 ELSE scoring_formula END,
+                scoring_cap = CASE WHEN $13 THEN $9 ELSE scoring_cap END,
+                scoring_multiplier = CASE WHEN $14 THEN $10 ELSE scoring_multiplier END
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            SEASON_COLUMNS
+        ))
+        .bind(id)
+        .bind(data.name)
+        .bind(data.start_date)
+        .bind(data.end_date)
+        .bind(data.best_results.flatten())
+        .bind(data.min_events)
+        .bind(data.drop_worst)
+        .bind(data.scoring_formula.clone().flatten())
+        .bind(data.scoring_cap.flatten())
+        .bind(data.scoring_multiplier.flatten())
+        .bind(data.best_results.is_some())
+        .bind(data.scoring_formula.is_some())
+        .bind(data.scoring_cap.is_some())
+        .bind(data.scoring_multiplier.is_some())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Whether another season of the club covers any day between the two dates
+    pub async fn overlaps(
+        &self,
+        club_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        except_id: Option<Uuid>,
+    ) -> SqlxResult<bool> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM seasons
+                WHERE club_id = $1
+                  AND start_date <= $3
+                  AND end_date >= $2
+                  AND ($4::UUID IS NULL OR id <> $4)
+            )
+            "#,
+        )
+        .bind(club_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(except_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Club tournaments that started during the season and are neither finished nor
+    /// cancelled yet
+    pub async fn unfinished_tournaments(&self, season: &SeasonRow) -> SqlxResult<i64> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM tournaments
+            WHERE club_id = $1
+              AND start_time::DATE BETWEEN $2 AND $3
+              AND live_status NOT IN ('finished', 'cancelled')
+            "#,
+        )
+        .bind(season.club_id)
+        .bind(season.start_date)
+        .bind(season.end_date)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Standings of a season: the frozen ones once it is closed, computed from the
+    /// results of its tournaments until then
+    pub async fn get_standings(&self, season: &SeasonRow) -> SqlxResult<Vec<SeasonStandingRow>> {
+        if season.closed_at.is_some() {
+            return sqlx::query_as::<_, SeasonStandingRow>(
+                r#"
+                SELECT season_id, user_id, rank, points, events_played, results_counted
+                FROM season_standings
+                WHERE season_id = $1
+                ORDER BY rank ASC NULLS LAST, points DESC, events_played DESC, user_id ASC
+                "#,
+            )
+            .bind(season.id)
+            .fetch_all(&self.pool)
+            .await;
+        }
+
+        let mut conn = self.pool.acquire().await?;
+        compute_standings(&mut conn, season).await
+    }
+
+    /// Close a season and freeze its standings, `None` when it is missing or already
+    /// closed
+    pub async fn close(&self, id: Uuid, closed_by: Uuid) -> SqlxResult<Option<SeasonRow>> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(season) = sqlx::query_as::<_, SeasonRow>(&format!(
+            "SELECT {} FROM seasons WHERE id = $1 AND closed_at IS NULL FOR UPDATE",
+            SEASON_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        for standing in compute_standings(&mut tx, &season).await? {
+            sqlx::query(
+                r#"
+                INSERT INTO season_standings (
+                    season_id, user_id, rank, points, events_played, results_counted
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(standing.season_id)
+            .bind(standing.user_id)
+            .bind(standing.rank)
+            .bind(standing.points)
+            .bind(standing.events_played)
+            .bind(standing.results_counted)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let season = sqlx::query_as::<_, SeasonRow>(&format!(
+            r#"
+            UPDATE seasons
+            SET closed_at = NOW(), closed_by = $2
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            SEASON_COLUMNS
+        ))
+        .bind(id)
+        .bind(closed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(season))
+    }
+}
+
+/// Rank the players of a season from the results of the club's finished tournaments
+/// that started during the season
+async fn compute_standings(
+    conn: &mut PgConnection,
+    season: &SeasonRow,
+) -> SqlxResult<Vec<SeasonStandingRow>> {
+    let results = sqlx::query_as::<_, (Uuid, i32)>(
+        r#"
+        SELECT tr.user_id, tr.points
+        FROM tournament_results tr
+        JOIN tournaments t ON t.id = tr.tournament_id
+        WHERE t.club_id = $1
+          AND t.start_time::DATE BETWEEN $2 AND $3
+          AND t.live_status = 'finished'
+        "#,
+    )
+    .bind(season.club_id)
+    .bind(season.start_date)
+    .bind(season.end_date)
+    .fetch_all(conn)
+    .await?;
+
+    let mut points_by_player: HashMap<Uuid, Vec<i32>> = HashMap::new();
+    for (user_id, points) in results {
+        points_by_player.entry(user_id).or_default().push(points);
+    }
+    let players = points_by_player
+        .into_iter()
+        .map(|(user_id, points)| PlayerSeasonResults { user_id, points })
+        .collect();
+
+    Ok(season_standings(players, &season.rules())
+        .into_iter()
+        .map(|standing| SeasonStandingRow {
+            season_id: season.id,
+            user_id: standing.user_id,
+            rank: standing.rank.map(|rank| rank as i32),
+            points: standing.points,
+            events_played: standing.events_played as i32,
+            results_counted: standing.results_counted as i32,
+        })
+        .collect())
+}
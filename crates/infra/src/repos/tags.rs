@@ -0,0 +1,57 @@
+use crate::{db::Db, models::TagRow};
+use sqlx::Result as SqlxResult;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TagRepo {
+    pool: Db,
+}
+
+impl TagRepo {
+    pub fn new(pool: Db) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_all(&self) -> SqlxResult<Vec<TagRow>> {
+        sqlx::query_as::<_, TagRow>(
+            r#"
+            SELECT id, slug, label, created_at, updated_at
+            FROM tags
+            ORDER BY label ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get the tags matching the given slugs (unknown slugs are skipped)
+    pub async fn get_by_slugs(&self, slugs: &[String]) -> SqlxResult<Vec<TagRow>> {
+        sqlx::query_as::<_, TagRow>(
+            r#"
+            SELECT id, slug, label, created_at, updated_at
+            FROM tags
+            WHERE slug = ANY($1)
+            ORDER BY label ASC
+            "#,
+        )
+        .bind(slugs)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get the tags attached to a tournament
+    pub async fn get_for_tournament(&self, tournament_id: Uuid) -> SqlxResult<Vec<TagRow>> {
+        sqlx::query_as::<_, TagRow>(
+            r#"
+            SELECT t.id, t.slug, t.label, t.created_at, t.updated_at
+            FROM tags t
+            JOIN tournament_tags tt ON tt.tag_id = t.id
+            WHERE tt.tournament_id = $1
+            ORDER BY t.label ASC
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -2,8 +2,9 @@ use crate::{
     db::Db,
     models::{TournamentClockRow, TournamentStructureRow},
 };
-use chrono::{Duration, Utc};
-use sqlx::Result as SqlxResult;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, Result as SqlxResult};
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -37,7 +38,7 @@ impl FromStr for ClockStatus {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TournamentStructureLevel {
     pub level_number: i32,
     pub small_blind: i32,
@@ -48,6 +49,20 @@ pub struct TournamentStructureLevel {
     pub break_duration_minutes: Option<i32>,
 }
 
+impl From<&TournamentStructureRow> for TournamentStructureLevel {
+    fn from(row: &TournamentStructureRow) -> Self {
+        Self {
+            level_number: row.level_number,
+            small_blind: row.small_blind,
+            big_blind: row.big_blind,
+            ante: row.ante,
+            duration_minutes: row.duration_minutes,
+            is_break: row.is_break,
+            break_duration_minutes: row.break_duration_minutes,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TournamentClockRepo {
     pub pool: Db,
@@ -82,34 +97,6 @@ impl TournamentClockRepo {
         .fetch_one(&self.pool)
         .await?;
 
-        // Get the first level structure to sync with tournament_state
-        if let Ok(structure) = self.get_current_structure(tournament_id).await {
-            // Ensure tournament_state is synced with initial clock level
-            sqlx::query(
-                "INSERT INTO tournament_state (
-                    tournament_id, current_level, current_small_blind, 
-                    current_big_blind, current_ante, level_duration_minutes
-                )
-                VALUES ($1, $2, $3, $4, $5, $6)
-                ON CONFLICT (tournament_id)
-                DO UPDATE SET
-                    current_level = $2,
-                    current_small_blind = $3,
-                    current_big_blind = $4,
-                    current_ante = $5,
-                    level_duration_minutes = $6,
-                    updated_at = NOW()",
-            )
-            .bind(tournament_id)
-            .bind(1i32) // Starting at level 1
-            .bind(structure.small_blind)
-            .bind(structure.big_blind)
-            .bind(structure.ante)
-            .bind(structure.duration_minutes)
-            .execute(&self.pool)
-            .await?;
-        }
-
         Ok(clock)
     }
 
@@ -226,32 +213,74 @@ impl TournamentClockRepo {
         Ok(clock)
     }
 
-    /// Advance to next level
+    /// Stop tournament clock for good, once the tournament is over
+    pub async fn stop_clock(
+        &self,
+        tournament_id: Uuid,
+        manager_id: Option<Uuid>,
+    ) -> SqlxResult<TournamentClockRow> {
+        let mut conn = self.pool.acquire().await?;
+
+        stop_clock(&mut conn, tournament_id, manager_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Advance to the next level at `now`. With `expected_level`, the clock only advances
+    /// if it is still on that level and `None` is returned otherwise, so retried or
+    /// concurrent advances of the same level move the clock once. Automatic advances only
+    /// move a running clock whose level ended by `now`, and return `None` once it was
+    /// paused, stopped or moved meanwhile. They start the next level when the previous
+    /// one ended, even if they run late.
     pub async fn advance_level(
         &self,
         tournament_id: Uuid,
+        expected_level: Option<i32>,
         auto: bool,
         manager_id: Option<Uuid>,
-    ) -> SqlxResult<TournamentClockRow> {
-        let now = Utc::now();
+        now: DateTime<Utc>,
+    ) -> SqlxResult<Option<TournamentClockRow>> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the clock so concurrent advances run one after the other
+        let (current_level, clock_status, previous_end_time): (i32, String, Option<DateTime<Utc>>) =
+            sqlx::query_as(
+                "SELECT current_level, clock_status, level_end_time FROM tournament_clocks
+             WHERE tournament_id = $1 FOR UPDATE",
+            )
+            .bind(tournament_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
 
-        // First increment the level
-        sqlx::query(
-            "UPDATE tournament_clocks SET current_level = current_level + 1 WHERE tournament_id = $1"
+        if expected_level.is_some_and(|level| level != current_level) {
+            return Ok(None);
+        }
+        let level_over = previous_end_time.filter(|end_time| *end_time <= now);
+        if auto && (clock_status != "running" || level_over.is_none()) {
+            return Ok(None);
+        }
+
+        // Get the new structure for timing
+        let duration_minutes: i32 = sqlx::query_scalar(
+            "SELECT duration_minutes FROM tournament_structures
+             WHERE tournament_id = $1 AND level_number = $2",
         )
         .bind(tournament_id)
-        .execute(&self.pool)
+        .bind(current_level + 1)
+        .fetch_one(&mut *tx)
         .await?;
-
-        // Get the new structure for timing
-        let structure = self.get_current_structure(tournament_id).await?;
-        let level_duration = Duration::minutes(structure.duration_minutes as i64);
-        let level_end_time = now + level_duration;
+        let level_started_at = match level_over {
+            Some(end_time) if auto => end_time,
+            _ => now,
+        };
+        let level_end_time = level_started_at + Duration::minutes(duration_minutes as i64);
 
         // Update tournament clock, preserving any accumulated pause time
         let clock = sqlx::query_as::<_, TournamentClockRow>(
             "UPDATE tournament_clocks 
-             SET level_started_at = $2,
+             SET current_level = $4,
+                 level_started_at = $2,
                  level_end_time = $3,
                  total_pause_duration = CASE 
                      WHEN pause_started_at IS NOT NULL THEN 
@@ -266,32 +295,13 @@ impl TournamentClockRepo {
                        pause_started_at, total_pause_duration, auto_advance, created_at, updated_at"
         )
         .bind(tournament_id)
-        .bind(now)
+        .bind(level_started_at)
         .bind(level_end_time)
-        .fetch_one(&self.pool)
+        .bind(current_level + 1)
+        .fetch_one(&mut *tx)
         .await?;
 
-        // Sync tournament_state table with the new level and structure values
-        sqlx::query(
-            "UPDATE tournament_state 
-             SET current_level = $2,
-                 current_small_blind = $3,
-                 current_big_blind = $4,
-                 current_ante = $5,
-                 level_started_at = $6,
-                 level_duration_minutes = $7,
-                 updated_at = NOW()
-             WHERE tournament_id = $1",
-        )
-        .bind(tournament_id)
-        .bind(clock.current_level)
-        .bind(structure.small_blind)
-        .bind(structure.big_blind)
-        .bind(structure.ante)
-        .bind(now)
-        .bind(structure.duration_minutes)
-        .execute(&self.pool)
-        .await?;
+        tx.commit().await?;
 
         // Log event
         let event_type = if auto {
@@ -308,44 +318,55 @@ impl TournamentClockRepo {
         )
         .await?;
 
-        Ok(clock)
+        Ok(Some(clock))
     }
 
-    /// Revert to previous level
+    /// Revert to the previous level. With `expected_level`, the clock only reverts if it
+    /// is still on that level and `None` is returned otherwise, so a repeated revert of the
+    /// same level moves the clock back once.
     pub async fn revert_level(
         &self,
         tournament_id: Uuid,
+        expected_level: Option<i32>,
         manager_id: Option<Uuid>,
-    ) -> SqlxResult<TournamentClockRow> {
+    ) -> SqlxResult<Option<TournamentClockRow>> {
         let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
 
-        // Check current level - don't allow going below level 1
-        let current_clock = self
-            .get_clock(tournament_id)
-            .await?
-            .ok_or_else(|| sqlx::Error::RowNotFound)?;
+        // Lock the clock so concurrent level changes run one after the other
+        let current_level: i32 = sqlx::query_scalar(
+            "SELECT current_level FROM tournament_clocks WHERE tournament_id = $1 FOR UPDATE",
+        )
+        .bind(tournament_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+        if expected_level.is_some_and(|level| level != current_level) {
+            return Ok(None);
+        }
 
-        if current_clock.current_level <= 1 {
+        // Don't allow going below level 1
+        if current_level <= 1 {
             return Err(sqlx::Error::RowNotFound);
         }
 
-        // Decrement the level
-        sqlx::query(
-            "UPDATE tournament_clocks SET current_level = current_level - 1 WHERE tournament_id = $1"
+        // Get the new structure for timing
+        let duration_minutes: i32 = sqlx::query_scalar(
+            "SELECT duration_minutes FROM tournament_structures
+             WHERE tournament_id = $1 AND level_number = $2",
         )
         .bind(tournament_id)
-        .execute(&self.pool)
+        .bind(current_level - 1)
+        .fetch_one(&mut *tx)
         .await?;
-
-        // Get the new structure for timing
-        let structure = self.get_current_structure(tournament_id).await?;
-        let level_duration = Duration::minutes(structure.duration_minutes as i64);
-        let level_end_time = now + level_duration;
+        let level_end_time = now + Duration::minutes(duration_minutes as i64);
 
         // Update tournament clock, preserving any accumulated pause time
         let clock = sqlx::query_as::<_, TournamentClockRow>(
             "UPDATE tournament_clocks 
-             SET level_started_at = $2,
+             SET current_level = $4,
+                 level_started_at = $2,
                  level_end_time = $3,
                  total_pause_duration = CASE 
                      WHEN pause_started_at IS NOT NULL THEN 
@@ -362,30 +383,11 @@ impl TournamentClockRepo {
         .bind(tournament_id)
         .bind(now)
         .bind(level_end_time)
-        .fetch_one(&self.pool)
+        .bind(current_level - 1)
+        .fetch_one(&mut *tx)
         .await?;
 
-        // Sync tournament_state table with the reverted level and structure values
-        sqlx::query(
-            "UPDATE tournament_state 
-             SET current_level = $2,
-                 current_small_blind = $3,
-                 current_big_blind = $4,
-                 current_ante = $5,
-                 level_started_at = $6,
-                 level_duration_minutes = $7,
-                 updated_at = NOW()
-             WHERE tournament_id = $1",
-        )
-        .bind(tournament_id)
-        .bind(clock.current_level)
-        .bind(structure.small_blind)
-        .bind(structure.big_blind)
-        .bind(structure.ante)
-        .bind(now)
-        .bind(structure.duration_minutes)
-        .execute(&self.pool)
-        .await?;
+        tx.commit().await?;
 
         // Log event
         self.log_event(
@@ -397,7 +399,7 @@ impl TournamentClockRepo {
         )
         .await?;
 
-        Ok(clock)
+        Ok(Some(clock))
     }
 
     /// Get current level structure
@@ -464,6 +466,81 @@ impl TournamentClockRepo {
         .await
     }
 
+    /// Replace every structure level of a tournament with `levels`, in one transaction.
+    /// The clock is locked while the current levels are compared with `expected`, the
+    /// structure the edit was based on; `None` if they differ, or if the edit changes a
+    /// level the clock has already reached.
+    pub async fn replace_structures(
+        &self,
+        tournament_id: Uuid,
+        expected: &[TournamentStructureLevel],
+        levels: &[TournamentStructureLevel],
+    ) -> SqlxResult<Option<Vec<TournamentStructureRow>>> {
+        let mut tx = self.pool.begin().await?;
+
+        let clock = sqlx::query_as::<_, TournamentClockRow>(
+            "SELECT id, tournament_id, clock_status, current_level, level_started_at, level_end_time, 
+                    pause_started_at, total_pause_duration, auto_advance, created_at, updated_at
+             FROM tournament_clocks WHERE tournament_id = $1
+             FOR UPDATE",
+        )
+        .bind(tournament_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let existing: Vec<TournamentStructureLevel> = sqlx::query_as::<_, TournamentStructureRow>(
+            "SELECT id, tournament_id, level_number, small_blind, big_blind, ante, 
+                    duration_minutes, is_break, break_duration_minutes, created_at
+             FROM tournament_structures 
+             WHERE tournament_id = $1 
+             ORDER BY level_number ASC",
+        )
+        .bind(tournament_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(Into::into)
+        .collect();
+        if existing != expected {
+            return Ok(None);
+        }
+
+        let last_played_level = clock.map(|c| c.last_played_level()).unwrap_or(0).max(0) as usize;
+        if (0..last_played_level).any(|i| existing.get(i) != levels.get(i)) {
+            return Ok(None);
+        }
+
+        sqlx::query("DELETE FROM tournament_structures WHERE tournament_id = $1")
+            .bind(tournament_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut rows = Vec::with_capacity(levels.len());
+        for level in levels {
+            let row = sqlx::query_as::<_, TournamentStructureRow>(
+                "INSERT INTO tournament_structures 
+                 (tournament_id, level_number, small_blind, big_blind, ante, duration_minutes, is_break, break_duration_minutes)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 RETURNING id, tournament_id, level_number, small_blind, big_blind, ante, 
+                           duration_minutes, is_break, break_duration_minutes, created_at"
+            )
+            .bind(tournament_id)
+            .bind(level.level_number)
+            .bind(level.small_blind)
+            .bind(level.big_blind)
+            .bind(level.ante)
+            .bind(level.duration_minutes)
+            .bind(level.is_break)
+            .bind(level.break_duration_minutes)
+            .fetch_one(&mut *tx)
+            .await?;
+            rows.push(row);
+        }
+
+        tx.commit().await?;
+        Ok(Some(rows))
+    }
+
     /// Log clock event
     async fn log_event(
         &self,
@@ -489,12 +566,13 @@ impl TournamentClockRepo {
         Ok(())
     }
 
-    /// Get tournaments that need level advancement
-    pub async fn get_tournaments_to_advance(&self) -> SqlxResult<Vec<Uuid>> {
-        let now = Utc::now();
-
-        let rows: Vec<(Uuid,)> = sqlx::query_as(
-            "SELECT tournament_id FROM tournament_clocks 
+    /// Get tournaments that need level advancement at `now`, with the level they are on
+    pub async fn get_tournaments_to_advance(
+        &self,
+        now: DateTime<Utc>,
+    ) -> SqlxResult<Vec<(Uuid, i32)>> {
+        sqlx::query_as(
+            "SELECT tournament_id, current_level FROM tournament_clocks 
              WHERE clock_status = 'running' 
                AND auto_advance = true 
                AND level_end_time IS NOT NULL 
@@ -502,8 +580,57 @@ impl TournamentClockRepo {
         )
         .bind(now)
         .fetch_all(&self.pool)
-        .await?;
+        .await
+    }
+
+    /// When the next running clock with auto-advance reaches the end of its level,
+    /// after `now`
+    pub async fn next_level_end_time(
+        &self,
+        now: DateTime<Utc>,
+    ) -> SqlxResult<Option<DateTime<Utc>>> {
+        sqlx::query_scalar(
+            "SELECT MIN(level_end_time) FROM tournament_clocks
+             WHERE clock_status = 'running' AND auto_advance = true AND level_end_time > $1",
+        )
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
 
-        Ok(rows.into_iter().map(|(id,)| id).collect())
+/// Stop the clock of a tournament for good on `conn`, so it can be part of a larger
+/// transaction. Returns `None` when the tournament has no clock.
+pub(crate) async fn stop_clock(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+    manager_id: Option<Uuid>,
+) -> SqlxResult<Option<TournamentClockRow>> {
+    let clock = sqlx::query_as::<_, TournamentClockRow>(
+        "UPDATE tournament_clocks 
+         SET clock_status = 'stopped',
+             level_end_time = NULL,
+             pause_started_at = NULL
+         WHERE tournament_id = $1
+         RETURNING id, tournament_id, clock_status, current_level, level_started_at, level_end_time,
+                   pause_started_at, total_pause_duration, auto_advance, created_at, updated_at",
+    )
+    .bind(tournament_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(clock) = &clock {
+        sqlx::query(
+            "INSERT INTO tournament_clock_events 
+             (tournament_id, event_type, level_number, manager_id, metadata)
+             VALUES ($1, 'stop', $2, $3, '{}'::jsonb)",
+        )
+        .bind(tournament_id)
+        .bind(clock.current_level)
+        .bind(manager_id)
+        .execute(&mut *conn)
+        .await?;
     }
+
+    Ok(clock)
 }
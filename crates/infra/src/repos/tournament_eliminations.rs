@@ -0,0 +1,584 @@
+use std::str::FromStr;
+
+use sqlx::{PgConnection, PgPool, Result, Row};
+use uuid::Uuid;
+
+use crate::bounty::{split_knockout, BountyFormat, KnockoutSplit};
+use crate::finishing::{finishing_positions, Bust};
+use crate::models::TournamentEliminationRow;
+use crate::repos::tournament_clock::stop_clock;
+use crate::repos::tournament_results::{create_result, recalculate_points, CreateTournamentResult};
+
+const ELIMINATION_COLUMNS: &str = r#"
+    id, tournament_id, registration_id, user_id, eliminated_by, bounty_cents,
+    bounty_added_cents, level, finishing_position, seat_assignment_id, stack_size,
+    previous_status, created_by, created_at
+"#;
+
+/// Registrations still in the tournament: seated, or holding a seat. Players who
+/// registered but never sat down are not in.
+const PLAYERS_REMAINING: &str = r#"
+    SELECT COUNT(*)
+    FROM tournament_registrations reg
+    WHERE reg.tournament_id = $1
+      AND (
+          reg.status = 'seated'
+          OR (
+              reg.status IN ('registered', 'checked_in')
+              AND EXISTS (
+                  SELECT 1
+                  FROM table_seat_assignments tsa
+                  WHERE tsa.tournament_id = reg.tournament_id
+                    AND tsa.user_id = reg.user_id
+                    AND tsa.is_current = true
+              )
+          )
+      )
+"#;
+
+/// Players busting in the same hand
+#[derive(Debug, Clone)]
+pub struct CreateTournamentEliminations {
+    pub tournament_id: Uuid,
+    pub players: Vec<EliminatedPlayer>,
+    pub notes: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EliminatedPlayer {
+    pub user_id: Uuid,
+    /// Player who won the hand, collects the bounty
+    pub eliminated_by: Option<Uuid>,
+}
+
+pub struct TournamentEliminationRepo {
+    db: PgPool,
+}
+
+impl TournamentEliminationRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Record the players busting in one hand: take their seats, mark them busted, derive
+    /// their finishing positions from the players remaining and pay their bounties to the
+    /// eliminators. In a progressive knockout half of a bounty goes on the eliminator's head.
+    ///
+    /// Every player must currently hold a seat.
+    pub async fn record(
+        &self,
+        data: CreateTournamentEliminations,
+    ) -> Result<Vec<TournamentEliminationRow>> {
+        let mut tx = self.db.begin().await?;
+
+        // Lock the tournament so concurrent eliminations count the remaining players in turn
+        let tournament = sqlx::query(
+            "SELECT bounty_format, bounty_cents FROM tournaments WHERE id = $1 FOR UPDATE",
+        )
+        .bind(data.tournament_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let format =
+            BountyFormat::from_str(tournament.try_get::<String, _>("bounty_format")?.as_str())
+                .unwrap_or(BountyFormat::None);
+        let starting_bounty: i32 = tournament.try_get("bounty_cents")?;
+
+        let level: Option<i32> = sqlx::query_scalar(
+            "SELECT current_level FROM tournament_clocks WHERE tournament_id = $1",
+        )
+        .bind(data.tournament_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let players_remaining: i64 = sqlx::query_scalar(PLAYERS_REMAINING)
+            .bind(data.tournament_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let mut victims = Vec::with_capacity(data.players.len());
+        for player in &data.players {
+            let victim = sqlx::query(
+                r#"
+                SELECT reg.id, reg.status, reg.bounty_cents,
+                       tsa.id AS seat_assignment_id, tsa.club_table_id, tsa.stack_size
+                FROM tournament_registrations reg
+                JOIN table_seat_assignments tsa
+                    ON tsa.tournament_id = reg.tournament_id
+                   AND tsa.user_id = reg.user_id
+                   AND tsa.is_current = true
+                WHERE reg.tournament_id = $1 AND reg.user_id = $2
+                "#,
+            )
+            .bind(data.tournament_id)
+            .bind(player.user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            victims.push(victim);
+        }
+
+        let busts: Vec<Bust> = victims
+            .iter()
+            .map(|victim| {
+                Ok(Bust {
+                    table_id: victim.try_get("club_table_id")?,
+                    stack_size: victim.try_get("stack_size")?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let positions = finishing_positions(players_remaining as i32, &busts);
+
+        let mut rows = Vec::with_capacity(data.players.len());
+        for ((player, victim), position) in data.players.iter().zip(&victims).zip(positions) {
+            let registration_id: Uuid = victim.try_get("id")?;
+            let seat_assignment_id: Uuid = victim.try_get("seat_assignment_id")?;
+            let bounty_cents = victim
+                .try_get::<Option<i32>, _>("bounty_cents")?
+                .unwrap_or(starting_bounty);
+
+            // Nobody collects the bounty when the eliminator isn't known
+            let split = match player.eliminated_by {
+                Some(_) => split_knockout(format, bounty_cents),
+                None => KnockoutSplit::default(),
+            };
+
+            if let Some(eliminated_by) = player.eliminated_by {
+                let updated = sqlx::query(
+                    r#"
+                    UPDATE tournament_registrations
+                    SET bounty_cents = COALESCE(bounty_cents, $3) + $4, updated_at = NOW()
+                    WHERE tournament_id = $1 AND user_id = $2
+                    "#,
+                )
+                .bind(data.tournament_id)
+                .bind(eliminated_by)
+                .bind(starting_bounty)
+                .bind(split.added_cents)
+                .execute(&mut *tx)
+                .await?;
+                if updated.rows_affected() == 0 {
+                    return Err(sqlx::Error::RowNotFound);
+                }
+            }
+
+            let claimed = player.eliminated_by.is_some() && format != BountyFormat::None;
+            sqlx::query(
+                r#"
+                UPDATE tournament_registrations
+                SET status = 'busted',
+                    bounty_cents = CASE WHEN $2 THEN 0 ELSE bounty_cents END,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(registration_id)
+            .bind(claimed)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE table_seat_assignments
+                SET stack_size = 0,
+                    is_current = false,
+                    unassigned_at = NOW(),
+                    assigned_by = COALESCE($2, assigned_by),
+                    notes = COALESCE($3, 'Player eliminated'),
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(seat_assignment_id)
+            .bind(data.created_by)
+            .bind(&data.notes)
+            .execute(&mut *tx)
+            .await?;
+
+            let row = sqlx::query_as::<_, TournamentEliminationRow>(&format!(
+                r#"
+                INSERT INTO tournament_eliminations (tournament_id, registration_id, user_id,
+                                                     eliminated_by, bounty_cents,
+                                                     bounty_added_cents, level,
+                                                     finishing_position, seat_assignment_id,
+                                                     stack_size, previous_status, created_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                RETURNING {}
+                "#,
+                ELIMINATION_COLUMNS
+            ))
+            .bind(data.tournament_id)
+            .bind(registration_id)
+            .bind(player.user_id)
+            .bind(player.eliminated_by)
+            .bind(split.cash_cents)
+            .bind(split.added_cents)
+            .bind(level)
+            .bind(position)
+            .bind(seat_assignment_id)
+            .bind(victim.try_get::<Option<i32>, _>("stack_size")?)
+            .bind(victim.try_get::<String, _>("status")?)
+            .bind(data.created_by)
+            .fetch_one(&mut *tx)
+            .await?;
+            rows.push(row);
+        }
+
+        tx.commit().await?;
+
+        Ok(rows)
+    }
+
+    /// Get all eliminations of a tournament, oldest first
+    pub async fn get_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentEliminationRow>> {
+        let rows = sqlx::query_as::<_, TournamentEliminationRow>(&format!(
+            r#"
+            SELECT {}
+            FROM tournament_eliminations
+            WHERE tournament_id = $1
+            ORDER BY created_at, finishing_position DESC, id
+            "#,
+            ELIMINATION_COLUMNS
+        ))
+        .bind(tournament_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Number of players still in a tournament, seated or holding a seat
+    pub async fn players_remaining(&self, tournament_id: Uuid) -> Result<i64> {
+        sqlx::query_scalar(PLAYERS_REMAINING)
+            .bind(tournament_id)
+            .fetch_one(&self.db)
+            .await
+    }
+
+    /// Final standings of a tournament as `(user_id, final_position)`, best first: the
+    /// players still in (as counted by [`Self::players_remaining`]) finish 1st, everyone
+    /// else where they last busted
+    pub async fn final_standings(&self, tournament_id: Uuid) -> Result<Vec<(Uuid, i32)>> {
+        sqlx::query_as(
+            r#"
+            SELECT reg.user_id, 1 AS final_position
+            FROM tournament_registrations reg
+            WHERE reg.tournament_id = $1
+              AND (
+                  reg.status = 'seated'
+                  OR (
+                      reg.status IN ('registered', 'checked_in')
+                      AND EXISTS (
+                          SELECT 1
+                          FROM table_seat_assignments tsa
+                          WHERE tsa.tournament_id = reg.tournament_id
+                            AND tsa.user_id = reg.user_id
+                            AND tsa.is_current = true
+                      )
+                  )
+              )
+            UNION ALL
+            SELECT user_id, finishing_position
+            FROM (
+                SELECT DISTINCT ON (el.registration_id) el.user_id, el.finishing_position
+                FROM tournament_eliminations el
+                JOIN tournament_registrations reg ON reg.id = el.registration_id
+                WHERE el.tournament_id = $1
+                  AND reg.status = 'busted'
+                  AND el.finishing_position IS NOT NULL
+                ORDER BY el.registration_id, el.created_at DESC
+            ) busts
+            ORDER BY final_position, user_id
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// Most recent elimination of a tournament
+    pub async fn get_last(&self, tournament_id: Uuid) -> Result<Option<TournamentEliminationRow>> {
+        sqlx::query_as::<_, TournamentEliminationRow>(&format!(
+            r#"
+            SELECT {}
+            FROM tournament_eliminations
+            WHERE tournament_id = $1
+            ORDER BY created_at DESC, finishing_position ASC, id DESC
+            LIMIT 1
+            "#,
+            ELIMINATION_COLUMNS
+        ))
+        .bind(tournament_id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Undo an elimination: give the player their seat, stack, status and bounty back,
+    /// take the bounty back from the eliminator and delete the record
+    ///
+    /// Undoing the elimination that finished the tournament deletes its results and puts
+    /// it back in progress. Its clock stays stopped until it is started again.
+    ///
+    /// # Returns
+    ///
+    /// `false`, changing nothing, when the elimination is no longer the tournament's last,
+    /// the player is back in, their seat was given away or a finished tournament has more
+    /// than one player left.
+    pub async fn undo(&self, elimination: &TournamentEliminationRow) -> Result<bool> {
+        let mut tx = self.db.begin().await?;
+
+        let live_status: String = sqlx::query_scalar(
+            "SELECT live_status::TEXT FROM tournaments WHERE id = $1 FOR UPDATE",
+        )
+        .bind(elimination.tournament_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let last_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id
+            FROM tournament_eliminations
+            WHERE tournament_id = $1
+            ORDER BY created_at DESC, finishing_position ASC, id DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .bind(elimination.tournament_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if last_id != Some(elimination.id) {
+            return Ok(false);
+        }
+
+        let status: Option<String> = sqlx::query_scalar(
+            "SELECT status::TEXT FROM tournament_registrations WHERE id = $1 FOR UPDATE",
+        )
+        .bind(elimination.registration_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if status.as_deref() != Some("busted") {
+            return Ok(false);
+        }
+
+        if live_status == "finished" {
+            let remaining: i64 = sqlx::query_scalar(PLAYERS_REMAINING)
+                .bind(elimination.tournament_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if remaining != 1 {
+                return Ok(false);
+            }
+        }
+
+        if let Some(seat_assignment_id) = elimination.seat_assignment_id {
+            let seat_taken: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM table_seat_assignments taken
+                    JOIN table_seat_assignments own ON own.id = $1
+                    WHERE taken.is_current = true
+                      AND (
+                          (taken.club_table_id = own.club_table_id AND taken.seat_number = own.seat_number)
+                          OR (taken.tournament_id = own.tournament_id AND taken.user_id = own.user_id)
+                      )
+                )
+                "#,
+            )
+            .bind(seat_assignment_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            if seat_taken {
+                return Ok(false);
+            }
+        }
+
+        if live_status == "finished" {
+            sqlx::query("DELETE FROM tournament_results WHERE tournament_id = $1")
+                .bind(elimination.tournament_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                "UPDATE tournaments SET live_status = 'in_progress', updated_at = NOW() WHERE id = $1",
+            )
+            .bind(elimination.tournament_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(seat_assignment_id) = elimination.seat_assignment_id {
+            sqlx::query(
+                r#"
+                UPDATE table_seat_assignments
+                SET stack_size = $2,
+                    is_current = true,
+                    unassigned_at = NULL,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(seat_assignment_id)
+            .bind(elimination.stack_size)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // The eliminated player's bounty was cleared only when someone collected it
+        sqlx::query(
+            r#"
+            UPDATE tournament_registrations reg
+            SET status = $2,
+                bounty_cents = CASE
+                    WHEN $3::uuid IS NOT NULL AND t.bounty_format <> 'none' THEN $4
+                    ELSE reg.bounty_cents
+                END,
+                updated_at = NOW()
+            FROM tournaments t
+            WHERE reg.id = $1 AND t.id = reg.tournament_id
+            "#,
+        )
+        .bind(elimination.registration_id)
+        .bind(&elimination.previous_status)
+        .bind(elimination.eliminated_by)
+        .bind(elimination.bounty_cents + elimination.bounty_added_cents)
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(eliminated_by) = elimination.eliminated_by {
+            sqlx::query(
+                r#"
+                UPDATE tournament_registrations
+                SET bounty_cents = bounty_cents - $3, updated_at = NOW()
+                WHERE tournament_id = $1 AND user_id = $2 AND bounty_cents IS NOT NULL
+                "#,
+            )
+            .bind(elimination.tournament_id)
+            .bind(eliminated_by)
+            .bind(elimination.bounty_added_cents)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("DELETE FROM tournament_eliminations WHERE id = $1")
+            .bind(elimination.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Bounty currently on a registered player's head
+    pub async fn current_bounty(&self, registration_id: Uuid) -> Result<Option<i32>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(reg.bounty_cents, t.bounty_cents)
+            FROM tournament_registrations reg
+            JOIN tournaments t ON t.id = reg.tournament_id
+            WHERE reg.id = $1
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Finish a tournament down to its last player in one transaction: store the results
+    /// with their bounties, finish the tournament (which awards the points) and stop the
+    /// clock
+    ///
+    /// # Returns
+    ///
+    /// `false`, changing nothing, when more than one player is still in or the tournament
+    /// already has results, whether entered by hand or by an earlier call.
+    pub async fn finalise(
+        &self,
+        tournament_id: Uuid,
+        results: Vec<CreateTournamentResult>,
+        stopped_by: Option<Uuid>,
+    ) -> Result<bool> {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query("SELECT id FROM tournaments WHERE id = $1 FOR UPDATE")
+            .bind(tournament_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let players_remaining: i64 = sqlx::query_scalar(PLAYERS_REMAINING)
+            .bind(tournament_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let has_results: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM tournament_results WHERE tournament_id = $1)",
+        )
+        .bind(tournament_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if players_remaining != 1 || has_results {
+            return Ok(false);
+        }
+
+        for result in results {
+            create_result(&mut tx, result).await?;
+        }
+        apply_bounties_to_results(&mut tx, tournament_id).await?;
+
+        sqlx::query(
+            "UPDATE tournaments SET live_status = 'finished', updated_at = NOW() WHERE id = $1",
+        )
+        .bind(tournament_id)
+        .execute(&mut *tx)
+        .await?;
+        recalculate_points(&mut tx, tournament_id).await?;
+        stop_clock(&mut tx, tournament_id, stopped_by).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Store the bounties each player won on their result: the knockouts they made, plus
+    /// their own bounty for the winner of a bounty event
+    pub async fn apply_bounties_to_results(&self, tournament_id: Uuid) -> Result<()> {
+        let mut conn = self.db.acquire().await?;
+        apply_bounties_to_results(&mut conn, tournament_id).await
+    }
+}
+
+/// Store the bounties won on the results of a tournament on `conn`, so it can be part of
+/// a larger transaction
+pub(crate) async fn apply_bounties_to_results(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE tournament_results tr
+        SET bounty_cents = COALESCE((
+                SELECT SUM(el.bounty_cents)
+                FROM tournament_eliminations el
+                WHERE el.tournament_id = tr.tournament_id AND el.eliminated_by = tr.user_id
+            ), 0)
+            + CASE
+                WHEN tr.final_position = 1 AND t.bounty_format <> 'none'
+                THEN COALESCE(reg.bounty_cents, t.bounty_cents)
+                ELSE 0
+              END,
+            updated_at = NOW()
+        FROM tournaments t, tournament_registrations reg
+        WHERE tr.tournament_id = $1
+          AND t.id = tr.tournament_id
+          AND reg.tournament_id = tr.tournament_id
+          AND reg.user_id = tr.user_id
+        "#,
+    )
+    .bind(tournament_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
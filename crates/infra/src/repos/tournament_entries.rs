@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+use crate::models::TournamentEntryRow;
+
+/// Entries of one type a registration has made
+const ENTRIES_OF_TYPE: &str =
+    "SELECT COUNT(*) FROM tournament_entries WHERE registration_id = $1 AND entry_type = $2";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentEntryType {
+    BuyIn,
+    Reentry,
+    Rebuy,
+    AddOn,
+}
+
+impl TournamentEntryType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TournamentEntryType::BuyIn => "buy_in",
+            TournamentEntryType::Reentry => "reentry",
+            TournamentEntryType::Rebuy => "rebuy",
+            TournamentEntryType::AddOn => "add_on",
+        }
+    }
+}
+
+impl FromStr for TournamentEntryType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "buy_in" => Ok(TournamentEntryType::BuyIn),
+            "reentry" => Ok(TournamentEntryType::Reentry),
+            "rebuy" => Ok(TournamentEntryType::Rebuy),
+            "add_on" => Ok(TournamentEntryType::AddOn),
+            _ => Err(format!("Unknown tournament entry type: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTournamentEntry {
+    pub registration_id: Uuid,
+    pub entry_type: TournamentEntryType,
+    pub amount_cents: i32,
+    /// Part of the amount kept by the house
+    pub rake_cents: i32,
+    /// Part of the amount paid to the dealers and staff
+    pub staff_fee_cents: i32,
+    /// Part of the amount put on the player's head
+    pub bounty_cents: i32,
+    pub created_by: Option<Uuid>,
+}
+
+/// Where the money paid into a tournament went
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+pub struct TournamentEntryTotals {
+    pub paid_cents: i64,
+    pub prize_cents: i64,
+    pub rake_cents: i64,
+    pub staff_fee_cents: i64,
+    pub bounty_cents: i64,
+}
+
+pub struct TournamentEntryRepo {
+    db: PgPool,
+}
+
+impl TournamentEntryRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Record an entry for a registration that made fewer than `max_entries` entries of
+    /// this type. A re-entry also puts the busted player back in the field with a fresh
+    /// starting bounty.
+    ///
+    /// The registration stays locked while its entries are counted, so concurrent entries
+    /// can't go over the limit. Returns `None`, recording nothing, when the limit is
+    /// reached or the player's status doesn't allow the entry: re-entries are for busted
+    /// players, rebuys and add-ons for players still in.
+    pub async fn create(
+        &self,
+        data: CreateTournamentEntry,
+        max_entries: i64,
+    ) -> Result<Option<TournamentEntryRow>> {
+        let mut tx = self.db.begin().await?;
+
+        let status: String = sqlx::query_scalar(
+            "SELECT status::TEXT FROM tournament_registrations WHERE id = $1 FOR UPDATE",
+        )
+        .bind(data.registration_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let allowed = match data.entry_type {
+            TournamentEntryType::BuyIn => true,
+            TournamentEntryType::Reentry => status == "busted",
+            TournamentEntryType::Rebuy | TournamentEntryType::AddOn => {
+                matches!(status.as_str(), "checked_in" | "seated")
+            }
+        };
+        let previous: i64 = sqlx::query_scalar(ENTRIES_OF_TYPE)
+            .bind(data.registration_id)
+            .bind(data.entry_type.as_str())
+            .fetch_one(&mut *tx)
+            .await?;
+        if !allowed || previous >= max_entries {
+            return Ok(None);
+        }
+
+        let row = sqlx::query_as::<_, TournamentEntryRow>(
+            r#"
+            INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type,
+                                            amount_cents, rake_cents, staff_fee_cents,
+                                            bounty_cents, created_by)
+            SELECT tournament_id, id, user_id, $2, $3, $4, $5, $6, $7
+            FROM tournament_registrations
+            WHERE id = $1
+            RETURNING id, tournament_id, registration_id, user_id, entry_type, amount_cents,
+                      rake_cents, staff_fee_cents, bounty_cents, prize_cents, created_by, created_at
+            "#,
+        )
+        .bind(data.registration_id)
+        .bind(data.entry_type.as_str())
+        .bind(data.amount_cents)
+        .bind(data.rake_cents)
+        .bind(data.staff_fee_cents)
+        .bind(data.bounty_cents)
+        .bind(data.created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if data.entry_type == TournamentEntryType::Reentry {
+            sqlx::query(
+                r#"
+                UPDATE tournament_registrations
+                SET status = 'checked_in', bounty_cents = NULL, updated_at = NOW()
+                WHERE id = $1 AND status = 'busted'
+                "#,
+            )
+            .bind(data.registration_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(row))
+    }
+
+    /// Get all entries of a tournament, oldest first
+    pub async fn get_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<TournamentEntryRow>> {
+        let rows = sqlx::query_as::<_, TournamentEntryRow>(
+            r#"
+            SELECT id, tournament_id, registration_id, user_id, entry_type, amount_cents,
+                   rake_cents, staff_fee_cents, bounty_cents, prize_cents, created_by, created_at
+            FROM tournament_entries
+            WHERE tournament_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Count the entries of one type a registration has made
+    pub async fn count_by_registration(
+        &self,
+        registration_id: Uuid,
+        entry_type: TournamentEntryType,
+    ) -> Result<i64> {
+        sqlx::query_scalar(ENTRIES_OF_TYPE)
+            .bind(registration_id)
+            .bind(entry_type.as_str())
+            .fetch_one(&self.db)
+            .await
+    }
+
+    /// Number of entries (buy-ins and re-entries) in a tournament
+    pub async fn entry_count(&self, tournament_id: Uuid) -> Result<i32> {
+        sqlx::query_scalar("SELECT tournament_entry_count($1)")
+            .bind(tournament_id)
+            .fetch_one(&self.db)
+            .await
+    }
+
+    /// Prize pool of a tournament: what was paid in, minus rake, staff fees and bounties
+    pub async fn prize_pool(&self, tournament_id: Uuid) -> Result<i32> {
+        sqlx::query_scalar("SELECT tournament_prize_pool($1)")
+            .bind(tournament_id)
+            .fetch_one(&self.db)
+            .await
+    }
+
+    /// Split of everything paid into a tournament
+    pub async fn totals(&self, tournament_id: Uuid) -> Result<TournamentEntryTotals> {
+        sqlx::query_as::<_, TournamentEntryTotals>(
+            r#"
+            SELECT COALESCE(SUM(amount_cents), 0)::BIGINT AS paid_cents,
+                   COALESCE(SUM(prize_cents), 0)::BIGINT AS prize_cents,
+                   COALESCE(SUM(rake_cents), 0)::BIGINT AS rake_cents,
+                   COALESCE(SUM(staff_fee_cents), 0)::BIGINT AS staff_fee_cents,
+                   COALESCE(SUM(bounty_cents), 0)::BIGINT AS bounty_cents
+            FROM tournament_entries
+            WHERE tournament_id = $1
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_one(&self.db)
+        .await
+    }
+}
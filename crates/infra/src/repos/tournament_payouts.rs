@@ -20,7 +20,7 @@ impl TournamentPayoutRepo {
         let row = sqlx::query_as::<_, TournamentPayoutRow>(
             r#"
             SELECT id, tournament_id, template_id, player_count, 
-                   total_prize_pool, payout_positions, created_at, updated_at
+                   total_prize_pool, overlay_cents, payout_positions, created_at, updated_at
             FROM tournament_payouts
             WHERE tournament_id = $1
             "#,
@@ -37,7 +37,7 @@ impl TournamentPayoutRepo {
         let row = sqlx::query_as::<_, TournamentPayoutRow>(
             r#"
             SELECT id, tournament_id, template_id, player_count, 
-                   total_prize_pool, payout_positions, created_at, updated_at
+                   total_prize_pool, overlay_cents, payout_positions, created_at, updated_at
             FROM tournament_payouts
             WHERE id = $1
             "#,
@@ -49,26 +49,23 @@ impl TournamentPayoutRepo {
         Ok(row)
     }
 
-    /// Manually recalculate payouts for a tournament (useful if template changes)
+    /// Manually recalculate payouts for a tournament (useful if template changes).
+    /// The prize pool is the prize part of every entry, without rake and staff fees.
     pub async fn recalculate(&self, tournament_id: Uuid) -> Result<Option<TournamentPayoutRow>> {
-        // Delete existing payout
+        let mut tx = self.db.begin().await?;
+
+        // Delete existing payout so the template is picked again
         sqlx::query("DELETE FROM tournament_payouts WHERE tournament_id = $1")
             .bind(tournament_id)
-            .execute(&self.db)
+            .execute(&mut *tx)
             .await?;
 
-        // Trigger recalculation by updating tournament status
-        // This is a workaround to trigger the SQL function
-        sqlx::query(
-            r#"
-            UPDATE tournaments 
-            SET live_status = 'in_progress', updated_at = NOW() 
-            WHERE id = $1 AND live_status = 'in_progress'
-            "#,
-        )
-        .bind(tournament_id)
-        .execute(&self.db)
-        .await?;
+        sqlx::query("SELECT refresh_tournament_payouts($1)")
+            .bind(tournament_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
 
         // Return the new payout
         self.get_by_tournament(tournament_id).await
@@ -86,7 +83,7 @@ impl TournamentPayoutRepo {
             SET payout_positions = $2, updated_at = NOW()
             WHERE tournament_id = $1
             RETURNING id, tournament_id, template_id, player_count, 
-                      total_prize_pool, payout_positions, created_at, updated_at
+                      total_prize_pool, overlay_cents, payout_positions, created_at, updated_at
             "#,
         )
         .bind(tournament_id)
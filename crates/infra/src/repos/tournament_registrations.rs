@@ -1,7 +1,11 @@
-use sqlx::{PgPool, Result};
+use sqlx::{PgPool, Postgres, Result, Transaction};
 use uuid::Uuid;
 
 use crate::models::TournamentRegistrationRow;
+use crate::repos::tournaments::TournamentLiveStatus;
+
+/// Registration statuses that take up one of the tournament's seats
+pub const SEAT_HOLDING_STATUSES: [&str; 4] = ["registered", "checked_in", "seated", "busted"];
 
 #[derive(Debug, Clone)]
 pub struct CreateTournamentRegistration {
@@ -10,6 +14,13 @@ pub struct CreateTournamentRegistration {
     pub notes: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct RegistrationStatusChange {
+    pub registration: TournamentRegistrationRow,
+    /// Waitlisted registrations that took the seat this change freed up
+    pub promoted: Vec<TournamentRegistrationRow>,
+}
+
 pub struct TournamentRegistrationRepo {
     db: PgPool,
 }
@@ -19,23 +30,65 @@ impl TournamentRegistrationRepo {
         Self { db }
     }
 
+    /// Register a player, or put them on the waitlist once the seat cap is reached. A
+    /// player whose registration was cancelled gets it back, at the end of the line.
+    /// Returns `None`, registering nobody, when the tournament is gone, finished or
+    /// cancelled, or the player is already registered.
     pub async fn create(
         &self,
         data: CreateTournamentRegistration,
-    ) -> Result<TournamentRegistrationRow> {
+    ) -> Result<Option<TournamentRegistrationRow>> {
+        let mut tx = self.db.begin().await?;
+
+        // Lock the tournament so two players can't both take the last seat
+        let tournament: Option<(Option<i32>, TournamentLiveStatus)> = sqlx::query_as(
+            "SELECT seat_cap, live_status FROM tournaments WHERE id = $1 FOR UPDATE",
+        )
+        .bind(data.tournament_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((seat_cap, live_status)) = tournament else {
+            return Ok(None);
+        };
+        if matches!(
+            live_status,
+            TournamentLiveStatus::Finished | TournamentLiveStatus::Cancelled
+        ) {
+            return Ok(None);
+        }
+
+        let status = match seat_cap {
+            Some(seat_cap)
+                if Self::seats_taken(&mut tx, data.tournament_id).await? >= seat_cap as i64 =>
+            {
+                "waitlisted"
+            }
+            _ => "registered",
+        };
+
         let row = sqlx::query_as::<_, TournamentRegistrationRow>(
             r#"
-            INSERT INTO tournament_registrations (tournament_id, user_id, notes)
-            VALUES ($1, $2, $3)
+            INSERT INTO tournament_registrations (tournament_id, user_id, notes, status)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tournament_id, user_id) DO UPDATE
+            SET status = EXCLUDED.status,
+                notes = EXCLUDED.notes,
+                registration_time = NOW(),
+                bounty_cents = NULL,
+                updated_at = NOW()
+            WHERE tournament_registrations.status = 'cancelled'
             RETURNING id, tournament_id, user_id, registration_time, status, notes, created_at, updated_at
             "#
         )
         .bind(data.tournament_id)
         .bind(data.user_id)
         .bind(data.notes)
-        .fetch_one(&self.db)
+        .bind(status)
+        .fetch_optional(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(row)
     }
 
@@ -112,4 +165,160 @@ impl TournamentRegistrationRepo {
 
         Ok(rows)
     }
+
+    /// Get the waitlist of a tournament, first in line first
+    pub async fn get_waitlist(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentRegistrationRow>> {
+        let rows = sqlx::query_as::<_, TournamentRegistrationRow>(
+            r#"
+            SELECT id, tournament_id, user_id, registration_time, status, notes, created_at, updated_at
+            FROM tournament_registrations
+            WHERE tournament_id = $1 AND status = 'waitlisted'
+            ORDER BY registration_time ASC, id ASC
+            "#
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Change the status of a registration (and its notes, when given). When the change
+    /// gives up a seat, the first waitlisted player is promoted in the same transaction.
+    ///
+    /// # Returns
+    ///
+    /// `None`, changing nothing, when the registration is gone or its status is no longer
+    /// `expected_status`.
+    pub async fn update_status(
+        &self,
+        id: Uuid,
+        expected_status: &str,
+        status: &str,
+        notes: Option<String>,
+    ) -> Result<Option<RegistrationStatusChange>> {
+        let Some(existing) = self.get_by_id(id).await? else {
+            return Ok(None);
+        };
+
+        let mut tx = self.db.begin().await?;
+
+        // Same lock order as registering: tournament first, then its registrations
+        sqlx::query("SELECT 1 FROM tournaments WHERE id = $1 FOR UPDATE")
+            .bind(existing.tournament_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let previous_status: Option<String> = sqlx::query_scalar(
+            "SELECT status FROM tournament_registrations WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(previous_status) = previous_status else {
+            return Ok(None);
+        };
+        if previous_status != expected_status {
+            return Ok(None);
+        }
+
+        let registration = sqlx::query_as::<_, TournamentRegistrationRow>(
+            r#"
+            UPDATE tournament_registrations
+            SET status = $2, notes = COALESCE($3, notes), updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, tournament_id, user_id, registration_time, status, notes, created_at, updated_at
+            "#
+        )
+        .bind(id)
+        .bind(status)
+        .bind(notes)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let frees_seat = SEAT_HOLDING_STATUSES.contains(&previous_status.as_str())
+            && !SEAT_HOLDING_STATUSES.contains(&status);
+        let promoted = if frees_seat {
+            Self::promote_waitlisted(&mut tx, registration.tournament_id).await?
+        } else {
+            Vec::new()
+        };
+
+        tx.commit().await?;
+
+        Ok(Some(RegistrationStatusChange {
+            registration,
+            promoted,
+        }))
+    }
+
+    /// Promote waitlisted players into any free seats, e.g. after the seat cap was raised
+    pub async fn promote_from_waitlist(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentRegistrationRow>> {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query("SELECT 1 FROM tournaments WHERE id = $1 FOR UPDATE")
+            .bind(tournament_id)
+            .execute(&mut *tx)
+            .await?;
+        let promoted = Self::promote_waitlisted(&mut tx, tournament_id).await?;
+
+        tx.commit().await?;
+
+        Ok(promoted)
+    }
+
+    async fn seats_taken(tx: &mut Transaction<'_, Postgres>, tournament_id: Uuid) -> Result<i64> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM tournament_registrations WHERE tournament_id = $1 AND status = ANY($2)",
+        )
+        .bind(tournament_id)
+        .bind(&SEAT_HOLDING_STATUSES[..])
+        .fetch_one(&mut **tx)
+        .await
+    }
+
+    /// Move waitlisted players, in waitlist order, into the seats that are free. The
+    /// tournament row must already be locked.
+    async fn promote_waitlisted(
+        tx: &mut Transaction<'_, Postgres>,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentRegistrationRow>> {
+        let mut promoted = sqlx::query_as::<_, TournamentRegistrationRow>(
+            r#"
+            WITH free_seats AS (
+                SELECT t.seat_cap - (
+                    SELECT COUNT(*) FROM tournament_registrations
+                    WHERE tournament_id = t.id AND status = ANY($2)
+                ) AS seats
+                FROM tournaments t
+                WHERE t.id = $1
+            ),
+            next_in_line AS (
+                SELECT id FROM tournament_registrations
+                WHERE tournament_id = $1 AND status = 'waitlisted'
+                ORDER BY registration_time ASC, id ASC
+                -- No seat cap means everyone gets in (LIMIT NULL)
+                LIMIT (SELECT CASE WHEN seats IS NULL THEN NULL ELSE GREATEST(seats, 0) END FROM free_seats)
+            )
+            UPDATE tournament_registrations
+            SET status = 'registered', updated_at = NOW()
+            WHERE id IN (SELECT id FROM next_in_line)
+            RETURNING id, tournament_id, user_id, registration_time, status, notes, created_at, updated_at
+            "#
+        )
+        .bind(tournament_id)
+        .bind(&SEAT_HOLDING_STATUSES[..])
+        .fetch_all(&mut **tx)
+        .await?;
+
+        promoted.sort_by_key(|row| (row.registration_time, row.id));
+
+        Ok(promoted)
+    }
 }
@@ -1,7 +1,10 @@
-use sqlx::{PgPool, Result, Row};
+use chrono::NaiveDate;
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Result, Row};
 use uuid::Uuid;
 
-use crate::models::TournamentResultRow;
+use crate::models::{TournamentResultCorrectionRow, TournamentResultRow};
+use crate::pagination::LimitOffset;
+use crate::repos::club_scoring_settings::settings_for_tournament;
 
 #[derive(Debug, Clone)]
 pub struct UserStatistics {
@@ -9,6 +12,7 @@ pub struct UserStatistics {
     pub total_tournaments: i32,
     pub total_winnings: i32,
     pub total_buy_ins: i32,
+    pub total_bounty_winnings: i32,
     pub itm_percentage: f64,
     pub roi_percentage: f64,
 }
@@ -23,17 +27,28 @@ pub struct LeaderboardEntry {
     pub phone: Option<String>,
     pub is_active: bool,
     pub role: Option<String>,
+    pub rank: i32,       // Shared by players on the same points (1, 2, 2, 4)
+    pub dense_rank: i32, // Shared without gaps (1, 2, 2, 3)
     pub total_tournaments: i32,
-    pub total_buy_ins: i32,  // Total amount spent (cents)
-    pub total_winnings: i32, // Total amount won (cents)
-    pub net_profit: i32,     // winnings - buy_ins (cents)
-    pub total_itm: i32,      // Number of tournaments where player finished in the money
-    pub itm_percentage: f64, // (total_itm / total_tournaments) * 100
-    pub roi_percentage: f64, // ((total_winnings - total_buy_ins) / total_buy_ins) * 100
+    pub total_buy_ins: i32,         // Total amount spent (cents)
+    pub total_winnings: i32,        // Total amount won (cents)
+    pub total_bounty_winnings: i32, // Total bounties won (cents)
+    pub net_profit: i32,            // winnings + bounties - buy_ins (cents)
+    pub total_itm: i32,             // Number of tournaments where player finished in the money
+    pub itm_percentage: f64,        // (total_itm / total_tournaments) * 100
+    pub roi_percentage: f64, // ((total_winnings + total_bounty_winnings - total_buy_ins) / total_buy_ins) * 100
     pub average_finish: f64, // Average finishing position
     pub first_places: i32,   // Number of first place finishes
     pub final_tables: i32,   // Number of final table finishes (typically top 8-10)
     pub points: f64,         // Calculated leaderboard points
+    pub winnings_hidden: bool, // The player hides their winnings from other players
+}
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    /// Players on the whole leaderboard, not just this page
+    pub total_players: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +69,16 @@ pub struct CreateTournamentResult {
     pub notes: Option<String>,
 }
 
+/// Correction of a result, fields left empty keep their value
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTournamentResult {
+    pub final_position: Option<i32>,
+    pub prize_cents: Option<i32>,
+    pub bounty_cents: Option<i32>,
+    /// `Some(None)` clears the notes
+    pub notes: Option<Option<String>>,
+}
+
 pub struct TournamentResultRepo {
     db: PgPool,
 }
@@ -64,30 +89,14 @@ impl TournamentResultRepo {
     }
 
     pub async fn create(&self, data: CreateTournamentResult) -> Result<TournamentResultRow> {
-        // Insert tournament result without calculating points
-        // Points will be calculated automatically when tournament status changes to 'finished'
-        let row = sqlx::query_as::<_, TournamentResultRow>(
-            r#"
-            INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents, points, notes)
-            VALUES ($1, $2, $3, $4, 0, $5)
-            RETURNING id, tournament_id, user_id, final_position, prize_cents, points, notes, created_at, updated_at
-            "#
-        )
-        .bind(data.tournament_id)
-        .bind(data.user_id)
-        .bind(data.final_position)
-        .bind(data.prize_cents)
-        .bind(data.notes)
-        .fetch_one(&self.db)
-        .await?;
-
-        Ok(row)
+        let mut conn = self.db.acquire().await?;
+        create_result(&mut conn, data).await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<TournamentResultRow>> {
         let row = sqlx::query_as::<_, TournamentResultRow>(
             r#"
-            SELECT id, tournament_id, user_id, final_position, prize_cents, points, notes, created_at, updated_at
+            SELECT id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
             FROM tournament_results
             WHERE id = $1
             "#
@@ -102,7 +111,7 @@ impl TournamentResultRepo {
     pub async fn get_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<TournamentResultRow>> {
         let rows = sqlx::query_as::<_, TournamentResultRow>(
             r#"
-            SELECT id, tournament_id, user_id, final_position, prize_cents, points, notes, created_at, updated_at
+            SELECT id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
             FROM tournament_results
             WHERE tournament_id = $1
             ORDER BY final_position ASC
@@ -122,7 +131,7 @@ impl TournamentResultRepo {
     ) -> Result<Vec<TournamentResultRow>> {
         let rows = sqlx::query_as::<_, TournamentResultRow>(
             r#"
-            SELECT tr.id, tr.tournament_id, tr.user_id, tr.final_position, tr.prize_cents, tr.points, tr.notes, tr.created_at, tr.updated_at
+            SELECT tr.id, tr.tournament_id, tr.user_id, tr.final_position, tr.prize_cents, tr.bounty_cents, tr.points, tr.notes, tr.created_at, tr.updated_at
             FROM tournament_results tr
             JOIN tournaments t ON tr.tournament_id = t.id
             WHERE tr.user_id = $1
@@ -163,14 +172,19 @@ impl TournamentResultRepo {
         .fetch_one(&self.db)
         .await?;
 
-        // Get total tournament count and buy-ins for the same period
+        // Get total tournament count and buy-ins for the same period. Buy-ins are everything
+        // the player paid: buy-ins, re-entries, rebuys and add-ons, fees included.
         let tournament_row = sqlx::query(
             r#"
             SELECT 
                 COUNT(DISTINCT reg.tournament_id) as total_tournaments,
-                COALESCE(SUM(t.buy_in_cents), 0) as total_buy_ins
+                COALESCE(SUM(paid.amount_cents), 0)::BIGINT as total_buy_ins
             FROM tournament_registrations reg
-            JOIN tournaments t ON reg.tournament_id = t.id
+            LEFT JOIN LATERAL (
+                SELECT SUM(e.amount_cents) as amount_cents
+                FROM tournament_entries e
+                WHERE e.registration_id = reg.id
+            ) paid ON true
             WHERE reg.user_id = $1 
                 AND reg.created_at >= $2
             "#,
@@ -180,15 +194,38 @@ impl TournamentResultRepo {
         .fetch_one(&self.db)
         .await?;
 
+        // Bounties won: the result's bounties once results are in, the knockouts otherwise
+        let bounty_row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(COALESCE(tr.bounty_cents, knockouts.bounty_cents, 0)), 0)::BIGINT as total_bounty_winnings
+            FROM tournament_registrations reg
+            LEFT JOIN tournament_results tr
+                ON tr.tournament_id = reg.tournament_id AND tr.user_id = reg.user_id
+            LEFT JOIN LATERAL (
+                SELECT SUM(el.bounty_cents) as bounty_cents
+                FROM tournament_eliminations el
+                WHERE el.tournament_id = reg.tournament_id AND el.eliminated_by = reg.user_id
+            ) knockouts ON true
+            WHERE reg.user_id = $1
+                AND reg.created_at >= $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(cutoff_date)
+        .fetch_one(&self.db)
+        .await?;
+
         let total_itm: i64 = itm_row.try_get("total_itm").unwrap_or(0);
         let total_winnings: i64 = itm_row.try_get("total_winnings").unwrap_or(0);
         let total_tournaments: i64 = tournament_row.try_get("total_tournaments").unwrap_or(0);
         let total_buy_ins: i64 = tournament_row.try_get("total_buy_ins").unwrap_or(0);
+        let total_bounty_winnings: i64 = bounty_row.try_get("total_bounty_winnings").unwrap_or(0);
 
         let total_itm = total_itm as i32;
         let total_winnings = total_winnings as i32;
         let total_tournaments = total_tournaments as i32;
         let total_buy_ins = total_buy_ins as i32;
+        let total_bounty_winnings = total_bounty_winnings as i32;
 
         // Calculate percentages
         let itm_percentage = if total_tournaments > 0 {
@@ -198,7 +235,7 @@ impl TournamentResultRepo {
         };
 
         let roi_percentage = if total_buy_ins > 0 {
-            let profit = total_winnings - total_buy_ins;
+            let profit = total_winnings + total_bounty_winnings - total_buy_ins;
             (profit as f64 / total_buy_ins as f64) * 100.0
         } else {
             0.0
@@ -209,6 +246,7 @@ impl TournamentResultRepo {
             total_tournaments,
             total_winnings,
             total_buy_ins,
+            total_bounty_winnings,
             itm_percentage,
             roi_percentage,
         })
@@ -226,7 +264,7 @@ impl TournamentResultRepo {
             UPDATE tournament_results 
             SET tournament_id = $2, user_id = $3, final_position = $4, prize_cents = $5, notes = $6, updated_at = NOW()
             WHERE id = $1
-            RETURNING id, tournament_id, user_id, final_position, prize_cents, points, notes, created_at, updated_at
+            RETURNING id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
             "#
         )
         .bind(id)
@@ -250,45 +288,327 @@ impl TournamentResultRepo {
         Ok(result.rows_affected() > 0)
     }
 
-    /// Calculate comprehensive leaderboard with points system
+    /// Correct a result, keep the old and new values in the audit trail and recalculate
+    /// the points of the whole tournament
+    pub async fn correct(
+        &self,
+        id: Uuid,
+        data: UpdateTournamentResult,
+        corrected_by: Uuid,
+        reason: Option<String>,
+    ) -> Result<Option<TournamentResultRow>> {
+        let mut tx = self.db.begin().await?;
+
+        let Some(old) = sqlx::query_as::<_, TournamentResultRow>(
+            r#"
+            SELECT id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
+            FROM tournament_results
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let new = sqlx::query_as::<_, TournamentResultRow>(
+            r#"
+            UPDATE tournament_results
+            SET final_position = COALESCE($2, final_position),
+                prize_cents = COALESCE($3, prize_cents),
+                bounty_cents = COALESCE($4, bounty_cents),
+                notes = CASE WHEN $6 THEN $5 ELSE notes END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(data.final_position)
+        .bind(data.prize_cents)
+        .bind(data.bounty_cents)
+        .bind(data.notes.clone().flatten())
+        .bind(data.notes.is_some())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_result_corrections (
+                tournament_id, result_id, user_id, action,
+                old_final_position, new_final_position, old_prize_cents, new_prize_cents,
+                old_bounty_cents, new_bounty_cents, old_notes, new_notes, reason, corrected_by
+            )
+            VALUES ($1, $2, $3, 'correct', $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#,
+        )
+        .bind(old.tournament_id)
+        .bind(old.id)
+        .bind(old.user_id)
+        .bind(old.final_position)
+        .bind(new.final_position)
+        .bind(old.prize_cents)
+        .bind(new.prize_cents)
+        .bind(old.bounty_cents)
+        .bind(new.bounty_cents)
+        .bind(&old.notes)
+        .bind(&new.notes)
+        .bind(reason)
+        .bind(corrected_by)
+        .execute(&mut *tx)
+        .await?;
+
+        recalculate_points(&mut tx, old.tournament_id).await?;
+
+        // Points of the corrected result changed with the recalculation
+        let row = sqlx::query_as::<_, TournamentResultRow>(
+            r#"
+            SELECT id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
+            FROM tournament_results
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(row))
+    }
+
+    /// Delete a result, keep it in the audit trail and recalculate the points of the
+    /// whole tournament
+    pub async fn delete_with_audit(
+        &self,
+        id: Uuid,
+        corrected_by: Uuid,
+        reason: Option<String>,
+    ) -> Result<bool> {
+        let mut tx = self.db.begin().await?;
+
+        let Some(old) = sqlx::query_as::<_, TournamentResultRow>(
+            r#"
+            DELETE FROM tournament_results
+            WHERE id = $1
+            RETURNING id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_result_corrections (
+                tournament_id, result_id, user_id, action,
+                old_final_position, old_prize_cents, old_bounty_cents, old_notes,
+                reason, corrected_by
+            )
+            VALUES ($1, $2, $3, 'delete', $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(old.tournament_id)
+        .bind(old.id)
+        .bind(old.user_id)
+        .bind(old.final_position)
+        .bind(old.prize_cents)
+        .bind(old.bounty_cents)
+        .bind(&old.notes)
+        .bind(reason)
+        .bind(corrected_by)
+        .execute(&mut *tx)
+        .await?;
+
+        recalculate_points(&mut tx, old.tournament_id).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Score every result of a tournament with the formula of its club and store the
+    /// points, returning the number of results scored
+    pub async fn recalculate_points(&self, tournament_id: Uuid) -> Result<u64> {
+        let mut conn = self.db.acquire().await?;
+        recalculate_points(&mut conn, tournament_id).await
+    }
+
+    /// Score again the finished tournaments a club started between two dates, returning
+    /// the number of results scored
+    pub async fn recalculate_club_points(
+        &self,
+        club_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<u64> {
+        let mut tx = self.db.begin().await?;
+
+        let tournament_ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id FROM tournaments
+            WHERE club_id = $1
+              AND start_time::DATE BETWEEN $2 AND $3
+              AND live_status = 'finished'
+            "#,
+        )
+        .bind(club_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut scored = 0;
+        for tournament_id in tournament_ids {
+            scored += recalculate_points(&mut tx, tournament_id).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(scored)
+    }
+
+    /// Audit trail of the corrections made to the results of a tournament, oldest first
+    pub async fn get_corrections(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentResultCorrectionRow>> {
+        sqlx::query_as::<_, TournamentResultCorrectionRow>(
+            r#"
+            SELECT id, tournament_id, result_id, user_id, action,
+                   old_final_position, new_final_position, old_prize_cents, new_prize_cents,
+                   old_bounty_cents, new_bounty_cents, old_notes, new_notes, reason,
+                   corrected_by, created_at
+            FROM tournament_result_corrections
+            WHERE tournament_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// Get a page of the leaderboard, best first
     pub async fn get_leaderboard(
         &self,
         period: LeaderboardPeriod,
-        limit: Option<i32>,
         club_id: Option<Uuid>,
-    ) -> Result<Vec<LeaderboardEntry>> {
-        let (date_filter, _params_count) = match period {
-            LeaderboardPeriod::AllTime => ("".to_string(), 0),
-            LeaderboardPeriod::LastYear => (
-                "AND t.start_time >= NOW() - INTERVAL '1 year'".to_string(),
-                0,
-            ),
-            LeaderboardPeriod::Last6Months => (
-                "AND t.start_time >= NOW() - INTERVAL '6 months'".to_string(),
-                0,
-            ),
-            LeaderboardPeriod::Last30Days => (
-                "AND t.start_time >= NOW() - INTERVAL '30 days'".to_string(),
-                0,
-            ),
-            LeaderboardPeriod::Last7Days => (
-                "AND t.start_time >= NOW() - INTERVAL '7 days'".to_string(),
-                0,
-            ),
-        };
+        page: LimitOffset,
+    ) -> Result<LeaderboardPage> {
+        let query = format!(
+            r#"
+            {}
+            SELECT * FROM ranked
+            ORDER BY position
+            LIMIT $2 OFFSET $3
+            "#,
+            leaderboard_sql(&period)
+        );
 
-        let club_filter = match club_id {
-            Some(_) => "AND t.club_id = $1".to_string(),
-            None => "".to_string(),
-        };
+        let rows = sqlx::query(&query)
+            .bind(club_id)
+            .bind(page.limit)
+            .bind(page.offset)
+            .fetch_all(&self.db)
+            .await?;
 
-        let limit_clause = match limit {
-            Some(l) => format!("LIMIT {}", l.clamp(1, 500)),
-            None => "LIMIT 100".to_string(),
+        // Pages past the end have no row to read the total from
+        let total_players = match rows.first() {
+            Some(row) => row.try_get::<i64, _>("total_players")?,
+            None if page.offset > 0 => self.count_leaderboard(&period, club_id).await?,
+            None => 0,
         };
 
+        Ok(LeaderboardPage {
+            entries: rows
+                .iter()
+                .map(leaderboard_entry)
+                .collect::<Result<Vec<_>>>()?,
+            total_players,
+        })
+    }
+
+    /// Get a player's leaderboard entry with up to `around` entries above and below it.
+    /// The page has no entries, but still the leaderboard's total, when the player isn't
+    /// on the leaderboard.
+    pub async fn get_leaderboard_around(
+        &self,
+        user_id: Uuid,
+        period: LeaderboardPeriod,
+        club_id: Option<Uuid>,
+        around: i64,
+    ) -> Result<LeaderboardPage> {
         let query = format!(
             r#"
+            {}
+            SELECT r.*
+            FROM ranked r
+            JOIN ranked me ON me.user_id = $2
+            WHERE r.position BETWEEN me.position - $3 AND me.position + $3
+            ORDER BY r.position
+            "#,
+            leaderboard_sql(&period)
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(club_id)
+            .bind(user_id)
+            .bind(around)
+            .fetch_all(&self.db)
+            .await?;
+
+        // Players off the leaderboard have no rows to read the total from
+        let total_players = match rows.first() {
+            Some(row) => row.try_get::<i64, _>("total_players")?,
+            None => self.count_leaderboard(&period, club_id).await?,
+        };
+
+        Ok(LeaderboardPage {
+            entries: rows
+                .iter()
+                .map(leaderboard_entry)
+                .collect::<Result<Vec<_>>>()?,
+            total_players,
+        })
+    }
+
+    /// Number of players on the leaderboard
+    async fn count_leaderboard(
+        &self,
+        period: &LeaderboardPeriod,
+        club_id: Option<Uuid>,
+    ) -> Result<i64> {
+        sqlx::query_scalar::<_, i64>(&format!(
+            "{} SELECT COUNT(*) FROM ranked",
+            leaderboard_sql(period)
+        ))
+        .bind(club_id)
+        .fetch_one(&self.db)
+        .await
+    }
+}
+
+/// Ranked leaderboard as a `ranked` CTE, bound to the club filter as `$1`. Players on
+/// the same points share their `rank` (1, 2, 2, 4) and `dense_rank` (1, 2, 2, 3);
+/// `position` orders every player, ties broken by winnings then tournaments played.
+fn leaderboard_sql(period: &LeaderboardPeriod) -> String {
+    let date_filter = match period {
+        LeaderboardPeriod::AllTime => "",
+        LeaderboardPeriod::LastYear => "AND t.start_time >= NOW() - INTERVAL '1 year'",
+        LeaderboardPeriod::Last6Months => "AND t.start_time >= NOW() - INTERVAL '6 months'",
+        LeaderboardPeriod::Last30Days => "AND t.start_time >= NOW() - INTERVAL '30 days'",
+        LeaderboardPeriod::Last7Days => "AND t.start_time >= NOW() - INTERVAL '7 days'",
+    };
+
+    format!(
+        r#"
             WITH player_stats AS (
                 SELECT 
                     u.id as user_id,
@@ -300,8 +620,9 @@ impl TournamentResultRepo {
                     u.is_active,
                     u.role,
                     COUNT(DISTINCT reg.tournament_id) as total_tournaments,
-                    COALESCE(SUM(t.buy_in_cents), 0) as total_buy_ins,
+                    COALESCE(SUM(paid.amount_cents), 0)::BIGINT as total_buy_ins,
                     COALESCE(SUM(tr.prize_cents), 0) as total_winnings,
+                    COALESCE(SUM(COALESCE(tr.bounty_cents, knockouts.bounty_cents, 0)), 0)::BIGINT as total_bounty_winnings,
                     COUNT(tr.id) as total_itm,
                     COALESCE(AVG(tr.final_position::float), 0) as average_finish,
                     SUM(CASE WHEN tr.final_position = 1 THEN 1 ELSE 0 END) as first_places,
@@ -310,83 +631,164 @@ impl TournamentResultRepo {
                 FROM users u
                 JOIN tournament_registrations reg ON u.id = reg.user_id
                 JOIN tournaments t ON reg.tournament_id = t.id
+                LEFT JOIN LATERAL (
+                    SELECT SUM(e.amount_cents) as amount_cents
+                    FROM tournament_entries e
+                    WHERE e.registration_id = reg.id
+                ) paid ON true
                 LEFT JOIN tournament_results tr ON u.id = tr.user_id AND t.id = tr.tournament_id
-                WHERE u.role = 'player' AND u.is_active = true 
-                    {} {}
+                LEFT JOIN LATERAL (
+                    SELECT SUM(el.bounty_cents) as bounty_cents
+                    FROM tournament_eliminations el
+                    WHERE el.tournament_id = t.id AND el.eliminated_by = u.id
+                ) knockouts ON true
+                WHERE u.role = 'player' AND u.is_active = true
+                    AND ($1::UUID IS NULL OR t.club_id = $1)
+                    {}
                 GROUP BY u.id, u.username, u.first_name, u.last_name, u.email, u.phone, u.is_active, u.role
                 HAVING COUNT(DISTINCT reg.tournament_id) > 0
+            ),
+            scores AS (
+                SELECT
+                    user_id,
+                    username,
+                    first_name,
+                    last_name,
+                    email,
+                    phone,
+                    is_active,
+                    role,
+                    total_tournaments,
+                    total_buy_ins,
+                    total_winnings,
+                    total_bounty_winnings,
+                    (total_winnings + total_bounty_winnings - total_buy_ins) as net_profit,
+                    total_itm,
+                    CASE 
+                        WHEN total_tournaments > 0 THEN ROUND(CAST((total_itm::float / total_tournaments::float) * 100.0 AS NUMERIC), 2)::double precision
+                        ELSE 0.0 
+                    END as itm_percentage,
+                    CASE 
+                        WHEN total_buy_ins > 0 THEN ROUND(CAST(((total_winnings + total_bounty_winnings - total_buy_ins)::float / total_buy_ins::float) * 100.0 AS NUMERIC), 2)::double precision
+                        ELSE 0.0 
+                    END as roi_percentage,
+                    ROUND(CAST(average_finish AS NUMERIC), 2)::double precision as average_finish,
+                    first_places,
+                    final_tables,
+                    total_points,
+                    -- Use total_points as the leaderboard score (sum of individual event points)
+                    total_points as points
+                FROM player_stats
+            ),
+            ranked AS (
+                SELECT
+                    scores.*,
+                    RANK() OVER (ORDER BY points DESC) AS rank,
+                    DENSE_RANK() OVER (ORDER BY points DESC) AS dense_rank,
+                    ROW_NUMBER() OVER (
+                        ORDER BY points DESC, total_winnings DESC, total_tournaments DESC, scores.user_id
+                    ) AS position,
+                    COUNT(*) OVER () AS total_players,
+                    COALESCE(privacy.hide_winnings, false) AS winnings_hidden
+                FROM scores
+                LEFT JOIN user_privacy_settings privacy ON privacy.user_id = scores.user_id
             )
-            SELECT 
-                user_id,
-                username,
-                first_name,
-                last_name,
-                email,
-                phone,
-                is_active,
-                role,
-                total_tournaments,
-                total_buy_ins,
-                total_winnings,
-                (total_winnings - total_buy_ins) as net_profit,
-                total_itm,
-                CASE 
-                    WHEN total_tournaments > 0 THEN ROUND(CAST((total_itm::float / total_tournaments::float) * 100.0 AS NUMERIC), 2)::double precision
-                    ELSE 0.0 
-                END as itm_percentage,
-                CASE 
-                    WHEN total_buy_ins > 0 THEN ROUND(CAST(((total_winnings - total_buy_ins)::float / total_buy_ins::float) * 100.0 AS NUMERIC), 2)::double precision
-                    ELSE 0.0 
-                END as roi_percentage,
-                ROUND(CAST(average_finish AS NUMERIC), 2)::double precision as average_finish,
-                first_places,
-                final_tables,
-                total_points,
-                -- Use total_points as the leaderboard score (sum of individual event points)
-                total_points as points
-            FROM player_stats
-            ORDER BY points DESC, total_winnings DESC, total_tournaments DESC
-            {}
-            "#,
-            date_filter, club_filter, limit_clause
-        );
-
-        let mut query_builder = sqlx::query(&query);
+        "#,
+        date_filter
+    )
+}
 
-        // Bind club_id parameter if provided
-        if let Some(club_uuid) = club_id {
-            query_builder = query_builder.bind(club_uuid);
-        }
+fn leaderboard_entry(row: &PgRow) -> Result<LeaderboardEntry> {
+    Ok(LeaderboardEntry {
+        user_id: row.try_get("user_id")?,
+        username: row.try_get("username")?,
+        first_name: row.try_get("first_name")?,
+        last_name: row.try_get("last_name")?,
+        email: row.try_get("email")?,
+        phone: row.try_get("phone")?,
+        is_active: row.try_get("is_active")?,
+        role: row.try_get("role")?,
+        rank: row.try_get::<i64, _>("rank")? as i32,
+        dense_rank: row.try_get::<i64, _>("dense_rank")? as i32,
+        total_tournaments: row.try_get::<i64, _>("total_tournaments")? as i32,
+        total_buy_ins: row.try_get::<i64, _>("total_buy_ins")? as i32,
+        total_winnings: row.try_get::<i64, _>("total_winnings")? as i32,
+        total_bounty_winnings: row.try_get::<i64, _>("total_bounty_winnings")? as i32,
+        net_profit: row.try_get::<i64, _>("net_profit")? as i32,
+        total_itm: row.try_get::<i64, _>("total_itm")? as i32,
+        itm_percentage: row.try_get::<f64, _>("itm_percentage")?,
+        roi_percentage: row.try_get::<f64, _>("roi_percentage")?,
+        average_finish: row.try_get::<f64, _>("average_finish")?,
+        first_places: row.try_get::<i64, _>("first_places")? as i32,
+        final_tables: row.try_get::<i64, _>("final_tables")? as i32,
+        points: row.try_get::<i64, _>("points")? as f64,
+        winnings_hidden: row.try_get("winnings_hidden")?,
+    })
+}
 
-        let rows = query_builder.fetch_all(&self.db).await?;
-
-        let mut leaderboard = Vec::new();
-
-        for row in rows {
-            let entry = LeaderboardEntry {
-                user_id: row.try_get("user_id")?,
-                username: row.try_get("username")?,
-                first_name: row.try_get("first_name")?,
-                last_name: row.try_get("last_name")?,
-                email: row.try_get("email")?,
-                phone: row.try_get("phone")?,
-                is_active: row.try_get("is_active")?,
-                role: row.try_get("role")?,
-                total_tournaments: row.try_get::<i64, _>("total_tournaments")? as i32,
-                total_buy_ins: row.try_get::<i64, _>("total_buy_ins")? as i32,
-                total_winnings: row.try_get::<i64, _>("total_winnings")? as i32,
-                net_profit: row.try_get::<i64, _>("net_profit")? as i32,
-                total_itm: row.try_get::<i64, _>("total_itm")? as i32,
-                itm_percentage: row.try_get::<f64, _>("itm_percentage")?,
-                roi_percentage: row.try_get::<f64, _>("roi_percentage")?,
-                average_finish: row.try_get::<f64, _>("average_finish")?,
-                first_places: row.try_get::<i64, _>("first_places")? as i32,
-                final_tables: row.try_get::<i64, _>("final_tables")? as i32,
-                points: row.try_get::<i64, _>("points")? as f64,
-            };
-            leaderboard.push(entry);
-        }
+/// Insert a tournament result without calculating points
+pub(crate) async fn create_result(
+    conn: &mut PgConnection,
+    data: CreateTournamentResult,
+) -> Result<TournamentResultRow> {
+    sqlx::query_as::<_, TournamentResultRow>(
+        r#"
+        INSERT INTO tournament_results (tournament_id, user_id, final_position, prize_cents, points, notes)
+        VALUES ($1, $2, $3, $4, 0, $5)
+        RETURNING id, tournament_id, user_id, final_position, prize_cents, bounty_cents, points, notes, created_at, updated_at
+        "#
+    )
+    .bind(data.tournament_id)
+    .bind(data.user_id)
+    .bind(data.final_position)
+    .bind(data.prize_cents)
+    .bind(data.notes)
+    .fetch_one(conn)
+    .await
+}
 
-        Ok(leaderboard)
+/// Score every result of a tournament with the formula of its club. The field size is
+/// every buy-in and re-entry; tournaments without recorded entries fall back to the
+/// number of results.
+pub(crate) async fn recalculate_points(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+) -> Result<u64> {
+    let settings = settings_for_tournament(&mut *conn, tournament_id).await?;
+
+    let Some((buy_in_cents, entry_count)) = sqlx::query_as::<_, (i32, i32)>(
+        "SELECT buy_in_cents, tournament_entry_count(id) FROM tournaments WHERE id = $1",
+    )
+    .bind(tournament_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(0);
+    };
+
+    let results = sqlx::query_as::<_, (Uuid, i32)>(
+        "SELECT id, final_position FROM tournament_results WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let field_size = if entry_count > 0 {
+        entry_count as u32
+    } else {
+        results.len() as u32
+    };
+    let buy_in_eur = buy_in_cents as f64 / 100.0;
+    let formula = settings.formula();
+
+    for (id, final_position) in &results {
+        let points = formula.points(field_size, (*final_position).max(0) as u32, buy_in_eur);
+        sqlx::query("UPDATE tournament_results SET points = $2, updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(points as i32)
+            .execute(&mut *conn)
+            .await?;
     }
+
+    Ok(results.len() as u64)
 }
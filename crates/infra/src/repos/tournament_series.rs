@@ -0,0 +1,379 @@
+use crate::{
+    db::Db, models::TournamentSeriesRow, repos::tournament_clock::TournamentStructureLevel,
+};
+use chrono::NaiveTime;
+use sqlx::{Postgres, Result as SqlxResult, Transaction};
+use uuid::Uuid;
+
+const SERIES_COLUMNS: &str = r#"
+    id, club_id, name, description, buy_in_cents, seat_cap, duration_minutes,
+    blind_structure, payout_template_id, weekdays, local_start_time, timezone,
+    weeks_ahead, is_paused, materialized_until, created_by, created_at, updated_at
+"#;
+
+#[derive(Debug, Clone)]
+pub struct CreateTournamentSeries {
+    pub club_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub buy_in_cents: i32,
+    pub seat_cap: Option<i32>,
+    pub duration_minutes: Option<i32>,
+    pub blind_structure: Vec<TournamentStructureLevel>,
+    pub payout_template_id: Option<Uuid>,
+    pub weekdays: Vec<i32>,
+    pub local_start_time: NaiveTime,
+    pub timezone: String,
+    pub weeks_ahead: i32,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTournamentSeries {
+    pub name: Option<String>,
+    /// `Some(None)` clears the description
+    pub description: Option<Option<String>>,
+    pub buy_in_cents: Option<i32>,
+    /// `Some(None)` clears the seat cap
+    pub seat_cap: Option<Option<i32>>,
+    /// `Some(None)` clears the duration
+    pub duration_minutes: Option<Option<i32>>,
+    pub blind_structure: Option<Vec<TournamentStructureLevel>>,
+    /// `Some(None)` clears the payout template
+    pub payout_template_id: Option<Option<Uuid>>,
+    pub weekdays: Option<Vec<i32>>,
+    pub local_start_time: Option<NaiveTime>,
+    pub timezone: Option<String>,
+    pub weeks_ahead: Option<i32>,
+}
+
+impl UpdateTournamentSeries {
+    /// Whether the update moves the dates or times instances start at. A new duration
+    /// only moves their end time, which `apply_to_future_instances` recomputes.
+    pub fn changes_schedule(&self) -> bool {
+        self.weekdays.is_some() || self.local_start_time.is_some() || self.timezone.is_some()
+    }
+}
+
+#[derive(Clone)]
+pub struct TournamentSeriesRepo {
+    pool: Db,
+}
+
+impl TournamentSeriesRepo {
+    pub fn new(pool: Db) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, id: Uuid) -> SqlxResult<Option<TournamentSeriesRow>> {
+        sqlx::query_as::<_, TournamentSeriesRow>(&format!(
+            "SELECT {} FROM tournament_series WHERE id = $1",
+            SERIES_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn list_by_club(&self, club_id: Uuid) -> SqlxResult<Vec<TournamentSeriesRow>> {
+        sqlx::query_as::<_, TournamentSeriesRow>(&format!(
+            "SELECT {} FROM tournament_series WHERE club_id = $1 ORDER BY name ASC",
+            SERIES_COLUMNS
+        ))
+        .bind(club_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Whether Postgres knows the given IANA time zone name
+    pub async fn is_valid_timezone(&self, timezone: &str) -> SqlxResult<bool> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+        )
+        .bind(timezone)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn create(&self, data: CreateTournamentSeries) -> SqlxResult<TournamentSeriesRow> {
+        sqlx::query_as::<_, TournamentSeriesRow>(&format!(
+            r#"
+            INSERT INTO tournament_series (club_id, name, description, buy_in_cents, seat_cap,
+                                           duration_minutes, blind_structure, payout_template_id,
+                                           weekdays, local_start_time, timezone, weeks_ahead,
+                                           created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING {}
+            "#,
+            SERIES_COLUMNS
+        ))
+        .bind(data.club_id)
+        .bind(data.name)
+        .bind(data.description)
+        .bind(data.buy_in_cents)
+        .bind(data.seat_cap)
+        .bind(data.duration_minutes)
+        .bind(serde_json::to_value(&data.blind_structure).unwrap_or_default())
+        .bind(data.payout_template_id)
+        .bind(data.weekdays)
+        .bind(data.local_start_time)
+        .bind(data.timezone)
+        .bind(data.weeks_ahead)
+        .bind(data.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Update the series itself, leaving fields that are `None` unchanged.
+    /// Instances that already exist are not touched.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTournamentSeries,
+    ) -> SqlxResult<Option<TournamentSeriesRow>> {
+        let blind_structure = data
+            .blind_structure
+            .map(|levels| serde_json::to_value(levels).unwrap_or_default());
+
+        sqlx::query_as::<_, TournamentSeriesRow>(&format!(
+            r#"
+            UPDATE tournament_series
+            SET name = COALESCE($2, name),
+                description = CASE WHEN $13 THEN $3 ELSE description END,
+                buy_in_cents = COALESCE($4, buy_in_cents),
+                seat_cap = CASE WHEN $14 THEN $5 ELSE seat_cap END,
+                duration_minutes = CASE WHEN $15 THEN $6 ELSE duration_minutes END,
+                blind_structure = COALESCE($7, blind_structure),
+                payout_template_id = CASE WHEN $16 THEN $8 ELSE payout_template_id END,
+                weekdays = COALESCE($9, weekdays),
+                local_start_time = COALESCE($10, local_start_time),
+                timezone = COALESCE($11, timezone),
+                weeks_ahead = COALESCE($12, weeks_ahead)
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            SERIES_COLUMNS
+        ))
+        .bind(id)
+        .bind(data.name)
+        .bind(data.description.clone().flatten())
+        .bind(data.buy_in_cents)
+        .bind(data.seat_cap.flatten())
+        .bind(data.duration_minutes.flatten())
+        .bind(blind_structure)
+        .bind(data.payout_template_id.flatten())
+        .bind(data.weekdays)
+        .bind(data.local_start_time)
+        .bind(data.timezone)
+        .bind(data.weeks_ahead)
+        .bind(data.description.is_some())
+        .bind(data.seat_cap.is_some())
+        .bind(data.duration_minutes.is_some())
+        .bind(data.payout_template_id.is_some())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Pause or resume a series. Paused series don't get new instances.
+    pub async fn set_paused(
+        &self,
+        id: Uuid,
+        is_paused: bool,
+    ) -> SqlxResult<Option<TournamentSeriesRow>> {
+        sqlx::query_as::<_, TournamentSeriesRow>(&format!(
+            "UPDATE tournament_series SET is_paused = $2 WHERE id = $1 RETURNING {}",
+            SERIES_COLUMNS
+        ))
+        .bind(id)
+        .bind(is_paused)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Copy the series' current details and blind structure onto its future instances
+    /// that haven't opened late registration yet. Returns the updated tournament ids.
+    pub async fn apply_to_future_instances(&self, id: Uuid) -> SqlxResult<Vec<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
+        let tournament_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE tournaments t
+            SET name = s.name,
+                description = s.description,
+                buy_in_cents = s.buy_in_cents,
+                seat_cap = s.seat_cap,
+                end_time = t.start_time + make_interval(mins => s.duration_minutes),
+                payout_template_id = s.payout_template_id,
+                updated_at = NOW()
+            FROM tournament_series s
+            WHERE s.id = $1
+              AND t.series_id = s.id
+              AND t.start_time > NOW()
+              AND t.live_status IN ('not_started', 'registration_open')
+            RETURNING t.id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM tournament_structures WHERE tournament_id = ANY($1)")
+            .bind(&tournament_ids)
+            .execute(&mut *tx)
+            .await?;
+        Self::insert_structures(&mut tx, id, &tournament_ids).await?;
+
+        tx.commit().await?;
+        Ok(tournament_ids)
+    }
+
+    /// Drop future instances nobody has registered for so they are created again on the
+    /// series' current schedule. Instances with registrations are kept as they are, and
+    /// no other instance is created on their day.
+    pub async fn reschedule_future_instances(&self, id: Uuid) -> SqlxResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM tournaments t
+            WHERE t.series_id = $1
+              AND t.start_time > NOW()
+              AND t.live_status IN ('not_started', 'registration_open')
+              AND NOT EXISTS (
+                  SELECT 1 FROM tournament_registrations r WHERE r.tournament_id = t.id
+              )
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        // The horizon only needs filling again where instances were removed
+        if result.rows_affected() > 0 {
+            sqlx::query("UPDATE tournament_series SET materialized_until = NULL WHERE id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Create the instances of a series that fall within its horizon and haven't been
+    /// created yet, skipping days that already have an instance of the series. Returns
+    /// the ids of the new tournaments.
+    pub async fn materialize(&self, id: Uuid) -> SqlxResult<Vec<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the series so concurrent runs don't both extend the horizon
+        let is_paused: Option<bool> =
+            sqlx::query_scalar("SELECT is_paused FROM tournament_series WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        if is_paused != Some(false) {
+            return Ok(Vec::new());
+        }
+
+        // Local dates are walked from yesterday so no slot is missed whatever the
+        // session time zone is; slots already in the past are skipped anyway
+        let tournament_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            WITH slots AS (
+                SELECT s.*,
+                       ((CURRENT_DATE - 1 + d) + s.local_start_time) AT TIME ZONE s.timezone
+                           AS slot_start
+                FROM tournament_series s,
+                     generate_series(0, s.weeks_ahead * 7 + 1) AS d
+                WHERE s.id = $1
+                  AND EXTRACT(ISODOW FROM CURRENT_DATE - 1 + d)::int = ANY(s.weekdays)
+            )
+            INSERT INTO tournaments (club_id, name, description, start_time, end_time,
+                                     buy_in_cents, seat_cap, series_id, payout_template_id)
+            SELECT club_id, name, description, slot_start,
+                   slot_start + make_interval(mins => duration_minutes),
+                   buy_in_cents, seat_cap, id, payout_template_id
+            FROM slots
+            WHERE slot_start > GREATEST(NOW(), COALESCE(materialized_until, NOW()))
+              AND slot_start <= NOW() + make_interval(weeks => weeks_ahead)
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM tournaments t
+                  WHERE t.series_id = slots.id
+                    AND (t.start_time AT TIME ZONE slots.timezone)::date
+                        = (slot_start AT TIME ZONE slots.timezone)::date
+              )
+            ON CONFLICT (series_id, start_time) WHERE series_id IS NOT NULL DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        Self::insert_structures(&mut tx, id, &tournament_ids).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tournament_series
+            SET materialized_until = NOW() + make_interval(weeks => weeks_ahead)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(tournament_ids)
+    }
+
+    /// Materialize every active series. Returns the ids of the new tournaments.
+    pub async fn materialize_all(&self) -> SqlxResult<Vec<Uuid>> {
+        let series_ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM tournament_series WHERE is_paused = false")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut tournament_ids = Vec::new();
+        for series_id in series_ids {
+            tournament_ids.extend(self.materialize(series_id).await?);
+        }
+        Ok(tournament_ids)
+    }
+
+    /// Copy the series' blind structure onto the given tournaments
+    async fn insert_structures(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        tournament_ids: &[Uuid],
+    ) -> SqlxResult<()> {
+        if tournament_ids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_structures
+                (tournament_id, level_number, small_blind, big_blind, ante,
+                 duration_minutes, is_break, break_duration_minutes)
+            SELECT t.id, l.level_number, l.small_blind, l.big_blind, l.ante,
+                   l.duration_minutes, l.is_break, l.break_duration_minutes
+            FROM tournament_series s
+            CROSS JOIN UNNEST($2::uuid[]) AS t(id)
+            CROSS JOIN jsonb_to_recordset(s.blind_structure) AS l(
+                level_number INTEGER, small_blind INTEGER, big_blind INTEGER, ante INTEGER,
+                duration_minutes INTEGER, is_break BOOLEAN, break_duration_minutes INTEGER
+            )
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(series_id)
+        .bind(tournament_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,23 @@
-use crate::{db::Db, models::TournamentRow, pagination::LimitOffset};
+use crate::{
+    bounty::BountyFormat,
+    db::Db,
+    models::{TournamentRegistrationRow, TournamentRow},
+    pagination::LimitOffset,
+    repos::{tournament_clock::stop_clock, tournament_results::recalculate_points},
+};
 use chrono::{DateTime, Utc};
 use sqlx::Result as SqlxResult;
 use std::str::FromStr;
 use uuid::Uuid;
 
+const TOURNAMENT_COLUMNS: &str = r#"
+    id, club_id, name, description, start_time, end_time,
+    buy_in_cents, seat_cap, live_status, unregister_cutoff_minutes, max_reentries,
+    max_rebuys, rebuy_cents, add_on_cents, rebuy_until_level, rake_cents, staff_fee_cents,
+    prize_contribution_cents, add_on_chips, guarantee_cents, bounty_format, bounty_cents,
+    created_at, updated_at
+"#;
+
 #[derive(Debug, Clone, Default)]
 pub struct TournamentFilter {
     pub club_id: Option<Uuid>,
@@ -12,11 +26,87 @@ pub struct TournamentFilter {
     pub status: Option<TournamentStatus>,
 }
 
+#[derive(Debug, Clone)]
+pub struct CreateTournament {
+    pub club_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub buy_in_cents: i32,
+    /// Part of the buy-in kept by the house
+    pub rake_cents: i32,
+    /// Part of the buy-in paid to the dealers and staff
+    pub staff_fee_cents: i32,
+    /// Guaranteed prize pool, the club covers any overlay
+    pub guarantee_cents: Option<i32>,
+    pub bounty_format: BountyFormat,
+    /// Part of the buy-in put on each player's head
+    pub bounty_cents: i32,
+    pub seat_cap: Option<i32>,
+    pub unregister_cutoff_minutes: i32,
+    pub entry_rules: TournamentEntryRules,
+    pub tag_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTournament {
+    pub name: Option<String>,
+    /// `Some(None)` clears the description
+    pub description: Option<Option<String>>,
+    pub start_time: Option<DateTime<Utc>>,
+    /// `Some(None)` clears the end time
+    pub end_time: Option<Option<DateTime<Utc>>>,
+    pub buy_in_cents: Option<i32>,
+    pub rake_cents: Option<i32>,
+    pub staff_fee_cents: Option<i32>,
+    /// `Some(None)` removes the guarantee
+    pub guarantee_cents: Option<Option<i32>>,
+    pub bounty_format: Option<BountyFormat>,
+    pub bounty_cents: Option<i32>,
+    /// `Some(None)` removes the seat cap
+    pub seat_cap: Option<Option<i32>>,
+    pub unregister_cutoff_minutes: Option<i32>,
+    /// Replaces all entry rules when set
+    pub entry_rules: Option<TournamentEntryRules>,
+}
+
+/// Re-entry, rebuy and add-on rules of a tournament
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TournamentEntryRules {
+    /// Re-entries allowed per player after busting
+    pub max_reentries: i32,
+    /// Rebuys allowed per player while still in
+    pub max_rebuys: i32,
+    /// Rebuy price, the buy-in when `None`
+    pub rebuy_cents: Option<i32>,
+    /// Add-on price, no add-on when `None`
+    pub add_on_cents: Option<i32>,
+    /// Chips the add-on buys
+    pub add_on_chips: Option<i32>,
+    /// Last level in which re-entries, rebuys and add-ons are allowed
+    pub rebuy_until_level: Option<i32>,
+}
+
+impl From<&TournamentRow> for TournamentEntryRules {
+    fn from(row: &TournamentRow) -> Self {
+        Self {
+            max_reentries: row.max_reentries,
+            max_rebuys: row.max_rebuys,
+            rebuy_cents: row.rebuy_cents,
+            add_on_cents: row.add_on_cents,
+            add_on_chips: row.add_on_chips,
+            rebuy_until_level: row.rebuy_until_level,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TournamentStatus {
     Upcoming,
     InProgress,
     Completed,
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, serde::Serialize, serde::Deserialize)]
@@ -29,6 +119,7 @@ pub enum TournamentLiveStatus {
     Break,
     FinalTable,
     Finished,
+    Cancelled,
 }
 
 impl TournamentLiveStatus {
@@ -41,6 +132,7 @@ impl TournamentLiveStatus {
             TournamentLiveStatus::Break => "break",
             TournamentLiveStatus::FinalTable => "final_table",
             TournamentLiveStatus::Finished => "finished",
+            TournamentLiveStatus::Cancelled => "cancelled",
         }
     }
 }
@@ -57,6 +149,7 @@ impl FromStr for TournamentLiveStatus {
             "break" => Ok(TournamentLiveStatus::Break),
             "final_table" => Ok(TournamentLiveStatus::FinalTable),
             "finished" => Ok(TournamentLiveStatus::Finished),
+            "cancelled" => Ok(TournamentLiveStatus::Cancelled),
             _ => Err(format!("Unknown tournament live status: {}", s)),
         }
     }
@@ -73,19 +166,76 @@ impl TournamentRepo {
     }
 
     pub async fn get(&self, id: Uuid) -> SqlxResult<Option<TournamentRow>> {
-        sqlx::query_as::<_, TournamentRow>(
+        sqlx::query_as::<_, TournamentRow>(&format!(
             r#"
-            SELECT id, club_id, name, description, start_time, end_time,
-                   buy_in_cents, seat_cap, live_status, created_at, updated_at
+            SELECT {}
             FROM tournaments
             WHERE id = $1
             "#,
-        )
+            TOURNAMENT_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
     }
 
+    /// Create a tournament and link its tags (the clock is created by a trigger)
+    pub async fn create(&self, data: CreateTournament) -> SqlxResult<TournamentRow> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, TournamentRow>(&format!(
+            r#"
+            INSERT INTO tournaments (club_id, name, description, start_time, end_time,
+                                     buy_in_cents, seat_cap, unregister_cutoff_minutes,
+                                     max_reentries, max_rebuys, rebuy_cents, add_on_cents,
+                                     rebuy_until_level, rake_cents, staff_fee_cents,
+                                     add_on_chips, guarantee_cents, bounty_format, bounty_cents)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+                    $18, $19)
+            RETURNING {}
+            "#,
+            TOURNAMENT_COLUMNS
+        ))
+        .bind(data.club_id)
+        .bind(data.name)
+        .bind(data.description)
+        .bind(data.start_time)
+        .bind(data.end_time)
+        .bind(data.buy_in_cents)
+        .bind(data.seat_cap)
+        .bind(data.unregister_cutoff_minutes)
+        .bind(data.entry_rules.max_reentries)
+        .bind(data.entry_rules.max_rebuys)
+        .bind(data.entry_rules.rebuy_cents)
+        .bind(data.entry_rules.add_on_cents)
+        .bind(data.entry_rules.rebuy_until_level)
+        .bind(data.rake_cents)
+        .bind(data.staff_fee_cents)
+        .bind(data.entry_rules.add_on_chips)
+        .bind(data.guarantee_cents)
+        .bind(data.bounty_format.as_str())
+        .bind(data.bounty_cents)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !data.tag_ids.is_empty() {
+            sqlx::query(
+                r#"
+                INSERT INTO tournament_tags (tournament_id, tag_id)
+                SELECT $1, UNNEST($2::uuid[])
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(row.id)
+            .bind(&data.tag_ids)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
     pub async fn list(
         &self,
         filter: TournamentFilter,
@@ -94,10 +244,9 @@ impl TournamentRepo {
         let p = page.unwrap_or_default();
 
         // Dynamic WHERE using COALESCE pattern to keep a single prepared statement
-        sqlx::query_as::<_, TournamentRow>(
+        sqlx::query_as::<_, TournamentRow>(&format!(
             r#"
-            SELECT id, club_id, name, description, start_time, end_time,
-                   buy_in_cents, seat_cap, live_status, created_at, updated_at
+            SELECT {}
             FROM tournaments
             WHERE ($1::uuid IS NULL OR club_id = $1)
               AND ($2::timestamptz IS NULL OR start_time >= $2)
@@ -107,11 +256,13 @@ impl TournamentRepo {
                 OR ($4 = 'upcoming' AND start_time > NOW())
                 OR ($4 = 'ongoing' AND start_time <= NOW() AND (end_time IS NULL OR end_time > NOW()))
                 OR ($4 = 'ended' AND end_time IS NOT NULL AND end_time <= NOW())
+                OR ($4 = 'cancelled' AND live_status = 'cancelled')
               )
             ORDER BY created_at DESC
             LIMIT $5 OFFSET $6
-            "#
-        )
+            "#,
+            TOURNAMENT_COLUMNS
+        ))
             .bind(filter.club_id)
             .bind(filter.from)
             .bind(filter.to)
@@ -119,6 +270,7 @@ impl TournamentRepo {
                 TournamentStatus::Upcoming => "upcoming",
                 TournamentStatus::InProgress => "in_progress", 
                 TournamentStatus::Completed => "completed",
+                TournamentStatus::Cancelled => "cancelled",
             }))
             .bind(p.limit)
             .bind(p.offset)
@@ -126,25 +278,202 @@ impl TournamentRepo {
             .await
     }
 
+    /// Update tournament details, leaving fields that are `None` unchanged
+    pub async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTournament,
+    ) -> SqlxResult<Option<TournamentRow>> {
+        let rules = data.entry_rules.clone().unwrap_or_default();
+        sqlx::query_as::<_, TournamentRow>(&format!(
+            r#"
+            UPDATE tournaments
+            SET name = COALESCE($2, name),
+                description = CASE WHEN $21 THEN $3 ELSE description END,
+                start_time = COALESCE($4, start_time),
+                end_time = CASE WHEN $22 THEN $5 ELSE end_time END,
+                buy_in_cents = COALESCE($6, buy_in_cents),
+                seat_cap = CASE WHEN $23 THEN $7 ELSE seat_cap END,
+                unregister_cutoff_minutes = COALESCE($8, unregister_cutoff_minutes),
+                max_reentries = CASE WHEN $9 THEN $10 ELSE max_reentries END,
+                max_rebuys = CASE WHEN $9 THEN $11 ELSE max_rebuys END,
+                rebuy_cents = CASE WHEN $9 THEN $12 ELSE rebuy_cents END,
+                add_on_cents = CASE WHEN $9 THEN $13 ELSE add_on_cents END,
+                rebuy_until_level = CASE WHEN $9 THEN $14 ELSE rebuy_until_level END,
+                add_on_chips = CASE WHEN $9 THEN $15 ELSE add_on_chips END,
+                rake_cents = COALESCE($16, rake_cents),
+                staff_fee_cents = COALESCE($17, staff_fee_cents),
+                guarantee_cents = CASE WHEN $24 THEN $18 ELSE guarantee_cents END,
+                bounty_format = COALESCE($19, bounty_format),
+                bounty_cents = COALESCE($20, bounty_cents),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            TOURNAMENT_COLUMNS
+        ))
+        .bind(id)
+        .bind(data.name)
+        .bind(data.description.clone().flatten())
+        .bind(data.start_time)
+        .bind(data.end_time.flatten())
+        .bind(data.buy_in_cents)
+        .bind(data.seat_cap.flatten())
+        .bind(data.unregister_cutoff_minutes)
+        .bind(data.entry_rules.is_some())
+        .bind(rules.max_reentries)
+        .bind(rules.max_rebuys)
+        .bind(rules.rebuy_cents)
+        .bind(rules.add_on_cents)
+        .bind(rules.rebuy_until_level)
+        .bind(rules.add_on_chips)
+        .bind(data.rake_cents)
+        .bind(data.staff_fee_cents)
+        .bind(data.guarantee_cents.flatten())
+        .bind(data.bounty_format.map(|format| format.as_str()))
+        .bind(data.bounty_cents)
+        .bind(data.description.is_some())
+        .bind(data.end_time.is_some())
+        .bind(data.seat_cap.is_some())
+        .bind(data.guarantee_cents.is_some())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Cancel a tournament, its registrations and its clock in one transaction.
+    /// Returns the cancelled tournament and the registrations that were cancelled, or
+    /// `None`, changing nothing, when the tournament is gone, finished or already cancelled.
+    pub async fn cancel(
+        &self,
+        id: Uuid,
+    ) -> SqlxResult<Option<(TournamentRow, Vec<TournamentRegistrationRow>)>> {
+        let mut tx = self.pool.begin().await?;
+
+        let live_status: Option<TournamentLiveStatus> =
+            sqlx::query_scalar("SELECT live_status FROM tournaments WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        if !matches!(
+            live_status,
+            Some(status) if status != TournamentLiveStatus::Finished
+                && status != TournamentLiveStatus::Cancelled
+        ) {
+            return Ok(None);
+        }
+
+        let tournament = sqlx::query_as::<_, TournamentRow>(&format!(
+            r#"
+            UPDATE tournaments
+            SET live_status = 'cancelled',
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            TOURNAMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(tournament) = tournament else {
+            return Ok(None);
+        };
+
+        let registrations = sqlx::query_as::<_, TournamentRegistrationRow>(
+            r#"
+            UPDATE tournament_registrations
+            SET status = 'cancelled',
+                updated_at = NOW()
+            WHERE tournament_id = $1 AND status != 'cancelled'
+            RETURNING id, tournament_id, user_id, registration_time, status, notes, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // A cancelled tournament's clock must not keep advancing levels
+        stop_clock(&mut tx, id, None).await?;
+
+        tx.commit().await?;
+        Ok(Some((tournament, registrations)))
+    }
+
+    /// Whether any play has been recorded for a tournament (seating, clock, results or deals)
+    pub async fn has_play_history(&self, id: Uuid) -> SqlxResult<bool> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM table_seat_assignments WHERE tournament_id = $1)
+                OR EXISTS (SELECT 1 FROM tournament_clock_events WHERE tournament_id = $1)
+                OR EXISTS (SELECT 1 FROM tournament_results WHERE tournament_id = $1)
+                OR EXISTS (SELECT 1 FROM player_deals WHERE tournament_id = $1)
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Delete a tournament (registrations, clock and structure cascade)
+    pub async fn delete(&self, id: Uuid) -> SqlxResult<bool> {
+        let result = sqlx::query("DELETE FROM tournaments WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Update tournament live status
     pub async fn update_live_status(
         &self,
         id: Uuid,
         live_status: TournamentLiveStatus,
     ) -> SqlxResult<Option<TournamentRow>> {
-        sqlx::query_as::<_, TournamentRow>(
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, TournamentRow>(&format!(
             r#"
             UPDATE tournaments
             SET live_status = $2,
                 updated_at = NOW()
             WHERE id = $1
-            RETURNING id, club_id, name, description, start_time, end_time,
-                     buy_in_cents, seat_cap, live_status, created_at, updated_at
+            RETURNING {}
             "#,
-        )
+            TOURNAMENT_COLUMNS
+        ))
         .bind(id)
-        .bind(live_status.as_str())
-        .fetch_optional(&self.pool)
+        .bind(live_status)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Finishing a tournament awards the leaderboard points of its results
+        if row.is_some() && live_status == TournamentLiveStatus::Finished {
+            recalculate_points(&mut tx, id).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(row)
+    }
+
+    /// Get the instances of a recurring series that haven't started yet
+    pub async fn list_upcoming_for_series(
+        &self,
+        series_id: Uuid,
+    ) -> SqlxResult<Vec<TournamentRow>> {
+        sqlx::query_as::<_, TournamentRow>(&format!(
+            r#"
+            SELECT {}
+            FROM tournaments
+            WHERE series_id = $1 AND start_time > NOW()
+            ORDER BY start_time ASC
+            "#,
+            TOURNAMENT_COLUMNS
+        ))
+        .bind(series_id)
+        .fetch_all(&self.pool)
         .await
     }
 
@@ -153,31 +482,31 @@ impl TournamentRepo {
         &self,
         live_status: TournamentLiveStatus,
     ) -> SqlxResult<Vec<TournamentRow>> {
-        sqlx::query_as::<_, TournamentRow>(
+        sqlx::query_as::<_, TournamentRow>(&format!(
             r#"
-            SELECT id, club_id, name, description, start_time, end_time,
-                   buy_in_cents, seat_cap, live_status, created_at, updated_at
+            SELECT {}
             FROM tournaments
             WHERE live_status = $1
             ORDER BY start_time ASC
             "#,
-        )
-        .bind(live_status.as_str())
+            TOURNAMENT_COLUMNS
+        ))
+        .bind(live_status)
         .fetch_all(&self.pool)
         .await
     }
 
     /// Get live tournaments (in progress, break, or final table)
     pub async fn get_live_tournaments(&self) -> SqlxResult<Vec<TournamentRow>> {
-        sqlx::query_as::<_, TournamentRow>(
+        sqlx::query_as::<_, TournamentRow>(&format!(
             r#"
-            SELECT id, club_id, name, description, start_time, end_time,
-                   buy_in_cents, seat_cap, live_status, created_at, updated_at
+            SELECT {}
             FROM tournaments
             WHERE live_status IN ('in_progress', 'break', 'final_table')
             ORDER BY start_time ASC
             "#,
-        )
+            TOURNAMENT_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await
     }
@@ -0,0 +1,49 @@
+use sqlx::{PgPool, Result};
+use uuid::Uuid;
+
+use crate::models::UserPrivacySettingsRow;
+
+pub struct UserPrivacySettingsRepo {
+    db: PgPool,
+}
+
+impl UserPrivacySettingsRepo {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Get the privacy settings a player saved, `None` when they show everything
+    pub async fn get_by_user(&self, user_id: Uuid) -> Result<Option<UserPrivacySettingsRow>> {
+        sqlx::query_as::<_, UserPrivacySettingsRow>(
+            r#"
+            SELECT user_id, hide_winnings, created_at, updated_at
+            FROM user_privacy_settings
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Save the privacy settings of a player
+    pub async fn upsert(
+        &self,
+        user_id: Uuid,
+        hide_winnings: bool,
+    ) -> Result<UserPrivacySettingsRow> {
+        sqlx::query_as::<_, UserPrivacySettingsRow>(
+            r#"
+            INSERT INTO user_privacy_settings (user_id, hide_winnings)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET hide_winnings = EXCLUDED.hide_winnings
+            RETURNING user_id, hide_winnings, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(hide_winnings)
+        .fetch_one(&self.db)
+        .await
+    }
+}
@@ -1,10 +1,114 @@
+//! Leaderboard scoring
+//!
+//! Every finish in a tournament earns points through a [`ScoringFormula`]. Clubs pick
+//! one of the built-in formulas and tune it with a cap and a multiplier, see
+//! [`ScoringSettings`].
+
+use std::str::FromStr;
+
+/// Turns a finishing position into leaderboard points
+pub trait ScoringFormula: Send + Sync {
+    /// Points for finishing `rank` (1 = winner) among `field_size` entries, re-entries
+    /// included, in an event with the given buy-in. Invalid inputs score 0.
+    fn points(&self, field_size: u32, rank: u32, buy_in_eur: f64) -> u32;
+}
+
 /// Tournament scoring utility with authoritative formula
 ///
-/// Formula: points = min(60, round(3 * (sqrt(field_size) / sqrt(rank)) * (log10(buy_in_eur) + 1) + 2))
+/// Formula: points = min(cap, round(multiplier * (3 * (sqrt(field_size) / sqrt(rank)) * (log10(buy_in_eur) + 1) + 2)))
+///
+/// The default cap is 60 and the default multiplier 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuthoritativeFormula {
+    pub cap: u32,
+    pub multiplier: f64,
+}
+
+impl Default for AuthoritativeFormula {
+    fn default() -> Self {
+        Self {
+            cap: DEFAULT_CAP,
+            multiplier: DEFAULT_MULTIPLIER,
+        }
+    }
+}
+
+impl ScoringFormula for AuthoritativeFormula {
+    fn points(&self, field_size: u32, rank: u32, buy_in_eur: f64) -> u32 {
+        if !is_valid_finish(field_size, rank) || buy_in_eur <= 0.0 {
+            return 0;
+        }
+
+        let raw_points =
+            3.0 * ((field_size as f64).sqrt() / (rank as f64).sqrt()) * (buy_in_eur.log10() + 1.0)
+                + 2.0;
+        finish_points(raw_points, self.multiplier, self.cap)
+    }
+}
+
+/// Linear points table: the winner scores one point per entry and every place below
+/// scores one point less, down to 1 point for the last place
+///
+/// Formula: points = min(cap, round(multiplier * (field_size - rank + 1)))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFormula {
+    pub cap: u32,
+    pub multiplier: f64,
+}
+
+impl ScoringFormula for LinearFormula {
+    fn points(&self, field_size: u32, rank: u32, _buy_in_eur: f64) -> u32 {
+        if !is_valid_finish(field_size, rank) {
+            return 0;
+        }
+
+        finish_points((field_size - rank + 1) as f64, self.multiplier, self.cap)
+    }
+}
+
+/// The authoritative formula without the buy-in: every event weighs the same whatever
+/// it costs
+///
+/// Formula: points = min(cap, round(multiplier * (3 * (sqrt(field_size) / sqrt(rank)) + 2)))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldSizeFormula {
+    pub cap: u32,
+    pub multiplier: f64,
+}
+
+impl ScoringFormula for FieldSizeFormula {
+    fn points(&self, field_size: u32, rank: u32, _buy_in_eur: f64) -> u32 {
+        if !is_valid_finish(field_size, rank) {
+            return 0;
+        }
+
+        let raw_points = 3.0 * ((field_size as f64).sqrt() / (rank as f64).sqrt()) + 2.0;
+        finish_points(raw_points, self.multiplier, self.cap)
+    }
+}
+
+pub const DEFAULT_CAP: u32 = 60;
+pub const DEFAULT_MULTIPLIER: f64 = 1.0;
+
+fn is_valid_finish(field_size: u32, rank: u32) -> bool {
+    field_size >= 1 && rank >= 1 && rank <= field_size
+}
+
+/// Apply the multiplier, keep points non-negative, round and cap them
+fn finish_points(raw_points: f64, multiplier: f64, cap: u32) -> u32 {
+    let points = raw_points * multiplier;
+    if points <= 0.0 || !points.is_finite() {
+        return 0;
+    }
+
+    std::cmp::min(cap, points.round() as u32)
+}
+
+/// Points for a finish with the authoritative formula and its default cap of 60
 ///
 /// # Arguments
 ///
-/// * `field_size` - Number of confirmed entrants (N ≥ 1)
+/// * `field_size` - Number of entries, re-entries included (N ≥ 1)
 /// * `rank` - Final position (1 = winner)
 /// * `buy_in_eur` - Buy-in amount in euros (> 0)
 ///
@@ -22,29 +126,82 @@
 /// assert_eq!(event_points(80, 9, 50.0), 26);
 /// ```
 pub fn event_points(field_size: u32, rank: u32, buy_in_eur: f64) -> u32 {
-    // Guardrails: validate inputs
-    if field_size < 1 || rank < 1 || rank > field_size || buy_in_eur <= 0.0 {
-        return 0;
+    AuthoritativeFormula::default().points(field_size, rank, buy_in_eur)
+}
+
+/// The built-in scoring formulas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoringFormulaKind {
+    #[default]
+    Authoritative,
+    Linear,
+    FieldSize,
+}
+
+impl ScoringFormulaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScoringFormulaKind::Authoritative => "authoritative",
+            ScoringFormulaKind::Linear => "linear",
+            ScoringFormulaKind::FieldSize => "field_size",
+        }
     }
+}
 
-    // Convert to f64 for calculations
-    let field_size_f = field_size as f64;
-    let rank_f = rank as f64;
+impl FromStr for ScoringFormulaKind {
+    type Err = String;
 
-    // Formula: 3 * (sqrt(field_size) / sqrt(rank)) * (log10(buy_in_eur) + 1) + 2
-    let sqrt_field = field_size_f.sqrt();
-    let sqrt_rank = rank_f.sqrt();
-    let log_buy_in = buy_in_eur.log10();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "authoritative" => Ok(ScoringFormulaKind::Authoritative),
+            "linear" => Ok(ScoringFormulaKind::Linear),
+            "field_size" => Ok(ScoringFormulaKind::FieldSize),
+            _ => Err(format!("Unknown scoring formula: {}", s)),
+        }
+    }
+}
 
-    let raw_points = 3.0 * (sqrt_field / sqrt_rank) * (log_buy_in + 1.0) + 2.0;
+/// A formula chosen by a club, with its parameters
+///
+/// # Examples
+///
+/// ```
+/// use infra::scoring::{ScoringFormulaKind, ScoringSettings};
+///
+/// let settings = ScoringSettings {
+///     formula: ScoringFormulaKind::Linear,
+///     cap: 100,
+///     multiplier: 2.0,
+/// };
+/// assert_eq!(settings.formula().points(10, 1, 50.0), 20);
+/// assert_eq!(settings.formula().points(10, 10, 50.0), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoringSettings {
+    pub formula: ScoringFormulaKind,
+    pub cap: u32,
+    pub multiplier: f64,
+}
 
-    // Ensure points are non-negative, round, and cap at 60
-    if raw_points <= 0.0 {
-        return 0;
+impl Default for ScoringSettings {
+    fn default() -> Self {
+        Self {
+            formula: ScoringFormulaKind::default(),
+            cap: DEFAULT_CAP,
+            multiplier: DEFAULT_MULTIPLIER,
+        }
     }
+}
 
-    let rounded_points = raw_points.round() as u32;
-    std::cmp::min(60, rounded_points)
+impl ScoringSettings {
+    pub fn formula(&self) -> Box<dyn ScoringFormula> {
+        let (cap, multiplier) = (self.cap, self.multiplier);
+        match self.formula {
+            ScoringFormulaKind::Authoritative => Box::new(AuthoritativeFormula { cap, multiplier }),
+            ScoringFormulaKind::Linear => Box::new(LinearFormula { cap, multiplier }),
+            ScoringFormulaKind::FieldSize => Box::new(FieldSizeFormula { cap, multiplier }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -128,4 +285,67 @@ mod tests {
         let max_points = event_points(1000, 1, 1000.0);
         assert_eq!(max_points, 60);
     }
+
+    #[test]
+    fn test_authoritative_formula_parameters() {
+        let formula = AuthoritativeFormula {
+            cap: 100,
+            multiplier: 2.0,
+        };
+        // Double points (rounded after the multiplier) with a higher cap
+        assert_eq!(formula.points(40, 1, 20.0), 91);
+        assert_eq!(formula.points(1000, 1, 1000.0), 100);
+    }
+
+    #[test]
+    fn test_linear_formula() {
+        let formula = LinearFormula {
+            cap: 60,
+            multiplier: 1.0,
+        };
+        assert_eq!(formula.points(10, 1, 20.0), 10);
+        assert_eq!(formula.points(10, 2, 20.0), 9);
+        assert_eq!(formula.points(10, 10, 20.0), 1);
+        assert_eq!(formula.points(100, 1, 20.0), 60);
+        assert_eq!(formula.points(10, 11, 20.0), 0);
+
+        // The buy-in doesn't matter
+        assert_eq!(formula.points(10, 1, 500.0), formula.points(10, 1, 5.0));
+    }
+
+    #[test]
+    fn test_field_size_formula() {
+        let formula = FieldSizeFormula {
+            cap: 60,
+            multiplier: 1.0,
+        };
+        // 3 * sqrt(40) + 2
+        assert_eq!(formula.points(40, 1, 20.0), 21);
+        assert_eq!(formula.points(40, 1, 500.0), 21);
+        assert!(formula.points(40, 1, 20.0) > formula.points(40, 4, 20.0));
+        assert_eq!(formula.points(40, 41, 20.0), 0);
+    }
+
+    #[test]
+    fn test_scoring_formula_kind_round_trip() {
+        for kind in [
+            ScoringFormulaKind::Authoritative,
+            ScoringFormulaKind::Linear,
+            ScoringFormulaKind::FieldSize,
+        ] {
+            assert_eq!(ScoringFormulaKind::from_str(kind.as_str()), Ok(kind));
+        }
+        assert!(ScoringFormulaKind::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn test_default_settings_match_event_points() {
+        let formula = ScoringSettings::default().formula();
+        for (field_size, rank, buy_in) in [(40, 1, 20.0), (50, 2, 30.0), (200, 1, 100.0)] {
+            assert_eq!(
+                formula.points(field_size, rank, buy_in),
+                event_points(field_size, rank, buy_in)
+            );
+        }
+    }
 }
@@ -0,0 +1,182 @@
+//! Season standings
+//!
+//! A season adds up the points players scored in the tournaments it covers. Its rules
+//! can leave results out (the worst ones, or everything past the best N) and require a
+//! minimum number of events before a player is ranked.
+
+use uuid::Uuid;
+
+/// Which results count towards a season
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeasonRules {
+    /// Only the best N results count
+    pub best_results: Option<u32>,
+    /// Events a player must play to be ranked
+    pub min_events: u32,
+    /// Worst results left out
+    pub drop_worst: u32,
+}
+
+/// Points a player scored in each event of the season
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerSeasonResults {
+    pub user_id: Uuid,
+    pub points: Vec<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub user_id: Uuid,
+    /// Shared by players on the same points, `None` below the minimum number of events
+    pub rank: Option<u32>,
+    pub points: i32,
+    pub events_played: u32,
+    pub results_counted: u32,
+}
+
+/// Points of the results that count, and how many results that is
+///
+/// The worst results are dropped first, then only the best N of the rest are kept.
+///
+/// # Examples
+///
+/// ```
+/// use infra::standings::{counted_points, SeasonRules};
+///
+/// let rules = SeasonRules {
+///     best_results: Some(2),
+///     min_events: 0,
+///     drop_worst: 0,
+/// };
+/// assert_eq!(counted_points(&[10, 30, 20], &rules), (50, 2));
+/// ```
+pub fn counted_points(points: &[i32], rules: &SeasonRules) -> (i32, u32) {
+    let mut points = points.to_vec();
+    points.sort_unstable_by(|a, b| b.cmp(a));
+
+    let kept = points.len().saturating_sub(rules.drop_worst as usize);
+    let kept = match rules.best_results {
+        Some(best) => kept.min(best as usize),
+        None => kept,
+    };
+
+    (points[..kept].iter().sum(), kept as u32)
+}
+
+/// Rank the players of a season
+///
+/// Players who played enough events come first, most points first; players on the same
+/// points share a rank (1, 2, 2, 4). Players short of the minimum number of events
+/// follow, unranked.
+pub fn season_standings(players: Vec<PlayerSeasonResults>, rules: &SeasonRules) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players
+        .into_iter()
+        .map(|player| {
+            let (points, results_counted) = counted_points(&player.points, rules);
+            Standing {
+                user_id: player.user_id,
+                rank: None,
+                points,
+                events_played: player.points.len() as u32,
+                results_counted,
+            }
+        })
+        .collect();
+
+    let qualifies = |standing: &Standing| standing.events_played >= rules.min_events;
+    standings.sort_by(|a, b| {
+        qualifies(b)
+            .cmp(&qualifies(a))
+            .then(b.points.cmp(&a.points))
+            .then(b.events_played.cmp(&a.events_played))
+            .then(a.user_id.cmp(&b.user_id))
+    });
+
+    let mut previous_points = None;
+    let mut rank = 0;
+    for (index, standing) in standings.iter_mut().enumerate() {
+        if !qualifies(standing) {
+            break;
+        }
+        if previous_points != Some(standing.points) {
+            rank = index as u32 + 1;
+            previous_points = Some(standing.points);
+        }
+        standing.rank = Some(rank);
+    }
+
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: u128, points: &[i32]) -> PlayerSeasonResults {
+        PlayerSeasonResults {
+            user_id: Uuid::from_u128(id),
+            points: points.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_counted_points_without_rules() {
+        assert_eq!(
+            counted_points(&[10, 30, 20], &SeasonRules::default()),
+            (60, 3)
+        );
+        assert_eq!(counted_points(&[], &SeasonRules::default()), (0, 0));
+    }
+
+    #[test]
+    fn test_counted_points_drops_worst_then_keeps_best() {
+        let rules = SeasonRules {
+            best_results: Some(2),
+            min_events: 0,
+            drop_worst: 1,
+        };
+        // 5 is dropped, then the best two of 40, 30, 10
+        assert_eq!(counted_points(&[10, 40, 5, 30], &rules), (70, 2));
+        // Everything dropped
+        assert_eq!(counted_points(&[10], &rules), (0, 0));
+    }
+
+    #[test]
+    fn test_season_standings_share_ranks_on_ties() {
+        let standings = season_standings(
+            vec![
+                player(1, &[10]),
+                player(2, &[20, 5]),
+                player(3, &[25]),
+                player(4, &[8]),
+            ],
+            &SeasonRules::default(),
+        );
+
+        let ranks: Vec<_> = standings
+            .iter()
+            .map(|standing| (standing.user_id.as_u128(), standing.rank))
+            .collect();
+        // Players 2 and 3 both have 25 points, 2 played more events
+        assert_eq!(
+            ranks,
+            vec![(2, Some(1)), (3, Some(1)), (1, Some(3)), (4, Some(4))]
+        );
+    }
+
+    #[test]
+    fn test_season_standings_minimum_events() {
+        let rules = SeasonRules {
+            best_results: None,
+            min_events: 2,
+            drop_worst: 0,
+        };
+        let standings = season_standings(vec![player(1, &[50]), player(2, &[5, 5])], &rules);
+
+        assert_eq!(standings[0].user_id, Uuid::from_u128(2));
+        assert_eq!(standings[0].rank, Some(1));
+        assert_eq!(standings[1].user_id, Uuid::from_u128(1));
+        assert_eq!(standings[1].rank, None);
+        assert_eq!(standings[1].points, 50);
+    }
+}
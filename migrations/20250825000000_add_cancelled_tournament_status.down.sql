@@ -0,0 +1,39 @@
+-- Postgres can't drop an enum value, so rebuild the type without 'cancelled'.
+-- Cancelled tournaments go back to not_started so no points or payouts are triggered.
+UPDATE tournaments SET live_status = 'not_started' WHERE live_status = 'cancelled';
+
+-- Triggers and indexes on live_status block the column type change
+DROP TRIGGER IF EXISTS tournament_status_points_trigger ON tournaments;
+DROP TRIGGER IF EXISTS trg_calculate_tournament_payouts ON tournaments;
+DROP INDEX IF EXISTS tournaments_live_status_idx;
+ALTER TABLE tournaments ALTER COLUMN live_status DROP DEFAULT;
+
+ALTER TYPE tournament_live_status RENAME TO tournament_live_status_old;
+
+CREATE TYPE tournament_live_status AS ENUM (
+    'not_started',
+    'registration_open',
+    'late_registration',
+    'in_progress',
+    'break',
+    'final_table',
+    'finished'
+);
+
+ALTER TABLE tournaments
+ALTER COLUMN live_status TYPE tournament_live_status USING live_status::text::tournament_live_status,
+ALTER COLUMN live_status SET DEFAULT 'not_started';
+
+DROP TYPE IF EXISTS tournament_live_status_old;
+
+CREATE INDEX tournaments_live_status_idx ON tournaments (live_status);
+
+CREATE TRIGGER tournament_status_points_trigger
+    AFTER UPDATE OF live_status ON tournaments
+    FOR EACH ROW
+    EXECUTE FUNCTION trigger_calculate_points();
+
+CREATE TRIGGER trg_calculate_tournament_payouts
+    AFTER UPDATE OF live_status ON tournaments
+    FOR EACH ROW
+    EXECUTE FUNCTION calculate_tournament_payouts();
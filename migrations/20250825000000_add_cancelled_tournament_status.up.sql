@@ -0,0 +1,2 @@
+-- Tournaments can be cancelled before they finish
+ALTER TYPE tournament_live_status ADD VALUE IF NOT EXISTS 'cancelled';
@@ -0,0 +1,142 @@
+-- Recurring tournament series: a tournament template plus a weekly recurrence rule.
+-- A background job materialises upcoming tournaments from each active series.
+CREATE TABLE tournament_series (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    club_id             UUID NOT NULL REFERENCES clubs(id) ON DELETE CASCADE,
+    name                TEXT NOT NULL,
+    description         TEXT,
+    buy_in_cents        INTEGER NOT NULL DEFAULT 0,
+    seat_cap            INTEGER,
+    duration_minutes    INTEGER,               -- Sets end_time on instances when present
+    blind_structure     JSONB NOT NULL DEFAULT '[]', -- Array of structure levels copied to each instance
+    payout_template_id  UUID REFERENCES payout_templates(id) ON DELETE SET NULL,
+    weekdays            INTEGER[] NOT NULL,    -- ISO weekdays, 1 = Monday ... 7 = Sunday
+    local_start_time    TIME NOT NULL,
+    timezone            TEXT NOT NULL DEFAULT 'Europe/Brussels',
+    weeks_ahead         INTEGER NOT NULL DEFAULT 4 CHECK (weeks_ahead BETWEEN 1 AND 26),
+    is_paused           BOOLEAN NOT NULL DEFAULT false,
+    materialized_until  TIMESTAMPTZ,           -- Instances up to this time have been created
+    created_by          UUID REFERENCES users(id),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (cardinality(weekdays) > 0 AND weekdays <@ ARRAY[1, 2, 3, 4, 5, 6, 7])
+);
+
+CREATE INDEX tournament_series_club_id_idx ON tournament_series (club_id);
+
+CREATE TRIGGER trg_tournament_series_updated_at
+    BEFORE UPDATE ON tournament_series
+    FOR EACH ROW EXECUTE PROCEDURE set_updated_at();
+
+-- Link materialised tournaments back to their series
+ALTER TABLE tournaments
+ADD COLUMN series_id UUID REFERENCES tournament_series(id) ON DELETE SET NULL,
+ADD COLUMN payout_template_id UUID REFERENCES payout_templates(id) ON DELETE SET NULL;
+
+-- One instance per series per start time, so materialising is idempotent
+CREATE UNIQUE INDEX tournaments_series_start_time_idx
+    ON tournaments (series_id, start_time) WHERE series_id IS NOT NULL;
+
+-- Prefer the tournament's own payout template over one picked by field size
+CREATE OR REPLACE FUNCTION calculate_tournament_payouts()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_player_count INTEGER;
+    v_total_prize_pool INTEGER;
+    v_template_id UUID;
+    v_payout_structure JSONB;
+    v_payout_positions JSONB;
+    v_position RECORD;
+    v_positions_array JSONB[];
+    v_payout_amount INTEGER;
+BEGIN
+    -- Only proceed if status changed from LATE_REGISTRATION to IN_PROGRESS
+    IF (OLD.live_status = 'late_registration' OR OLD.live_status = 'not_started') 
+       AND NEW.live_status = 'in_progress' THEN
+        
+        -- Check if payouts already exist for this tournament
+        IF EXISTS (SELECT 1 FROM tournament_payouts WHERE tournament_id = NEW.id) THEN
+            RETURN NEW;
+        END IF;
+        
+        -- Count players who took part
+        SELECT COUNT(*) INTO v_player_count
+        FROM tournament_registrations
+        WHERE tournament_id = NEW.id
+        AND status IN ('registered', 'checked_in', 'seated', 'busted');
+        
+        -- Skip if no players
+        IF v_player_count = 0 THEN
+            RETURN NEW;
+        END IF;
+        
+        -- Calculate total prize pool (buy-in * number of players)
+        v_total_prize_pool := NEW.buy_in_cents * v_player_count;
+        
+        -- Use the tournament's template if it has one
+        IF NEW.payout_template_id IS NOT NULL THEN
+            SELECT id, payout_structure INTO v_template_id, v_payout_structure
+            FROM payout_templates
+            WHERE id = NEW.payout_template_id;
+        END IF;
+
+        -- Otherwise find appropriate payout template based on player count
+        IF v_template_id IS NULL THEN
+            SELECT id, payout_structure INTO v_template_id, v_payout_structure
+            FROM payout_templates
+            WHERE min_players <= v_player_count 
+            AND (max_players IS NULL OR max_players >= v_player_count)
+            ORDER BY min_players DESC
+            LIMIT 1;
+        END IF;
+        
+        -- If no template found, log warning and return
+        IF v_template_id IS NULL THEN
+            RAISE WARNING 'No payout template found for % players in tournament %', v_player_count, NEW.id;
+            RETURN NEW;
+        END IF;
+        
+        -- Calculate payout for each position
+        v_positions_array := ARRAY[]::JSONB[];
+        
+        FOR v_position IN 
+            SELECT * FROM jsonb_array_elements(v_payout_structure)
+        LOOP
+            -- Extract position and percentage
+            v_payout_amount := FLOOR((v_position.value->>'percentage')::NUMERIC * v_total_prize_pool / 100);
+            
+            v_positions_array := array_append(
+                v_positions_array, 
+                jsonb_build_object(
+                    'position', (v_position.value->>'position')::INTEGER,
+                    'amount_cents', v_payout_amount,
+                    'percentage', (v_position.value->>'percentage')::NUMERIC
+                )
+            );
+        END LOOP;
+        
+        v_payout_positions := to_jsonb(v_positions_array);
+        
+        -- Insert the calculated payouts
+        INSERT INTO tournament_payouts (
+            tournament_id,
+            template_id,
+            player_count,
+            total_prize_pool,
+            payout_positions
+        ) VALUES (
+            NEW.id,
+            v_template_id,
+            v_player_count,
+            v_total_prize_pool,
+            v_payout_positions
+        );
+        
+        RAISE NOTICE 'Created payouts for tournament % with % players using template %', 
+            NEW.id, v_player_count, v_template_id;
+    END IF;
+    
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
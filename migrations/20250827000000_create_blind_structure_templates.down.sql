@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS trg_blind_structure_templates_updated_at ON blind_structure_templates;
+DROP TABLE IF EXISTS blind_structure_templates;
@@ -0,0 +1,17 @@
+-- Named blind structures a club can reuse across tournaments
+CREATE TABLE blind_structure_templates (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    club_id     UUID NOT NULL REFERENCES clubs(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    description TEXT,
+    levels      JSONB NOT NULL DEFAULT '[]', -- Array of structure levels, first level first
+    created_by  UUID REFERENCES users(id),
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_blind_structure_template_name UNIQUE (club_id, name)
+);
+
+CREATE TRIGGER trg_blind_structure_templates_updated_at
+    BEFORE UPDATE ON blind_structure_templates
+    FOR EACH ROW EXECUTE PROCEDURE set_updated_at();
@@ -0,0 +1,4 @@
+-- How long before the start players can still cancel their own registration
+ALTER TABLE tournaments
+ADD COLUMN unregister_cutoff_minutes INTEGER NOT NULL DEFAULT 60
+    CHECK (unregister_cutoff_minutes >= 0);
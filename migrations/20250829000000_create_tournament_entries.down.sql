@@ -0,0 +1,194 @@
+DROP TRIGGER IF EXISTS trg_tournament_entries_refresh_payouts ON tournament_entries;
+DROP FUNCTION IF EXISTS refresh_payouts_after_entry();
+DROP TRIGGER IF EXISTS trg_tournament_registrations_buy_in ON tournament_registrations;
+DROP FUNCTION IF EXISTS sync_registration_buy_in();
+
+-- Restore the payout trigger function from before entries
+CREATE OR REPLACE FUNCTION calculate_tournament_payouts()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_player_count INTEGER;
+    v_total_prize_pool INTEGER;
+    v_template RECORD;
+    v_payout_structure JSONB;
+    v_payout_positions JSONB;
+    v_position RECORD;
+    v_positions_array JSONB[];
+    v_payout_amount INTEGER;
+BEGIN
+    -- Only proceed if status changed from LATE_REGISTRATION to IN_PROGRESS
+    IF (OLD.live_status = 'late_registration' OR OLD.live_status = 'not_started') 
+       AND NEW.live_status = 'in_progress' THEN
+        
+        -- Check if payouts already exist for this tournament
+        IF EXISTS (SELECT 1 FROM tournament_payouts WHERE tournament_id = NEW.id) THEN
+            RETURN NEW;
+        END IF;
+        
+        -- Count players who took part
+        SELECT COUNT(*) INTO v_player_count
+        FROM tournament_registrations
+        WHERE tournament_id = NEW.id
+        AND status IN ('registered', 'checked_in', 'seated', 'busted');
+        
+        -- Skip if no players
+        IF v_player_count = 0 THEN
+            RETURN NEW;
+        END IF;
+        
+        -- Calculate total prize pool (buy-in * number of players)
+        v_total_prize_pool := NEW.buy_in_cents * v_player_count;
+        
+        -- Use the tournament's template if it has one
+        IF NEW.payout_template_id IS NOT NULL THEN
+            SELECT * INTO v_template
+            FROM payout_templates
+            WHERE id = NEW.payout_template_id;
+        END IF;
+
+        -- Otherwise find appropriate payout template based on player count
+        IF v_template.id IS NULL THEN
+            SELECT * INTO v_template
+            FROM payout_templates
+            WHERE min_players <= v_player_count 
+            AND (max_players IS NULL OR max_players >= v_player_count)
+            ORDER BY min_players DESC
+            LIMIT 1;
+        END IF;
+        
+        -- If no template found, log warning and return
+        IF v_template.id IS NULL THEN
+            RAISE WARNING 'No payout template found for % players in tournament %', v_player_count, NEW.id;
+            RETURN NEW;
+        END IF;
+        
+        -- Calculate payout for each position
+        v_positions_array := ARRAY[]::JSONB[];
+        
+        FOR v_position IN 
+            SELECT * FROM jsonb_array_elements(v_template.payout_structure)
+        LOOP
+            -- Extract position and percentage
+            v_payout_amount := FLOOR((v_position.value->>'percentage')::NUMERIC * v_total_prize_pool / 100);
+            
+            v_positions_array := array_append(
+                v_positions_array, 
+                jsonb_build_object(
+                    'position', (v_position.value->>'position')::INTEGER,
+                    'amount_cents', v_payout_amount,
+                    'percentage', (v_position.value->>'percentage')::NUMERIC
+                )
+            );
+        END LOOP;
+        
+        v_payout_positions := to_jsonb(v_positions_array);
+        
+        -- Insert the calculated payouts
+        INSERT INTO tournament_payouts (
+            tournament_id,
+            template_id,
+            player_count,
+            total_prize_pool,
+            payout_positions
+        ) VALUES (
+            NEW.id,
+            v_template.id,
+            v_player_count,
+            v_total_prize_pool,
+            v_payout_positions
+        );
+        
+        RAISE NOTICE 'Created payouts for tournament % with % players using template %', 
+            NEW.id, v_player_count, v_template.name;
+    END IF;
+    
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Restore the points function counting registrations
+CREATE OR REPLACE FUNCTION calculate_tournament_points(tournament_id_param UUID)
+RETURNS INTEGER AS $$
+DECLARE
+    tournament_record RECORD;
+    field_size_count INTEGER;
+    buy_in_eur DECIMAL;
+    result_record RECORD;
+    calculated_points INTEGER;
+    total_updated INTEGER := 0;
+BEGIN
+    -- Get tournament information
+    SELECT t.buy_in_cents 
+    INTO tournament_record
+    FROM tournaments t 
+    WHERE t.id = tournament_id_param;
+    
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Tournament not found: %', tournament_id_param;
+    END IF;
+    
+    -- Calculate field size from registrations
+    SELECT COUNT(*)
+    INTO field_size_count
+    FROM tournament_registrations tr
+    WHERE tr.tournament_id = tournament_id_param;
+    
+    IF field_size_count = 0 THEN
+        RAISE WARNING 'No registrations found for tournament: %', tournament_id_param;
+        RETURN 0;
+    END IF;
+    
+    -- Convert buy-in to euros
+    buy_in_eur := tournament_record.buy_in_cents::DECIMAL / 100.0;
+    
+    IF buy_in_eur <= 0 THEN
+        RAISE WARNING 'Invalid buy-in amount for tournament: %', tournament_id_param;
+        RETURN 0;
+    END IF;
+    
+    -- Calculate points for each result
+    FOR result_record IN 
+        SELECT id, final_position 
+        FROM tournament_results 
+        WHERE tournament_id = tournament_id_param
+          AND final_position > 0
+    LOOP
+        -- Apply the authoritative formula
+        -- points = min(60, round(3 * (sqrt(field_size) / sqrt(rank)) * (log10(buy_in_eur) + 1) + 2))
+        calculated_points := LEAST(60, 
+            ROUND(
+                3.0 * (
+                    SQRT(field_size_count::DECIMAL) / SQRT(result_record.final_position::DECIMAL)
+                ) * (
+                    LOG(buy_in_eur) + 1.0
+                ) + 2.0
+            )::INTEGER
+        );
+        
+        -- Ensure non-negative points
+        calculated_points := GREATEST(0, calculated_points);
+        
+        -- Update the result with calculated points
+        UPDATE tournament_results 
+        SET points = calculated_points, updated_at = NOW()
+        WHERE id = result_record.id;
+        
+        total_updated := total_updated + 1;
+    END LOOP;
+    
+    RAISE INFO 'Updated % tournament results with calculated points for tournament %', total_updated, tournament_id_param;
+    RETURN total_updated;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS refresh_tournament_payouts(UUID);
+DROP FUNCTION IF EXISTS tournament_prize_pool(UUID);
+DROP FUNCTION IF EXISTS tournament_entry_count(UUID);
+DROP TABLE IF EXISTS tournament_entries;
+
+ALTER TABLE tournaments
+DROP COLUMN IF EXISTS rebuy_until_level,
+DROP COLUMN IF EXISTS add_on_cents,
+DROP COLUMN IF EXISTS rebuy_cents,
+DROP COLUMN IF EXISTS max_rebuys,
+DROP COLUMN IF EXISTS max_reentries;
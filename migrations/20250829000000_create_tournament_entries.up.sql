@@ -0,0 +1,257 @@
+-- Re-entry, rebuy and add-on rules
+ALTER TABLE tournaments
+ADD COLUMN max_reentries INTEGER NOT NULL DEFAULT 0 CHECK (max_reentries >= 0),
+ADD COLUMN max_rebuys INTEGER NOT NULL DEFAULT 0 CHECK (max_rebuys >= 0),
+ADD COLUMN rebuy_cents INTEGER CHECK (rebuy_cents >= 0),        -- NULL: same as the buy-in
+ADD COLUMN add_on_cents INTEGER CHECK (add_on_cents >= 0),      -- NULL: no add-on
+ADD COLUMN rebuy_until_level INTEGER CHECK (rebuy_until_level >= 1); -- NULL: no limit
+
+-- Every buy-in paid into a tournament
+CREATE TABLE tournament_entries (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id   UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    registration_id UUID NOT NULL REFERENCES tournament_registrations(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    entry_type      TEXT NOT NULL CHECK (entry_type IN ('buy_in', 'reentry', 'rebuy', 'add_on')),
+    amount_cents    INTEGER NOT NULL CHECK (amount_cents >= 0),
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX tournament_entries_tournament_id_idx ON tournament_entries (tournament_id);
+CREATE INDEX tournament_entries_user_id_idx ON tournament_entries (user_id);
+
+-- One initial buy-in and at most one add-on per player
+CREATE UNIQUE INDEX tournament_entries_one_buy_in_idx
+    ON tournament_entries (registration_id) WHERE entry_type = 'buy_in';
+CREATE UNIQUE INDEX tournament_entries_one_add_on_idx
+    ON tournament_entries (registration_id) WHERE entry_type = 'add_on';
+
+-- Entries that put a player in the field (rebuys and add-ons only add chips)
+CREATE OR REPLACE FUNCTION tournament_entry_count(p_tournament_id UUID)
+RETURNS INTEGER AS $$
+    SELECT COUNT(*)::INTEGER
+    FROM tournament_entries
+    WHERE tournament_id = p_tournament_id
+      AND entry_type IN ('buy_in', 'reentry');
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION tournament_prize_pool(p_tournament_id UUID)
+RETURNS INTEGER AS $$
+    SELECT COALESCE(SUM(amount_cents), 0)::INTEGER
+    FROM tournament_entries
+    WHERE tournament_id = p_tournament_id;
+$$ LANGUAGE sql STABLE;
+
+-- A registration pays its buy-in when it takes a seat, and gets it back if it gives the
+-- seat up without playing (cancelled or no-show)
+CREATE OR REPLACE FUNCTION sync_registration_buy_in()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_had_seat BOOLEAN := FALSE;
+    v_has_seat BOOLEAN;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        v_had_seat := OLD.status IN ('registered', 'checked_in', 'seated', 'busted');
+    END IF;
+    v_has_seat := NEW.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+    IF v_has_seat AND NOT v_had_seat THEN
+        INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type, amount_cents)
+        SELECT NEW.tournament_id, NEW.id, NEW.user_id, 'buy_in', t.buy_in_cents
+        FROM tournaments t
+        WHERE t.id = NEW.tournament_id
+        ON CONFLICT DO NOTHING;
+    ELSIF v_had_seat AND NOT v_has_seat THEN
+        DELETE FROM tournament_entries
+        WHERE registration_id = NEW.id AND entry_type = 'buy_in';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_tournament_registrations_buy_in
+    AFTER INSERT OR UPDATE OF status ON tournament_registrations
+    FOR EACH ROW EXECUTE FUNCTION sync_registration_buy_in();
+
+-- Existing registrations already paid
+INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type, amount_cents, created_at)
+SELECT tr.tournament_id, tr.id, tr.user_id, 'buy_in', t.buy_in_cents, tr.registration_time
+FROM tournament_registrations tr
+JOIN tournaments t ON t.id = tr.tournament_id
+WHERE tr.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+-- (Re)calculate the payouts of a tournament from its entries
+CREATE OR REPLACE FUNCTION refresh_tournament_payouts(p_tournament_id UUID)
+RETURNS VOID AS $$
+DECLARE
+    v_entry_count INTEGER;
+    v_total_prize_pool INTEGER;
+    v_template_id UUID;
+    v_payout_structure JSONB;
+    v_payout_positions JSONB;
+BEGIN
+    v_entry_count := tournament_entry_count(p_tournament_id);
+    IF v_entry_count = 0 THEN
+        RETURN;
+    END IF;
+    v_total_prize_pool := tournament_prize_pool(p_tournament_id);
+
+    -- Use the tournament's template if it has one
+    SELECT pt.id, pt.payout_structure INTO v_template_id, v_payout_structure
+    FROM tournaments t
+    JOIN payout_templates pt ON pt.id = t.payout_template_id
+    WHERE t.id = p_tournament_id;
+
+    -- Otherwise find appropriate payout template based on the number of entries
+    IF v_template_id IS NULL THEN
+        SELECT id, payout_structure INTO v_template_id, v_payout_structure
+        FROM payout_templates
+        WHERE min_players <= v_entry_count
+        AND (max_players IS NULL OR max_players >= v_entry_count)
+        ORDER BY min_players DESC
+        LIMIT 1;
+    END IF;
+
+    IF v_template_id IS NULL THEN
+        RAISE WARNING 'No payout template found for % entries in tournament %', v_entry_count, p_tournament_id;
+        RETURN;
+    END IF;
+
+    SELECT COALESCE(jsonb_agg(
+        jsonb_build_object(
+            'position', (pos->>'position')::INTEGER,
+            'amount_cents', FLOOR((pos->>'percentage')::NUMERIC * v_total_prize_pool / 100),
+            'percentage', (pos->>'percentage')::NUMERIC
+        ) ORDER BY (pos->>'position')::INTEGER
+    ), '[]'::jsonb)
+    INTO v_payout_positions
+    FROM jsonb_array_elements(v_payout_structure) pos;
+
+    INSERT INTO tournament_payouts (tournament_id, template_id, player_count, total_prize_pool, payout_positions)
+    VALUES (p_tournament_id, v_template_id, v_entry_count, v_total_prize_pool, v_payout_positions)
+    ON CONFLICT (tournament_id) DO UPDATE
+    SET template_id = EXCLUDED.template_id,
+        player_count = EXCLUDED.player_count,
+        total_prize_pool = EXCLUDED.total_prize_pool,
+        payout_positions = EXCLUDED.payout_positions;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Payouts are first calculated when the tournament starts...
+CREATE OR REPLACE FUNCTION calculate_tournament_payouts()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF (OLD.live_status = 'late_registration' OR OLD.live_status = 'not_started')
+       AND NEW.live_status = 'in_progress'
+       AND NOT EXISTS (SELECT 1 FROM tournament_payouts WHERE tournament_id = NEW.id) THEN
+        PERFORM refresh_tournament_payouts(NEW.id);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ...and follow every entry made after that
+CREATE OR REPLACE FUNCTION refresh_payouts_after_entry()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_tournament_id UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        v_tournament_id := OLD.tournament_id;
+    ELSE
+        v_tournament_id := NEW.tournament_id;
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM tournament_payouts WHERE tournament_id = v_tournament_id) THEN
+        PERFORM refresh_tournament_payouts(v_tournament_id);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_tournament_entries_refresh_payouts
+    AFTER INSERT OR DELETE ON tournament_entries
+    FOR EACH ROW EXECUTE FUNCTION refresh_payouts_after_entry();
+
+-- Points use the number of entries as the field size
+CREATE OR REPLACE FUNCTION calculate_tournament_points(tournament_id_param UUID)
+RETURNS INTEGER AS $$
+DECLARE
+    tournament_record RECORD;
+    field_size_count INTEGER;
+    buy_in_eur DECIMAL;
+    result_record RECORD;
+    calculated_points INTEGER;
+    total_updated INTEGER := 0;
+BEGIN
+    -- Get tournament information
+    SELECT t.buy_in_cents 
+    INTO tournament_record
+    FROM tournaments t 
+    WHERE t.id = tournament_id_param;
+    
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Tournament not found: %', tournament_id_param;
+    END IF;
+    
+    -- Field size is every buy-in and re-entry; tournaments without recorded entries
+    -- fall back to the number of results
+    field_size_count := tournament_entry_count(tournament_id_param);
+    IF field_size_count = 0 THEN
+        SELECT COUNT(*)
+        INTO field_size_count
+        FROM tournament_results tr
+        WHERE tr.tournament_id = tournament_id_param;
+    END IF;
+    
+    IF field_size_count = 0 THEN
+        RAISE WARNING 'No entries found for tournament: %', tournament_id_param;
+        RETURN 0;
+    END IF;
+    
+    -- Convert buy-in to euros
+    buy_in_eur := tournament_record.buy_in_cents::DECIMAL / 100.0;
+    
+    IF buy_in_eur <= 0 THEN
+        RAISE WARNING 'Invalid buy-in amount for tournament: %', tournament_id_param;
+        RETURN 0;
+    END IF;
+    
+    -- Calculate points for each result
+    FOR result_record IN 
+        SELECT id, final_position 
+        FROM tournament_results 
+        WHERE tournament_id = tournament_id_param
+          AND final_position > 0
+    LOOP
+        -- Apply the authoritative formula
+        -- points = min(60, round(3 * (sqrt(field_size) / sqrt(rank)) * (log10(buy_in_eur) + 1) + 2))
+        calculated_points := LEAST(60, 
+            ROUND(
+                3.0 * (
+                    SQRT(field_size_count::DECIMAL) / SQRT(result_record.final_position::DECIMAL)
+                ) * (
+                    LOG(buy_in_eur) + 1.0
+                ) + 2.0
+            )::INTEGER
+        );
+        
+        -- Ensure non-negative points
+        calculated_points := GREATEST(0, calculated_points);
+        
+        -- Update the result with calculated points
+        UPDATE tournament_results 
+        SET points = calculated_points, updated_at = NOW()
+        WHERE id = result_record.id;
+        
+        total_updated := total_updated + 1;
+    END LOOP;
+    
+    RAISE INFO 'Updated % tournament results with calculated points for tournament %', total_updated, tournament_id_param;
+    RETURN total_updated;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,53 @@
+DROP TRIGGER IF EXISTS trg_tournament_entries_refresh_payouts ON tournament_entries;
+CREATE TRIGGER trg_tournament_entries_refresh_payouts
+    AFTER INSERT OR DELETE ON tournament_entries
+    FOR EACH ROW EXECUTE FUNCTION refresh_payouts_after_entry();
+
+DROP TRIGGER IF EXISTS trg_tournaments_buy_in_price ON tournaments;
+DROP FUNCTION IF EXISTS sync_tournament_buy_in_price();
+
+CREATE OR REPLACE FUNCTION sync_registration_buy_in()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_had_seat BOOLEAN := FALSE;
+    v_has_seat BOOLEAN;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        v_had_seat := OLD.status IN ('registered', 'checked_in', 'seated', 'busted');
+    END IF;
+    v_has_seat := NEW.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+    IF v_has_seat AND NOT v_had_seat THEN
+        INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type, amount_cents)
+        SELECT NEW.tournament_id, NEW.id, NEW.user_id, 'buy_in', t.buy_in_cents
+        FROM tournaments t
+        WHERE t.id = NEW.tournament_id
+        ON CONFLICT DO NOTHING;
+    ELSIF v_had_seat AND NOT v_has_seat THEN
+        DELETE FROM tournament_entries
+        WHERE registration_id = NEW.id AND entry_type = 'buy_in';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION tournament_prize_pool(p_tournament_id UUID)
+RETURNS INTEGER AS $$
+    SELECT COALESCE(SUM(amount_cents), 0)::INTEGER
+    FROM tournament_entries
+    WHERE tournament_id = p_tournament_id;
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE tournament_entries
+DROP CONSTRAINT IF EXISTS tournament_entries_fees_within_amount,
+DROP COLUMN IF EXISTS prize_cents,
+DROP COLUMN IF EXISTS staff_fee_cents,
+DROP COLUMN IF EXISTS rake_cents;
+
+ALTER TABLE tournaments
+DROP CONSTRAINT IF EXISTS tournaments_fees_within_buy_in,
+DROP COLUMN IF EXISTS add_on_chips,
+DROP COLUMN IF EXISTS prize_contribution_cents,
+DROP COLUMN IF EXISTS staff_fee_cents,
+DROP COLUMN IF EXISTS rake_cents;
@@ -0,0 +1,84 @@
+-- Split the buy-in into what goes to the prize pool, the house (rake) and the staff
+ALTER TABLE tournaments
+ADD COLUMN rake_cents INTEGER NOT NULL DEFAULT 0 CHECK (rake_cents >= 0),
+ADD COLUMN staff_fee_cents INTEGER NOT NULL DEFAULT 0 CHECK (staff_fee_cents >= 0),
+ADD COLUMN prize_contribution_cents INTEGER
+    GENERATED ALWAYS AS (buy_in_cents - rake_cents - staff_fee_cents) STORED,
+ADD COLUMN add_on_chips INTEGER CHECK (add_on_chips > 0),
+ADD CONSTRAINT tournaments_fees_within_buy_in
+    CHECK (rake_cents + staff_fee_cents <= buy_in_cents);
+
+-- Every entry records where its money went
+ALTER TABLE tournament_entries
+ADD COLUMN rake_cents INTEGER NOT NULL DEFAULT 0 CHECK (rake_cents >= 0),
+ADD COLUMN staff_fee_cents INTEGER NOT NULL DEFAULT 0 CHECK (staff_fee_cents >= 0),
+ADD COLUMN prize_cents INTEGER
+    GENERATED ALWAYS AS (amount_cents - rake_cents - staff_fee_cents) STORED,
+ADD CONSTRAINT tournament_entries_fees_within_amount
+    CHECK (rake_cents + staff_fee_cents <= amount_cents);
+
+-- Only the prize part of each entry goes to the prize pool
+CREATE OR REPLACE FUNCTION tournament_prize_pool(p_tournament_id UUID)
+RETURNS INTEGER AS $$
+    SELECT COALESCE(SUM(prize_cents), 0)::INTEGER
+    FROM tournament_entries
+    WHERE tournament_id = p_tournament_id;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION sync_registration_buy_in()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_had_seat BOOLEAN := FALSE;
+    v_has_seat BOOLEAN;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        v_had_seat := OLD.status IN ('registered', 'checked_in', 'seated', 'busted');
+    END IF;
+    v_has_seat := NEW.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+    IF v_has_seat AND NOT v_had_seat THEN
+        INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type,
+                                        amount_cents, rake_cents, staff_fee_cents)
+        SELECT NEW.tournament_id, NEW.id, NEW.user_id, 'buy_in',
+               t.buy_in_cents, t.rake_cents, t.staff_fee_cents
+        FROM tournaments t
+        WHERE t.id = NEW.tournament_id
+        ON CONFLICT DO NOTHING;
+    ELSIF v_had_seat AND NOT v_has_seat THEN
+        DELETE FROM tournament_entries
+        WHERE registration_id = NEW.id AND entry_type = 'buy_in';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Buy-ins already taken follow a change of the tournament's price
+CREATE OR REPLACE FUNCTION sync_tournament_buy_in_price()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE tournament_entries
+    SET amount_cents = NEW.buy_in_cents,
+        rake_cents = NEW.rake_cents,
+        staff_fee_cents = NEW.staff_fee_cents
+    WHERE tournament_id = NEW.id
+      AND entry_type = 'buy_in';
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_tournaments_buy_in_price
+    AFTER UPDATE OF buy_in_cents, rake_cents, staff_fee_cents ON tournaments
+    FOR EACH ROW
+    WHEN (OLD.buy_in_cents IS DISTINCT FROM NEW.buy_in_cents
+          OR OLD.rake_cents IS DISTINCT FROM NEW.rake_cents
+          OR OLD.staff_fee_cents IS DISTINCT FROM NEW.staff_fee_cents)
+    EXECUTE FUNCTION sync_tournament_buy_in_price();
+
+-- Payouts also follow price changes of existing entries
+DROP TRIGGER IF EXISTS trg_tournament_entries_refresh_payouts ON tournament_entries;
+CREATE TRIGGER trg_tournament_entries_refresh_payouts
+    AFTER INSERT OR DELETE OR UPDATE OF amount_cents, rake_cents, staff_fee_cents
+    ON tournament_entries
+    FOR EACH ROW EXECUTE FUNCTION refresh_payouts_after_entry();
@@ -0,0 +1,65 @@
+DROP TRIGGER IF EXISTS trg_tournaments_guarantee_refresh_payouts ON tournaments;
+DROP FUNCTION IF EXISTS refresh_payouts_after_guarantee_change();
+
+-- Restore payouts from the collected prize pool only
+CREATE OR REPLACE FUNCTION refresh_tournament_payouts(p_tournament_id UUID)
+RETURNS VOID AS $$
+DECLARE
+    v_entry_count INTEGER;
+    v_total_prize_pool INTEGER;
+    v_template_id UUID;
+    v_payout_structure JSONB;
+    v_payout_positions JSONB;
+BEGIN
+    v_entry_count := tournament_entry_count(p_tournament_id);
+    IF v_entry_count = 0 THEN
+        RETURN;
+    END IF;
+    v_total_prize_pool := tournament_prize_pool(p_tournament_id);
+
+    -- Use the tournament's template if it has one
+    SELECT pt.id, pt.payout_structure INTO v_template_id, v_payout_structure
+    FROM tournaments t
+    JOIN payout_templates pt ON pt.id = t.payout_template_id
+    WHERE t.id = p_tournament_id;
+
+    -- Otherwise find appropriate payout template based on the number of entries
+    IF v_template_id IS NULL THEN
+        SELECT id, payout_structure INTO v_template_id, v_payout_structure
+        FROM payout_templates
+        WHERE min_players <= v_entry_count
+        AND (max_players IS NULL OR max_players >= v_entry_count)
+        ORDER BY min_players DESC
+        LIMIT 1;
+    END IF;
+
+    IF v_template_id IS NULL THEN
+        RAISE WARNING 'No payout template found for % entries in tournament %', v_entry_count, p_tournament_id;
+        RETURN;
+    END IF;
+
+    SELECT COALESCE(jsonb_agg(
+        jsonb_build_object(
+            'position', (pos->>'position')::INTEGER,
+            'amount_cents', FLOOR((pos->>'percentage')::NUMERIC * v_total_prize_pool / 100),
+            'percentage', (pos->>'percentage')::NUMERIC
+        ) ORDER BY (pos->>'position')::INTEGER
+    ), '[]'::jsonb)
+    INTO v_payout_positions
+    FROM jsonb_array_elements(v_payout_structure) pos;
+
+    INSERT INTO tournament_payouts (tournament_id, template_id, player_count, total_prize_pool, payout_positions)
+    VALUES (p_tournament_id, v_template_id, v_entry_count, v_total_prize_pool, v_payout_positions)
+    ON CONFLICT (tournament_id) DO UPDATE
+    SET template_id = EXCLUDED.template_id,
+        player_count = EXCLUDED.player_count,
+        total_prize_pool = EXCLUDED.total_prize_pool,
+        payout_positions = EXCLUDED.payout_positions;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE tournament_payouts
+DROP COLUMN IF EXISTS overlay_cents;
+
+ALTER TABLE tournaments
+DROP COLUMN IF EXISTS guarantee_cents;
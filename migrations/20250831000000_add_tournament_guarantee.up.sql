@@ -0,0 +1,94 @@
+-- Advertised guaranteed prize pool ("GTD"), NULL when there is none
+ALTER TABLE tournaments
+ADD COLUMN guarantee_cents INTEGER CHECK (guarantee_cents >= 0);
+
+-- What the club adds to the collected prize pool to cover the guarantee
+ALTER TABLE tournament_payouts
+ADD COLUMN overlay_cents INTEGER NOT NULL DEFAULT 0 CHECK (overlay_cents >= 0);
+
+-- Pay out max(guarantee, collected prize pool)
+CREATE OR REPLACE FUNCTION refresh_tournament_payouts(p_tournament_id UUID)
+RETURNS VOID AS $$
+DECLARE
+    v_entry_count INTEGER;
+    v_collected INTEGER;
+    v_total_prize_pool INTEGER;
+    v_overlay INTEGER;
+    v_template_id UUID;
+    v_payout_structure JSONB;
+    v_payout_positions JSONB;
+BEGIN
+    v_entry_count := tournament_entry_count(p_tournament_id);
+    IF v_entry_count = 0 THEN
+        RETURN;
+    END IF;
+
+    -- The club makes up the difference when the entries don't cover the guarantee
+    v_collected := tournament_prize_pool(p_tournament_id);
+    SELECT GREATEST(v_collected, COALESCE(t.guarantee_cents, 0))
+    INTO v_total_prize_pool
+    FROM tournaments t
+    WHERE t.id = p_tournament_id;
+    v_overlay := v_total_prize_pool - v_collected;
+
+    -- Use the tournament's template if it has one
+    SELECT pt.id, pt.payout_structure INTO v_template_id, v_payout_structure
+    FROM tournaments t
+    JOIN payout_templates pt ON pt.id = t.payout_template_id
+    WHERE t.id = p_tournament_id;
+
+    -- Otherwise find appropriate payout template based on the number of entries
+    IF v_template_id IS NULL THEN
+        SELECT id, payout_structure INTO v_template_id, v_payout_structure
+        FROM payout_templates
+        WHERE min_players <= v_entry_count
+        AND (max_players IS NULL OR max_players >= v_entry_count)
+        ORDER BY min_players DESC
+        LIMIT 1;
+    END IF;
+
+    IF v_template_id IS NULL THEN
+        RAISE WARNING 'No payout template found for % entries in tournament %', v_entry_count, p_tournament_id;
+        RETURN;
+    END IF;
+
+    SELECT COALESCE(jsonb_agg(
+        jsonb_build_object(
+            'position', (pos->>'position')::INTEGER,
+            'amount_cents', FLOOR((pos->>'percentage')::NUMERIC * v_total_prize_pool / 100),
+            'percentage', (pos->>'percentage')::NUMERIC
+        ) ORDER BY (pos->>'position')::INTEGER
+    ), '[]'::jsonb)
+    INTO v_payout_positions
+    FROM jsonb_array_elements(v_payout_structure) pos;
+
+    INSERT INTO tournament_payouts (tournament_id, template_id, player_count, total_prize_pool,
+                                    overlay_cents, payout_positions)
+    VALUES (p_tournament_id, v_template_id, v_entry_count, v_total_prize_pool, v_overlay,
+            v_payout_positions)
+    ON CONFLICT (tournament_id) DO UPDATE
+    SET template_id = EXCLUDED.template_id,
+        player_count = EXCLUDED.player_count,
+        total_prize_pool = EXCLUDED.total_prize_pool,
+        overlay_cents = EXCLUDED.overlay_cents,
+        payout_positions = EXCLUDED.payout_positions;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Payouts follow a change of the guarantee
+CREATE OR REPLACE FUNCTION refresh_payouts_after_guarantee_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM tournament_payouts WHERE tournament_id = NEW.id) THEN
+        PERFORM refresh_tournament_payouts(NEW.id);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_tournaments_guarantee_refresh_payouts
+    AFTER UPDATE OF guarantee_cents ON tournaments
+    FOR EACH ROW
+    WHEN (OLD.guarantee_cents IS DISTINCT FROM NEW.guarantee_cents)
+    EXECUTE FUNCTION refresh_payouts_after_guarantee_change();
@@ -0,0 +1,86 @@
+DROP TRIGGER IF EXISTS trg_tournament_entries_refresh_payouts ON tournament_entries;
+CREATE TRIGGER trg_tournament_entries_refresh_payouts
+    AFTER INSERT OR DELETE OR UPDATE OF amount_cents, rake_cents, staff_fee_cents
+    ON tournament_entries
+    FOR EACH ROW EXECUTE FUNCTION refresh_payouts_after_entry();
+
+DROP TRIGGER IF EXISTS trg_tournaments_buy_in_price ON tournaments;
+CREATE TRIGGER trg_tournaments_buy_in_price
+    AFTER UPDATE OF buy_in_cents, rake_cents, staff_fee_cents ON tournaments
+    FOR EACH ROW
+    WHEN (OLD.buy_in_cents IS DISTINCT FROM NEW.buy_in_cents
+          OR OLD.rake_cents IS DISTINCT FROM NEW.rake_cents
+          OR OLD.staff_fee_cents IS DISTINCT FROM NEW.staff_fee_cents)
+    EXECUTE FUNCTION sync_tournament_buy_in_price();
+
+CREATE OR REPLACE FUNCTION sync_tournament_buy_in_price()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE tournament_entries
+    SET amount_cents = NEW.buy_in_cents,
+        rake_cents = NEW.rake_cents,
+        staff_fee_cents = NEW.staff_fee_cents
+    WHERE tournament_id = NEW.id
+      AND entry_type = 'buy_in';
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_registration_buy_in()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_had_seat BOOLEAN := FALSE;
+    v_has_seat BOOLEAN;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        v_had_seat := OLD.status IN ('registered', 'checked_in', 'seated', 'busted');
+    END IF;
+    v_has_seat := NEW.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+    IF v_has_seat AND NOT v_had_seat THEN
+        INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type,
+                                        amount_cents, rake_cents, staff_fee_cents)
+        SELECT NEW.tournament_id, NEW.id, NEW.user_id, 'buy_in',
+               t.buy_in_cents, t.rake_cents, t.staff_fee_cents
+        FROM tournaments t
+        WHERE t.id = NEW.tournament_id
+        ON CONFLICT DO NOTHING;
+    ELSIF v_had_seat AND NOT v_has_seat THEN
+        DELETE FROM tournament_entries
+        WHERE registration_id = NEW.id AND entry_type = 'buy_in';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TABLE IF EXISTS tournament_eliminations;
+
+ALTER TABLE tournament_results
+DROP COLUMN IF EXISTS bounty_cents;
+
+ALTER TABLE tournament_registrations
+DROP COLUMN IF EXISTS bounty_cents;
+
+ALTER TABLE tournament_entries
+DROP CONSTRAINT IF EXISTS tournament_entries_fees_within_amount,
+DROP COLUMN IF EXISTS prize_cents,
+DROP COLUMN IF EXISTS bounty_cents;
+ALTER TABLE tournament_entries
+ADD COLUMN prize_cents INTEGER
+    GENERATED ALWAYS AS (amount_cents - rake_cents - staff_fee_cents) STORED,
+ADD CONSTRAINT tournament_entries_fees_within_amount
+    CHECK (rake_cents + staff_fee_cents <= amount_cents);
+
+ALTER TABLE tournaments
+DROP CONSTRAINT IF EXISTS tournaments_fees_within_buy_in,
+DROP CONSTRAINT IF EXISTS tournaments_bounty_needs_format,
+DROP COLUMN IF EXISTS prize_contribution_cents,
+DROP COLUMN IF EXISTS bounty_cents,
+DROP COLUMN IF EXISTS bounty_format;
+ALTER TABLE tournaments
+ADD COLUMN prize_contribution_cents INTEGER
+    GENERATED ALWAYS AS (buy_in_cents - rake_cents - staff_fee_cents) STORED,
+ADD CONSTRAINT tournaments_fees_within_buy_in
+    CHECK (rake_cents + staff_fee_cents <= buy_in_cents);
@@ -0,0 +1,112 @@
+-- Knockout formats: a regular bounty pays the eliminator the full bounty, a progressive
+-- knockout (PKO) pays half and adds the other half to the eliminator's own bounty
+ALTER TABLE tournaments
+ADD COLUMN bounty_format TEXT NOT NULL DEFAULT 'none'
+    CHECK (bounty_format IN ('none', 'regular', 'progressive')),
+ADD COLUMN bounty_cents INTEGER NOT NULL DEFAULT 0 CHECK (bounty_cents >= 0),
+ADD CONSTRAINT tournaments_bounty_needs_format
+    CHECK (bounty_format <> 'none' OR bounty_cents = 0);
+
+-- The bounty portion of the buy-in stays out of the prize pool
+ALTER TABLE tournaments
+DROP CONSTRAINT tournaments_fees_within_buy_in,
+DROP COLUMN prize_contribution_cents;
+ALTER TABLE tournaments
+ADD COLUMN prize_contribution_cents INTEGER
+    GENERATED ALWAYS AS (buy_in_cents - rake_cents - staff_fee_cents - bounty_cents) STORED,
+ADD CONSTRAINT tournaments_fees_within_buy_in
+    CHECK (rake_cents + staff_fee_cents + bounty_cents <= buy_in_cents);
+
+ALTER TABLE tournament_entries
+ADD COLUMN bounty_cents INTEGER NOT NULL DEFAULT 0 CHECK (bounty_cents >= 0),
+DROP CONSTRAINT tournament_entries_fees_within_amount,
+DROP COLUMN prize_cents;
+ALTER TABLE tournament_entries
+ADD COLUMN prize_cents INTEGER
+    GENERATED ALWAYS AS (amount_cents - rake_cents - staff_fee_cents - bounty_cents) STORED,
+ADD CONSTRAINT tournament_entries_fees_within_amount
+    CHECK (rake_cents + staff_fee_cents + bounty_cents <= amount_cents);
+
+-- Bounty currently on each player's head, NULL: the tournament's starting bounty
+ALTER TABLE tournament_registrations
+ADD COLUMN bounty_cents INTEGER CHECK (bounty_cents >= 0);
+
+-- Bounties won, kept apart from the prize ladder
+ALTER TABLE tournament_results
+ADD COLUMN bounty_cents INTEGER NOT NULL DEFAULT 0 CHECK (bounty_cents >= 0);
+
+-- Who eliminated whom, and the bounty it paid
+CREATE TABLE tournament_eliminations (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id      UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    registration_id    UUID NOT NULL REFERENCES tournament_registrations(id) ON DELETE CASCADE,
+    user_id            UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    eliminated_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    bounty_cents       INTEGER NOT NULL DEFAULT 0 CHECK (bounty_cents >= 0),       -- paid to the eliminator
+    bounty_added_cents INTEGER NOT NULL DEFAULT 0 CHECK (bounty_added_cents >= 0), -- added to the eliminator's bounty
+    created_by         UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (eliminated_by IS DISTINCT FROM user_id)
+);
+
+CREATE INDEX tournament_eliminations_tournament_id_idx ON tournament_eliminations (tournament_id);
+CREATE INDEX tournament_eliminations_eliminated_by_idx ON tournament_eliminations (eliminated_by);
+
+CREATE OR REPLACE FUNCTION sync_registration_buy_in()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_had_seat BOOLEAN := FALSE;
+    v_has_seat BOOLEAN;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        v_had_seat := OLD.status IN ('registered', 'checked_in', 'seated', 'busted');
+    END IF;
+    v_has_seat := NEW.status IN ('registered', 'checked_in', 'seated', 'busted');
+
+    IF v_has_seat AND NOT v_had_seat THEN
+        INSERT INTO tournament_entries (tournament_id, registration_id, user_id, entry_type,
+                                        amount_cents, rake_cents, staff_fee_cents, bounty_cents)
+        SELECT NEW.tournament_id, NEW.id, NEW.user_id, 'buy_in',
+               t.buy_in_cents, t.rake_cents, t.staff_fee_cents, t.bounty_cents
+        FROM tournaments t
+        WHERE t.id = NEW.tournament_id
+        ON CONFLICT DO NOTHING;
+    ELSIF v_had_seat AND NOT v_has_seat THEN
+        DELETE FROM tournament_entries
+        WHERE registration_id = NEW.id AND entry_type = 'buy_in';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_tournament_buy_in_price()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE tournament_entries
+    SET amount_cents = NEW.buy_in_cents,
+        rake_cents = NEW.rake_cents,
+        staff_fee_cents = NEW.staff_fee_cents,
+        bounty_cents = NEW.bounty_cents
+    WHERE tournament_id = NEW.id
+      AND entry_type = 'buy_in';
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_tournaments_buy_in_price ON tournaments;
+CREATE TRIGGER trg_tournaments_buy_in_price
+    AFTER UPDATE OF buy_in_cents, rake_cents, staff_fee_cents, bounty_cents ON tournaments
+    FOR EACH ROW
+    WHEN (OLD.buy_in_cents IS DISTINCT FROM NEW.buy_in_cents
+          OR OLD.rake_cents IS DISTINCT FROM NEW.rake_cents
+          OR OLD.staff_fee_cents IS DISTINCT FROM NEW.staff_fee_cents
+          OR OLD.bounty_cents IS DISTINCT FROM NEW.bounty_cents)
+    EXECUTE FUNCTION sync_tournament_buy_in_price();
+
+DROP TRIGGER IF EXISTS trg_tournament_entries_refresh_payouts ON tournament_entries;
+CREATE TRIGGER trg_tournament_entries_refresh_payouts
+    AFTER INSERT OR DELETE OR UPDATE OF amount_cents, rake_cents, staff_fee_cents, bounty_cents
+    ON tournament_entries
+    FOR EACH ROW EXECUTE FUNCTION refresh_payouts_after_entry();
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS tournament_eliminations_created_at_idx;
+
+ALTER TABLE tournament_eliminations
+DROP COLUMN IF EXISTS previous_status,
+DROP COLUMN IF EXISTS stack_size,
+DROP COLUMN IF EXISTS seat_assignment_id,
+DROP COLUMN IF EXISTS finishing_position,
+DROP COLUMN IF EXISTS level;
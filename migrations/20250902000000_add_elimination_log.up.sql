@@ -0,0 +1,12 @@
+-- Elimination log: when and at which level each player busted, and where they finished.
+-- The seat, stack and registration status before the bust are kept so the last
+-- elimination can be undone.
+ALTER TABLE tournament_eliminations
+ADD COLUMN level              INTEGER,  -- clock level at the bust, NULL without a clock
+ADD COLUMN finishing_position INTEGER CHECK (finishing_position >= 1),
+ADD COLUMN seat_assignment_id UUID REFERENCES table_seat_assignments(id) ON DELETE SET NULL,
+ADD COLUMN stack_size         INTEGER,  -- stack at the start of the hand
+ADD COLUMN previous_status    TEXT NOT NULL DEFAULT 'seated';
+
+CREATE INDEX tournament_eliminations_created_at_idx
+    ON tournament_eliminations (tournament_id, created_at DESC);
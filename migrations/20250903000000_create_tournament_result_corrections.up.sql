@@ -0,0 +1,22 @@
+-- Audit trail of every correction made to a tournament result
+CREATE TABLE tournament_result_corrections (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id        UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    result_id            UUID NOT NULL, -- No foreign key: deleted results stay audited
+    user_id              UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    action               TEXT NOT NULL CHECK (action IN ('correct', 'delete')),
+    old_final_position   INTEGER NOT NULL,
+    new_final_position   INTEGER,       -- New values are NULL when the result was deleted
+    old_prize_cents      INTEGER NOT NULL,
+    new_prize_cents      INTEGER,
+    old_bounty_cents     INTEGER NOT NULL,
+    new_bounty_cents     INTEGER,
+    old_notes            TEXT,
+    new_notes            TEXT,
+    reason               TEXT,
+    corrected_by         UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX tournament_result_corrections_tournament_id_idx
+    ON tournament_result_corrections (tournament_id, created_at);
@@ -0,0 +1,22 @@
+-- Leaderboard scoring formula chosen by each club. Clubs without a row score with the
+-- authoritative formula, capped at 60 points.
+CREATE TABLE club_scoring_settings (
+    club_id     UUID PRIMARY KEY REFERENCES clubs(id) ON DELETE CASCADE,
+    formula     TEXT NOT NULL DEFAULT 'authoritative'
+                CHECK (formula IN ('authoritative', 'linear', 'field_size')),
+    cap         INTEGER NOT NULL DEFAULT 60 CHECK (cap > 0),
+    multiplier  DOUBLE PRECISION NOT NULL DEFAULT 1.0 CHECK (multiplier > 0),
+    updated_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER trg_club_scoring_settings_updated_at
+    BEFORE UPDATE ON club_scoring_settings
+    FOR EACH ROW EXECUTE PROCEDURE set_updated_at();
+
+-- Points are now computed and stored by the application
+DROP TRIGGER IF EXISTS tournament_status_points_trigger ON tournaments;
+DROP FUNCTION IF EXISTS trigger_calculate_points();
+DROP FUNCTION IF EXISTS recalculate_all_tournament_points(UUID);
+DROP FUNCTION IF EXISTS calculate_tournament_points(UUID);
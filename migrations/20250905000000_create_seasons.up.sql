@@ -0,0 +1,39 @@
+-- Named league seasons of a club. The rules decide which results count and seasons can
+-- override the club's scoring settings for the tournaments they cover.
+CREATE TABLE seasons (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    club_id             UUID NOT NULL REFERENCES clubs(id) ON DELETE CASCADE,
+    name                TEXT NOT NULL,
+    start_date          DATE NOT NULL,
+    end_date            DATE NOT NULL,
+    best_results        INTEGER CHECK (best_results > 0),                   -- Only the best N results count
+    min_events          INTEGER NOT NULL DEFAULT 0 CHECK (min_events >= 0), -- Events needed to be ranked
+    drop_worst          INTEGER NOT NULL DEFAULT 0 CHECK (drop_worst >= 0), -- Worst results left out
+    scoring_formula     TEXT CHECK (scoring_formula IN ('authoritative', 'linear', 'field_size')),
+    scoring_cap         INTEGER CHECK (scoring_cap > 0),
+    scoring_multiplier  DOUBLE PRECISION CHECK (scoring_multiplier > 0),
+    closed_at           TIMESTAMPTZ,           -- Standings are frozen once the season is closed
+    closed_by           UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_by          UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (end_date >= start_date)
+);
+
+CREATE INDEX seasons_club_id_idx ON seasons (club_id, start_date);
+
+CREATE TRIGGER trg_seasons_updated_at
+    BEFORE UPDATE ON seasons
+    FOR EACH ROW EXECUTE PROCEDURE set_updated_at();
+
+-- Final standings, written when a season closes
+CREATE TABLE season_standings (
+    season_id        UUID NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    user_id          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rank             INTEGER,               -- NULL below the minimum number of events
+    points           INTEGER NOT NULL,
+    events_played    INTEGER NOT NULL,
+    results_counted  INTEGER NOT NULL,
+    PRIMARY KEY (season_id, user_id)
+);
@@ -0,0 +1,11 @@
+-- What players show on their public profile. Players without a row show everything.
+CREATE TABLE user_privacy_settings (
+    user_id        UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    hide_winnings  BOOLEAN NOT NULL DEFAULT false,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER trg_user_privacy_settings_updated_at
+    BEFORE UPDATE ON user_privacy_settings
+    FOR EACH ROW EXECUTE PROCEDURE set_updated_at();
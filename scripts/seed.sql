@@ -170,8 +170,8 @@ WITH tournament_tag_pairs AS (
 INSERT INTO tournament_tags (tournament_id, tag_id)
 SELECT tournament_id, tag_id FROM tournament_tag_pairs LIMIT 50; -- Add some random tags
 
--- Calculate points for all tournaments with results using the PostgreSQL function
-SELECT recalculate_all_tournament_points();
+-- Points stay at 0: only the API scores results, with each club's own formula, when a
+-- tournament finishes or its results are corrected
 
 -- Delete existing payout templates to avoid conflicts
 DELETE FROM payout_templates;